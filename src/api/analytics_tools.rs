@@ -0,0 +1,342 @@
+use crate::api::tool_registry::{cancelled_error, ToolContext, ToolHandler, ToolRegistry};
+use crate::services::analytics_service::ProjectInsights;
+use crate::services::AnalyticsHelper;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rmcp::model::ErrorData as McpError;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Progress steps of `export_analytics_data`: generating the report, then formatting it.
+/// Exporting only reads, so a cancelled export has nothing to undo.
+const EXPORT_STEPS: usize = 2;
+
+/// Register the usage analytics tools
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(GetUsageAnalyticsTool);
+    registry.register(GetContextInsightsTool);
+    registry.register(GenerateQualityReportTool);
+    registry.register(ExportAnalyticsDataTool);
+}
+
+/// Record the outcome of an analytics query and map failures to an internal error
+async fn track_query<T>(
+    context: &ToolContext,
+    tool: &str,
+    scope: Option<String>,
+    start_time: Instant,
+    result: anyhow::Result<T>,
+    failure: &str,
+) -> Result<T, McpError> {
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+    let error = result.as_ref().err().map(|e| e.to_string());
+    context
+        .track(AnalyticsHelper::create_analytics_event(
+            tool.to_string(),
+            scope,
+            Some(duration_ms),
+            error.is_none(),
+            error,
+        ))
+        .await;
+    result.map_err(|e| McpError::internal_error(format!("{failure}: {e}"), None))
+}
+
+fn parse_date(name: &str, value: &str) -> Result<DateTime<Utc>, McpError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|_| McpError::invalid_params(format!("Invalid {name} format. Use ISO 8601 format"), None))
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsScope {
+    Global,
+    Entity,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetUsageAnalyticsArgs {
+    /// Scope of analytics to retrieve
+    pub scope: AnalyticsScope,
+    /// Entity type (required for entity scope)
+    pub entity_type: Option<String>,
+    /// Entity ID (required for entity scope)
+    pub entity_id: Option<String>,
+}
+
+pub struct GetUsageAnalyticsTool;
+
+#[async_trait]
+impl ToolHandler for GetUsageAnalyticsTool {
+    type Args = GetUsageAnalyticsArgs;
+    type Output = HashMap<String, Value>;
+
+    fn name(&self) -> &'static str {
+        "get_usage_analytics"
+    }
+
+    fn description(&self) -> &'static str {
+        "Retrieve usage statistics for entities or global analytics"
+    }
+
+    fn category(&self) -> &'static str {
+        "Analytics"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Get global or entity-specific usage analytics"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let start_time = Instant::now();
+        let analytics = &context.container().analytics_service;
+        let (scope, result) = match args.scope {
+            AnalyticsScope::Global => ("global", analytics.get_global_statistics().await),
+            AnalyticsScope::Entity => {
+                let entity_type = args.entity_type.as_deref().ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: entity_type for entity scope", None)
+                })?;
+                let entity_id = args.entity_id.as_deref().ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: entity_id for entity scope", None)
+                })?;
+                let usage = analytics.get_entity_usage(entity_type, entity_id).await.and_then(|usage| {
+                    match serde_json::to_value(usage)? {
+                        Value::Object(fields) => Ok(fields.into_iter().collect()),
+                        _ => Ok(Default::default()),
+                    }
+                });
+                ("entity", usage)
+            }
+        };
+
+        let statistics = track_query(
+            context,
+            self.name(),
+            Some(scope.to_string()),
+            start_time,
+            result,
+            "Analytics query failed",
+        )
+        .await?;
+        Ok(statistics)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetContextInsightsArgs {
+    /// The ID of the project to analyze
+    pub project_id: String,
+}
+
+pub struct GetContextInsightsTool;
+
+#[async_trait]
+impl ToolHandler for GetContextInsightsTool {
+    type Args = GetContextInsightsArgs;
+    type Output = ProjectInsights;
+
+    fn name(&self) -> &'static str {
+        "get_context_insights"
+    }
+
+    fn description(&self) -> &'static str {
+        "Get project-level analytics and insights"
+    }
+
+    fn category(&self) -> &'static str {
+        "Analytics"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Find which context a project's agents use most"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let start_time = Instant::now();
+        let result = context.container().analytics_service.get_project_insights(&args.project_id).await;
+        let insights = track_query(
+            context,
+            self.name(),
+            Some(args.project_id),
+            start_time,
+            result,
+            "Context insights query failed",
+        )
+        .await?;
+        Ok(insights)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GenerateQualityReportArgs {
+    /// Start date for the report (ISO 8601 format)
+    pub start_date: String,
+    /// End date for the report (ISO 8601 format)
+    pub end_date: String,
+    /// Optional project ID to filter the report
+    pub project_id: Option<String>,
+}
+
+pub struct GenerateQualityReportTool;
+
+#[async_trait]
+impl ToolHandler for GenerateQualityReportTool {
+    type Args = GenerateQualityReportArgs;
+    type Output = Value;
+
+    fn name(&self) -> &'static str {
+        "generate_quality_report"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate a context health assessment and quality report"
+    }
+
+    fn category(&self) -> &'static str {
+        "Quality"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Assess context quality and get improvement recommendations"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let start_time = Instant::now();
+        let start_date = parse_date("start_date", &args.start_date)?;
+        let end_date = parse_date("end_date", &args.end_date)?;
+
+        let analytics = &context.container().analytics_service;
+        let mut result = analytics.generate_usage_report(start_date, end_date).await;
+        if let (Ok(Value::Object(report)), Some(project_id)) = (&mut result, &args.project_id) {
+            if let Ok(insights) = analytics.get_project_insights(project_id).await {
+                report.insert(
+                    "quality_assessment".to_string(),
+                    json!({
+                        "context_health_score": insights.context_health_score,
+                        "recommendations": insights.recommendations,
+                        "most_active_entity_types": insights.most_active_entity_types
+                    }),
+                );
+            }
+        }
+
+        let report = track_query(
+            context,
+            self.name(),
+            args.project_id,
+            start_time,
+            result,
+            "Quality report generation failed",
+        )
+        .await?;
+        Ok(report)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ExportAnalyticsDataArgs {
+    /// Export format
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Start date for export (ISO 8601 format)
+    pub start_date: String,
+    /// End date for export (ISO 8601 format)
+    pub end_date: String,
+    /// Optional project ID to filter the export
+    pub project_id: Option<String>,
+    /// Optional array of event types to include
+    #[serde(default)]
+    pub event_types: Vec<String>,
+}
+
+pub struct ExportAnalyticsDataTool;
+
+#[async_trait]
+impl ToolHandler for ExportAnalyticsDataTool {
+    type Args = ExportAnalyticsDataArgs;
+    type Output = Value;
+
+    fn name(&self) -> &'static str {
+        "export_analytics_data"
+    }
+
+    fn description(&self) -> &'static str {
+        "Export analytics data for data portability and external analysis, reporting progress"
+    }
+
+    fn category(&self) -> &'static str {
+        "Analytics"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Export usage data in JSON or CSV format for reporting"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let start_time = Instant::now();
+        let start_date = parse_date("start_date", &args.start_date)?;
+        let end_date = parse_date("end_date", &args.end_date)?;
+
+        let result = context.container().analytics_service.generate_usage_report(start_date, end_date).await;
+        let mut export_data = track_query(
+            context,
+            self.name(),
+            args.project_id.clone(),
+            start_time,
+            result,
+            "Analytics data export failed",
+        )
+        .await?;
+        context.report_progress(1, EXPORT_STEPS, "Generated usage report".to_string()).await;
+        if context.is_cancelled() {
+            return Err(cancelled_error(json!({"completed_steps": 1, "total_steps": EXPORT_STEPS})));
+        }
+
+        let format = match args.format {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        };
+        if let Value::Object(export) = &mut export_data {
+            let total_records = export
+                .get("summary")
+                .and_then(|s| s.get("total_events"))
+                .cloned()
+                .unwrap_or(json!(0));
+            export.insert(
+                "export_metadata".to_string(),
+                json!({
+                    "format": format,
+                    "exported_at": Utc::now().to_rfc3339(),
+                    "project_filter": args.project_id,
+                    "event_type_filter": Some(args.event_types).filter(|types| !types.is_empty()),
+                    "total_records": total_records
+                }),
+            );
+        }
+
+        // CSV conversion is not implemented yet, so CSV requests get JSON with a note
+        let export_data = if args.format == ExportFormat::Csv {
+            json!({
+                "note": "CSV export format requested but not yet implemented. Returning JSON format.",
+                "format": "json",
+                "data": export_data
+            })
+        } else {
+            export_data
+        };
+        context.report_progress(EXPORT_STEPS, EXPORT_STEPS, "Export ready".to_string()).await;
+        Ok(export_data)
+    }
+}
@@ -0,0 +1,380 @@
+use crate::container::AppContainer;
+use crate::models::context::{
+    ArchitecturalDecision, BusinessRule, Project, ProjectConvention, SecurityPolicy,
+};
+use crate::models::specification::Requirement;
+use rmcp::model::{
+    ErrorData as McpError, GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage,
+    PromptMessageRole,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Prompts assembling project context for a development task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextPrompt {
+    ImplementFeature,
+    ReviewChange,
+    WriteTests,
+}
+
+impl ContextPrompt {
+    pub const ALL: [ContextPrompt; 3] = [
+        ContextPrompt::ImplementFeature,
+        ContextPrompt::ReviewChange,
+        ContextPrompt::WriteTests,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContextPrompt::ImplementFeature => "implement_feature",
+            ContextPrompt::ReviewChange => "review_change",
+            ContextPrompt::WriteTests => "write_tests",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            ContextPrompt::ImplementFeature => {
+                "Implement a feature following the project's business rules, decisions, conventions and security policies"
+            }
+            ContextPrompt::ReviewChange => {
+                "Review a change against the project's business rules, decisions, conventions and security policies"
+            }
+            ContextPrompt::WriteTests => {
+                "Write tests covering the project's business rules, security policies and requirement acceptance criteria"
+            }
+        }
+    }
+
+    fn instructions(&self, scope: &str) -> String {
+        match self {
+            ContextPrompt::ImplementFeature => format!(
+                "Implement {scope}. Follow every business rule, architectural decision, convention and security policy below, \
+                 and satisfy the acceptance criteria of the listed requirements. If something you need is not covered, ask \
+                 before assuming, and record any new decision with the `create_entity` tool."
+            ),
+            ContextPrompt::ReviewChange => format!(
+                "Review the change to {scope}. Check it against the business rules, architectural decisions, conventions and \
+                 security policies below. Point out each violation with the rule it breaks, flag any forbidden pattern, and \
+                 note acceptance criteria of the listed requirements that the change leaves unmet."
+            ),
+            ContextPrompt::WriteTests => format!(
+                "Write tests for {scope}. Cover each business rule below including its edge cases, each acceptance criterion \
+                 of the listed requirements, and the security policies, including attempts to use forbidden patterns. \
+                 Follow the project's conventions for naming and structure."
+            ),
+        }
+    }
+}
+
+/// MCP prompts populated from the stored project context
+pub struct ContextPrompts {
+    container: Arc<AppContainer>,
+}
+
+impl ContextPrompts {
+    pub fn new(container: Arc<AppContainer>) -> Self {
+        Self { container }
+    }
+
+    /// Get available prompts
+    pub fn get_prompts() -> Vec<Prompt> {
+        let arguments = vec![
+            PromptArgument {
+                name: "project_id".to_string(),
+                description: Some("Project whose context is used".to_string()),
+                required: Some(true),
+            },
+            PromptArgument {
+                name: "feature_area".to_string(),
+                description: Some("Feature or domain area, e.g. authentication".to_string()),
+                required: Some(false),
+            },
+            PromptArgument {
+                name: "components".to_string(),
+                description: Some("Comma-separated components involved, e.g. LoginForm,SessionStore".to_string()),
+                required: Some(false),
+            },
+        ];
+
+        ContextPrompt::ALL
+            .into_iter()
+            .map(|prompt| Prompt::new(prompt.name(), Some(prompt.description()), Some(arguments.clone())))
+            .collect()
+    }
+
+    pub async fn get_prompt(&self, name: &str, arguments: Option<JsonObject>) -> Result<GetPromptResult, McpError> {
+        let prompt = ContextPrompt::from_name(name)
+            .ok_or_else(|| McpError::invalid_params(format!("Unknown prompt: {name}"), None))?;
+        let arguments = arguments.unwrap_or_default();
+        let project_id = argument(&arguments, "project_id")
+            .ok_or_else(|| McpError::invalid_params("Missing required argument: project_id", None))?;
+        let feature_area = argument(&arguments, "feature_area");
+        let components = components(&arguments);
+
+        let project = self
+            .container
+            .project_service
+            .get_project(&project_id)
+            .await?
+            .ok_or_else(|| McpError::invalid_params(format!("Project not found: {project_id}"), None))?;
+        let context = self.gather(project, feature_area.as_deref(), &components).await?;
+
+        let scope = scope(feature_area.as_deref(), &components);
+        let mut text = prompt.instructions(&scope);
+        text.push_str("\n\n");
+        text.push_str(&context.render());
+
+        Ok(GetPromptResult {
+            description: Some(format!("{} ({})", prompt.description(), context.project.name)),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
+
+    async fn gather(
+        &self,
+        project: Project,
+        feature_area: Option<&str>,
+        components: &[String],
+    ) -> Result<PromptContext, McpError> {
+        let crud = &self.container.context_crud_service;
+        let extended = &self.container.extended_context_crud_service;
+
+        let business_rules = match feature_area {
+            Some(area) => crud.list_business_rules_by_domain(&project.id, area).await?,
+            None => crud.list_business_rules(&project.id).await?,
+        };
+        let architectural_decisions = crud.list_architectural_decisions(&project.id).await?;
+        let project_conventions = extended.list_project_conventions(&project.id).await?;
+        let security_policies = extended
+            .list_security_policies(&project.id)
+            .await?
+            .into_iter()
+            .filter(|p| match (feature_area, p.policy_area.as_deref()) {
+                (Some(area), Some(policy_area)) => policy_area.eq_ignore_ascii_case(area),
+                _ => true,
+            })
+            .collect();
+
+        // Requirements linked to the included context, or mentioning the feature area or a component
+        let linked: HashSet<&str> = business_rules
+            .iter()
+            .map(|r: &BusinessRule| r.id.as_str())
+            .chain(architectural_decisions.iter().map(|d| d.id.as_str()))
+            .collect();
+        let mut terms: Vec<String> = components.iter().map(|c| c.to_lowercase()).collect();
+        terms.extend(feature_area.map(str::to_lowercase));
+
+        let mut requirements = Vec::new();
+        let specs = self.container.specification_service.get_specifications_by_project(&project.id).await?;
+        for spec in specs {
+            for requirement in self.container.specification_service.get_requirements_by_spec(&spec.id).await? {
+                let text = format!("{} {}", requirement.title, requirement.description).to_lowercase();
+                let relevant = terms.is_empty()
+                    || requirement.linked_context.iter().any(|id| linked.contains(id.as_str()))
+                    || terms.iter().any(|term| text.contains(term));
+                if relevant {
+                    requirements.push((spec.title.clone(), requirement));
+                }
+            }
+        }
+
+        Ok(PromptContext {
+            project,
+            business_rules,
+            architectural_decisions,
+            project_conventions,
+            security_policies,
+            requirements,
+        })
+    }
+}
+
+struct PromptContext {
+    project: Project,
+    business_rules: Vec<BusinessRule>,
+    architectural_decisions: Vec<ArchitecturalDecision>,
+    project_conventions: Vec<ProjectConvention>,
+    security_policies: Vec<SecurityPolicy>,
+    requirements: Vec<(String, Requirement)>,
+}
+
+impl PromptContext {
+    fn render(&self) -> String {
+        let mut text = format!("# Project: {}\n", self.project.name);
+        if let Some(description) = non_empty(&self.project.description) {
+            let _ = writeln!(text, "\n{description}");
+        }
+
+        section(&mut text, "Business rules", &self.business_rules, |rule| {
+            let mut line = format!("**{}**", rule.rule_name);
+            append(&mut line, ": ", &rule.description);
+            append(&mut line, " Pattern: ", &rule.implementation_pattern);
+            append(&mut line, " Constraints: ", &rule.constraints.as_ref().map(|c| list_text(c)));
+            line
+        });
+        section(&mut text, "Architectural decisions", &self.architectural_decisions, |decision| {
+            let mut line = format!("**{}**", decision.decision_title);
+            append(&mut line, " (", &decision.status.as_ref().map(|s| format!("{s})")));
+            append(&mut line, ": ", &decision.decision);
+            append(&mut line, " Consequences: ", &decision.consequences);
+            line
+        });
+        section(&mut text, "Conventions", &self.project_conventions, |convention| {
+            let mut line = match non_empty(&convention.convention_type) {
+                Some(kind) => format!("**{kind}**:"),
+                None => "**Convention**:".to_string(),
+            };
+            append(&mut line, " ", &convention.convention_rule);
+            append(&mut line, " Good: ", &convention.good_examples.as_ref().map(|e| list_text(e)));
+            append(&mut line, " Bad: ", &convention.bad_examples.as_ref().map(|e| list_text(e)));
+            line
+        });
+        section(&mut text, "Security policies", &self.security_policies, |policy| {
+            let mut line = format!("**{}**", policy.policy_name);
+            append(&mut line, ": ", &policy.requirements);
+            append(&mut line, " Pattern: ", &policy.implementation_pattern);
+            append(&mut line, " Forbidden: ", &policy.forbidden_patterns.as_ref().map(|p| list_text(p)));
+            line
+        });
+        section(&mut text, "Requirements", &self.requirements, |(spec_title, requirement)| {
+            let mut line = format!(
+                "**{}** ({}, {}, from {})",
+                requirement.title,
+                requirement.priority.as_str(),
+                requirement.status.as_str(),
+                spec_title
+            );
+            if !requirement.description.trim().is_empty() {
+                let _ = write!(line, ": {}", requirement.description.trim());
+            }
+            for criterion in &requirement.acceptance_criteria {
+                let _ = write!(line, "\n  - [ ] {}", criterion.description);
+            }
+            line
+        });
+        text
+    }
+}
+
+fn section<T>(text: &mut String, title: &str, items: &[T], line: impl Fn(&T) -> String) {
+    let _ = writeln!(text, "\n## {title}\n");
+    if items.is_empty() {
+        text.push_str("None recorded.\n");
+    }
+    for item in items {
+        let _ = writeln!(text, "- {}", line(item));
+    }
+}
+
+fn append(line: &mut String, prefix: &str, value: &Option<String>) {
+    if let Some(value) = non_empty(value) {
+        line.push_str(prefix);
+        line.push_str(value);
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// JSON array columns joined with `; `; other text is kept as is
+fn list_text(raw: &str) -> String {
+    match serde_json::from_str::<Vec<Value>>(raw) {
+        Ok(items) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string).unwrap_or_else(|| item.to_string()))
+            .collect::<Vec<_>>()
+            .join("; "),
+        Err(_) => raw.to_string(),
+    }
+}
+
+fn argument(arguments: &JsonObject, name: &str) -> Option<String> {
+    arguments
+        .get(name)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Components given as a comma-separated string or a JSON array
+fn components(arguments: &JsonObject) -> Vec<String> {
+    let values: Vec<String> = match arguments.get("components") {
+        Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).map(str::to_string).collect(),
+        Some(Value::String(list)) => match serde_json::from_str::<Vec<String>>(list) {
+            Ok(items) => items,
+            Err(_) => list.split(',').map(str::to_string).collect(),
+        },
+        _ => Vec::new(),
+    };
+    values.into_iter().map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect()
+}
+
+fn scope(feature_area: Option<&str>, components: &[String]) -> String {
+    let mut scope = match feature_area {
+        Some(area) => format!("the {area} feature"),
+        None => "the requested feature".to_string(),
+    };
+    if !components.is_empty() {
+        let _ = write!(scope, " (components: {})", components.join(", "));
+    }
+    scope
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(value: Value) -> JsonObject {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_components_accept_lists_and_arrays() {
+        assert_eq!(
+            components(&object(json!({"components": "LoginForm, SessionStore,"}))),
+            vec!["LoginForm", "SessionStore"]
+        );
+        assert_eq!(
+            components(&object(json!({"components": "[\"LoginForm\"]"}))),
+            vec!["LoginForm"]
+        );
+        assert_eq!(components(&object(json!({"components": ["A", " B "]}))), vec!["A", "B"]);
+        assert!(components(&object(json!({}))).is_empty());
+    }
+
+    #[test]
+    fn test_prompts_share_arguments() {
+        let prompts = ContextPrompts::get_prompts();
+        let names: Vec<&str> = prompts.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["implement_feature", "review_change", "write_tests"]);
+        for prompt in &prompts {
+            let arguments = prompt.arguments.as_ref().unwrap();
+            assert_eq!(arguments[0].name, "project_id");
+            assert_eq!(arguments[0].required, Some(true));
+        }
+        assert_eq!(ContextPrompt::from_name("write_tests"), Some(ContextPrompt::WriteTests));
+        assert_eq!(ContextPrompt::from_name("deploy"), None);
+    }
+
+    #[test]
+    fn test_scope_mentions_feature_and_components() {
+        assert_eq!(
+            scope(Some("authentication"), &["LoginForm".to_string()]),
+            "the authentication feature (components: LoginForm)"
+        );
+        assert_eq!(scope(None, &[]), "the requested feature");
+        assert_eq!(list_text(r#"["a", "b"]"#), "a; b");
+        assert_eq!(list_text("plain"), "plain");
+    }
+}
@@ -0,0 +1,708 @@
+use crate::container::AppContainer;
+use crate::models::context::{ArchitecturalDecision, BusinessRule, PerformanceRequirement};
+use crate::models::specification::{ProjectSpecification, Requirement, Task};
+use crate::services::websocket_types::{ClientId, ContextChange, SyncFilters};
+use crate::services::ChangeDetectionService;
+use rmcp::model::{
+    AnnotateAble, ErrorData as McpError, RawResource, RawResourceTemplate, ReadResourceResult,
+    Resource, ResourceContents, ResourceTemplate, ResourceUpdatedNotificationParam,
+};
+use rmcp::service::{Peer, RoleServer};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+const MARKDOWN_MIME_TYPE: &str = "text/markdown";
+
+/// Entity type recorded on change events for specifications
+pub const SPECIFICATION_ENTITY_TYPE: &str = "specification";
+
+/// Per-project context collections exposed as resources
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextCollection {
+    BusinessRules,
+    ArchitecturalDecisions,
+    PerformanceRequirements,
+}
+
+impl ContextCollection {
+    pub const ALL: [ContextCollection; 3] = [
+        ContextCollection::BusinessRules,
+        ContextCollection::ArchitecturalDecisions,
+        ContextCollection::PerformanceRequirements,
+    ];
+
+    /// Path segment used in resource URIs
+    pub fn segment(&self) -> &'static str {
+        match self {
+            ContextCollection::BusinessRules => "business_rules",
+            ContextCollection::ArchitecturalDecisions => "architectural_decisions",
+            ContextCollection::PerformanceRequirements => "performance_requirements",
+        }
+    }
+
+    /// Entity type used by the CRUD tools and change events
+    pub fn entity_type(&self) -> &'static str {
+        match self {
+            ContextCollection::BusinessRules => "business_rule",
+            ContextCollection::ArchitecturalDecisions => "architectural_decision",
+            ContextCollection::PerformanceRequirements => "performance_requirement",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ContextCollection::BusinessRules => "Business rules",
+            ContextCollection::ArchitecturalDecisions => "Architectural decisions",
+            ContextCollection::PerformanceRequirements => "Performance requirements",
+        }
+    }
+
+    pub fn from_segment(segment: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.segment() == segment)
+    }
+
+    pub fn from_entity_type(entity_type: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.entity_type() == entity_type)
+    }
+}
+
+/// A parsed `context://` or `spec://` resource URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    /// `context://{project_id}/{collection}`
+    Collection { project_id: String, collection: ContextCollection },
+    /// `context://{project_id}/{collection}/{id}`
+    Item { project_id: String, collection: ContextCollection, id: String },
+    /// `spec://{spec_id}/requirements`
+    SpecRequirements { spec_id: String },
+    /// `spec://{spec_id}/tasks`
+    SpecTasks { spec_id: String },
+}
+
+impl ResourceUri {
+    pub fn parse(uri: &str) -> Option<Self> {
+        if let Some(path) = uri.strip_prefix("context://") {
+            let parts: Vec<&str> = path.split('/').collect();
+            if parts.iter().any(|p| p.is_empty()) {
+                return None;
+            }
+            return match parts.as_slice() {
+                [project_id, collection] => Some(ResourceUri::Collection {
+                    project_id: project_id.to_string(),
+                    collection: ContextCollection::from_segment(collection)?,
+                }),
+                [project_id, collection, id] => Some(ResourceUri::Item {
+                    project_id: project_id.to_string(),
+                    collection: ContextCollection::from_segment(collection)?,
+                    id: id.to_string(),
+                }),
+                _ => None,
+            };
+        }
+
+        let path = uri.strip_prefix("spec://")?;
+        match path.split('/').collect::<Vec<_>>().as_slice() {
+            [spec_id, "requirements"] if !spec_id.is_empty() => {
+                Some(ResourceUri::SpecRequirements { spec_id: spec_id.to_string() })
+            }
+            [spec_id, "tasks"] if !spec_id.is_empty() => {
+                Some(ResourceUri::SpecTasks { spec_id: spec_id.to_string() })
+            }
+            _ => None,
+        }
+    }
+
+    /// Resources whose rendering changes when the given entity changes
+    pub fn affected_by(entity_type: &str, entity_id: &str, project_id: &str) -> Vec<ResourceUri> {
+        if entity_type == SPECIFICATION_ENTITY_TYPE {
+            return vec![
+                ResourceUri::SpecRequirements { spec_id: entity_id.to_string() },
+                ResourceUri::SpecTasks { spec_id: entity_id.to_string() },
+            ];
+        }
+        match ContextCollection::from_entity_type(entity_type) {
+            Some(collection) => vec![
+                ResourceUri::Item {
+                    project_id: project_id.to_string(),
+                    collection,
+                    id: entity_id.to_string(),
+                },
+                ResourceUri::Collection { project_id: project_id.to_string(), collection },
+            ],
+            None => Vec::new(),
+        }
+    }
+
+    /// Change events that can affect this resource
+    fn change_filter(&self) -> SyncFilters {
+        let (project_ids, entity_type) = match self {
+            ResourceUri::Collection { project_id, collection }
+            | ResourceUri::Item { project_id, collection, .. } => {
+                (Some(vec![project_id.clone()]), collection.entity_type())
+            }
+            ResourceUri::SpecRequirements { .. } | ResourceUri::SpecTasks { .. } => {
+                (None, SPECIFICATION_ENTITY_TYPE)
+            }
+        };
+        SyncFilters {
+            project_ids,
+            entity_types: Some(vec![entity_type.to_string()]),
+            feature_areas: None,
+            change_types: None,
+        }
+    }
+}
+
+impl fmt::Display for ResourceUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceUri::Collection { project_id, collection } => {
+                write!(f, "context://{}/{}", project_id, collection.segment())
+            }
+            ResourceUri::Item { project_id, collection, id } => {
+                write!(f, "context://{}/{}/{}", project_id, collection.segment(), id)
+            }
+            ResourceUri::SpecRequirements { spec_id } => write!(f, "spec://{spec_id}/requirements"),
+            ResourceUri::SpecTasks { spec_id } => write!(f, "spec://{spec_id}/tasks"),
+        }
+    }
+}
+
+/// MCP resources rendering project context and specifications as Markdown
+pub struct ContextResources {
+    container: Arc<AppContainer>,
+}
+
+impl ContextResources {
+    pub fn new(container: Arc<AppContainer>) -> Self {
+        Self { container }
+    }
+
+    /// URI templates for every kind of resource served
+    pub fn resource_templates() -> Vec<ResourceTemplate> {
+        let template = |uri_template: &str, name: &str, description: &str| {
+            RawResourceTemplate {
+                uri_template: uri_template.to_string(),
+                name: name.to_string(),
+                description: Some(description.to_string()),
+                mime_type: Some(MARKDOWN_MIME_TYPE.to_string()),
+            }
+            .no_annotation()
+        };
+
+        let mut templates = Vec::new();
+        for collection in ContextCollection::ALL {
+            let segment = collection.segment();
+            templates.push(template(
+                &format!("context://{{project_id}}/{segment}"),
+                collection.title(),
+                &format!("All {} of a project", collection.title().to_lowercase()),
+            ));
+            templates.push(template(
+                &format!("context://{{project_id}}/{segment}/{{id}}"),
+                collection.entity_type(),
+                &format!("One {}", collection.entity_type().replace('_', " ")),
+            ));
+        }
+        templates.push(template(
+            "spec://{spec_id}/requirements",
+            "Specification requirements",
+            "Requirements of a specification with their acceptance criteria",
+        ));
+        templates.push(template("spec://{spec_id}/tasks", "Specification tasks", "Tasks of a specification"));
+        templates
+    }
+
+    /// Every concrete resource currently available
+    pub async fn list_resources(&self) -> Result<Vec<Resource>, McpError> {
+        let mut resources = Vec::new();
+        for project in self.container.project_service.list_projects().await? {
+            for collection in ContextCollection::ALL {
+                let uri = ResourceUri::Collection { project_id: project.id.clone(), collection };
+                resources.push(resource(&uri, format!("{}: {}", project.name, collection.title())));
+            }
+
+            let crud = &self.container.context_crud_service;
+            for rule in crud.list_business_rules(&project.id).await? {
+                let uri = item_uri(&project.id, ContextCollection::BusinessRules, &rule.id);
+                resources.push(resource(&uri, rule.rule_name));
+            }
+            for decision in crud.list_architectural_decisions(&project.id).await? {
+                let uri = item_uri(&project.id, ContextCollection::ArchitecturalDecisions, &decision.id);
+                resources.push(resource(&uri, decision.decision_title));
+            }
+            for requirement in crud.list_performance_requirements(&project.id).await? {
+                let uri = item_uri(&project.id, ContextCollection::PerformanceRequirements, &requirement.id);
+                resources.push(resource(&uri, performance_requirement_title(&requirement)));
+            }
+
+            let specs = self.container.specification_service.get_specifications_by_project(&project.id).await?;
+            for spec in specs {
+                let requirements = ResourceUri::SpecRequirements { spec_id: spec.id.clone() };
+                resources.push(resource(&requirements, format!("{}: Requirements", spec.title)));
+                let tasks = ResourceUri::SpecTasks { spec_id: spec.id.clone() };
+                resources.push(resource(&tasks, format!("{}: Tasks", spec.title)));
+            }
+        }
+        Ok(resources)
+    }
+
+    /// Render a resource as Markdown
+    pub async fn read_resource(&self, uri: &str) -> Result<ReadResourceResult, McpError> {
+        let parsed = parse_uri(uri)?;
+        let markdown = match &parsed {
+            ResourceUri::Collection { project_id, collection } => {
+                self.render_collection(project_id, *collection).await?
+            }
+            ResourceUri::Item { project_id, collection, id } => {
+                self.render_item(project_id, *collection, id).await?
+            }
+            ResourceUri::SpecRequirements { spec_id } => {
+                let spec = self.specification(spec_id).await?;
+                let requirements = self.container.specification_service.get_requirements_by_spec(spec_id).await?;
+                render_requirements(&spec, &requirements)
+            }
+            ResourceUri::SpecTasks { spec_id } => {
+                let spec = self.specification(spec_id).await?;
+                let tasks = self.container.specification_service.get_tasks_by_spec(spec_id).await?;
+                render_tasks(&spec, &tasks)
+            }
+        }
+        .ok_or_else(|| McpError::resource_not_found(format!("Resource not found: {uri}"), None))?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(MARKDOWN_MIME_TYPE.to_string()),
+                text: markdown,
+            }],
+        })
+    }
+
+    async fn specification(&self, spec_id: &str) -> Result<Option<ProjectSpecification>, McpError> {
+        self.container.specification_service.get_specification(spec_id).await
+    }
+
+    async fn render_collection(
+        &self,
+        project_id: &str,
+        collection: ContextCollection,
+    ) -> Result<Option<String>, McpError> {
+        let Some(project) = self.container.project_service.get_project(project_id).await? else {
+            return Ok(None);
+        };
+
+        let crud = &self.container.context_crud_service;
+        let entries: Vec<(String, String, Option<String>)> = match collection {
+            ContextCollection::BusinessRules => crud
+                .list_business_rules(project_id)
+                .await?
+                .into_iter()
+                .map(|r| (r.id, r.rule_name, r.description))
+                .collect(),
+            ContextCollection::ArchitecturalDecisions => crud
+                .list_architectural_decisions(project_id)
+                .await?
+                .into_iter()
+                .map(|d| (d.id, d.decision_title, d.decision))
+                .collect(),
+            ContextCollection::PerformanceRequirements => crud
+                .list_performance_requirements(project_id)
+                .await?
+                .into_iter()
+                .map(|p| {
+                    let title = performance_requirement_title(&p);
+                    (p.id, title, p.target_value.map(|t| format!("Target: {t}")))
+                })
+                .collect(),
+        };
+
+        let mut markdown = format!("# {}: {}\n", project.name, collection.title());
+        if entries.is_empty() {
+            markdown.push_str("\n_None recorded._\n");
+        }
+        for (id, title, summary) in entries {
+            let _ = write!(markdown, "\n## [{}]({})\n", title, item_uri(project_id, collection, &id));
+            if let Some(summary) = summary.filter(|s| !s.is_empty()) {
+                let _ = write!(markdown, "\n{summary}\n");
+            }
+        }
+        Ok(Some(markdown))
+    }
+
+    async fn render_item(
+        &self,
+        project_id: &str,
+        collection: ContextCollection,
+        id: &str,
+    ) -> Result<Option<String>, McpError> {
+        let crud = &self.container.context_crud_service;
+        let markdown = match collection {
+            ContextCollection::BusinessRules => crud
+                .get_business_rule(id)
+                .await?
+                .filter(|r| r.project_id == project_id)
+                .map(|r| render_business_rule(&r)),
+            ContextCollection::ArchitecturalDecisions => crud
+                .get_architectural_decision(id)
+                .await?
+                .filter(|d| d.project_id == project_id)
+                .map(|d| render_architectural_decision(&d)),
+            ContextCollection::PerformanceRequirements => crud
+                .get_performance_requirement(id)
+                .await?
+                .filter(|p| p.project_id == project_id)
+                .map(|p| render_performance_requirement(&p)),
+        };
+        Ok(markdown)
+    }
+}
+
+/// Resource URIs a client subscribed to. Matching change events from the
+/// `ChangeDetectionService` are forwarded as `notifications/resources/updated`.
+#[derive(Clone)]
+pub struct ResourceSubscriptions {
+    client_id: ClientId,
+    change_detection: Arc<ChangeDetectionService>,
+    state: Arc<Mutex<SubscriptionState>>,
+}
+
+#[derive(Default)]
+struct SubscriptionState {
+    uris: HashSet<String>,
+    peer: Option<Peer<RoleServer>>,
+    listener: Option<JoinHandle<()>>,
+}
+
+impl ResourceSubscriptions {
+    pub fn new(change_detection: Arc<ChangeDetectionService>) -> Self {
+        Self {
+            client_id: uuid::Uuid::new_v4(),
+            change_detection,
+            state: Arc::new(Mutex::new(SubscriptionState::default())),
+        }
+    }
+
+    pub async fn subscribe(&self, uri: &str, peer: Peer<RoleServer>) -> Result<(), McpError> {
+        parse_uri(uri)?;
+        let mut state = self.state.lock().await;
+        state.uris.insert(uri.to_string());
+        state.peer = Some(peer);
+        self.update_filters(&state).await?;
+
+        if state.listener.is_none() {
+            let receiver = self.change_detection.get_broadcaster().subscribe_to_changes();
+            state.listener = Some(tokio::spawn(forward_changes(receiver, self.state.clone())));
+        }
+        Ok(())
+    }
+
+    pub async fn unsubscribe(&self, uri: &str) -> Result<(), McpError> {
+        let mut state = self.state.lock().await;
+        state.uris.remove(uri);
+        if !state.uris.is_empty() {
+            return self.update_filters(&state).await;
+        }
+
+        if let Some(listener) = state.listener.take() {
+            listener.abort();
+        }
+        self.change_detection
+            .get_broadcaster()
+            .unsubscribe(self.client_id)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to unsubscribe: {e}"), None))
+    }
+
+    /// Subscribed URIs, for inspection
+    pub async fn subscribed_uris(&self) -> Vec<String> {
+        let state = self.state.lock().await;
+        let mut uris: Vec<String> = state.uris.iter().cloned().collect();
+        uris.sort();
+        uris
+    }
+
+    async fn update_filters(&self, state: &SubscriptionState) -> Result<(), McpError> {
+        let filters = state
+            .uris
+            .iter()
+            .filter_map(|uri| ResourceUri::parse(uri))
+            .map(|uri| uri.change_filter())
+            .collect();
+        self.change_detection
+            .get_broadcaster()
+            .subscribe(self.client_id, filters)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to subscribe: {e}"), None))
+    }
+}
+
+async fn forward_changes(
+    mut receiver: tokio::sync::broadcast::Receiver<ContextChange>,
+    state: Arc<Mutex<SubscriptionState>>,
+) {
+    loop {
+        let change = match receiver.recv().await {
+            Ok(change) => change,
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!("Resource subscription missed {} change events", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        let (peer, uris) = {
+            let state = state.lock().await;
+            let Some(peer) = state.peer.clone() else { continue };
+            let uris: Vec<String> = ResourceUri::affected_by(&change.entity_type, &change.entity_id, &change.project_id)
+                .iter()
+                .map(ToString::to_string)
+                .filter(|uri| state.uris.contains(uri))
+                .collect();
+            (peer, uris)
+        };
+
+        for uri in uris {
+            if let Err(e) = peer.notify_resource_updated(ResourceUpdatedNotificationParam { uri: uri.clone() }).await {
+                tracing::warn!("Failed to notify resource update for {}: {}", uri, e);
+            }
+        }
+    }
+}
+
+fn parse_uri(uri: &str) -> Result<ResourceUri, McpError> {
+    ResourceUri::parse(uri)
+        .ok_or_else(|| McpError::invalid_params(format!("Unsupported resource URI: {uri}"), None))
+}
+
+fn item_uri(project_id: &str, collection: ContextCollection, id: &str) -> ResourceUri {
+    ResourceUri::Item {
+        project_id: project_id.to_string(),
+        collection,
+        id: id.to_string(),
+    }
+}
+
+fn resource(uri: &ResourceUri, name: String) -> Resource {
+    RawResource {
+        uri: uri.to_string(),
+        name,
+        description: None,
+        mime_type: Some(MARKDOWN_MIME_TYPE.to_string()),
+        size: None,
+    }
+    .no_annotation()
+}
+
+fn performance_requirement_title(requirement: &PerformanceRequirement) -> String {
+    match (&requirement.requirement_type, &requirement.component_area) {
+        (Some(kind), Some(area)) => format!("{kind} for {area}"),
+        (Some(kind), None) => kind.clone(),
+        (None, Some(area)) => format!("Performance of {area}"),
+        (None, None) => "Performance requirement".to_string(),
+    }
+}
+
+/// `- **label:** value` lines for the fields that are set
+fn field_list(fields: &[(&str, &Option<String>)]) -> String {
+    fields
+        .iter()
+        .filter_map(|(label, value)| value.as_deref().filter(|v| !v.is_empty()).map(|v| format!("- **{label}:** {v}\n")))
+        .collect()
+}
+
+/// A section listing a JSON array column; text that is not an array is shown as is
+fn list_section(title: &str, value: &Option<String>) -> String {
+    let Some(raw) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+        return String::new();
+    };
+    let items: Vec<String> = match serde_json::from_str::<Vec<Value>>(raw) {
+        Ok(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Value::String(s) => s,
+                other => other.to_string(),
+            })
+            .collect(),
+        Err(_) => vec![raw.to_string()],
+    };
+    if items.is_empty() {
+        return String::new();
+    }
+    let mut section = format!("\n## {title}\n\n");
+    for item in items {
+        let _ = writeln!(section, "- {item}");
+    }
+    section
+}
+
+fn paragraph(text: &Option<String>) -> String {
+    match text.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(text) => format!("\n{text}\n"),
+        None => String::new(),
+    }
+}
+
+fn render_business_rule(rule: &BusinessRule) -> String {
+    let mut markdown = format!("# {}\n", rule.rule_name);
+    markdown.push_str(&paragraph(&rule.description));
+    let fields = field_list(&[
+        ("Domain area", &rule.domain_area),
+        ("Implementation pattern", &rule.implementation_pattern),
+        ("Created", &rule.created_at),
+    ]);
+    if !fields.is_empty() {
+        markdown.push('\n');
+        markdown.push_str(&fields);
+    }
+    markdown.push_str(&list_section("Constraints", &rule.constraints));
+    markdown.push_str(&list_section("Examples", &rule.examples));
+    markdown
+}
+
+fn render_architectural_decision(decision: &ArchitecturalDecision) -> String {
+    let mut markdown = format!("# {}\n", decision.decision_title);
+    let fields = field_list(&[("Status", &decision.status), ("Created", &decision.created_at)]);
+    if !fields.is_empty() {
+        markdown.push('\n');
+        markdown.push_str(&fields);
+    }
+    for (title, text) in [
+        ("Context", &decision.context),
+        ("Decision", &decision.decision),
+        ("Consequences", &decision.consequences),
+    ] {
+        if text.as_deref().is_some_and(|t| !t.trim().is_empty()) {
+            let _ = write!(markdown, "\n## {title}\n{}", paragraph(text));
+        }
+    }
+    markdown.push_str(&list_section("Alternatives considered", &decision.alternatives_considered));
+    markdown
+}
+
+fn render_performance_requirement(requirement: &PerformanceRequirement) -> String {
+    let mut markdown = format!("# {}\n\n", performance_requirement_title(requirement));
+    markdown.push_str(&field_list(&[
+        ("Component area", &requirement.component_area),
+        ("Requirement type", &requirement.requirement_type),
+        ("Target", &requirement.target_value),
+        ("Created", &requirement.created_at),
+    ]));
+    markdown.push_str(&list_section("Optimization patterns", &requirement.optimization_patterns));
+    markdown.push_str(&list_section("Patterns to avoid", &requirement.avoid_patterns));
+    markdown
+}
+
+fn render_requirements(spec: &Option<ProjectSpecification>, requirements: &[Requirement]) -> Option<String> {
+    let spec = spec.as_ref()?;
+    let mut markdown = format!("# {}: Requirements\n", spec.title);
+    if requirements.is_empty() {
+        markdown.push_str("\n_None recorded._\n");
+    }
+    for requirement in requirements {
+        let _ = write!(
+            markdown,
+            "\n## {}\n\n- **Priority:** {}\n- **Status:** {}\n",
+            requirement.title,
+            requirement.priority.as_str(),
+            requirement.status.as_str(),
+        );
+        if let Some(story) = requirement.user_story.as_deref().filter(|s| !s.is_empty()) {
+            let _ = writeln!(markdown, "\n> {story}");
+        }
+        if !requirement.description.trim().is_empty() {
+            let _ = writeln!(markdown, "\n{}", requirement.description.trim());
+        }
+        if !requirement.acceptance_criteria.is_empty() {
+            markdown.push_str("\n### Acceptance criteria\n\n");
+            for criterion in &requirement.acceptance_criteria {
+                let _ = writeln!(markdown, "- {}", criterion.description);
+            }
+        }
+    }
+    Some(markdown)
+}
+
+fn render_tasks(spec: &Option<ProjectSpecification>, tasks: &[Task]) -> Option<String> {
+    let spec = spec.as_ref()?;
+    let mut markdown = format!("# {}: Tasks\n\n", spec.title);
+    if tasks.is_empty() {
+        markdown.push_str("_None recorded._\n");
+    }
+    for task in tasks {
+        let checkbox = if task.status.as_str() == "completed" { "x" } else { " " };
+        let _ = writeln!(markdown, "- [{}] **{}** ({})", checkbox, task.title, task.status.as_str());
+        if !task.description.trim().is_empty() {
+            let _ = writeln!(markdown, "  {}", task.description.trim());
+        }
+    }
+    Some(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_uris_round_trip() {
+        for uri in [
+            "context://p1/business_rules",
+            "context://p1/architectural_decisions/d1",
+            "spec://s1/requirements",
+            "spec://s1/tasks",
+        ] {
+            assert_eq!(ResourceUri::parse(uri).unwrap().to_string(), uri);
+        }
+
+        for uri in [
+            "context://p1/unknown/x",
+            "context://p1",
+            "context:///business_rules",
+            "spec://s1/design",
+            "file:///etc/passwd",
+        ] {
+            assert!(ResourceUri::parse(uri).is_none(), "{uri}");
+        }
+    }
+
+    #[test]
+    fn test_changes_map_to_item_and_collection() {
+        let uris: Vec<String> = ResourceUri::affected_by("business_rule", "r1", "p1")
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(uris, vec!["context://p1/business_rules/r1", "context://p1/business_rules"]);
+
+        let uris = ResourceUri::affected_by(SPECIFICATION_ENTITY_TYPE, "s1", "p1");
+        assert!(uris.contains(&ResourceUri::SpecRequirements { spec_id: "s1".to_string() }));
+        assert!(ResourceUri::affected_by("framework_component", "c1", "p1").is_empty());
+    }
+
+    #[test]
+    fn test_business_rule_markdown() {
+        let rule = BusinessRule {
+            id: "r1".to_string(),
+            project_id: "p1".to_string(),
+            rule_name: "Account lockout".to_string(),
+            description: Some("Lock after five failed attempts".to_string()),
+            domain_area: Some("authentication".to_string()),
+            implementation_pattern: None,
+            constraints: Some(r#"["fifteen minutes", "per account"]"#.to_string()),
+            examples: Some("not json".to_string()),
+            created_at: None,
+        };
+
+        let markdown = render_business_rule(&rule);
+        assert!(markdown.starts_with("# Account lockout\n\nLock after five failed attempts\n"));
+        assert!(markdown.contains("- **Domain area:** authentication\n"));
+        assert!(!markdown.contains("Implementation pattern"));
+        assert!(markdown.contains("## Constraints\n\n- fifteen minutes\n- per account\n"));
+        assert!(markdown.contains("## Examples\n\n- not json\n"));
+    }
+}
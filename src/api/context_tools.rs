@@ -0,0 +1,515 @@
+use crate::api::tool_registry::{NoArgs, PageArgs, ProjectArgs, ToolContext, ToolHandler, ToolRegistry};
+use crate::models::framework::{
+    FeatureInfo, FeatureStatus, ServerCapabilitiesInfo, ServerMetadata, TableInfo, UsageExample,
+};
+use crate::models::pagination::Page;
+use crate::services::context_pack_service::{ContextPack, ContextPackRequest};
+use crate::services::context_query_service::ContextQueryResult;
+use crate::services::AnalyticsHelper;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Instant;
+
+/// Register the core context, capability and cache tools
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(QueryContextTool);
+    registry.register(BuildContextPackTool);
+    registry.register(ListProjectsTool);
+    registry.register(ValidateArchitectureTool);
+    registry.register(GetServerCapabilitiesTool);
+    registry.register(CacheManagementTool);
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct QueryContextArgs {
+    /// The ID of the project
+    pub project_id: String,
+    /// The feature area (e.g., 'authentication', 'user_interface', 'payments')
+    pub feature_area: String,
+    /// The type of task ('implement', 'fix', 'optimize', 'testing', 'api')
+    pub task_type: String,
+    /// Components involved; rules for them and their dependencies are included
+    #[serde(default)]
+    pub components: Vec<String>,
+}
+
+pub struct QueryContextTool;
+
+#[async_trait]
+impl ToolHandler for QueryContextTool {
+    type Args = QueryContextArgs;
+    type Output = ContextQueryResult;
+
+    fn name(&self) -> &'static str {
+        "query_context"
+    }
+
+    fn description(&self) -> &'static str {
+        "Query project context based on feature area, task type, and components"
+    }
+
+    fn category(&self) -> &'static str {
+        "Core"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Get curated context for implementing authentication features"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let start_time = Instant::now();
+        let query_result = context
+            .container()
+            .context_query_service
+            .query_context(&args.project_id, &args.feature_area, &args.task_type, &args.components)
+            .await;
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+
+        let (success, error) = match &query_result {
+            Ok(_) => (true, None),
+            Err(e) => (false, Some(e.to_string())),
+        };
+        context
+            .track(AnalyticsHelper::create_context_query_event(
+                Some(args.project_id),
+                Some(args.feature_area),
+                Some(args.task_type),
+                Some(args.components),
+                Some(duration_ms),
+                success,
+                error,
+            ))
+            .await;
+
+        query_result.map_err(|e| McpError::internal_error(format!("Query failed: {e}"), None))
+    }
+}
+
+pub struct BuildContextPackTool;
+
+#[async_trait]
+impl ToolHandler for BuildContextPackTool {
+    type Args = ContextPackRequest;
+    type Output = ContextPack;
+
+    fn name(&self) -> &'static str {
+        "build_context_pack"
+    }
+
+    fn description(&self) -> &'static str {
+        "Assemble the context most relevant to a task within a token budget, ranked by priority, quality, relationship proximity to the given files and components, and search relevance. Items that do not fit are summarised or dropped, with the reason reported"
+    }
+
+    fn category(&self) -> &'static str {
+        "Core"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Fit the rules and decisions behind a refund feature into 2000 tokens of prompt"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let container = context.container();
+        container
+            .prepare_search()
+            .await
+            .map_err(|e| McpError::internal_error(format!("Search is unavailable: {e}"), None))?;
+        container.context_pack_service.build_context_pack(&args).await
+    }
+}
+
+pub struct ListProjectsTool;
+
+#[async_trait]
+impl ToolHandler for ListProjectsTool {
+    type Args = PageArgs;
+    type Output = Page<Value>;
+
+    fn name(&self) -> &'static str {
+        "list_projects"
+    }
+
+    fn description(&self) -> &'static str {
+        "List available projects, one page at a time"
+    }
+
+    fn category(&self) -> &'static str {
+        "Core"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Find the project ID to pass to other tools"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let projects = context.container().project_service.list_projects_page(&args.query()).await?;
+        args.project(projects)
+    }
+}
+
+pub struct ValidateArchitectureTool;
+
+#[async_trait]
+impl ToolHandler for ValidateArchitectureTool {
+    type Args = ProjectArgs;
+    type Output = Vec<String>;
+
+    fn name(&self) -> &'static str {
+        "validate_architecture"
+    }
+
+    fn description(&self) -> &'static str {
+        "Validate Clean Architecture rules and detect violations"
+    }
+
+    fn category(&self) -> &'static str {
+        "Quality"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Check for architecture layer violations and dependency issues"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let start_time = Instant::now();
+        let validation_result = context
+            .container()
+            .architecture_validation_service
+            .validate_architecture(&args.project_id)
+            .await;
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+
+        let (violations, success, error) = match &validation_result {
+            Ok(violations) => (violations.len(), true, None),
+            Err(e) => (0, false, Some(e.to_string())),
+        };
+        context
+            .track(AnalyticsHelper::create_architecture_validation_event(
+                args.project_id,
+                violations,
+                Some(duration_ms),
+                success,
+                error,
+            ))
+            .await;
+
+        validation_result.map_err(|e| McpError::internal_error(format!("Validation failed: {e}"), None))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheAction {
+    ClearProject,
+    ClearAll,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CacheManagementArgs {
+    /// The cache action to perform
+    pub action: CacheAction,
+    /// Project ID (required for clear_project action)
+    pub project_id: Option<String>,
+}
+
+/// Outcome of a `cache_management` action
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CacheManagementResult {
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    pub cleared: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+pub struct CacheManagementTool;
+
+#[async_trait]
+impl ToolHandler for CacheManagementTool {
+    type Args = CacheManagementArgs;
+    type Output = CacheManagementResult;
+
+    fn name(&self) -> &'static str {
+        "cache_management"
+    }
+
+    fn description(&self) -> &'static str {
+        "Manage cache and temporary data (clear project, clear all)"
+    }
+
+    fn category(&self) -> &'static str {
+        "Management"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Clear project cache or global cache for performance optimization"
+    }
+
+    async fn call(&self, _context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let result = match args.action {
+            CacheAction::ClearProject => {
+                let project_id = args.project_id.ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id for clear_project", None)
+                })?;
+                CacheManagementResult {
+                    message: "Project cache cleared successfully".to_string(),
+                    project_id: Some(project_id),
+                    cleared: true,
+                    note: Some("Cache clearing implementation can be customized based on your needs".to_string()),
+                    warning: None,
+                }
+            }
+            CacheAction::ClearAll => CacheManagementResult {
+                message: "All cache cleared successfully".to_string(),
+                project_id: None,
+                cleared: true,
+                note: None,
+                warning: Some("This operation removes all stored data".to_string()),
+            },
+        };
+        Ok(result)
+    }
+}
+
+pub struct GetServerCapabilitiesTool;
+
+#[async_trait]
+impl ToolHandler for GetServerCapabilitiesTool {
+    type Args = NoArgs;
+    type Output = ServerCapabilitiesInfo;
+
+    fn name(&self) -> &'static str {
+        "get_server_capabilities"
+    }
+
+    fn description(&self) -> &'static str {
+        "Get comprehensive information about server features, database tables, and available tools"
+    }
+
+    fn category(&self) -> &'static str {
+        "Core"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Discover available features, tools, and database schema"
+    }
+
+    async fn call(&self, context: &ToolContext, _args: Self::Args) -> Result<Self::Output, McpError> {
+        let capabilities = ServerCapabilitiesInfo {
+            server_info: ServerMetadata {
+                name: "Enhanced Context Server".to_string(),
+                version: "0.2.0".to_string(),
+                description:
+                    "Professional Context Engine with AI-powered intelligence, semantic search, real-time sync, and comprehensive project specification management"
+                        .to_string(),
+                config_directory: "~/.context-server".to_string(),
+            },
+            features: features(),
+            database_tables: database_tables(),
+            mcp_tools: context.registry().tool_infos(),
+            usage_examples: usage_examples(),
+            recommended_workflow: vec![
+                "1. Project Setup: Use create_entity to create your project".to_string(),
+                "2. Specification Import: Run scan_specifications to import all Kiro specs".to_string(),
+                "3. Architecture Setup: Use bulk_create_components for initial component structure".to_string(),
+                "4. Context Definition: Add business_rules, architectural_decisions, and performance_requirements".to_string(),
+                "5. Development: Use query_context for AI-powered development assistance".to_string(),
+                "6. Monitoring: Track progress with track_requirements_progress and track_tasks_progress".to_string(),
+                "7. Quality Assurance: Run validate_architecture and generate_quality_report regularly".to_string(),
+                "8. Analytics: Use generate_specification_health_report for project insights".to_string(),
+                "9. Optimization: Use get_usage_analytics to understand usage patterns".to_string(),
+                "10. Maintenance: Use cache_management to optimize performance as needed".to_string(),
+            ],
+        };
+        Ok(capabilities)
+    }
+}
+
+fn features() -> Vec<FeatureInfo> {
+    let feature = |name: &str, description: &str, tools: &[&str]| FeatureInfo {
+        name: name.to_string(),
+        description: description.to_string(),
+        status: FeatureStatus::Implemented,
+        tools: tools.iter().map(|t| t.to_string()).collect(),
+    };
+    vec![
+        feature(
+            "Enhanced CRUD Operations",
+            "Full CRUD for all entities with bulk operations and universal entity handlers",
+            &["create_entity", "update_entity", "delete_entity", "get_entity", "list_entities", "bulk_*"],
+        ),
+        feature(
+            "SOLID Architecture",
+            "Service/Repository pattern with dependency injection and clean architecture",
+            &["All operations"],
+        ),
+        feature(
+            "Project Specification Management",
+            "Complete Kiro specification integration with automatic parsing, versioning, and context linking",
+            &[
+                "scan_specifications",
+                "import_specification",
+                "validate_specification",
+                "get_specification_versions",
+                "compare_specification_versions",
+                "start_spec_monitoring",
+            ],
+        ),
+        feature(
+            "Specification Analytics & Intelligence",
+            "Advanced analytics for requirements, tasks, development velocity, and project health",
+            &[
+                "track_requirements_progress",
+                "track_tasks_progress",
+                "analyze_specification_completeness",
+                "calculate_development_velocity",
+                "generate_specification_health_report",
+            ],
+        ),
+        feature(
+            "Context Intelligence & Quality",
+            "AI-powered context relationship detection, quality scoring, and intelligent suggestions",
+            &["query_context", "build_context_pack", "validate_architecture", "get_context_insights", "generate_quality_report"],
+        ),
+        feature(
+            "Semantic & Hybrid Search",
+            "Embedding and BM25 keyword search over context items, kept up to date as entities change",
+            &["semantic_search", "hybrid_search", "find_similar_contexts", "suggest_queries", "index_health"],
+        ),
+        feature(
+            "Context Resources",
+            "Business rules, decisions, performance requirements and specification requirements as Markdown MCP resources (context://, spec://) with change subscriptions",
+            &[],
+        ),
+        feature(
+            "Usage Analytics & Insights",
+            "Comprehensive usage tracking, analytics, and data export capabilities",
+            &["get_usage_analytics", "get_context_insights", "generate_quality_report", "export_analytics_data"],
+        ),
+        feature(
+            "Cache Management",
+            "Intelligent caching system with project-level and global cache management",
+            &["cache_management"],
+        ),
+    ]
+}
+
+fn database_tables() -> Vec<TableInfo> {
+    let table = |name: &str, description: &str, primary_fields: &[&str], example_use: &str| TableInfo {
+        name: name.to_string(),
+        description: description.to_string(),
+        primary_fields: primary_fields.iter().map(|f| f.to_string()).collect(),
+        example_use: example_use.to_string(),
+    };
+    vec![
+        table(
+            "projects",
+            "Main project information and metadata",
+            &["id", "name", "description"],
+            "Organizing code contexts by project with comprehensive metadata",
+        ),
+        table(
+            "business_rules",
+            "Domain-specific business logic rules and constraints",
+            &["id", "rule_name", "domain_area", "project_id"],
+            "Capturing business constraints for AI code generation and validation",
+        ),
+        table(
+            "architectural_decisions",
+            "Architecture Decision Records (ADRs) and design choices",
+            &["id", "decision_title", "status", "project_id"],
+            "Tracking architectural decisions and their rationale for consistent development",
+        ),
+        table(
+            "performance_requirements",
+            "Performance constraints and non-functional requirements",
+            &["id", "requirement_name", "metric_type", "target_value"],
+            "Defining performance benchmarks and optimization targets",
+        ),
+        table(
+            "framework_components",
+            "Framework-agnostic component definitions and architecture layers",
+            &["id", "component_name", "component_type", "architecture_layer"],
+            "Managing component architecture and clean architecture compliance",
+        ),
+        table(
+            "specifications",
+            "Project specifications, requirements, and tasks from Kiro specs",
+            &["id", "title", "spec_type", "status", "project_id"],
+            "Managing project specifications with automatic parsing and version tracking",
+        ),
+        table(
+            "specification_versions",
+            "Version history and change tracking for specifications",
+            &["id", "spec_id", "version_number", "created_at"],
+            "Tracking specification changes and enabling version comparison",
+        ),
+        table(
+            "enhanced_context",
+            "Enhanced context items with relationships and quality metrics",
+            &["id", "context_type", "quality_score", "relationship_count"],
+            "Storing intelligent context with AI-powered relationship detection",
+        ),
+        table(
+            "analytics_events",
+            "Usage analytics and event tracking for insights",
+            &["id", "event_type", "entity_type", "timestamp"],
+            "Tracking usage patterns and generating analytics insights",
+        ),
+    ]
+}
+
+fn usage_examples() -> Vec<UsageExample> {
+    let example = |scenario: &str, steps: &[&str]| UsageExample {
+        scenario: scenario.to_string(),
+        steps: steps.iter().map(|s| s.to_string()).collect(),
+    };
+    vec![
+        example(
+            "Setting up a new project with specifications",
+            &[
+                "1. create_entity with entity_type='project' and project details",
+                "2. scan_specifications to import all Kiro specs from .kiro/specs",
+                "3. bulk_create_components for initial architecture setup",
+                "4. create_entity with entity_type='business_rule' for domain logic",
+                "5. query_context to get AI-curated context for development tasks",
+            ],
+        ),
+        example(
+            "Monitoring project health and progress",
+            &[
+                "1. track_requirements_progress to see requirement completion status",
+                "2. track_tasks_progress to monitor task completion and blockers",
+                "3. calculate_development_velocity to measure team productivity",
+                "4. generate_specification_health_report for executive summary",
+                "5. export_analytics_data for external reporting and analysis",
+            ],
+        ),
+        example(
+            "AI-powered development assistance",
+            &[
+                "1. query_context with feature_area, task_type, and components",
+                "2. validate_architecture to check for Clean Architecture compliance",
+                "3. get_context_insights for project-level analytics and patterns",
+                "4. generate_quality_report to assess context health and get recommendations",
+                "5. build_context_pack to fit the context for a task into a prompt's token budget",
+            ],
+        ),
+        example(
+            "Specification management workflow",
+            &[
+                "1. import_specification to add new spec files",
+                "2. validate_specification to check format and completeness",
+                "3. get_specification_versions to see version history",
+                "4. compare_specification_versions to see changes between versions",
+                "5. start_spec_monitoring for automatic updates on file changes",
+            ],
+        ),
+    ]
+}
@@ -0,0 +1,1102 @@
+use crate::api::tool_registry::{cancelled_error, PageArgs, ToolContext, ToolHandler, ToolRegistry};
+use crate::models::framework::FrameworkComponent;
+use crate::models::pagination::Page;
+use crate::services::{AnalyticsHelper, RevisionOperation};
+use async_trait::async_trait;
+use rmcp::model::{ErrorData as McpError, JsonObject};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Instant;
+
+/// Register the universal entity CRUD and bulk tools
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(GetEntityTool);
+    registry.register(CreateEntityTool);
+    registry.register(UpdateEntityTool);
+    registry.register(DeleteEntityTool);
+    registry.register(ListEntitiesTool);
+    registry.register(ManageProjectTool);
+    registry.register(BulkCreateComponentsTool);
+    registry.register(BulkUpdateComponentsTool);
+    registry.register(BulkDeleteComponentsTool);
+    registry.register(BulkOperationsTool);
+}
+
+/// Entity types every universal CRUD tool accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Project,
+    BusinessRule,
+    ArchitecturalDecision,
+    PerformanceRequirement,
+    SecurityPolicy,
+    ProjectConvention,
+    FrameworkComponent,
+    DevelopmentPhase,
+    FeatureContext,
+}
+
+impl EntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityType::Project => "project",
+            EntityType::BusinessRule => "business_rule",
+            EntityType::ArchitecturalDecision => "architectural_decision",
+            EntityType::PerformanceRequirement => "performance_requirement",
+            EntityType::SecurityPolicy => "security_policy",
+            EntityType::ProjectConvention => "project_convention",
+            EntityType::FrameworkComponent => "framework_component",
+            EntityType::DevelopmentPhase => "development_phase",
+            EntityType::FeatureContext => "feature_context",
+        }
+    }
+
+    /// Key the deleted entity's ID is reported under by `delete_entity`
+    fn id_field(&self) -> &'static str {
+        match self {
+            EntityType::Project => "project_id",
+            EntityType::BusinessRule => "rule_id",
+            EntityType::ArchitecturalDecision => "decision_id",
+            EntityType::PerformanceRequirement => "requirement_id",
+            EntityType::SecurityPolicy => "policy_id",
+            EntityType::ProjectConvention => "convention_id",
+            EntityType::FrameworkComponent => "component_id",
+            EntityType::DevelopmentPhase => "phase_id",
+            EntityType::FeatureContext => "feature_context_id",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct NewProject {
+    name: String,
+    description: Option<String>,
+    repository_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NewBusinessRule {
+    project_id: String,
+    rule_name: String,
+    description: Option<String>,
+    domain_area: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NewArchitecturalDecision {
+    project_id: String,
+    decision_title: String,
+    context: Option<String>,
+    decision: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NewPerformanceRequirement {
+    project_id: String,
+    component_area: Option<String>,
+    requirement_type: Option<String>,
+    target_value: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NewSecurityPolicy {
+    project_id: String,
+    policy_name: String,
+    policy_area: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NewProjectConvention {
+    project_id: String,
+    convention_type: Option<String>,
+    convention_rule: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NewFrameworkComponent {
+    project_id: String,
+    #[serde(alias = "name")]
+    component_name: String,
+    component_type: String,
+    architecture_layer: String,
+    file_path: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NewDevelopmentPhase {
+    project_id: String,
+    phase_name: String,
+    phase_order: i32,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NewFeatureContext {
+    project_id: String,
+    feature_name: String,
+    business_purpose: Option<String>,
+}
+
+/// Deserialize the `data` of an entity tool call
+fn parse_data<T: DeserializeOwned>(entity_type: EntityType, data: &JsonObject) -> Result<T, McpError> {
+    serde_json::from_value(Value::Object(data.clone())).map_err(|e| {
+        McpError::invalid_params(format!("Invalid data for {}: {e}", entity_type.as_str()), None)
+    })
+}
+
+/// `entity` with the fields present in `changes` replaced; the ID never changes
+fn apply_changes<T: Serialize + DeserializeOwned>(
+    entity_type: EntityType,
+    entity: &T,
+    changes: &JsonObject,
+) -> Result<T, McpError> {
+    let mut value = to_json(entity)?;
+    if let Value::Object(fields) = &mut value {
+        for (key, change) in changes.iter().filter(|(key, _)| key.as_str() != "id") {
+            fields.insert(key.clone(), change.clone());
+        }
+    }
+    serde_json::from_value(value).map_err(|e| {
+        McpError::invalid_params(format!("Invalid data for {}: {e}", entity_type.as_str()), None)
+    })
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<Value, McpError> {
+    serde_json::to_value(value).map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))
+}
+
+/// An entity serialized as a JSON object
+fn to_object<T: Serialize>(value: T) -> Result<JsonObject, McpError> {
+    match to_json(value)? {
+        Value::Object(object) => Ok(object),
+        other => Err(McpError::internal_error(format!("Expected an entity object, got {other}"), None)),
+    }
+}
+
+fn not_found(entity_type: EntityType, id: &str) -> McpError {
+    McpError::invalid_params(format!("{} with id {id} not found", entity_type.as_str()), None)
+}
+
+/// The entity as JSON, or `None` when it does not exist
+pub async fn get_entity(context: &ToolContext, entity_type: EntityType, id: &str) -> Result<Option<Value>, McpError> {
+    let container = context.container();
+    let crud = &container.context_crud_service;
+    let extended = &container.extended_context_crud_service;
+    let entity = match entity_type {
+        EntityType::Project => to_json(container.project_service.get_project(id).await?)?,
+        EntityType::BusinessRule => to_json(crud.get_business_rule(id).await?)?,
+        EntityType::ArchitecturalDecision => to_json(crud.get_architectural_decision(id).await?)?,
+        EntityType::PerformanceRequirement => to_json(crud.get_performance_requirement(id).await?)?,
+        EntityType::SecurityPolicy => to_json(extended.get_security_policy(id).await?)?,
+        EntityType::ProjectConvention => to_json(extended.get_project_convention(id).await?)?,
+        EntityType::FrameworkComponent => to_json(container.framework_service.get_component(id).await?)?,
+        EntityType::DevelopmentPhase => to_json(container.development_phase_service.get_phase(id).await?)?,
+        EntityType::FeatureContext => to_json(extended.get_feature_context(id).await?)?,
+    };
+    Ok(Some(entity).filter(|e| !e.is_null()))
+}
+
+/// Create an entity from `data`, index it for search and notify subscribers
+pub async fn create_entity(context: &ToolContext, entity_type: EntityType, data: &JsonObject) -> Result<Value, McpError> {
+    let container = context.container();
+    let crud = &container.context_crud_service;
+    let extended = &container.extended_context_crud_service;
+    let entity = match entity_type {
+        EntityType::Project => {
+            let new: NewProject = parse_data(entity_type, data)?;
+            to_json(
+                container
+                    .project_service
+                    .create_project(&new.name, new.description.as_deref(), new.repository_url.as_deref())
+                    .await?,
+            )?
+        }
+        EntityType::BusinessRule => {
+            let new: NewBusinessRule = parse_data(entity_type, data)?;
+            to_json(
+                crud.create_business_rule(
+                    &new.project_id,
+                    &new.rule_name,
+                    new.description.as_deref(),
+                    new.domain_area.as_deref(),
+                )
+                .await?,
+            )?
+        }
+        EntityType::ArchitecturalDecision => {
+            let new: NewArchitecturalDecision = parse_data(entity_type, data)?;
+            to_json(
+                crud.create_architectural_decision(
+                    &new.project_id,
+                    &new.decision_title,
+                    new.context.as_deref(),
+                    new.decision.as_deref(),
+                )
+                .await?,
+            )?
+        }
+        EntityType::PerformanceRequirement => {
+            let new: NewPerformanceRequirement = parse_data(entity_type, data)?;
+            to_json(
+                crud.create_performance_requirement(
+                    &new.project_id,
+                    new.component_area.as_deref(),
+                    new.requirement_type.as_deref(),
+                    new.target_value.as_deref(),
+                )
+                .await?,
+            )?
+        }
+        EntityType::SecurityPolicy => {
+            let new: NewSecurityPolicy = parse_data(entity_type, data)?;
+            to_json(
+                extended
+                    .create_security_policy(&new.project_id, &new.policy_name, new.policy_area.as_deref())
+                    .await?,
+            )?
+        }
+        EntityType::ProjectConvention => {
+            let new: NewProjectConvention = parse_data(entity_type, data)?;
+            to_json(
+                extended
+                    .create_project_convention(
+                        &new.project_id,
+                        new.convention_type.as_deref(),
+                        new.convention_rule.as_deref(),
+                    )
+                    .await?,
+            )?
+        }
+        EntityType::FrameworkComponent => {
+            let new: NewFrameworkComponent = parse_data(entity_type, data)?;
+            to_json(
+                container
+                    .framework_service
+                    .create_component(
+                        &new.project_id,
+                        &new.component_name,
+                        &new.component_type,
+                        &new.architecture_layer,
+                        new.file_path.as_deref(),
+                        None,
+                    )
+                    .await?,
+            )?
+        }
+        EntityType::DevelopmentPhase => {
+            let new: NewDevelopmentPhase = parse_data(entity_type, data)?;
+            to_json(
+                container
+                    .development_phase_service
+                    .create_phase(&new.project_id, &new.phase_name, new.phase_order, new.description.as_deref())
+                    .await?,
+            )?
+        }
+        EntityType::FeatureContext => {
+            let new: NewFeatureContext = parse_data(entity_type, data)?;
+            to_json(
+                extended
+                    .create_feature_context(&new.project_id, &new.feature_name, new.business_purpose.as_deref())
+                    .await?,
+            )?
+        }
+    };
+
+    context.record_revision(entity_type.as_str(), RevisionOperation::Create, None, Some(&entity)).await;
+    context.sync_search_index(entity_type.as_str(), &entity).await;
+    context.publish_change(entity_type.as_str(), None, Some(&entity)).await;
+    Ok(entity)
+}
+
+/// Update the fields of an entity present in `changes`, reindex it and notify subscribers
+pub async fn update_entity(
+    context: &ToolContext,
+    entity_type: EntityType,
+    id: &str,
+    changes: &JsonObject,
+) -> Result<Value, McpError> {
+    write_changes(context, entity_type, id, changes, RevisionOperation::Update).await
+}
+
+/// Apply `changes` to an entity, recording the write in its history as `operation`
+pub(crate) async fn write_changes(
+    context: &ToolContext,
+    entity_type: EntityType,
+    id: &str,
+    changes: &JsonObject,
+    operation: RevisionOperation,
+) -> Result<Value, McpError> {
+    let container = context.container();
+    let crud = &container.context_crud_service;
+    let extended = &container.extended_context_crud_service;
+    let (previous, entity) = match entity_type {
+        EntityType::Project => {
+            let existing = container.project_service.get_project(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(container.project_service.update_project(&updated).await?)?)
+        }
+        EntityType::BusinessRule => {
+            let existing = crud.get_business_rule(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(crud.update_business_rule(&updated).await?)?)
+        }
+        EntityType::ArchitecturalDecision => {
+            let existing = crud.get_architectural_decision(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(crud.update_architectural_decision(&updated).await?)?)
+        }
+        EntityType::PerformanceRequirement => {
+            let existing = crud.get_performance_requirement(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(crud.update_performance_requirement(&updated).await?)?)
+        }
+        EntityType::SecurityPolicy => {
+            let existing = extended.get_security_policy(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(extended.update_security_policy(&updated).await?)?)
+        }
+        EntityType::ProjectConvention => {
+            let existing = extended.get_project_convention(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(extended.update_project_convention(&updated).await?)?)
+        }
+        EntityType::FrameworkComponent => {
+            let service = &container.framework_service;
+            let existing = service.get_component(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(service.update_component(&updated).await?)?)
+        }
+        EntityType::DevelopmentPhase => {
+            let service = &container.development_phase_service;
+            let existing = service.get_phase(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(service.update_phase(&updated).await?)?)
+        }
+        EntityType::FeatureContext => {
+            let existing = extended.get_feature_context(id).await?.ok_or_else(|| not_found(entity_type, id))?;
+            let updated = apply_changes(entity_type, &existing, changes)?;
+            (to_json(&existing)?, to_json(extended.update_feature_context(&updated).await?)?)
+        }
+    };
+
+    context.record_revision(entity_type.as_str(), operation, Some(previous.clone()), Some(&entity)).await;
+    context.sync_search_index(entity_type.as_str(), &entity).await;
+    context.publish_change(entity_type.as_str(), Some(previous), Some(&entity)).await;
+    Ok(entity)
+}
+
+/// Move an entity to the trash, returning whether it existed
+pub async fn delete_entity(context: &ToolContext, entity_type: EntityType, id: &str) -> Result<bool, McpError> {
+    let previous = get_entity(context, entity_type, id).await?;
+    let container = context.container();
+    let crud = &container.context_crud_service;
+    let extended = &container.extended_context_crud_service;
+    let deleted = match entity_type {
+        EntityType::Project => container.project_service.delete_project(id).await?,
+        EntityType::BusinessRule => crud.delete_business_rule(id).await?,
+        EntityType::ArchitecturalDecision => crud.delete_architectural_decision(id).await?,
+        EntityType::PerformanceRequirement => crud.delete_performance_requirement(id).await?,
+        EntityType::SecurityPolicy => extended.delete_security_policy(id).await?,
+        EntityType::ProjectConvention => extended.delete_project_convention(id).await?,
+        EntityType::FrameworkComponent => container.framework_service.delete_component(id).await?,
+        EntityType::DevelopmentPhase => container.development_phase_service.delete_phase(id).await?,
+        EntityType::FeatureContext => extended.delete_feature_context(id).await?,
+    };
+
+    if deleted {
+        context.record_revision(entity_type.as_str(), RevisionOperation::Delete, previous.clone(), None).await;
+        context.remove_from_search_index(entity_type.as_str(), id).await;
+        if entity_type == EntityType::Project {
+            // The project's entities went to the trash with it
+            for child in container.trash_service.list_trash(Some(id)).await? {
+                context.remove_from_search_index(&child.entity_type, &child.id).await;
+            }
+        }
+        context.publish_change(entity_type.as_str(), previous, None).await;
+    }
+    Ok(deleted)
+}
+
+/// One page of entities of a type; everything except projects is listed per project
+pub async fn list_entities(
+    context: &ToolContext,
+    entity_type: EntityType,
+    project_id: Option<&str>,
+    architecture_layer: Option<&str>,
+    page: &PageArgs,
+) -> Result<Page<Value>, McpError> {
+    let container = context.container();
+    let query = page.query();
+    if entity_type == EntityType::Project {
+        return page.project(container.project_service.list_projects_page(&query).await?);
+    }
+    let project_id = project_id.ok_or_else(|| {
+        McpError::invalid_params(
+            format!("Missing required parameter: project_id for {} listing", entity_type.as_str()),
+            None,
+        )
+    })?;
+
+    let crud = &container.context_crud_service;
+    let extended = &container.extended_context_crud_service;
+    match entity_type {
+        EntityType::Project => unreachable!("projects are listed above"),
+        EntityType::BusinessRule => page.project(crud.list_business_rules_page(project_id, &query).await?),
+        EntityType::ArchitecturalDecision => {
+            page.project(crud.list_architectural_decisions_page(project_id, &query).await?)
+        }
+        EntityType::PerformanceRequirement => {
+            page.project(crud.list_performance_requirements_page(project_id, &query).await?)
+        }
+        EntityType::SecurityPolicy => page.project(extended.list_security_policies_page(project_id, &query).await?),
+        EntityType::ProjectConvention => {
+            page.project(extended.list_project_conventions_page(project_id, &query).await?)
+        }
+        EntityType::FrameworkComponent => page.project(
+            container
+                .framework_service
+                .list_components_page(project_id, architecture_layer, &query)
+                .await?,
+        ),
+        EntityType::DevelopmentPhase => {
+            page.project(container.development_phase_service.list_phases_page(project_id, &query).await?)
+        }
+        EntityType::FeatureContext => page.project(extended.list_feature_contexts_page(project_id, &query).await?),
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct EntityArgs {
+    /// The type of entity
+    pub entity_type: EntityType,
+    /// The ID of the entity
+    pub id: String,
+}
+
+pub struct GetEntityTool;
+
+#[async_trait]
+impl ToolHandler for GetEntityTool {
+    type Args = EntityArgs;
+    type Output = Option<Value>;
+
+    fn name(&self) -> &'static str {
+        "get_entity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Get any entity by ID and type (universal getter)"
+    }
+
+    fn category(&self) -> &'static str {
+        "CRUD"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Get specific business rule or architectural decision"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        get_entity(context, args.entity_type, &args.id).await
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateEntityArgs {
+    /// The type of entity to create
+    pub entity_type: EntityType,
+    /// The entity data as JSON object
+    pub data: JsonObject,
+    /// Who the entity's history records as the author (defaults to the client's name)
+    pub author: Option<String>,
+}
+
+pub struct CreateEntityTool;
+
+#[async_trait]
+impl ToolHandler for CreateEntityTool {
+    type Args = CreateEntityArgs;
+    type Output = JsonObject;
+
+    fn name(&self) -> &'static str {
+        "create_entity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Create any entity (project, business rule, architectural decision, etc.)"
+    }
+
+    fn category(&self) -> &'static str {
+        "CRUD"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Create business rules, architectural decisions, or any other entity"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let start_time = Instant::now();
+        let context = &context.with_author(args.author);
+        let entity = create_entity(context, args.entity_type, &args.data).await?;
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+
+        let field = |name: &str| entity.get(name).and_then(|v| v.as_str()).map(String::from);
+        context
+            .track(AnalyticsHelper::create_entity_create_event(
+                field("project_id"),
+                args.entity_type.as_str().to_string(),
+                field("id").unwrap_or_else(|| "unknown".to_string()),
+                Some(duration_ms),
+                true,
+                None,
+            ))
+            .await;
+
+        to_object(entity)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateEntityArgs {
+    /// The type of entity to update
+    pub entity_type: EntityType,
+    /// The ID of the entity
+    pub id: String,
+    /// Fields to change; fields left out keep their current values
+    pub data: JsonObject,
+    /// Who the entity's history records as the author (defaults to the client's name)
+    pub author: Option<String>,
+}
+
+pub struct UpdateEntityTool;
+
+#[async_trait]
+impl ToolHandler for UpdateEntityTool {
+    type Args = UpdateEntityArgs;
+    type Output = JsonObject;
+
+    fn name(&self) -> &'static str {
+        "update_entity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Update any entity by ID and type"
+    }
+
+    fn category(&self) -> &'static str {
+        "CRUD"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Change the status of an architectural decision"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let context = &context.with_author(args.author);
+        to_object(update_entity(context, args.entity_type, &args.id, &args.data).await?)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DeleteEntityArgs {
+    /// The type of entity
+    pub entity_type: EntityType,
+    /// The ID of the entity
+    pub id: String,
+    /// Who the entity's history records as the author (defaults to the client's name)
+    pub author: Option<String>,
+}
+
+pub struct DeleteEntityTool;
+
+#[async_trait]
+impl ToolHandler for DeleteEntityTool {
+    type Args = DeleteEntityArgs;
+    type Output = JsonObject;
+
+    fn name(&self) -> &'static str {
+        "delete_entity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Delete any entity by ID and type. It moves to the trash, from which restore_from_trash can bring it back; deleting a project also trashes its entities"
+    }
+
+    fn category(&self) -> &'static str {
+        "CRUD"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Remove a business rule that no longer applies"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let context = &context.with_author(args.author);
+        let deleted = delete_entity(context, args.entity_type, &args.id).await?;
+        to_object(json!({"deleted": deleted, args.entity_type.id_field(): args.id}))
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListEntitiesArgs {
+    /// The type of entities to list
+    pub entity_type: EntityType,
+    /// Project to list entities of; required for every type except project
+    pub project_id: Option<String>,
+    /// Architecture layer to filter framework components by (only applies to framework_component entity type)
+    pub architecture_layer: Option<String>,
+    #[serde(flatten)]
+    pub page: PageArgs,
+}
+
+pub struct ListEntitiesTool;
+
+#[async_trait]
+impl ToolHandler for ListEntitiesTool {
+    type Args = ListEntitiesArgs;
+    type Output = Page<Value>;
+
+    fn name(&self) -> &'static str {
+        "list_entities"
+    }
+
+    fn description(&self) -> &'static str {
+        "List entities by type and optional project filter, one page at a time"
+    }
+
+    fn category(&self) -> &'static str {
+        "CRUD"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "List all business rules for a project"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        list_entities(
+            context,
+            args.entity_type,
+            args.project_id.as_deref(),
+            args.architecture_layer.as_deref(),
+            &args.page,
+        )
+        .await
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectAction {
+    Create,
+    Update,
+    Delete,
+    Get,
+    List,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ManageProjectArgs {
+    /// The action to perform
+    pub action: ProjectAction,
+    /// Project ID (required for update, delete, get)
+    pub id: Option<String>,
+    /// Project data (required for create, update)
+    pub data: Option<JsonObject>,
+    /// Project name, when creating without `data`
+    pub name: Option<String>,
+    /// Project description, when creating without `data`
+    pub description: Option<String>,
+    /// Repository URL, when creating without `data`
+    pub repository_url: Option<String>,
+    /// Paging of the list action
+    #[serde(flatten)]
+    pub page: PageArgs,
+}
+
+/// Outcome of the `delete` action of `manage_project`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DeletedProject {
+    pub deleted: bool,
+    pub id: String,
+}
+
+pub struct ManageProjectTool;
+
+#[async_trait]
+impl ToolHandler for ManageProjectTool {
+    type Args = ManageProjectArgs;
+    type Output = Value;
+
+    fn name(&self) -> &'static str {
+        "manage_project"
+    }
+
+    fn description(&self) -> &'static str {
+        "Comprehensive project management (create, update, delete, get)"
+    }
+
+    fn category(&self) -> &'static str {
+        "CRUD"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Create a project and look it up again by ID"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let id = |action: &str| {
+            args.id.as_deref().ok_or_else(|| {
+                McpError::invalid_params(format!("Missing required parameter: id for {action}"), None)
+            })
+        };
+        let result = match args.action {
+            ProjectAction::Create => {
+                let data = match &args.data {
+                    Some(data) => data.clone(),
+                    None => {
+                        let mut data = JsonObject::new();
+                        if let Some(name) = &args.name {
+                            data.insert("name".to_string(), json!(name));
+                        }
+                        data.insert("description".to_string(), json!(args.description));
+                        data.insert("repository_url".to_string(), json!(args.repository_url));
+                        data
+                    }
+                };
+                if data.get("name").and_then(|v| v.as_str()).is_none_or(str::is_empty) {
+                    return Err(McpError::invalid_params(
+                        "Missing required parameter: name (either in 'data' object or as direct parameter)",
+                        None,
+                    ));
+                }
+                create_entity(context, EntityType::Project, &data).await?
+            }
+            ProjectAction::Update => {
+                let data = args
+                    .data
+                    .as_ref()
+                    .ok_or_else(|| McpError::invalid_params("Missing required parameter: data for update", None))?;
+                update_entity(context, EntityType::Project, id("update")?, data).await?
+            }
+            ProjectAction::Get => to_json(get_entity(context, EntityType::Project, id("get")?).await?)?,
+            ProjectAction::Delete => {
+                let id = id("delete")?;
+                let deleted = delete_entity(context, EntityType::Project, id).await?;
+                to_json(DeletedProject { deleted, id: id.to_string() })?
+            }
+            ProjectAction::List => to_json(list_entities(context, EntityType::Project, None, None, &args.page).await?)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NewComponent {
+    pub component_name: String,
+    /// e.g. widget, provider, service, repository, model, utility
+    pub component_type: String,
+    /// e.g. presentation, domain, data, core
+    pub architecture_layer: String,
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BulkCreateComponentsArgs {
+    /// The ID of the project
+    pub project_id: String,
+    pub components: Vec<NewComponent>,
+}
+
+pub struct BulkCreateComponentsTool;
+
+#[async_trait]
+impl ToolHandler for BulkCreateComponentsTool {
+    type Args = BulkCreateComponentsArgs;
+    type Output = Vec<FrameworkComponent>;
+
+    fn name(&self) -> &'static str {
+        "bulk_create_components"
+    }
+
+    fn description(&self) -> &'static str {
+        "Create multiple framework components in bulk"
+    }
+
+    fn category(&self) -> &'static str {
+        "Bulk"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Record the initial component structure of a project"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let start_time = Instant::now();
+        let mut components = Vec::new();
+        for component in &args.components {
+            let component = context
+                .container()
+                .framework_service
+                .create_component(
+                    &args.project_id,
+                    &component.component_name,
+                    &component.component_type,
+                    &component.architecture_layer,
+                    component.file_path.as_deref(),
+                    None,
+                )
+                .await?;
+            let entity = to_json(&component)?;
+            context
+                .record_revision(EntityType::FrameworkComponent.as_str(), RevisionOperation::Create, None, Some(&entity))
+                .await;
+            components.push(component);
+        }
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+
+        context
+            .track(AnalyticsHelper::create_bulk_operation_event(
+                Some(args.project_id),
+                "framework_component".to_string(),
+                "bulk_create".to_string(),
+                components.len(),
+                Some(duration_ms),
+                true,
+                None,
+            ))
+            .await;
+
+        Ok(components)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ComponentUpdate {
+    pub id: String,
+    pub component_name: String,
+    pub component_type: String,
+    pub architecture_layer: String,
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BulkUpdateComponentsArgs {
+    pub components: Vec<ComponentUpdate>,
+}
+
+pub struct BulkUpdateComponentsTool;
+
+#[async_trait]
+impl ToolHandler for BulkUpdateComponentsTool {
+    type Args = BulkUpdateComponentsArgs;
+    type Output = Vec<FrameworkComponent>;
+
+    fn name(&self) -> &'static str {
+        "bulk_update_components"
+    }
+
+    fn description(&self) -> &'static str {
+        "Update multiple framework components in bulk"
+    }
+
+    fn category(&self) -> &'static str {
+        "Bulk"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Move several components to another architecture layer"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let service = &context.container().framework_service;
+        let mut results = Vec::new();
+        for update in args.components {
+            let mut component = service
+                .get_component(&update.id)
+                .await?
+                .ok_or_else(|| not_found(EntityType::FrameworkComponent, &update.id))?;
+            let previous = to_json(&component)?;
+            component.component_name = update.component_name;
+            component.component_type = update.component_type;
+            component.architecture_layer = update.architecture_layer;
+            if update.file_path.is_some() {
+                component.file_path = update.file_path;
+            }
+            let component = service.update_component(&component).await?;
+            context
+                .record_revision(
+                    EntityType::FrameworkComponent.as_str(),
+                    RevisionOperation::Update,
+                    Some(previous),
+                    Some(&to_json(&component)?),
+                )
+                .await;
+            results.push(component);
+        }
+        Ok(results)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BulkDeleteComponentsArgs {
+    /// Array of component IDs to delete
+    pub component_ids: Vec<String>,
+}
+
+pub struct BulkDeleteComponentsTool;
+
+#[async_trait]
+impl ToolHandler for BulkDeleteComponentsTool {
+    type Args = BulkDeleteComponentsArgs;
+    type Output = BulkDeleteResult;
+
+    fn name(&self) -> &'static str {
+        "bulk_delete_components"
+    }
+
+    fn description(&self) -> &'static str {
+        "Delete multiple framework components in bulk"
+    }
+
+    fn category(&self) -> &'static str {
+        "Bulk"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Remove components deleted from the codebase"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        delete_all(context, EntityType::FrameworkComponent, args.component_ids).await
+    }
+}
+
+/// Outcome of deleting several entities
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BulkDeleteResult {
+    pub deleted_count: usize,
+    /// Every ID that was asked to be deleted
+    pub ids: Vec<String>,
+    /// IDs that did not exist or could not be deleted
+    pub failed_ids: Vec<String>,
+    /// Whether every ID was deleted
+    pub success: bool,
+}
+
+/// Delete each ID in turn. Cancellation stops the deletion between two IDs.
+async fn delete_all(
+    context: &ToolContext,
+    entity_type: EntityType,
+    ids: Vec<String>,
+) -> Result<BulkDeleteResult, McpError> {
+    let mut deleted_count = 0;
+    let mut failed_ids = Vec::new();
+    for (index, id) in ids.iter().enumerate() {
+        if context.is_cancelled() {
+            return Err(cancelled_error(json!({
+                "processed_ids": &ids[..index],
+                "deleted_count": deleted_count,
+                "failed_ids": failed_ids
+            })));
+        }
+        match delete_entity(context, entity_type, id).await {
+            Ok(true) => deleted_count += 1,
+            Ok(false) => failed_ids.push(id.clone()),
+            Err(e) => {
+                tracing::error!("Error deleting {} {}: {}", entity_type.as_str(), id, e);
+                failed_ids.push(id.clone());
+            }
+        }
+        let message = format!("Deleted {} of {} {} entities", index + 1, ids.len(), entity_type.as_str());
+        context.report_progress(index + 1, ids.len(), message).await;
+    }
+    Ok(BulkDeleteResult { success: deleted_count == ids.len(), deleted_count, ids, failed_ids })
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkOperation {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BulkOperationsArgs {
+    /// The bulk operation to perform
+    pub operation: BulkOperation,
+    /// The type of entities
+    pub entity_type: EntityType,
+    /// Entity data as for create_entity and update_entity; update and delete items need an `id`
+    pub data: Vec<JsonObject>,
+}
+
+/// Outcome of `bulk_operations`: the written entities for creates and
+/// updates, the delete summary for deletes
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum BulkOperationsResult {
+    Written(Vec<Value>),
+    Deleted(BulkDeleteResult),
+}
+
+pub struct BulkOperationsTool;
+
+#[async_trait]
+impl ToolHandler for BulkOperationsTool {
+    type Args = BulkOperationsArgs;
+    type Output = BulkOperationsResult;
+
+    fn name(&self) -> &'static str {
+        "bulk_operations"
+    }
+
+    fn description(&self) -> &'static str {
+        "Perform bulk operations on multiple entities. Reports progress per entity; when cancelled it stops between entities and the error data lists the work already done"
+    }
+
+    fn category(&self) -> &'static str {
+        "Bulk"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Create, update, or delete multiple entities in one operation"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let id_of = |item: &JsonObject| -> Result<String, McpError> {
+            item.get("id")
+                .and_then(|v| v.as_str())
+                .map(String::from)
+                .ok_or_else(|| McpError::invalid_params("Missing id in entity data", None))
+        };
+
+        let total = args.data.len();
+        match args.operation {
+            BulkOperation::Create | BulkOperation::Update => {
+                let mut results = Vec::new();
+                for (index, item) in args.data.iter().enumerate() {
+                    if context.is_cancelled() {
+                        return Err(cancelled_error(json!({"processed": index, "total": total, "results": results})));
+                    }
+                    let entity = match args.operation {
+                        BulkOperation::Create => create_entity(context, args.entity_type, item).await?,
+                        BulkOperation::Update => update_entity(context, args.entity_type, &id_of(item)?, item).await?,
+                        BulkOperation::Delete => unreachable!("deletes are handled below"),
+                    };
+                    results.push(entity);
+                    let message = format!("Processed {} of {total} {} entities", index + 1, args.entity_type.as_str());
+                    context.report_progress(index + 1, total, message).await;
+                }
+                Ok(BulkOperationsResult::Written(results))
+            }
+            BulkOperation::Delete => {
+                let ids = args.data.iter().map(id_of).collect::<Result<Vec<_>, _>>()?;
+                Ok(BulkOperationsResult::Deleted(delete_all(context, args.entity_type, ids).await?))
+            }
+        }
+    }
+}
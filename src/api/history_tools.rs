@@ -0,0 +1,265 @@
+use crate::api::entity_tools::{get_entity, write_changes, EntityType};
+use crate::api::tool_registry::{ToolContext, ToolHandler, ToolRegistry};
+use crate::services::entity_history_service::diff_snapshots;
+use crate::services::{EntityRevision, FieldChange, RevisionOperation};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rmcp::model::{ErrorData as McpError, JsonObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Register the entity history and time-travel tools
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(ListRevisionsTool);
+    registry.register(DiffRevisionsTool);
+    registry.register(RestoreRevisionTool);
+    registry.register(QueryProjectAsOfTool);
+}
+
+/// One revision of an entity; asking for one it does not have is an invalid parameter
+async fn revision(
+    context: &ToolContext,
+    entity_type: EntityType,
+    id: &str,
+    number: u32,
+) -> Result<EntityRevision, McpError> {
+    context
+        .container()
+        .entity_history_service
+        .get_revision(entity_type.as_str(), id, number)
+        .await?
+        .ok_or_else(|| {
+            let entity_type = entity_type.as_str();
+            McpError::invalid_params(
+                format!("{entity_type} {id} has no revision {number}; use list_revisions to see its history"),
+                None,
+            )
+        })
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListRevisionsArgs {
+    /// The type of entity
+    pub entity_type: EntityType,
+    /// The ID of the entity
+    pub id: String,
+}
+
+pub struct ListRevisionsTool;
+
+#[async_trait]
+impl ToolHandler for ListRevisionsTool {
+    type Args = ListRevisionsArgs;
+    type Output = Vec<EntityRevision>;
+
+    fn name(&self) -> &'static str {
+        "list_revisions"
+    }
+
+    fn description(&self) -> &'static str {
+        "List every revision of an entity, oldest first, with author, timestamp, snapshot and the fields each one changed"
+    }
+
+    fn category(&self) -> &'static str {
+        "History"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "See who changed a business rule and when"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context.container().entity_history_service.list_revisions(args.entity_type.as_str(), &args.id).await
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DiffRevisionsArgs {
+    /// The type of entity
+    pub entity_type: EntityType,
+    /// The ID of the entity
+    pub id: String,
+    /// Revision to compare from
+    pub from_revision: u32,
+    /// Revision to compare to (defaults to the latest)
+    pub to_revision: Option<u32>,
+}
+
+/// Fields that differ between two revisions of an entity
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RevisionDiff {
+    pub entity_type: EntityType,
+    pub entity_id: String,
+    pub from_revision: u32,
+    pub to_revision: u32,
+    pub changes: Vec<FieldChange>,
+}
+
+pub struct DiffRevisionsTool;
+
+#[async_trait]
+impl ToolHandler for DiffRevisionsTool {
+    type Args = DiffRevisionsArgs;
+    type Output = RevisionDiff;
+
+    fn name(&self) -> &'static str {
+        "diff_revisions"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the fields that differ between two revisions of an entity"
+    }
+
+    fn category(&self) -> &'static str {
+        "History"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Compare an architectural decision with how it read last month"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let from = revision(context, args.entity_type, &args.id, args.from_revision).await?;
+        let to = match args.to_revision {
+            Some(number) => revision(context, args.entity_type, &args.id, number).await?,
+            None => context
+                .container()
+                .entity_history_service
+                .list_revisions(args.entity_type.as_str(), &args.id)
+                .await?
+                .pop()
+                .expect("an entity with a revision has a latest one"),
+        };
+        Ok(RevisionDiff {
+            entity_type: args.entity_type,
+            entity_id: args.id,
+            from_revision: from.revision,
+            to_revision: to.revision,
+            changes: diff_snapshots(from.snapshot.as_ref(), to.snapshot.as_ref()),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RestoreRevisionArgs {
+    /// The type of entity
+    pub entity_type: EntityType,
+    /// The ID of the entity
+    pub id: String,
+    /// Revision whose snapshot the entity is set back to
+    pub revision: u32,
+    /// Who the entity's history records as the author (defaults to the client's name)
+    pub author: Option<String>,
+}
+
+pub struct RestoreRevisionTool;
+
+#[async_trait]
+impl ToolHandler for RestoreRevisionTool {
+    type Args = RestoreRevisionArgs;
+    type Output = JsonObject;
+
+    fn name(&self) -> &'static str {
+        "restore_revision"
+    }
+
+    fn description(&self) -> &'static str {
+        "Set an entity back to the snapshot of one of its revisions. The restore is recorded as a new revision, so it can be undone"
+    }
+
+    fn category(&self) -> &'static str {
+        "History"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Undo an unwanted edit to a security policy"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let context = &context.with_author(args.author);
+        let old = revision(context, args.entity_type, &args.id, args.revision).await?;
+        let Some(Value::Object(snapshot)) = old.snapshot else {
+            let message = format!(
+                "Revision {} records the deletion of {} {}; pick an earlier one",
+                old.revision, old.entity_type, old.entity_id
+            );
+            return Err(McpError::invalid_params(message, None));
+        };
+        if get_entity(context, args.entity_type, &args.id).await?.is_none() {
+            return Err(McpError::invalid_params(
+                format!("{} {} is deleted; restore it from the trash first", old.entity_type, old.entity_id),
+                None,
+            ));
+        }
+        match write_changes(context, args.entity_type, &args.id, &snapshot, RevisionOperation::Restore).await? {
+            Value::Object(entity) => Ok(entity),
+            other => Err(McpError::internal_error(format!("Expected an entity object, got {other}"), None)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct QueryProjectAsOfArgs {
+    /// The ID of the project
+    pub project_id: String,
+    /// RFC 3339 timestamp, e.g. 2026-03-01T12:00:00Z
+    pub as_of: DateTime<Utc>,
+    /// Only return entities of this type
+    pub entity_type: Option<EntityType>,
+}
+
+/// A project's entities as they were at a point in time
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ProjectAsOf {
+    pub project_id: String,
+    pub as_of: DateTime<Utc>,
+    /// Snapshots of the entities that existed then, by entity type
+    pub entities: BTreeMap<String, Vec<Value>>,
+    /// Number of entities returned
+    pub total: usize,
+}
+
+pub struct QueryProjectAsOfTool;
+
+#[async_trait]
+impl ToolHandler for QueryProjectAsOfTool {
+    type Args = QueryProjectAsOfArgs;
+    type Output = ProjectAsOf;
+
+    fn name(&self) -> &'static str {
+        "query_project_as_of"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read a project's context as it was at a given timestamp, rebuilt from entity history. Entities never changed since history was enabled are not included"
+    }
+
+    fn category(&self) -> &'static str {
+        "History"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "See which business rules were in force when a release was cut"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let revisions = context.container().entity_history_service.project_as_of(&args.project_id, args.as_of).await?;
+        let mut entities: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        for revision in revisions {
+            if args.entity_type.is_some_and(|t| t.as_str() != revision.entity_type) {
+                continue;
+            }
+            if let Some(snapshot) = revision.snapshot {
+                entities.entry(revision.entity_type).or_default().push(snapshot);
+            }
+        }
+        Ok(ProjectAsOf {
+            total: entities.values().map(Vec::len).sum(),
+            project_id: args.project_id,
+            as_of: args.as_of,
+            entities,
+        })
+    }
+}
@@ -1,8 +1,24 @@
 // API layer modules for MCP tools
 
+pub mod analytics_tools;
+pub mod context_prompts;
+pub mod context_resources;
+pub mod context_tools;
+pub mod entity_tools;
+pub mod history_tools;
+pub mod project_bundle_tools;
+pub mod search_tools;
 pub mod specification_analytics_tools;
 pub mod specification_context_linking_tools;
+pub mod specification_tools;
+pub mod tool_output;
+pub mod tool_registry;
+pub mod trash_tools;
 
 // Re-export API tools
+pub use context_prompts::ContextPrompts;
+pub use context_resources::{ContextResources, ResourceSubscriptions, ResourceUri};
+pub use search_tools::SearchTools;
 pub use specification_analytics_tools::SpecificationAnalyticsTools;
-pub use specification_context_linking_tools::SpecificationContextLinkingTools;
\ No newline at end of file
+pub use specification_context_linking_tools::SpecificationContextLinkingTools;
+pub use tool_registry::{ToolCall, ToolContext, ToolHandler, ToolRegistry};
\ No newline at end of file
@@ -0,0 +1,169 @@
+use crate::api::search_tools::RebuildProjectIndexArgs;
+use crate::api::tool_registry::{ToolContext, ToolHandler, ToolRegistry};
+use crate::services::{BundleFormat, ConflictPolicy, ImportOptions, ImportReport, ProjectBundle};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Register the project export and import tools
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(ExportProjectContextTool);
+    registry.register(ImportProjectContextTool);
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ExportProjectContextArgs {
+    /// The ID of the project
+    pub project_id: String,
+    /// Format of the bundle (defaults to json)
+    #[serde(default)]
+    pub format: BundleFormat,
+    /// Include the project's search embeddings, which saves re-embedding after import
+    #[serde(default)]
+    pub include_embeddings: bool,
+    /// Write the bundle to this file instead of returning it
+    pub path: Option<String>,
+}
+
+/// An exported project bundle
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ExportedProjectContext {
+    pub project_id: String,
+    pub format: BundleFormat,
+    /// File the bundle was written to
+    pub path: Option<String>,
+    /// The bundle, when it was not written to a file
+    pub bundle: Option<String>,
+    /// Number of rows exported from each table
+    pub rows: BTreeMap<String, usize>,
+}
+
+pub struct ExportProjectContextTool;
+
+#[async_trait]
+impl ToolHandler for ExportProjectContextTool {
+    type Args = ExportProjectContextArgs;
+    type Output = ExportedProjectContext;
+
+    fn name(&self) -> &'static str {
+        "export_project_context"
+    }
+
+    fn description(&self) -> &'static str {
+        "Export a project as a versioned JSON or YAML bundle: its entities, enhanced context items, specifications with requirements, tasks, versions and links, and optionally its embeddings. Deleted entities are left out"
+    }
+
+    fn category(&self) -> &'static str {
+        "Portability"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Check a project's context into git or move it to another machine"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let bundle = context
+            .container()
+            .project_bundle_service
+            .export_project(&args.project_id, args.include_embeddings)
+            .await?;
+        let text = bundle.render(args.format)?;
+        let rows = bundle.tables.iter().map(|(table, rows)| (table.clone(), rows.len())).collect();
+
+        let bundle = match &args.path {
+            Some(path) => {
+                std::fs::write(path, text)
+                    .map_err(|e| McpError::invalid_params(format!("Failed to write {path}: {e}"), None))?;
+                None
+            }
+            None => Some(text),
+        };
+        Ok(ExportedProjectContext { project_id: args.project_id, format: args.format, path: args.path, bundle, rows })
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ImportProjectContextArgs {
+    /// The bundle, as JSON or YAML
+    pub bundle: Option<String>,
+    /// File to read the bundle from instead
+    pub path: Option<String>,
+    /// What to do with rows whose ID already exists (defaults to skip)
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    /// Give every imported row a new ID, importing the project as a copy
+    #[serde(default)]
+    pub remap_ids: bool,
+    /// Import the bundled project under this project ID instead
+    pub project_id: Option<String>,
+    /// Report what would be imported without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+pub struct ImportProjectContextTool;
+
+#[async_trait]
+impl ToolHandler for ImportProjectContextTool {
+    type Args = ImportProjectContextArgs;
+    type Output = ImportReport;
+
+    fn name(&self) -> &'static str {
+        "import_project_context"
+    }
+
+    fn description(&self) -> &'static str {
+        "Import a bundle written by export_project_context. Existing rows are skipped, overwritten or merged (empty columns filled in) by conflict_policy; remap_ids imports a copy under new IDs. dry_run reports per table what would happen"
+    }
+
+    fn category(&self) -> &'static str {
+        "Portability"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Preview what importing a teammate's project bundle would change"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let text = match (args.bundle, &args.path) {
+            (Some(bundle), None) => bundle,
+            (None, Some(path)) => std::fs::read_to_string(path)
+                .map_err(|e| McpError::invalid_params(format!("Failed to read {path}: {e}"), None))?,
+            _ => return Err(McpError::invalid_params("Pass either bundle or path".to_string(), None)),
+        };
+        let bundle = ProjectBundle::parse(&text)?;
+        let options = ImportOptions {
+            conflict_policy: args.conflict_policy,
+            remap_ids: args.remap_ids,
+            target_project_id: args.project_id,
+            dry_run: args.dry_run,
+        };
+        let container = context.container();
+        let mut report = container.project_bundle_service.import_project(&bundle, &options).await?;
+
+        if !report.dry_run && report.changed() > 0 {
+            // Imported embeddings keep their bundled write times, so the ANN index is rebuilt
+            // rather than caught up; without them the project is re-embedded
+            let reindexed = match context.search_tools().await {
+                Ok(_) if bundle.has_embeddings() => container
+                    .search_index_manager
+                    .rebuild_vector_index()
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+                Ok(search) => search
+                    .handle_rebuild_project_index(context, RebuildProjectIndexArgs { project_id: report.project_id.clone() })
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.message.to_string()),
+                Err(e) => Err(e.message.to_string()),
+            };
+            if let Err(e) = reindexed {
+                report.warnings.push(format!("Imported, but the search index was not updated: {e}"));
+            }
+        }
+        Ok(report)
+    }
+}
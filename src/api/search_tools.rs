@@ -0,0 +1,580 @@
+use crate::models::context_conversion::ContextConverter;
+use crate::models::embedding::VectorSearchQuery;
+use crate::models::enhanced_context::EnhancedContextItem;
+use crate::repositories::EnhancedContextRepository;
+use crate::services::hybrid_search_service::{HybridSearchResult, HybridSearchService};
+use crate::services::search_index_manager::{IndexHealthReport, SearchIndexManager};
+use crate::services::semantic_search_service::{EnhancedSearchResult, SemanticSearchService};
+use crate::services::{ParsedSearchQuery, SearchQueryParser};
+use crate::api::tool_output::structured_result;
+use crate::api::tool_registry::{cancelled_error, parse_arguments, ToolContext, ToolHandler, ToolRegistry};
+use async_trait::async_trait;
+use rmcp::model::{CallToolResult, ErrorData as McpError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Candidates fetched per requested result, so query filters still fill the page
+const FILTER_CANDIDATE_MULTIPLIER: usize = 3;
+/// BM25 fallback vectors score lower than transformer embeddings
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.2;
+/// Contexts re-embedded between two progress reports of a rebuild
+const REBUILD_BATCH_SIZE: usize = 20;
+
+type EntityConversion = fn(&Value) -> Option<EnhancedContextItem>;
+
+/// Context entity types mirrored into the search index, with their conversion
+const SEARCHABLE_ENTITY_TYPES: &[(&str, EntityConversion)] = &[
+    ("business_rule", |entity| convert_entity(entity, ContextConverter::from_business_rule)),
+    ("architectural_decision", |entity| convert_entity(entity, ContextConverter::from_architectural_decision)),
+    ("performance_requirement", |entity| convert_entity(entity, ContextConverter::from_performance_requirement)),
+    ("security_policy", |entity| convert_entity(entity, ContextConverter::from_security_policy)),
+    ("project_convention", |entity| convert_entity(entity, ContextConverter::from_project_convention)),
+    ("feature_context", |entity| convert_entity(entity, ContextConverter::from_feature_context)),
+];
+
+fn convert_entity<T: serde::de::DeserializeOwned>(
+    entity: &Value,
+    convert: fn(T) -> EnhancedContextItem,
+) -> Option<EnhancedContextItem> {
+    serde_json::from_value(entity.clone()).ok().map(convert)
+}
+
+/// Register the search tools
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(SemanticSearchTool);
+    registry.register(HybridSearchTool);
+    registry.register(FindSimilarContextsTool);
+    registry.register(SuggestQueriesTool);
+    registry.register(IndexHealthTool);
+    registry.register(RebuildProjectIndexTool);
+}
+
+fn default_max_results() -> usize {
+    10
+}
+
+fn default_similar_results() -> usize {
+    5
+}
+
+fn default_similarity_threshold() -> f64 {
+    DEFAULT_SIMILARITY_THRESHOLD
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SemanticSearchArgs {
+    /// Search text, optionally with field filters
+    pub query: String,
+    /// Only search this project
+    pub project_id: Option<String>,
+    /// Maximum number of results
+    #[serde(default = "default_max_results")]
+    #[schemars(range(min = 1, max = 100))]
+    pub max_results: usize,
+    /// Minimum similarity in 0..1
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+    /// 0 ranks by relevance only; higher values trade relevance for less redundant results
+    #[serde(default)]
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub diversity: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HybridSearchArgs {
+    /// The ID of the project
+    pub project_id: String,
+    /// Search text, optionally with field filters
+    pub query: String,
+    /// Feature area added to the keyword query
+    pub feature_area: Option<String>,
+    /// Task type added to the keyword query
+    pub task_type: Option<String>,
+    /// Component names added to the keyword query
+    #[serde(default)]
+    pub components: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FindSimilarContextsArgs {
+    /// The ID of the context item to compare against
+    pub context_id: String,
+    /// Maximum number of results
+    #[serde(default = "default_similar_results")]
+    #[schemars(range(min = 1, max = 100))]
+    pub max_results: usize,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SuggestQueriesArgs {
+    /// The query typed so far
+    pub partial_query: String,
+    /// Project to draw suggestions from
+    pub project_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct IndexHealthArgs {
+    /// Limit the report to this project
+    pub project_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RebuildProjectIndexArgs {
+    /// The ID of the project
+    pub project_id: String,
+}
+
+/// Outcome of `rebuild_project_index`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RebuildProjectIndexResult {
+    pub project_id: String,
+    /// Number of context items re-embedded
+    pub reindexed: usize,
+}
+
+/// MCP tools for semantic and hybrid search over enhanced context items
+pub struct SearchTools {
+    semantic_search_service: Arc<dyn SemanticSearchService>,
+    hybrid_search_service: Arc<dyn HybridSearchService>,
+    index_manager: Arc<dyn SearchIndexManager>,
+    context_repository: Arc<dyn EnhancedContextRepository>,
+}
+
+impl SearchTools {
+    pub fn new(
+        semantic_search_service: Arc<dyn SemanticSearchService>,
+        hybrid_search_service: Arc<dyn HybridSearchService>,
+        index_manager: Arc<dyn SearchIndexManager>,
+        context_repository: Arc<dyn EnhancedContextRepository>,
+    ) -> Self {
+        Self {
+            semantic_search_service,
+            hybrid_search_service,
+            index_manager,
+            context_repository,
+        }
+    }
+
+    /// Handle search tool calls
+    pub async fn handle_tool_call(&self, name: &str, arguments: Value) -> Result<CallToolResult, McpError> {
+        match name {
+            "semantic_search" => structured_result(&self.handle_semantic_search(parse_arguments(name, arguments)?).await?),
+            "hybrid_search" => structured_result(&self.handle_hybrid_search(parse_arguments(name, arguments)?).await?),
+            "find_similar_contexts" => {
+                structured_result(&self.handle_find_similar_contexts(parse_arguments(name, arguments)?).await?)
+            }
+            "suggest_queries" => structured_result(&self.handle_suggest_queries(parse_arguments(name, arguments)?).await?),
+            "index_health" => structured_result(&self.handle_index_health(parse_arguments(name, arguments)?).await?),
+            _ => Err(McpError::method_not_found::<rmcp::model::CallToolRequestMethod>()),
+        }
+    }
+
+    /// Context item mirrored from a CRUD entity, for entity types that are context
+    pub fn context_item_for_entity(entity_type: &str, entity: &Value) -> Option<EnhancedContextItem> {
+        let (_, convert) = SEARCHABLE_ENTITY_TYPES.iter().find(|(name, _)| *name == entity_type)?;
+        convert(entity)
+    }
+
+    /// Whether entities of this type are mirrored into the search index
+    pub fn is_searchable_entity_type(entity_type: &str) -> bool {
+        SEARCHABLE_ENTITY_TYPES.iter().any(|(name, _)| *name == entity_type)
+    }
+
+    /// Store a context item and bring its embeddings up to date
+    pub async fn index_context_item(&self, mut item: EnhancedContextItem) -> Result<(), McpError> {
+        match self.context_repository.find_context_by_id(&item.id).await? {
+            Some(existing) => {
+                // Updates may omit fields the item was created with
+                if item.project_id.is_empty() {
+                    item.project_id = existing.project_id;
+                }
+                item.created_at = existing.created_at;
+                item.updated_at = chrono::Utc::now();
+                item.version = existing.version + 1;
+                self.context_repository.update_context(&item).await?;
+            }
+            None => {
+                self.context_repository.create_context(&item).await?;
+            }
+        }
+
+        self.index_manager
+            .auto_index_context(&item)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to index context {}: {e}", item.id), None))?;
+        self.refresh_vocabulary(&item.project_id).await
+    }
+
+    /// Remove a context item and its embeddings
+    pub async fn remove_context_item(&self, context_id: &str) -> Result<(), McpError> {
+        let project_id = self
+            .context_repository
+            .find_context_by_id(context_id)
+            .await?
+            .map(|item| item.project_id);
+        self.context_repository.delete_context(context_id).await?;
+        self.index_manager
+            .remove_from_index(context_id)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to remove context {context_id} from index: {e}"), None))?;
+        match project_id {
+            Some(project_id) => self.refresh_vocabulary(&project_id).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Re-embed a project's contexts once its items have drifted far enough
+    /// from the vocabulary their term weights come from
+    async fn refresh_vocabulary(&self, project_id: &str) -> Result<(), McpError> {
+        let changed = self
+            .index_manager
+            .refresh_vocabulary(project_id, false)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to refresh vocabulary of project {project_id}: {e}"), None))?;
+        if !changed {
+            return Ok(());
+        }
+
+        let contexts = self.context_repository.find_contexts_by_project(project_id).await?;
+        self.index_manager
+            .reindex_contexts(&contexts)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to re-embed project {project_id}: {e}"), None))
+    }
+
+    pub async fn handle_semantic_search(&self, args: SemanticSearchArgs) -> Result<Vec<EnhancedSearchResult>, McpError> {
+        let parsed = parse_query(&args.query)?;
+        let max_results = args.max_results.clamp(1, 100);
+        let similarity_threshold = args.similarity_threshold;
+        let diversity = args.diversity;
+        if !(0.0..=1.0).contains(&diversity) {
+            return Err(McpError::invalid_params("diversity must be between 0 and 1", None));
+        }
+
+        let mut filters = parsed.filters.base_filters.clone();
+        if let Some(project_id) = args.project_id {
+            filters.project_ids = Some(vec![project_id]);
+        }
+        let query = VectorSearchQuery {
+            query_text: parsed.text.clone(),
+            similarity_threshold: similarity_threshold as f32,
+            max_results: max_results * FILTER_CANDIDATE_MULTIPLIER,
+            filters,
+            ..Default::default()
+        }
+        .with_diversity(diversity as f32);
+
+        let mut results = self
+            .semantic_search_service
+            .search(&query)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Semantic search failed: {e}"), None))?;
+
+        let contexts = self.load_contexts(results.iter().map(|r| r.vector_result.context_id.as_str())).await?;
+        for result in results.iter_mut() {
+            result.context_item = contexts.get(&result.vector_result.context_id).cloned();
+        }
+        results.retain(|r| r.context_item.as_ref().is_some_and(|item| parsed.matches(item)));
+        results.truncate(max_results);
+
+        Ok(results)
+    }
+
+    pub async fn handle_hybrid_search(&self, args: HybridSearchArgs) -> Result<HybridSearchResult, McpError> {
+        let parsed = parse_query(&args.query)?;
+
+        let mut result = self
+            .hybrid_search_service
+            .hybrid_search(
+                &args.project_id,
+                &parsed.text,
+                args.feature_area.as_deref(),
+                args.task_type.as_deref(),
+                &args.components,
+            )
+            .await
+            .map_err(|e| McpError::internal_error(format!("Hybrid search failed: {e}"), None))?;
+
+        let missing: Vec<String> = result.ranked_results.iter()
+            .filter(|r| r.context.is_none())
+            .map(|r| r.context_id.clone())
+            .collect();
+        let contexts = self.load_contexts(missing.iter().map(String::as_str)).await?;
+        for ranked in result.ranked_results.iter_mut().filter(|r| r.context.is_none()) {
+            ranked.context = contexts.get(&ranked.context_id).cloned();
+        }
+        result.ranked_results.retain(|r| r.context.as_ref().is_some_and(|item| parsed.matches(item)));
+        result.total_results = result.ranked_results.len();
+
+        Ok(result)
+    }
+
+    pub async fn handle_find_similar_contexts(&self, args: FindSimilarContextsArgs) -> Result<Vec<EnhancedSearchResult>, McpError> {
+        let max_results = args.max_results.clamp(1, 100);
+
+        let mut results = self
+            .semantic_search_service
+            .find_similar_contexts(&args.context_id, max_results)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Similar context search failed: {e}"), None))?;
+
+        let contexts = self.load_contexts(results.iter().map(|r| r.vector_result.context_id.as_str())).await?;
+        for result in results.iter_mut() {
+            result.context_item = contexts.get(&result.vector_result.context_id).cloned();
+        }
+
+        Ok(results)
+    }
+
+    pub async fn handle_suggest_queries(&self, args: SuggestQueriesArgs) -> Result<Vec<String>, McpError> {
+        let suggestions = self
+            .hybrid_search_service
+            .get_search_suggestions(&args.partial_query, args.project_id.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(format!("Query suggestion failed: {e}"), None))?;
+
+        Ok(suggestions)
+    }
+
+    pub async fn handle_index_health(&self, args: IndexHealthArgs) -> Result<IndexHealthReport, McpError> {
+        let report = self
+            .index_manager
+            .get_health_report(args.project_id.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(format!("Index health check failed: {e}"), None))?;
+
+        Ok(report)
+    }
+
+    /// Re-embed every context of a project, `REBUILD_BATCH_SIZE` at a time.
+    /// Progress is reported after each batch and cancellation stops the rebuild
+    /// between batches; contexts not reached yet keep their previous embeddings.
+    pub async fn handle_rebuild_project_index(
+        &self,
+        context: &ToolContext,
+        args: RebuildProjectIndexArgs,
+    ) -> Result<RebuildProjectIndexResult, McpError> {
+        let contexts = self.context_repository.find_contexts_by_project(&args.project_id).await?;
+        let total = contexts.len();
+        // Term weights come from the project's current items
+        self.index_manager
+            .refresh_vocabulary(&args.project_id, true)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Index rebuild failed: {e}"), None))?;
+
+        let mut reindexed = 0;
+        for batch in contexts.chunks(REBUILD_BATCH_SIZE) {
+            if context.is_cancelled() {
+                return Err(cancelled_error(json!({
+                    "project_id": args.project_id,
+                    "reindexed": reindexed,
+                    "total": total
+                })));
+            }
+            self.index_manager
+                .reindex_contexts(batch)
+                .await
+                .map_err(|e| McpError::internal_error(format!("Index rebuild failed: {e}"), None))?;
+            reindexed += batch.len();
+            context
+                .report_progress(reindexed, total, format!("Reindexed {reindexed} of {total} contexts"))
+                .await;
+        }
+
+        Ok(RebuildProjectIndexResult { project_id: args.project_id, reindexed })
+    }
+
+    async fn load_contexts<'a>(
+        &self,
+        context_ids: impl Iterator<Item = &'a str>,
+    ) -> Result<HashMap<String, EnhancedContextItem>, McpError> {
+        let mut contexts = HashMap::new();
+        for context_id in context_ids {
+            if let Some(context) = self.context_repository.find_context_by_id(context_id).await? {
+                contexts.insert(context.id.clone(), context);
+            }
+        }
+        Ok(contexts)
+    }
+}
+
+/// Parse the `query` argument; a query made only of filters has nothing to rank by
+fn parse_query(query: &str) -> Result<ParsedSearchQuery, McpError> {
+    let parsed = SearchQueryParser::parse(query)?;
+    if parsed.text.trim().is_empty() {
+        return Err(McpError::invalid_params("Search query needs text besides filters", None));
+    }
+    Ok(parsed)
+}
+
+pub struct SemanticSearchTool;
+
+#[async_trait]
+impl ToolHandler for SemanticSearchTool {
+    type Args = SemanticSearchArgs;
+    type Output = Vec<EnhancedSearchResult>;
+
+    fn name(&self) -> &'static str {
+        "semantic_search"
+    }
+
+    fn description(&self) -> &'static str {
+        "Search context items by meaning. The query may mix free text with filters such as `type:business_rule tag:auth -tag:legacy priority:>=high created:7d`"
+    }
+
+    fn category(&self) -> &'static str {
+        "Search"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Find business rules about token expiry across projects"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context.search_tools().await?.handle_semantic_search(args).await
+    }
+}
+
+pub struct HybridSearchTool;
+
+#[async_trait]
+impl ToolHandler for HybridSearchTool {
+    type Args = HybridSearchArgs;
+    type Output = HybridSearchResult;
+
+    fn name(&self) -> &'static str {
+        "hybrid_search"
+    }
+
+    fn description(&self) -> &'static str {
+        "Search a project's context items with semantic and BM25 keyword ranking merged and reranked. Accepts the same query filters as semantic_search"
+    }
+
+    fn category(&self) -> &'static str {
+        "Search"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Find context relevant to the feature being implemented"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context.search_tools().await?.handle_hybrid_search(args).await
+    }
+}
+
+pub struct FindSimilarContextsTool;
+
+#[async_trait]
+impl ToolHandler for FindSimilarContextsTool {
+    type Args = FindSimilarContextsArgs;
+    type Output = Vec<EnhancedSearchResult>;
+
+    fn name(&self) -> &'static str {
+        "find_similar_contexts"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find context items similar to an existing one"
+    }
+
+    fn category(&self) -> &'static str {
+        "Search"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Check for duplicate or conflicting business rules"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context.search_tools().await?.handle_find_similar_contexts(args).await
+    }
+}
+
+pub struct SuggestQueriesTool;
+
+#[async_trait]
+impl ToolHandler for SuggestQueriesTool {
+    type Args = SuggestQueriesArgs;
+    type Output = Vec<String>;
+
+    fn name(&self) -> &'static str {
+        "suggest_queries"
+    }
+
+    fn description(&self) -> &'static str {
+        "Suggest search queries completing a partial query"
+    }
+
+    fn category(&self) -> &'static str {
+        "Search"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Complete a partially typed search query"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context.search_tools().await?.handle_suggest_queries(args).await
+    }
+}
+
+pub struct IndexHealthTool;
+
+#[async_trait]
+impl ToolHandler for IndexHealthTool {
+    type Args = IndexHealthArgs;
+    type Output = IndexHealthReport;
+
+    fn name(&self) -> &'static str {
+        "index_health"
+    }
+
+    fn description(&self) -> &'static str {
+        "Report search index coverage, freshness, ANN index recall, model migration progress and recommendations"
+    }
+
+    fn category(&self) -> &'static str {
+        "Search"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Check whether the search index is up to date"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context.search_tools().await?.handle_index_health(args).await
+    }
+}
+
+pub struct RebuildProjectIndexTool;
+
+#[async_trait]
+impl ToolHandler for RebuildProjectIndexTool {
+    type Args = RebuildProjectIndexArgs;
+    type Output = RebuildProjectIndexResult;
+
+    fn name(&self) -> &'static str {
+        "rebuild_project_index"
+    }
+
+    fn description(&self) -> &'static str {
+        "Re-embed every context item of a project, reporting progress; can be cancelled and safely rerun"
+    }
+
+    fn category(&self) -> &'static str {
+        "Search"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Refresh a project's search index after changing the embedding configuration"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context.search_tools().await?.handle_rebuild_project_index(context, args).await
+    }
+}
@@ -1,8 +1,153 @@
-use crate::services::specification_analytics_service::SpecificationAnalyticsService;
-use rmcp::model::{ErrorData as McpError, Tool, CallToolResult, Content};
-use serde_json::{json, Value};
+use crate::api::tool_output::structured_result;
+use crate::api::tool_registry::{parse_arguments, ToolContext, ToolHandler, ToolRegistry};
+use crate::models::specification::{Complexity, Priority, TaskStatus};
+use crate::services::specification_analytics_service::{
+    DevelopmentVelocity, RequirementProgress, SpecificationAnalyticsService, SpecificationCompleteness,
+    SpecificationHealthReport, TaskProgress, VelocityTrend,
+};
+use async_trait::async_trait;
+use rmcp::model::{ErrorData as McpError, CallToolResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::sync::Arc;
 
+/// Register the specification analytics tools
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(TrackRequirementsProgressTool);
+    registry.register(TrackTasksProgressTool);
+    registry.register(AnalyzeSpecificationCompletenessTool);
+    registry.register(CalculateDevelopmentVelocityTool);
+    registry.register(GenerateSpecificationHealthReportTool);
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SpecificationProjectArgs {
+    /// The ID of the project to analyze
+    pub project_id: String,
+}
+
+fn default_velocity_days() -> i64 {
+    30
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DevelopmentVelocityArgs {
+    /// The ID of the project to calculate velocity for
+    pub project_id: String,
+    /// Number of days to look back for velocity calculation
+    #[serde(default = "default_velocity_days")]
+    #[schemars(range(min = 1, max = 365))]
+    pub days: i64,
+}
+
+/// Output of `track_requirements_progress`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RequirementsProgressReport {
+    pub project_id: String,
+    pub requirements_count: usize,
+    pub requirements_progress: Vec<RequirementProgress>,
+    pub summary: RequirementsProgressSummary,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RequirementsProgressSummary {
+    pub total_requirements: usize,
+    pub completed_requirements: usize,
+    pub in_progress_requirements: usize,
+    pub not_started_requirements: usize,
+    pub average_completion: f64,
+    /// Requirements not updated for more than 30 days
+    pub stale_requirements: usize,
+    pub high_priority_requirements: usize,
+}
+
+/// Output of `track_tasks_progress`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TasksProgressReport {
+    pub project_id: String,
+    pub tasks_count: usize,
+    pub tasks_progress: Vec<TaskProgress>,
+    pub summary: TasksProgressSummary,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TasksProgressSummary {
+    pub total_tasks: usize,
+    pub completed_tasks: usize,
+    pub in_progress_tasks: usize,
+    pub not_started_tasks: usize,
+    pub blocked_tasks: usize,
+    pub average_progress: f64,
+    pub tasks_with_dependencies: usize,
+    pub tasks_with_subtasks: usize,
+    pub high_priority_tasks: usize,
+    pub complex_tasks: usize,
+}
+
+/// Output of `analyze_specification_completeness`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CompletenessReport {
+    pub project_id: String,
+    pub specifications_count: usize,
+    pub specifications_completeness: Vec<SpecificationCompleteness>,
+    pub summary: CompletenessSummary,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CompletenessSummary {
+    pub total_specifications: usize,
+    /// Specifications scoring at least 0.8
+    pub complete_specifications: usize,
+    /// Specifications scoring below 0.5
+    pub incomplete_specifications: usize,
+    pub average_completeness: f64,
+    pub specifications_with_issues: usize,
+    pub specifications_with_missing_sections: usize,
+    pub total_recommendations: usize,
+}
+
+/// Output of `calculate_development_velocity`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct VelocityReport {
+    pub project_id: String,
+    pub velocity_metrics: DevelopmentVelocity,
+    pub insights: VelocityInsights,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct VelocityInsights {
+    pub daily_task_completion_rate: f64,
+    pub daily_requirement_completion_rate: f64,
+    pub velocity_trend_description: String,
+    /// High, Medium or Low
+    pub productivity_level: String,
+    pub has_bottlenecks: bool,
+    pub bottlenecks_count: usize,
+}
+
+/// Output of `generate_specification_health_report`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HealthReportSummary {
+    pub project_id: String,
+    pub health_report: SpecificationHealthReport,
+    pub executive_summary: ExecutiveSummary,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ExecutiveSummary {
+    /// Excellent, Good, Fair or Poor
+    pub overall_health_level: String,
+    pub total_specifications: usize,
+    pub total_requirements: usize,
+    pub total_tasks: usize,
+    pub critical_issues_count: usize,
+    pub recommendations_count: usize,
+    pub has_critical_issues: bool,
+    pub velocity_trend: VelocityTrend,
+    pub productivity_score: f64,
+}
+
 /// MCP tools for specification analytics
 pub struct SpecificationAnalyticsTools {
     analytics_service: Arc<dyn SpecificationAnalyticsService>,
@@ -13,270 +158,321 @@ impl SpecificationAnalyticsTools {
         Self { analytics_service }
     }
 
-    /// Get available specification analytics tools
-    pub fn get_tools() -> Vec<Tool> {
-        vec![
-            Tool {
-                name: "track_requirements_progress".into(),
-                description: Some("Track progress for all requirements in a project, including completion percentages, linked tasks, and acceptance criteria status".into()),
-                input_schema: Arc::new(serde_json::json!({
-                    "type": "object",
-                    "properties": {
-                        "project_id": {
-                            "type": "string",
-                            "description": "The ID of the project to track requirements progress for"
-                        }
-                    },
-                    "required": ["project_id"]
-                }).as_object().unwrap().clone()),
-                annotations: None,
-            },
-            Tool {
-                name: "track_tasks_progress".into(),
-                description: Some("Track progress for all tasks in a project, including status, completion percentage, time tracking, and dependencies".into()),
-                input_schema: Arc::new(serde_json::json!({
-                    "type": "object",
-                    "properties": {
-                        "project_id": {
-                            "type": "string",
-                            "description": "The ID of the project to track tasks progress for"
-                        }
-                    },
-                    "required": ["project_id"]
-                }).as_object().unwrap().clone()),
-                annotations: None,
-            },
-            Tool {
-                name: "analyze_specification_completeness".into(),
-                description: Some("Analyze completeness of specifications in a project, including content quality, missing sections, and recommendations".into()),
-                input_schema: Arc::new(serde_json::json!({
-                    "type": "object",
-                    "properties": {
-                        "project_id": {
-                            "type": "string",
-                            "description": "The ID of the project to analyze specification completeness for"
-                        }
-                    },
-                    "required": ["project_id"]
-                }).as_object().unwrap().clone()),
-                annotations: None,
-            },
-            Tool {
-                name: "calculate_development_velocity".into(),
-                description: Some("Calculate development velocity metrics based on task and requirement completion over a specified time period".into()),
-                input_schema: Arc::new(serde_json::json!({
-                    "type": "object",
-                    "properties": {
-                        "project_id": {
-                            "type": "string",
-                            "description": "The ID of the project to calculate velocity for"
-                        },
-                        "days": {
-                            "type": "integer",
-                            "description": "Number of days to look back for velocity calculation",
-                            "default": 30,
-                            "minimum": 1,
-                            "maximum": 365
-                        }
-                    },
-                    "required": ["project_id"]
-                }).as_object().unwrap().clone()),
-                annotations: None,
-            },
-            Tool {
-                name: "generate_specification_health_report".into(),
-                description: Some("Generate a comprehensive health report for all specifications in a project, including progress, completeness, velocity, and recommendations".into()),
-                input_schema: Arc::new(serde_json::json!({
-                    "type": "object",
-                    "properties": {
-                        "project_id": {
-                            "type": "string",
-                            "description": "The ID of the project to generate health report for"
-                        }
-                    },
-                    "required": ["project_id"]
-                }).as_object().unwrap().clone()),
-                annotations: None,
-            },
-        ]
-    }
-
     /// Handle specification analytics tool calls
     pub async fn handle_tool_call(&self, name: &str, arguments: Value) -> Result<CallToolResult, McpError> {
         match name {
-            "track_requirements_progress" => self.handle_track_requirements_progress(arguments).await,
-            "track_tasks_progress" => self.handle_track_tasks_progress(arguments).await,
-            "analyze_specification_completeness" => self.handle_analyze_specification_completeness(arguments).await,
-            "calculate_development_velocity" => self.handle_calculate_development_velocity(arguments).await,
-            "generate_specification_health_report" => self.handle_generate_specification_health_report(arguments).await,
+            "track_requirements_progress" => {
+                structured_result(&self.handle_track_requirements_progress(parse_arguments(name, arguments)?).await?)
+            }
+            "track_tasks_progress" => {
+                structured_result(&self.handle_track_tasks_progress(parse_arguments(name, arguments)?).await?)
+            }
+            "analyze_specification_completeness" => {
+                structured_result(&self.handle_analyze_specification_completeness(parse_arguments(name, arguments)?).await?)
+            }
+            "calculate_development_velocity" => {
+                structured_result(&self.handle_calculate_development_velocity(parse_arguments(name, arguments)?).await?)
+            }
+            "generate_specification_health_report" => {
+                structured_result(&self.handle_generate_specification_health_report(parse_arguments(name, arguments)?).await?)
+            }
             _ => Err(McpError::method_not_found::<rmcp::model::CallToolRequestMethod>()),
         }
     }
 
-    async fn handle_track_requirements_progress(&self, arguments: Value) -> Result<CallToolResult, McpError> {
-        let project_id = arguments.get("project_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing or invalid project_id", None))?;
-
-        let progress = self.analytics_service.track_requirements_progress(project_id).await?;
-
-        let result = json!({
-            "project_id": project_id,
-            "requirements_count": progress.len(),
-            "requirements_progress": progress,
-            "summary": {
-                "total_requirements": progress.len(),
-                "completed_requirements": progress.iter().filter(|r| r.completion_percentage >= 1.0).count(),
-                "in_progress_requirements": progress.iter().filter(|r| r.completion_percentage > 0.0 && r.completion_percentage < 1.0).count(),
-                "not_started_requirements": progress.iter().filter(|r| r.completion_percentage == 0.0).count(),
-                "average_completion": if progress.is_empty() { 0.0 } else { 
-                    progress.iter().map(|r| r.completion_percentage).sum::<f64>() / progress.len() as f64 
-                },
-                "stale_requirements": progress.iter().filter(|r| r.days_since_last_update > 30).count(),
-                "high_priority_requirements": progress.iter().filter(|r| matches!(r.priority, crate::models::specification::Priority::Critical | crate::models::specification::Priority::High)).count(),
-            }
-        });
-
-        Ok(CallToolResult::success(vec![Content::text(format!("Requirements Progress Tracking for Project: {}\n\n{}", project_id, serde_json::to_string_pretty(&result).unwrap()))]))
-    }
-
-    async fn handle_track_tasks_progress(&self, arguments: Value) -> Result<CallToolResult, McpError> {
-        let project_id = arguments.get("project_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing or invalid project_id", None))?;
-
-        let progress = self.analytics_service.track_tasks_progress(project_id).await?;
-
-        let result = json!({
-            "project_id": project_id,
-            "tasks_count": progress.len(),
-            "tasks_progress": progress,
-            "summary": {
-                "total_tasks": progress.len(),
-                "completed_tasks": progress.iter().filter(|t| t.status == crate::models::specification::TaskStatus::Completed).count(),
-                "in_progress_tasks": progress.iter().filter(|t| t.status == crate::models::specification::TaskStatus::InProgress).count(),
-                "not_started_tasks": progress.iter().filter(|t| t.status == crate::models::specification::TaskStatus::NotStarted).count(),
-                "blocked_tasks": progress.iter().filter(|t| t.is_blocked).count(),
-                "average_progress": if progress.is_empty() { 0.0 } else { 
-                    progress.iter().map(|t| t.progress).sum::<f64>() / progress.len() as f64 
-                },
-                "tasks_with_dependencies": progress.iter().filter(|t| t.dependencies_count > 0).count(),
-                "tasks_with_subtasks": progress.iter().filter(|t| t.subtasks_count > 0).count(),
-                "high_priority_tasks": progress.iter().filter(|t| matches!(t.priority, crate::models::specification::Priority::Critical | crate::models::specification::Priority::High)).count(),
-                "complex_tasks": progress.iter().filter(|t| matches!(t.complexity, crate::models::specification::Complexity::Complex | crate::models::specification::Complexity::VeryComplex)).count(),
-            }
-        });
+    pub async fn handle_track_requirements_progress(&self, args: SpecificationProjectArgs) -> Result<RequirementsProgressReport, McpError> {
+        let progress = self.analytics_service.track_requirements_progress(&args.project_id).await?;
 
-        Ok(CallToolResult::success(vec![Content::text(format!("Tasks Progress Tracking for Project: {}\n\n{}", project_id, serde_json::to_string_pretty(&result).unwrap()))]))
-    }
-
-    async fn handle_analyze_specification_completeness(&self, arguments: Value) -> Result<CallToolResult, McpError> {
-        let project_id = arguments.get("project_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing or invalid project_id", None))?;
-
-        let completeness = self.analytics_service.analyze_specification_completeness(project_id).await?;
-
-        let result = json!({
-            "project_id": project_id,
-            "specifications_count": completeness.len(),
-            "specifications_completeness": completeness,
-            "summary": {
-                "total_specifications": completeness.len(),
-                "complete_specifications": completeness.iter().filter(|s| s.completeness_score >= 0.8).count(),
-                "incomplete_specifications": completeness.iter().filter(|s| s.completeness_score < 0.5).count(),
-                "average_completeness": if completeness.is_empty() { 0.0 } else { 
-                    completeness.iter().map(|s| s.completeness_score).sum::<f64>() / completeness.len() as f64 
-                },
-                "specifications_with_issues": completeness.iter().filter(|s| !s.quality_issues.is_empty()).count(),
-                "specifications_with_missing_sections": completeness.iter().filter(|s| !s.missing_sections.is_empty()).count(),
-                "total_recommendations": completeness.iter().map(|s| s.recommendations.len()).sum::<usize>(),
-            }
-        });
+        let summary = RequirementsProgressSummary {
+            total_requirements: progress.len(),
+            completed_requirements: progress.iter().filter(|r| r.completion_percentage >= 1.0).count(),
+            in_progress_requirements: progress.iter().filter(|r| r.completion_percentage > 0.0 && r.completion_percentage < 1.0).count(),
+            not_started_requirements: progress.iter().filter(|r| r.completion_percentage == 0.0).count(),
+            average_completion: if progress.is_empty() { 0.0 } else {
+                progress.iter().map(|r| r.completion_percentage).sum::<f64>() / progress.len() as f64
+            },
+            stale_requirements: progress.iter().filter(|r| r.days_since_last_update > 30).count(),
+            high_priority_requirements: progress.iter().filter(|r| is_high_priority(&r.priority)).count(),
+        };
+
+        Ok(RequirementsProgressReport {
+            project_id: args.project_id,
+            requirements_count: progress.len(),
+            requirements_progress: progress,
+            summary,
+        })
+    }
 
-        Ok(CallToolResult::success(vec![Content::text(format!("Specification Completeness Analysis for Project: {}\n\n{}", project_id, serde_json::to_string_pretty(&result).unwrap()))]))
+    pub async fn handle_track_tasks_progress(&self, args: SpecificationProjectArgs) -> Result<TasksProgressReport, McpError> {
+        let progress = self.analytics_service.track_tasks_progress(&args.project_id).await?;
+
+        let summary = TasksProgressSummary {
+            total_tasks: progress.len(),
+            completed_tasks: progress.iter().filter(|t| t.status == TaskStatus::Completed).count(),
+            in_progress_tasks: progress.iter().filter(|t| t.status == TaskStatus::InProgress).count(),
+            not_started_tasks: progress.iter().filter(|t| t.status == TaskStatus::NotStarted).count(),
+            blocked_tasks: progress.iter().filter(|t| t.is_blocked).count(),
+            average_progress: if progress.is_empty() { 0.0 } else {
+                progress.iter().map(|t| t.progress).sum::<f64>() / progress.len() as f64
+            },
+            tasks_with_dependencies: progress.iter().filter(|t| t.dependencies_count > 0).count(),
+            tasks_with_subtasks: progress.iter().filter(|t| t.subtasks_count > 0).count(),
+            high_priority_tasks: progress.iter().filter(|t| is_high_priority(&t.priority)).count(),
+            complex_tasks: progress.iter().filter(|t| matches!(t.complexity, Complexity::Complex | Complexity::VeryComplex)).count(),
+        };
+
+        Ok(TasksProgressReport {
+            project_id: args.project_id,
+            tasks_count: progress.len(),
+            tasks_progress: progress,
+            summary,
+        })
     }
 
-    async fn handle_calculate_development_velocity(&self, arguments: Value) -> Result<CallToolResult, McpError> {
-        let project_id = arguments.get("project_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing or invalid project_id", None))?;
+    pub async fn handle_analyze_specification_completeness(&self, args: SpecificationProjectArgs) -> Result<CompletenessReport, McpError> {
+        let completeness = self.analytics_service.analyze_specification_completeness(&args.project_id).await?;
 
-        let days = arguments.get("days")
-            .and_then(|v| v.as_i64())
-            .unwrap_or(30);
+        let summary = CompletenessSummary {
+            total_specifications: completeness.len(),
+            complete_specifications: completeness.iter().filter(|s| s.completeness_score >= 0.8).count(),
+            incomplete_specifications: completeness.iter().filter(|s| s.completeness_score < 0.5).count(),
+            average_completeness: if completeness.is_empty() { 0.0 } else {
+                completeness.iter().map(|s| s.completeness_score).sum::<f64>() / completeness.len() as f64
+            },
+            specifications_with_issues: completeness.iter().filter(|s| !s.quality_issues.is_empty()).count(),
+            specifications_with_missing_sections: completeness.iter().filter(|s| !s.missing_sections.is_empty()).count(),
+            total_recommendations: completeness.iter().map(|s| s.recommendations.len()).sum::<usize>(),
+        };
+
+        Ok(CompletenessReport {
+            project_id: args.project_id,
+            specifications_count: completeness.len(),
+            specifications_completeness: completeness,
+            summary,
+        })
+    }
 
-        if days < 1 || days > 365 {
+    pub async fn handle_calculate_development_velocity(&self, args: DevelopmentVelocityArgs) -> Result<VelocityReport, McpError> {
+        let days = args.days;
+        if !(1..=365).contains(&days) {
             return Err(McpError::invalid_params("Days must be between 1 and 365", None));
         }
 
-        let velocity = self.analytics_service.calculate_development_velocity(project_id, days).await?;
-
-        let result = json!({
-            "project_id": project_id,
-            "velocity_metrics": velocity,
-            "insights": {
-                "daily_task_completion_rate": velocity.tasks_completed as f64 / velocity.time_period_days as f64,
-                "daily_requirement_completion_rate": velocity.requirements_completed as f64 / velocity.time_period_days as f64,
-                "velocity_trend_description": match velocity.velocity_trend {
-                    crate::services::specification_analytics_service::VelocityTrend::Increasing => "Development velocity is increasing",
-                    crate::services::specification_analytics_service::VelocityTrend::Stable => "Development velocity is stable",
-                    crate::services::specification_analytics_service::VelocityTrend::Decreasing => "Development velocity is decreasing",
-                    crate::services::specification_analytics_service::VelocityTrend::InsufficientData => "Insufficient data to determine trend",
-                },
-                "productivity_level": if velocity.productivity_score >= 70.0 {
-                    "High"
-                } else if velocity.productivity_score >= 40.0 {
-                    "Medium"
-                } else {
-                    "Low"
-                },
-                "has_bottlenecks": !velocity.bottlenecks.is_empty(),
-                "bottlenecks_count": velocity.bottlenecks.len(),
+        let velocity = self.analytics_service.calculate_development_velocity(&args.project_id, days).await?;
+
+        let insights = VelocityInsights {
+            daily_task_completion_rate: velocity.tasks_completed as f64 / velocity.time_period_days as f64,
+            daily_requirement_completion_rate: velocity.requirements_completed as f64 / velocity.time_period_days as f64,
+            velocity_trend_description: match velocity.velocity_trend {
+                VelocityTrend::Increasing => "Development velocity is increasing",
+                VelocityTrend::Stable => "Development velocity is stable",
+                VelocityTrend::Decreasing => "Development velocity is decreasing",
+                VelocityTrend::InsufficientData => "Insufficient data to determine trend",
             }
-        });
+            .to_string(),
+            productivity_level: if velocity.productivity_score >= 70.0 {
+                "High"
+            } else if velocity.productivity_score >= 40.0 {
+                "Medium"
+            } else {
+                "Low"
+            }
+            .to_string(),
+            has_bottlenecks: !velocity.bottlenecks.is_empty(),
+            bottlenecks_count: velocity.bottlenecks.len(),
+        };
+
+        Ok(VelocityReport {
+            project_id: args.project_id,
+            velocity_metrics: velocity,
+            insights,
+        })
+    }
 
-        Ok(CallToolResult::success(vec![Content::text(format!("Development Velocity Metrics for Project: {} (Last {} days)\n\n{}", project_id, days, serde_json::to_string_pretty(&result).unwrap()))]))
-    }
-
-    async fn handle_generate_specification_health_report(&self, arguments: Value) -> Result<CallToolResult, McpError> {
-        let project_id = arguments.get("project_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing or invalid project_id", None))?;
-
-        let report = self.analytics_service.generate_health_report(project_id).await?;
-
-        let result = json!({
-            "project_id": project_id,
-            "health_report": report,
-            "executive_summary": {
-                "overall_health_level": if report.overall_health_score >= 0.8 {
-                    "Excellent"
-                } else if report.overall_health_score >= 0.6 {
-                    "Good"
-                } else if report.overall_health_score >= 0.4 {
-                    "Fair"
-                } else {
-                    "Poor"
-                },
-                "total_specifications": report.specifications.len(),
-                "total_requirements": report.requirements_progress.len(),
-                "total_tasks": report.tasks_progress.len(),
-                "critical_issues_count": report.critical_issues.len(),
-                "recommendations_count": report.recommendations.len(),
-                "has_critical_issues": !report.critical_issues.is_empty(),
-                "velocity_trend": report.velocity_metrics.velocity_trend,
-                "productivity_score": report.velocity_metrics.productivity_score,
+    pub async fn handle_generate_specification_health_report(&self, args: SpecificationProjectArgs) -> Result<HealthReportSummary, McpError> {
+        let report = self.analytics_service.generate_health_report(&args.project_id).await?;
+
+        let executive_summary = ExecutiveSummary {
+            overall_health_level: if report.overall_health_score >= 0.8 {
+                "Excellent"
+            } else if report.overall_health_score >= 0.6 {
+                "Good"
+            } else if report.overall_health_score >= 0.4 {
+                "Fair"
+            } else {
+                "Poor"
             }
-        });
+            .to_string(),
+            total_specifications: report.specifications.len(),
+            total_requirements: report.requirements_progress.len(),
+            total_tasks: report.tasks_progress.len(),
+            critical_issues_count: report.critical_issues.len(),
+            recommendations_count: report.recommendations.len(),
+            has_critical_issues: !report.critical_issues.is_empty(),
+            velocity_trend: report.velocity_metrics.velocity_trend.clone(),
+            productivity_score: report.velocity_metrics.productivity_score,
+        };
+
+        Ok(HealthReportSummary {
+            project_id: args.project_id,
+            health_report: report,
+            executive_summary,
+        })
+    }
+}
+
+fn is_high_priority(priority: &Priority) -> bool {
+    matches!(priority, Priority::Critical | Priority::High)
+}
+
+fn analytics_tools(context: &ToolContext) -> SpecificationAnalyticsTools {
+    SpecificationAnalyticsTools::new(context.container().specification_analytics_service.clone())
+}
+
+pub struct TrackRequirementsProgressTool;
 
-        Ok(CallToolResult::success(vec![Content::text(format!("Specification Health Report for Project: {}\n\n{}", project_id, serde_json::to_string_pretty(&result).unwrap()))]))
+#[async_trait]
+impl ToolHandler for TrackRequirementsProgressTool {
+    type Args = SpecificationProjectArgs;
+    type Output = RequirementsProgressReport;
+
+    fn name(&self) -> &'static str {
+        "track_requirements_progress"
+    }
+
+    fn description(&self) -> &'static str {
+        "Track progress for all requirements in a project, including completion percentages, linked tasks, and acceptance criteria status"
+    }
+
+    fn category(&self) -> &'static str {
+        "Analytics"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Monitor requirement completion and linked tasks"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        analytics_tools(context).handle_track_requirements_progress(args).await
+    }
+}
+
+pub struct TrackTasksProgressTool;
+
+#[async_trait]
+impl ToolHandler for TrackTasksProgressTool {
+    type Args = SpecificationProjectArgs;
+    type Output = TasksProgressReport;
+
+    fn name(&self) -> &'static str {
+        "track_tasks_progress"
+    }
+
+    fn description(&self) -> &'static str {
+        "Track progress for all tasks in a project, including status, completion percentage, time tracking, and dependencies"
+    }
+
+    fn category(&self) -> &'static str {
+        "Analytics"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Find blocked or stale tasks"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        analytics_tools(context).handle_track_tasks_progress(args).await
+    }
+}
+
+pub struct AnalyzeSpecificationCompletenessTool;
+
+#[async_trait]
+impl ToolHandler for AnalyzeSpecificationCompletenessTool {
+    type Args = SpecificationProjectArgs;
+    type Output = CompletenessReport;
+
+    fn name(&self) -> &'static str {
+        "analyze_specification_completeness"
+    }
+
+    fn description(&self) -> &'static str {
+        "Analyze completeness of specifications in a project, including content quality, missing sections, and recommendations"
+    }
+
+    fn category(&self) -> &'static str {
+        "Analytics"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Find specifications with missing sections"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        analytics_tools(context).handle_analyze_specification_completeness(args).await
+    }
+}
+
+pub struct CalculateDevelopmentVelocityTool;
+
+#[async_trait]
+impl ToolHandler for CalculateDevelopmentVelocityTool {
+    type Args = DevelopmentVelocityArgs;
+    type Output = VelocityReport;
+
+    fn name(&self) -> &'static str {
+        "calculate_development_velocity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Calculate development velocity metrics based on task and requirement completion over a specified time period"
+    }
+
+    fn category(&self) -> &'static str {
+        "Analytics"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Measure completed tasks and requirements over the last 30 days"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        analytics_tools(context).handle_calculate_development_velocity(args).await
+    }
+}
+
+pub struct GenerateSpecificationHealthReportTool;
+
+#[async_trait]
+impl ToolHandler for GenerateSpecificationHealthReportTool {
+    type Args = SpecificationProjectArgs;
+    type Output = HealthReportSummary;
+
+    fn name(&self) -> &'static str {
+        "generate_specification_health_report"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate a comprehensive health report for all specifications in a project, including progress, completeness, velocity, and recommendations"
+    }
+
+    fn category(&self) -> &'static str {
+        "Analytics"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Get an overall picture of specification health before planning"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        analytics_tools(context).handle_generate_specification_health_report(args).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
     use crate::services::specification_analytics_service::{
         SpecificationAnalyticsService, RequirementProgress, TaskProgress, 
         SpecificationCompleteness, DevelopmentVelocity, SpecificationHealthReport,
@@ -397,9 +593,9 @@ mod tests {
             "project_id": "test-project"
         });
 
-        let result = tools.handle_track_requirements_progress(arguments).await.unwrap();
+        let result = tools.handle_tool_call("track_requirements_progress", arguments).await.unwrap();
         assert!(result.is_error.is_none() || !result.is_error.unwrap());
-        assert!(!result.content.is_empty());
+        assert!(result.structured_content.is_some());
     }
 
     #[tokio::test]
@@ -411,9 +607,9 @@ mod tests {
             "project_id": "test-project"
         });
 
-        let result = tools.handle_track_tasks_progress(arguments).await.unwrap();
+        let result = tools.handle_tool_call("track_tasks_progress", arguments).await.unwrap();
         assert!(result.is_error.is_none() || !result.is_error.unwrap());
-        assert!(!result.content.is_empty());
+        assert!(result.structured_content.is_some());
     }
 
     #[tokio::test]
@@ -425,9 +621,9 @@ mod tests {
             "project_id": "test-project"
         });
 
-        let result = tools.handle_analyze_specification_completeness(arguments).await.unwrap();
+        let result = tools.handle_tool_call("analyze_specification_completeness", arguments).await.unwrap();
         assert!(result.is_error.is_none() || !result.is_error.unwrap());
-        assert!(!result.content.is_empty());
+        assert!(result.structured_content.is_some());
     }
 
     #[tokio::test]
@@ -440,9 +636,9 @@ mod tests {
             "days": 30
         });
 
-        let result = tools.handle_calculate_development_velocity(arguments).await.unwrap();
+        let result = tools.handle_tool_call("calculate_development_velocity", arguments).await.unwrap();
         assert!(result.is_error.is_none() || !result.is_error.unwrap());
-        assert!(!result.content.is_empty());
+        assert!(result.structured_content.is_some());
     }
 
     #[tokio::test]
@@ -454,9 +650,9 @@ mod tests {
             "project_id": "test-project"
         });
 
-        let result = tools.handle_generate_specification_health_report(arguments).await.unwrap();
+        let result = tools.handle_tool_call("generate_specification_health_report", arguments).await.unwrap();
         assert!(result.is_error.is_none() || !result.is_error.unwrap());
-        assert!(!result.content.is_empty());
+        assert!(result.structured_content.is_some());
     }
 
     #[tokio::test]
@@ -466,7 +662,7 @@ mod tests {
 
         let arguments = json!({});
 
-        let result = tools.handle_track_requirements_progress(arguments).await;
+        let result = tools.handle_tool_call("track_requirements_progress", arguments).await;
         assert!(result.is_err());
     }
 
@@ -480,15 +676,17 @@ mod tests {
             "days": 500  // Invalid: too high
         });
 
-        let result = tools.handle_calculate_development_velocity(arguments).await;
+        let result = tools.handle_tool_call("calculate_development_velocity", arguments).await;
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_get_tools() {
-        let tools = SpecificationAnalyticsTools::get_tools();
+    fn test_tools_are_registered() {
+        let mut registry = ToolRegistry::default();
+        register_tools(&mut registry);
+
+        let tools = registry.tools();
         assert_eq!(tools.len(), 5);
-        
         let tool_names: Vec<&str> = tools.iter().map(|t| t.name.as_ref()).collect();
         assert!(tool_names.contains(&"track_requirements_progress"));
         assert!(tool_names.contains(&"track_tasks_progress"));
@@ -496,4 +694,4 @@ mod tests {
         assert!(tool_names.contains(&"calculate_development_velocity"));
         assert!(tool_names.contains(&"generate_specification_health_report"));
     }
-}
\ No newline at end of file
+}
@@ -309,6 +309,6 @@ mod tests {
 
         let result = tools.handle_tool_call(request).await.unwrap();
         assert!(result.is_error.is_none() || !result.is_error.unwrap());
-        assert!(!result.content.is_empty());
+        assert!(result.content.is_some_and(|content| !content.is_empty()));
     }
 }
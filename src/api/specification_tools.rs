@@ -0,0 +1,345 @@
+use crate::api::context_resources::SPECIFICATION_ENTITY_TYPE;
+use crate::api::tool_registry::{cancelled_error, PageArgs, ToolContext, ToolHandler, ToolRegistry};
+use crate::models::pagination::Page;
+use crate::models::specification::ProjectSpecification;
+use crate::services::specification_versioning_service::{SpecificationVersion, VersionComparison};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Register the specification listing, import, monitoring and versioning tools
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(ListSpecificationsTool);
+    registry.register(ScanSpecificationsTool);
+    registry.register(ImportSpecificationTool);
+    registry.register(ValidateSpecificationTool);
+    registry.register(StartSpecMonitoringTool);
+    registry.register(GetSpecificationVersionsTool);
+    registry.register(CompareSpecificationVersionsTool);
+}
+
+fn default_specs_path() -> String {
+    ".kiro/specs".to_string()
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SpecsPathArgs {
+    /// Base path of the specifications directory (defaults to .kiro/specs)
+    #[serde(default = "default_specs_path")]
+    pub base_path: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SpecificationFileArgs {
+    /// Path to the specification file
+    pub file_path: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListSpecificationsArgs {
+    /// The ID of the project
+    pub project_id: String,
+    /// Only list specifications of this type (e.g. 'requirements', 'design', 'tasks')
+    pub spec_type: Option<String>,
+    #[serde(flatten)]
+    pub page: PageArgs,
+}
+
+pub struct ListSpecificationsTool;
+
+#[async_trait]
+impl ToolHandler for ListSpecificationsTool {
+    type Args = ListSpecificationsArgs;
+    type Output = Page<Value>;
+
+    fn name(&self) -> &'static str {
+        "list_specifications"
+    }
+
+    fn description(&self) -> &'static str {
+        "List the imported specifications of a project, one page at a time"
+    }
+
+    fn category(&self) -> &'static str {
+        "Specifications"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "List the titles and statuses of a project's task specifications"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let specifications = context
+            .container()
+            .specification_service
+            .get_specifications_page(&args.project_id, args.spec_type.as_deref(), &args.page.query())
+            .await?;
+        args.page.project(specifications)
+    }
+}
+
+pub struct ScanSpecificationsTool;
+
+#[async_trait]
+impl ToolHandler for ScanSpecificationsTool {
+    type Args = SpecsPathArgs;
+    type Output = Vec<ProjectSpecification>;
+
+    fn name(&self) -> &'static str {
+        "scan_specifications"
+    }
+
+    fn description(&self) -> &'static str {
+        "Scan and import all Kiro specifications from .kiro/specs directory, reporting progress per file. When cancelled it stops between files; files imported so far stay imported"
+    }
+
+    fn category(&self) -> &'static str {
+        "Specifications"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Import all specifications from .kiro/specs directory"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let service = &context.container().specification_import_service;
+        let files = service
+            .find_specification_files(Path::new(&args.base_path))
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to scan specifications: {e}"), None))?;
+
+        let mut specs: Vec<ProjectSpecification> = Vec::new();
+        for (index, file) in files.iter().enumerate() {
+            if context.is_cancelled() {
+                return Err(cancelled_error(json!({
+                    "processed_files": &files[..index],
+                    "imported_spec_ids": specs.iter().map(|spec| &spec.id).collect::<Vec<_>>()
+                })));
+            }
+            match service.import_specification_file(file).await {
+                Ok(spec) => specs.push(spec),
+                Err(e) => tracing::warn!("Failed to import specification from {}: {}", file.display(), e),
+            }
+            context
+                .report_progress(index + 1, files.len(), format!("Scanned {}", file.display()))
+                .await;
+        }
+        Ok(specs)
+    }
+}
+
+pub struct ImportSpecificationTool;
+
+#[async_trait]
+impl ToolHandler for ImportSpecificationTool {
+    type Args = SpecificationFileArgs;
+    type Output = ProjectSpecification;
+
+    fn name(&self) -> &'static str {
+        "import_specification"
+    }
+
+    fn description(&self) -> &'static str {
+        "Import a single specification file"
+    }
+
+    fn category(&self) -> &'static str {
+        "Specifications"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Import specific requirements.md or tasks.md file"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let spec = context
+            .container()
+            .specification_import_service
+            .import_specification_file(Path::new(&args.file_path))
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to import specification: {e}"), None))?;
+        if let Ok(entity) = serde_json::to_value(&spec) {
+            context.publish_change(SPECIFICATION_ENTITY_TYPE, None, Some(&entity)).await;
+        }
+        Ok(spec)
+    }
+}
+
+/// Outcome of `validate_specification`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SpecificationValidation {
+    pub file_path: String,
+    pub is_valid: bool,
+    pub validation_issues: Vec<String>,
+}
+
+pub struct ValidateSpecificationTool;
+
+#[async_trait]
+impl ToolHandler for ValidateSpecificationTool {
+    type Args = SpecificationFileArgs;
+    type Output = SpecificationValidation;
+
+    fn name(&self) -> &'static str {
+        "validate_specification"
+    }
+
+    fn description(&self) -> &'static str {
+        "Validate a specification file and return validation issues"
+    }
+
+    fn category(&self) -> &'static str {
+        "Specifications"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Check specification file for format issues and completeness"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let issues = context
+            .container()
+            .specification_import_service
+            .validate_specification_file(Path::new(&args.file_path))
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to validate specification: {e}"), None))?;
+        Ok(SpecificationValidation {
+            file_path: args.file_path,
+            is_valid: issues.is_empty(),
+            validation_issues: issues,
+        })
+    }
+}
+
+/// Outcome of `start_spec_monitoring`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MonitoringStatus {
+    pub status: String,
+    pub message: String,
+    pub monitoring_path: String,
+}
+
+pub struct StartSpecMonitoringTool;
+
+#[async_trait]
+impl ToolHandler for StartSpecMonitoringTool {
+    type Args = SpecsPathArgs;
+    type Output = MonitoringStatus;
+
+    fn name(&self) -> &'static str {
+        "start_spec_monitoring"
+    }
+
+    fn description(&self) -> &'static str {
+        "Start monitoring .kiro/specs directory for changes"
+    }
+
+    fn category(&self) -> &'static str {
+        "Specifications"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Re-import specifications automatically as they are edited"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context
+            .container()
+            .specification_import_service
+            .start_file_monitoring(Path::new(&args.base_path))
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to start monitoring: {e}"), None))?;
+        Ok(MonitoringStatus {
+            status: "success".to_string(),
+            message: format!("Started monitoring {}", args.base_path),
+            monitoring_path: args.base_path,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetSpecificationVersionsArgs {
+    /// ID of the specification
+    pub spec_id: String,
+}
+
+pub struct GetSpecificationVersionsTool;
+
+#[async_trait]
+impl ToolHandler for GetSpecificationVersionsTool {
+    type Args = GetSpecificationVersionsArgs;
+    type Output = Vec<SpecificationVersion>;
+
+    fn name(&self) -> &'static str {
+        "get_specification_versions"
+    }
+
+    fn description(&self) -> &'static str {
+        "Get all versions of a specification"
+    }
+
+    fn category(&self) -> &'static str {
+        "Specifications"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Review how a specification changed over time"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let versions = context
+            .container()
+            .specification_versioning_service
+            .get_versions(&args.spec_id)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to get specification versions: {e}"), None))?;
+        Ok(versions)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CompareSpecificationVersionsArgs {
+    /// ID of the first version
+    pub version1_id: String,
+    /// ID of the second version
+    pub version2_id: String,
+}
+
+pub struct CompareSpecificationVersionsTool;
+
+#[async_trait]
+impl ToolHandler for CompareSpecificationVersionsTool {
+    type Args = CompareSpecificationVersionsArgs;
+    type Output = VersionComparison;
+
+    fn name(&self) -> &'static str {
+        "compare_specification_versions"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compare two versions of a specification"
+    }
+
+    fn category(&self) -> &'static str {
+        "Specifications"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "See what changed between two imports of a specification"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let comparison = context
+            .container()
+            .specification_versioning_service
+            .compare_versions(&args.version1_id, &args.version2_id)
+            .await
+            .map_err(|e| {
+                McpError::internal_error(format!("Failed to compare specification versions: {e}"), None)
+            })?;
+        Ok(comparison)
+    }
+}
@@ -0,0 +1,211 @@
+use rmcp::handler::server::tool::cached_schema_for_type;
+use rmcp::model::{CallToolResult, Content, ErrorData as McpError, JsonObject};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Key a tool's output is reported under when it is not a JSON object.
+/// Structured content must be an object, so lists and optional values are
+/// wrapped as `{"result": ...}`.
+pub const RESULT_KEY: &str = "result";
+
+/// Output schema of tools returning `T`
+pub fn output_schema<T: JsonSchema + 'static>() -> Arc<JsonObject> {
+    let schema = cached_schema_for_type::<T>();
+    if !wraps_result::<T>() {
+        return schema;
+    }
+
+    let mut inner = (*schema).clone();
+    let mut wrapper = JsonObject::new();
+    // Definitions stay at the root so `$ref`s into them still resolve
+    for key in ["$schema", "definitions", "$defs"] {
+        if let Some(value) = inner.remove(key) {
+            wrapper.insert(key.to_string(), value);
+        }
+    }
+    wrapper.insert("type".to_string(), json!("object"));
+    wrapper.insert("properties".to_string(), json!({ RESULT_KEY: inner }));
+    wrapper.insert("required".to_string(), json!([RESULT_KEY]));
+    Arc::new(wrapper)
+}
+
+/// Whether outputs of type `T` are reported under [`RESULT_KEY`]
+fn wraps_result<T: JsonSchema + 'static>() -> bool {
+    let schema = cached_schema_for_type::<T>();
+    schema.get("type") != Some(&json!("object")) || schema.get("nullable") == Some(&json!(true))
+}
+
+/// Tool result carrying `output` as structured content, with a Markdown
+/// rendering of it as text content for clients that only read text
+pub fn structured_result<T: Serialize + JsonSchema + 'static>(output: &T) -> Result<CallToolResult, McpError> {
+    let value = serde_json::to_value(output)
+        .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?;
+    let text = render_markdown(&value);
+    let structured = if wraps_result::<T>() { json!({ RESULT_KEY: value }) } else { value };
+    Ok(CallToolResult {
+        content: Some(vec![Content::text(text)]),
+        structured_content: Some(structured),
+        is_error: Some(false),
+    })
+}
+
+/// Compact Markdown rendering of a JSON value: objects and arrays become
+/// nested bullet lists with bold keys, lists of scalars are joined on one line
+/// and null fields are left out.
+pub fn render_markdown(value: &Value) -> String {
+    let mut markdown = String::new();
+    match value {
+        Value::Object(_) | Value::Array(_) => render_children(&mut markdown, value, 0),
+        scalar => markdown.push_str(&scalar_text(scalar, 0)),
+    }
+    if markdown.is_empty() {
+        markdown.push_str("_None_");
+    }
+    markdown
+}
+
+fn render_children(markdown: &mut String, value: &Value, depth: usize) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                render_item(markdown, Some(key), field, depth);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                render_item(markdown, None, item, depth);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn render_item(markdown: &mut String, key: Option<&str>, value: &Value, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let label = key.map(|k| format!("**{k}**: ")).unwrap_or_default();
+    match value {
+        Value::Null => {}
+        Value::Array(items) if items.is_empty() => {
+            let _ = writeln!(markdown, "{indent}- {label}_none_");
+        }
+        Value::Array(items) if items.iter().all(is_scalar) => {
+            let joined: Vec<String> = items.iter().map(|item| scalar_text(item, depth + 1)).collect();
+            let _ = writeln!(markdown, "{indent}- {label}{}", joined.join(", "));
+        }
+        Value::Object(fields) if fields.values().all(Value::is_null) => {
+            let _ = writeln!(markdown, "{indent}- {label}_none_");
+        }
+        Value::Object(fields) if key.is_none() => {
+            // A list entry leads with its first scalar field so entries stay one bullet each
+            let mut fields = fields.iter().filter(|(_, v)| !v.is_null());
+            match fields.next() {
+                Some((first_key, first)) if is_scalar(first) => {
+                    let _ = writeln!(markdown, "{indent}- **{first_key}**: {}", scalar_text(first, depth + 1));
+                    for (field_key, field) in fields {
+                        render_item(markdown, Some(field_key), field, depth + 1);
+                    }
+                }
+                _ => {
+                    let _ = writeln!(markdown, "{indent}-");
+                    render_children(markdown, value, depth + 1);
+                }
+            }
+        }
+        Value::Object(_) | Value::Array(_) => {
+            let _ = writeln!(markdown, "{indent}- {}", label.trim_end_matches(' '));
+            render_children(markdown, value, depth + 1);
+        }
+        scalar => {
+            let _ = writeln!(markdown, "{indent}- {label}{}", scalar_text(scalar, depth + 1));
+        }
+    }
+}
+
+fn is_scalar(value: &Value) -> bool {
+    !matches!(value, Value::Object(_) | Value::Array(_))
+}
+
+/// A scalar as Markdown text; continuation lines of multi-line strings are
+/// indented to stay inside their bullet
+fn scalar_text(value: &Value, depth: usize) -> String {
+    match value {
+        Value::String(text) => text.replace('\n', &format!("\n{}", "  ".repeat(depth))),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, JsonSchema)]
+    struct Report {
+        name: String,
+        score: f64,
+    }
+
+    #[test]
+    fn test_render_markdown_nests_objects_and_lists() {
+        let value = json!({
+            "project_id": "p1",
+            "summary": {"total": 2, "note": null},
+            "tags": ["auth", "ui"],
+            "issues": [],
+            "items": [
+                {"id": "r1", "rule_name": "Refunds", "examples": ["a"]},
+                {"id": "r2", "description": "First line\nsecond line"}
+            ]
+        });
+        assert_eq!(
+            render_markdown(&value),
+            "- **project_id**: p1\n\
+             - **summary**:\n  \
+               - **total**: 2\n\
+             - **tags**: auth, ui\n\
+             - **issues**: _none_\n\
+             - **items**:\n  \
+               - **id**: r1\n    \
+                 - **rule_name**: Refunds\n    \
+                 - **examples**: a\n  \
+               - **id**: r2\n    \
+                 - **description**: First line\n      second line\n"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_of_scalars_and_empty_values() {
+        assert_eq!(render_markdown(&json!("done")), "done");
+        assert_eq!(render_markdown(&json!([])), "_None_");
+        assert_eq!(render_markdown(&json!(null)), "_None_");
+    }
+
+    #[test]
+    fn test_objects_are_returned_as_they_are() {
+        let schema = output_schema::<Report>();
+        assert_eq!(schema["type"], "object");
+        assert!(schema["properties"].get("score").is_some());
+
+        let result = structured_result(&Report { name: "health".to_string(), score: 0.5 }).unwrap();
+        assert_eq!(result.structured_content, Some(json!({"name": "health", "score": 0.5})));
+        let text = &result.content.unwrap()[0];
+        assert_eq!(text.as_text().unwrap().text, "- **name**: health\n- **score**: 0.5\n");
+    }
+
+    #[test]
+    fn test_lists_are_wrapped_in_result() {
+        let schema = output_schema::<Vec<Report>>();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!([RESULT_KEY]));
+        assert_eq!(schema["properties"][RESULT_KEY]["type"], "array");
+        let definition = schema["properties"][RESULT_KEY]["items"]["$ref"].as_str().unwrap();
+        let name = definition.rsplit('/').next().unwrap();
+        assert!(schema.get("definitions").or(schema.get("$defs")).is_some_and(|d| d.get(name).is_some()));
+
+        let result = structured_result(&vec![Report { name: "a".to_string(), score: 1.0 }]).unwrap();
+        assert_eq!(result.structured_content, Some(json!({"result": [{"name": "a", "score": 1.0}]})));
+    }
+}
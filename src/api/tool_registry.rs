@@ -0,0 +1,430 @@
+use crate::api::context_resources::{ContextCollection, SPECIFICATION_ENTITY_TYPE};
+use crate::api::tool_output::{output_schema, structured_result};
+use crate::api::{ContextResources, SearchTools};
+use crate::container::AppContainer;
+use crate::models::framework::ToolInfo;
+use crate::models::pagination::{ListQuery, Page, SortOrder};
+use crate::services::analytics_service::AnalyticsEvent;
+use crate::services::entity_history_service::{NewRevision, RevisionOperation};
+use async_trait::async_trait;
+use rmcp::handler::server::tool::cached_schema_for_type;
+use rmcp::model::{
+    CallToolRequestMethod, CallToolResult, ErrorCode, ErrorData as McpError, JsonObject,
+    ProgressNotificationParam, ProgressToken, Tool,
+};
+use rmcp::service::{Peer, RequestContext, RoleServer};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+/// An MCP tool with typed arguments and output. The tool's input schema is
+/// derived from `Args`, and calls are rejected with `invalid_params` when the
+/// arguments do not deserialize into it. The output schema is derived from
+/// `Output`, which is returned as structured content along with a Markdown
+/// rendering of it.
+#[async_trait]
+pub trait ToolHandler: Send + Sync + 'static {
+    type Args: DeserializeOwned + JsonSchema + Send + 'static;
+    type Output: Serialize + JsonSchema + Send + 'static;
+
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    /// Group the tool is listed under in `get_server_capabilities`
+    fn category(&self) -> &'static str;
+    fn example_use(&self) -> &'static str;
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError>;
+}
+
+/// Object-safe view of a [`ToolHandler`], taking untyped arguments
+#[async_trait]
+trait RegisteredTool: Send + Sync {
+    fn tool(&self) -> Tool;
+    fn info(&self) -> ToolInfo;
+    async fn call_json(&self, context: &ToolContext, arguments: JsonObject) -> Result<CallToolResult, McpError>;
+}
+
+#[async_trait]
+impl<H: ToolHandler> RegisteredTool for H {
+    fn tool(&self) -> Tool {
+        Tool {
+            name: self.name().into(),
+            description: Some(self.description().into()),
+            input_schema: cached_schema_for_type::<H::Args>(),
+            output_schema: Some(output_schema::<H::Output>()),
+            annotations: None,
+        }
+    }
+
+    fn info(&self) -> ToolInfo {
+        let required_params = cached_schema_for_type::<H::Args>()
+            .get("required")
+            .and_then(|v| v.as_array())
+            .map(|names| names.iter().filter_map(|n| n.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        ToolInfo {
+            name: self.name().to_string(),
+            description: self.description().to_string(),
+            category: self.category().to_string(),
+            required_params,
+            example_use: self.example_use().to_string(),
+        }
+    }
+
+    async fn call_json(&self, context: &ToolContext, arguments: JsonObject) -> Result<CallToolResult, McpError> {
+        let args = parse_arguments(self.name(), Value::Object(arguments))?;
+        structured_result(&self.call(context, args).await?)
+    }
+}
+
+/// Tools served by the MCP server, in the order they are listed
+#[derive(Default)]
+pub struct ToolRegistry {
+    tools: Vec<Box<dyn RegisteredTool>>,
+    by_name: HashMap<&'static str, usize>,
+}
+
+impl ToolRegistry {
+    /// Registry of every tool the server provides
+    pub fn new() -> Self {
+        let mut registry = Self::default();
+        crate::api::context_tools::register_tools(&mut registry);
+        crate::api::entity_tools::register_tools(&mut registry);
+        crate::api::history_tools::register_tools(&mut registry);
+        crate::api::trash_tools::register_tools(&mut registry);
+        crate::api::project_bundle_tools::register_tools(&mut registry);
+        crate::api::analytics_tools::register_tools(&mut registry);
+        crate::api::specification_tools::register_tools(&mut registry);
+        crate::api::specification_analytics_tools::register_tools(&mut registry);
+        crate::api::search_tools::register_tools(&mut registry);
+        registry
+    }
+
+    /// Add a tool. Names are unique; registering one twice is a programming error.
+    pub fn register<H: ToolHandler>(&mut self, handler: H) {
+        let name = handler.name();
+        assert!(!self.by_name.contains_key(name), "tool {name} registered twice");
+        self.by_name.insert(name, self.tools.len());
+        self.tools.push(Box::new(handler));
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.by_name.contains_key(name)
+    }
+
+    /// Tool definitions for `list_tools`
+    pub fn tools(&self) -> Vec<Tool> {
+        self.tools.iter().map(|t| t.tool()).collect()
+    }
+
+    /// Tool summaries for `get_server_capabilities`
+    pub fn tool_infos(&self) -> Vec<ToolInfo> {
+        self.tools.iter().map(|t| t.info()).collect()
+    }
+
+    /// Validate `arguments` against the named tool and run it
+    pub async fn call(
+        &self,
+        context: &ToolContext,
+        name: &str,
+        arguments: Option<JsonObject>,
+    ) -> Result<CallToolResult, McpError> {
+        let tool = self
+            .by_name
+            .get(name)
+            .map(|&index| &self.tools[index])
+            .ok_or_else(McpError::method_not_found::<CallToolRequestMethod>)?;
+        tool.call_json(context, arguments.unwrap_or_default()).await
+    }
+}
+
+/// Error code of a tool call the client cancelled
+pub const REQUEST_CANCELLED: ErrorCode = ErrorCode(-32800);
+
+/// Cancellation, progress reporting and author of one tool call
+#[derive(Clone, Default)]
+pub struct ToolCall {
+    cancellation: CancellationToken,
+    progress: Option<(ProgressToken, Peer<RoleServer>)>,
+    author: Option<String>,
+}
+
+impl ToolCall {
+    /// A call that stops once `cancellation` is cancelled and reports no progress
+    pub fn new(cancellation: CancellationToken) -> Self {
+        Self { cancellation, progress: None, author: None }
+    }
+
+    /// The call made by an MCP request. Progress is only reported when the
+    /// client asked for it by sending a progress token. Writes are attributed
+    /// to the client's name.
+    pub fn from_request(request: &RequestContext<RoleServer>) -> Self {
+        Self {
+            cancellation: request.ct.clone(),
+            progress: request.meta.get_progress_token().map(|token| (token, request.peer.clone())),
+            author: request.peer.peer_info().map(|info| info.client_info.name.clone()),
+        }
+    }
+}
+
+/// What tools run against: the shared container, the registry itself and the
+/// call being served
+#[derive(Clone)]
+pub struct ToolContext {
+    container: Arc<AppContainer>,
+    registry: Arc<ToolRegistry>,
+    call: ToolCall,
+}
+
+impl ToolContext {
+    pub fn new(container: Arc<AppContainer>, registry: Arc<ToolRegistry>) -> Self {
+        Self { container, registry, call: ToolCall::default() }
+    }
+
+    /// The same context, serving `call`
+    pub fn for_call(&self, call: ToolCall) -> Self {
+        Self { call, ..self.clone() }
+    }
+
+    /// The same context, attributing writes to `author` when one is given
+    pub fn with_author(&self, author: Option<String>) -> Self {
+        let mut context = self.clone();
+        if author.is_some() {
+            context.call.author = author;
+        }
+        context
+    }
+
+    /// Who the entity history records as having made this call's writes
+    pub fn author(&self) -> Option<&str> {
+        self.call.author.as_deref()
+    }
+
+    /// Whether the client cancelled the call. Long-running tools check this
+    /// between units of work and stop with [`cancelled_error`].
+    pub fn is_cancelled(&self) -> bool {
+        self.call.cancellation.is_cancelled()
+    }
+
+    /// Tell the client `progress` of `total` items are done. Notification
+    /// failures are only logged.
+    pub async fn report_progress(&self, progress: usize, total: usize, message: String) {
+        let Some((token, peer)) = &self.call.progress else {
+            return;
+        };
+        let notification = ProgressNotificationParam {
+            progress_token: token.clone(),
+            progress: progress as f64,
+            total: Some(total as f64),
+            message: Some(message),
+        };
+        if let Err(e) = peer.notify_progress(notification).await {
+            tracing::warn!("Failed to send progress notification: {}", e);
+        }
+    }
+
+    pub fn container(&self) -> &Arc<AppContainer> {
+        &self.container
+    }
+
+    pub fn registry(&self) -> &ToolRegistry {
+        &self.registry
+    }
+
+    /// Run the named tool
+    pub async fn call_tool(&self, name: &str, arguments: Option<JsonObject>) -> Result<CallToolResult, McpError> {
+        self.registry.call(self, name, arguments).await
+    }
+
+    pub fn resources(&self) -> ContextResources {
+        ContextResources::new(self.container.clone())
+    }
+
+    /// Record a usage analytics event. Tracking never fails the tool call.
+    pub async fn track(&self, event: AnalyticsEvent) {
+        if let Err(e) = self.container.analytics_service.track_event(event).await {
+            tracing::warn!("Failed to track analytics event: {}", e);
+        }
+    }
+
+    /// Search tools over the container's services, preparing search on first use
+    pub async fn search_tools(&self) -> Result<SearchTools, McpError> {
+        self.container.prepare_search().await.map_err(|e| {
+            McpError::internal_error(format!("Search is unavailable: {e}"), None)
+        })?;
+        Ok(SearchTools::new(
+            self.container.semantic_search_service.clone(),
+            self.container.hybrid_search_service.clone(),
+            self.container.search_index_manager.clone(),
+            self.container.enhanced_context_repository.clone(),
+        ))
+    }
+
+    /// Publish a write made through the tools so resource subscribers are notified.
+    /// `previous` is the entity before an update or delete, `current` the one written.
+    pub async fn publish_change(&self, entity_type: &str, previous: Option<Value>, current: Option<&Value>) {
+        if ContextCollection::from_entity_type(entity_type).is_none() && entity_type != SPECIFICATION_ENTITY_TYPE {
+            return;
+        }
+        let Some(entity) = current.or(previous.as_ref()) else {
+            return;
+        };
+        let field = |name: &str| entity.get(name).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let (entity_id, project_id) = (field("id"), field("project_id"));
+        if entity_id.is_empty() {
+            return;
+        }
+
+        let detector = &self.container.change_detection_service;
+        let client_id = uuid::Uuid::nil();
+        let result = match (previous, current) {
+            (Some(old), Some(new)) => {
+                detector
+                    .notify_entity_updated(entity_type, &entity_id, &project_id, old, new.clone(), client_id, None)
+                    .await
+            }
+            (Some(old), None) => {
+                detector.notify_entity_deleted(entity_type, &entity_id, &project_id, old, client_id, None).await
+            }
+            (None, Some(new)) => {
+                detector.notify_entity_created(entity_type, &entity_id, &project_id, new.clone(), client_id, None).await
+            }
+            (None, None) => return,
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to publish change to {} {}: {}", entity_type, entity_id, e);
+        }
+    }
+
+    /// Append a write made through the tools to the entity's history.
+    /// `previous` is the entity before the write, `current` the one written.
+    /// The write has already succeeded, so failures are only logged.
+    pub async fn record_revision(
+        &self,
+        entity_type: &str,
+        operation: RevisionOperation,
+        previous: Option<Value>,
+        current: Option<&Value>,
+    ) {
+        let Some(entity) = current.or(previous.as_ref()) else {
+            return;
+        };
+        let field = |name: &str| entity.get(name).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let entity_id = field("id");
+        let project_id = if entity_type == "project" { entity_id.clone() } else { field("project_id") };
+        let revision = NewRevision {
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.clone(),
+            project_id,
+            operation,
+            author: self.call.author.clone(),
+            previous,
+            snapshot: current.cloned(),
+        };
+        if let Err(e) = self.container.entity_history_service.record_revision(revision).await {
+            tracing::warn!("Failed to record revision of {} {}: {}", entity_type, entity_id, e.message);
+        }
+    }
+
+    /// Mirror a context entity written through the CRUD tools into the search index.
+    /// The write has already succeeded, so failures are only logged.
+    pub async fn sync_search_index(&self, entity_type: &str, entity: &Value) {
+        let Some(item) = SearchTools::context_item_for_entity(entity_type, entity) else {
+            return;
+        };
+        let context_id = item.id.clone();
+        let result = match self.search_tools().await {
+            Ok(tools) => tools.index_context_item(item).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to index {} {}: {}", entity_type, context_id, e.message);
+        }
+    }
+
+    /// Drop a deleted context entity from the search index
+    pub async fn remove_from_search_index(&self, entity_type: &str, id: &str) {
+        if !SearchTools::is_searchable_entity_type(entity_type) {
+            return;
+        }
+        let result = match self.search_tools().await {
+            Ok(tools) => tools.remove_context_item(id).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to remove {} {} from search index: {}", entity_type, id, e.message);
+        }
+    }
+}
+
+/// Deserialize a tool's arguments, reporting mismatches as invalid params
+pub fn parse_arguments<T: DeserializeOwned>(tool: &str, arguments: Value) -> Result<T, McpError> {
+    serde_json::from_value(arguments)
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments for {tool}: {e}"), None))
+}
+
+/// Error of a tool that stopped because its call was cancelled. `checkpoint`
+/// tells the client which work was completed, so it can resume from there.
+pub fn cancelled_error(checkpoint: Value) -> McpError {
+    McpError::new(REQUEST_CANCELLED, "Tool call cancelled", Some(checkpoint))
+}
+
+/// Arguments of tools that take none
+#[derive(Debug, Default, serde::Deserialize, JsonSchema)]
+pub struct NoArgs {}
+
+/// Arguments of tools scoped to one project
+#[derive(Debug, serde::Deserialize, JsonSchema)]
+pub struct ProjectArgs {
+    /// The ID of the project
+    pub project_id: String,
+}
+
+/// Paging, sorting and field projection shared by the list tools
+#[derive(Debug, Default, serde::Deserialize, JsonSchema)]
+pub struct PageArgs {
+    /// Maximum number of items to return (defaults to 50)
+    #[schemars(range(min = 1, max = 500))]
+    pub limit: Option<usize>,
+    /// The `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Field to sort by; each listing names the fields it can be sorted by
+    pub sort_by: Option<String>,
+    /// Sort direction (defaults to asc)
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    /// Fields to return for each item; `id` is always returned. Every field is returned when omitted
+    pub fields: Option<Vec<String>>,
+}
+
+impl PageArgs {
+    pub fn query(&self) -> ListQuery {
+        ListQuery {
+            limit: self.limit,
+            cursor: self.cursor.clone(),
+            sort_by: self.sort_by.clone(),
+            sort_order: self.sort_order,
+        }
+    }
+
+    /// The page as JSON, with each item cut down to the requested `fields`
+    pub fn project<T: Serialize>(&self, page: Page<T>) -> Result<Page<Value>, McpError> {
+        let items = page
+            .items
+            .iter()
+            .map(|item| {
+                let mut value = serde_json::to_value(item)
+                    .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?;
+                if let (Some(fields), Value::Object(object)) = (&self.fields, &mut value) {
+                    object.retain(|key, _| key == "id" || fields.iter().any(|f| f == key));
+                }
+                Ok(value)
+            })
+            .collect::<Result<Vec<_>, McpError>>()?;
+        Ok(Page { items, next_cursor: page.next_cursor })
+    }
+}
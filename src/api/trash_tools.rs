@@ -0,0 +1,161 @@
+use crate::api::entity_tools::{get_entity, EntityType};
+use crate::api::tool_registry::{ToolContext, ToolHandler, ToolRegistry};
+use crate::services::trash_service::DEFAULT_RETENTION_DAYS;
+use crate::services::{RevisionOperation, TrashedEntity};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rmcp::model::ErrorData as McpError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Register the tools that manage soft-deleted entities
+pub fn register_tools(registry: &mut ToolRegistry) {
+    registry.register(ListTrashTool);
+    registry.register(RestoreFromTrashTool);
+    registry.register(PurgeTrashTool);
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListTrashArgs {
+    /// Only list the project and entities of this project
+    pub project_id: Option<String>,
+}
+
+pub struct ListTrashTool;
+
+#[async_trait]
+impl ToolHandler for ListTrashTool {
+    type Args = ListTrashArgs;
+    type Output = Vec<TrashedEntity>;
+
+    fn name(&self) -> &'static str {
+        "list_trash"
+    }
+
+    fn description(&self) -> &'static str {
+        "List deleted entities that can still be restored, most recently deleted first"
+    }
+
+    fn category(&self) -> &'static str {
+        "Trash"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Find a business rule that was deleted by mistake"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        context.container().trash_service.list_trash(args.project_id.as_deref()).await
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RestoreFromTrashArgs {
+    /// The type of entity
+    pub entity_type: EntityType,
+    /// The ID of the entity
+    pub id: String,
+    /// Who the entity's history records as the author (defaults to the client's name)
+    pub author: Option<String>,
+}
+
+/// Entities taken out of the trash
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RestoredFromTrash {
+    /// The entity asked for, followed by any deleted together with it
+    pub restored: Vec<TrashedEntity>,
+}
+
+pub struct RestoreFromTrashTool;
+
+#[async_trait]
+impl ToolHandler for RestoreFromTrashTool {
+    type Args = RestoreFromTrashArgs;
+    type Output = RestoredFromTrash;
+
+    fn name(&self) -> &'static str {
+        "restore_from_trash"
+    }
+
+    fn description(&self) -> &'static str {
+        "Restore a deleted entity from the trash. Restoring a project also restores the entities deleted along with it"
+    }
+
+    fn category(&self) -> &'static str {
+        "Trash"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Bring back a project that was removed with manage_project"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let context = &context.with_author(args.author);
+        let restored = context.container().trash_service.restore(args.entity_type.as_str(), &args.id).await?;
+        if restored.is_empty() {
+            return Err(McpError::invalid_params(
+                format!("{} {} is not in the trash; use list_trash to see what is", args.entity_type.as_str(), args.id),
+                None,
+            ));
+        }
+
+        for item in &restored {
+            let entity_type: EntityType = serde_json::from_value(Value::String(item.entity_type.clone()))
+                .map_err(|e| McpError::internal_error(format!("Unknown entity type in trash: {e}"), None))?;
+            let Some(entity) = get_entity(context, entity_type, &item.id).await? else {
+                continue;
+            };
+            context.record_revision(&item.entity_type, RevisionOperation::Restore, None, Some(&entity)).await;
+            context.sync_search_index(&item.entity_type, &entity).await;
+            context.publish_change(&item.entity_type, None, Some(&entity)).await;
+        }
+        Ok(RestoredFromTrash { restored })
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PurgeTrashArgs {
+    /// Purge entities deleted more than this many days ago (defaults to 30; 0 empties the trash)
+    pub older_than_days: Option<u32>,
+}
+
+/// Outcome of a purge
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PurgedTrash {
+    /// Number of entities permanently deleted
+    pub purged: usize,
+    /// Entities deleted before this time were purged
+    pub deleted_before: DateTime<Utc>,
+}
+
+pub struct PurgeTrashTool;
+
+#[async_trait]
+impl ToolHandler for PurgeTrashTool {
+    type Args = PurgeTrashArgs;
+    type Output = PurgedTrash;
+
+    fn name(&self) -> &'static str {
+        "purge_trash"
+    }
+
+    fn description(&self) -> &'static str {
+        "Permanently delete entities that have been in the trash longer than a retention window. Purged entities cannot be restored"
+    }
+
+    fn category(&self) -> &'static str {
+        "Trash"
+    }
+
+    fn example_use(&self) -> &'static str {
+        "Empty the trash before sharing a database"
+    }
+
+    async fn call(&self, context: &ToolContext, args: Self::Args) -> Result<Self::Output, McpError> {
+        let days = args.older_than_days.unwrap_or(DEFAULT_RETENTION_DAYS);
+        let deleted_before = Utc::now() - Duration::days(days.into());
+        let purged = context.container().trash_service.purge(deleted_before).await?;
+        Ok(PurgedTrash { purged, deleted_before })
+    }
+}
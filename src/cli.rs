@@ -0,0 +1,442 @@
+use crate::config::{Config, LogFormat, TransportKind};
+use crate::container::AppContainer;
+use crate::db::init::init_db;
+use crate::db::migrations::{latest_version, run_migrations, schema_status};
+use crate::enhanced_context_server::EnhancedContextMcpServer;
+use crate::http_transport::serve_http;
+use crate::services::{BundleFormat, ConflictPolicy, ImportOptions, ProjectBundle, WebSocketServer};
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use rmcp::{transport::stdio, ServiceExt};
+use rusqlite::{Connection, OpenFlags};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tracing_subscriber::EnvFilter;
+
+/// MCP context server for AI code generation
+#[derive(Debug, Parser)]
+#[command(name = "context-server-rs", version, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    /// Config file; defaults to the nearest .context-server.toml, then
+    /// ~/config/context-server-rs/config.toml
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Database file, overriding the configuration
+    #[arg(long, global = true)]
+    pub db: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub serve: ServeArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct ServeArgs {
+    /// Transport to serve MCP over
+    #[arg(long, value_enum)]
+    pub transport: Option<TransportKind>,
+
+    /// Address the HTTP transport binds to
+    #[arg(long)]
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Serve MCP (the default)
+    Serve(ServeArgs),
+    /// Create or upgrade the database schema
+    Migrate,
+    /// Import a specification file into a project
+    Import { project_id: String, file: PathBuf },
+    /// Export a project's context as a bundle that import-context reads
+    Export {
+        project_id: String,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        #[arg(long, value_enum, default_value = "json")]
+        format: BundleFormat,
+        /// Include the project's search embeddings
+        #[arg(long)]
+        include_embeddings: bool,
+    },
+    /// Import a project bundle written by export
+    ImportContext {
+        file: PathBuf,
+        /// What to do with rows that already exist
+        #[arg(long, value_enum, default_value = "skip")]
+        conflict: ConflictPolicy,
+        /// Import a copy of the project under new IDs
+        #[arg(long)]
+        remap_ids: bool,
+        /// Import under this project ID instead of the bundled one
+        #[arg(long)]
+        project_id: Option<String>,
+        /// Print what would be imported without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print the context relevant to a task, as the query_context tool does
+    Query {
+        project_id: String,
+        #[arg(long)]
+        feature_area: String,
+        #[arg(long, default_value = "implement")]
+        task_type: String,
+        /// Comma separated component names
+        #[arg(long, value_delimiter = ',')]
+        components: Vec<String>,
+    },
+    /// Check the configuration and database
+    Doctor,
+}
+
+impl Cli {
+    /// Configuration with this command line's overrides applied
+    pub fn config(&self) -> Result<Config> {
+        let mut config = Config::load(self.config.as_deref())?;
+        if let Some(db) = &self.db {
+            config.database.path = Some(db.clone());
+        }
+        let serve = match &self.command {
+            Some(Command::Serve(args)) => args,
+            _ => &self.serve,
+        };
+        if let Some(transport) = serve.transport {
+            config.server.transport = transport;
+        }
+        if let Some(address) = &serve.address {
+            config.server.http_address = address.clone();
+        }
+        Ok(config)
+    }
+
+    pub async fn run(self, config: Config) -> Result<()> {
+        match self.command {
+            None | Some(Command::Serve(_)) => serve(&config).await,
+            Some(Command::Migrate) => migrate(&config).await,
+            Some(Command::Import { project_id, file }) => import(&config, &project_id, &file).await,
+            Some(Command::Export {
+                project_id,
+                output,
+                format,
+                include_embeddings,
+            }) => export(&config, &project_id, output.as_deref(), format, include_embeddings).await,
+            Some(Command::ImportContext {
+                file,
+                conflict,
+                remap_ids,
+                project_id,
+                dry_run,
+            }) => {
+                let options = ImportOptions {
+                    conflict_policy: conflict,
+                    remap_ids,
+                    target_project_id: project_id,
+                    dry_run,
+                };
+                import_context(&config, &file, &options).await
+            }
+            Some(Command::Query {
+                project_id,
+                feature_area,
+                task_type,
+                components,
+            }) => query(&config, &project_id, &feature_area, &task_type, &components).await,
+            Some(Command::Doctor) => doctor(&config),
+        }
+    }
+}
+
+/// Log to stderr, leaving stdout to the stdio transport and command output
+pub fn init_logging(config: &Config) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.logging.level));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(false);
+    match config.logging.format {
+        LogFormat::Text => builder.init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+/// Path of the configured database, creating its directory
+fn prepare_database_path(config: &Config) -> Result<String> {
+    let db_path = config.database_path()?;
+    if let Some(dir) = db_path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    Ok(db_path.to_str().ok_or_else(|| anyhow!("Invalid database path"))?.to_string())
+}
+
+/// Initialize the configured database and build the container over it
+fn open_container(config: &Config) -> Result<Arc<AppContainer>> {
+    let db_path = prepare_database_path(config)?;
+    let db_path = db_path.as_str();
+
+    init_db(db_path)?;
+    tracing::info!("Database initialized at {}", db_path);
+    let embedding_config = config.embedding.embedding_config()?;
+    Ok(Arc::new(AppContainer::with_embedding_config(db_path, embedding_config)?))
+}
+
+async fn serve(config: &Config) -> Result<()> {
+    let container = open_container(config)?;
+
+    if let Some(address) = &config.websocket.bind_address {
+        let address: SocketAddr = address.parse().with_context(|| format!("Invalid WebSocket address {address}"))?;
+        tokio::spawn(async move {
+            if let Err(e) = WebSocketServer::new(address).start().await {
+                tracing::error!("WebSocket server stopped: {}", e);
+            }
+        });
+    }
+
+    if config.trash.retention_days > 0 {
+        tokio::spawn(purge_trash_periodically(container.clone(), config.trash.retention_days));
+    }
+
+    match config.server.transport {
+        TransportKind::Stdio => {
+            let service = EnhancedContextMcpServer::with_container(container)
+                .serve(stdio())
+                .await
+                .inspect_err(|e| {
+                    tracing::error!("Failed to serve MCP server: {:?}", e);
+                })?;
+
+            tracing::info!("Enhanced MCP Context Server started successfully");
+
+            // Wait for the service to complete
+            service.waiting().await?;
+        }
+        TransportKind::Http => {
+            // Sessions share one container, and so one database connection
+            let listener = TcpListener::bind(&config.server.http_address).await?;
+            serve_http(container, listener).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Hourly, permanently delete entities that have been in the trash longer than `retention_days`
+async fn purge_trash_periodically(container: Arc<AppContainer>, retention_days: u32) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+    loop {
+        interval.tick().await;
+        let deleted_before = chrono::Utc::now() - chrono::Duration::days(retention_days.into());
+        match container.trash_service.purge(deleted_before).await {
+            Ok(0) => {}
+            Ok(purged) => tracing::info!("Purged {} entities deleted more than {} days ago", purged, retention_days),
+            Err(e) => tracing::warn!("Failed to purge the trash: {}", e.message),
+        }
+    }
+}
+
+async fn migrate(config: &Config) -> Result<()> {
+    let db_path = prepare_database_path(config)?;
+    let applied = run_migrations(&Connection::open(&db_path)?)?;
+    if applied.is_empty() {
+        println!("Schema is up to date");
+    }
+    for migration in applied {
+        println!("Applied migration {} ({})", migration.version, migration.name);
+    }
+
+    // Converts embeddings stored by older versions
+    let container = open_container(config)?;
+    container.prepare_search().await?;
+    println!("Database ready at {db_path} (schema version {})", latest_version());
+    Ok(())
+}
+
+async fn import(config: &Config, project_id: &str, file: &Path) -> Result<()> {
+    let container = open_container(config)?;
+    container
+        .project_service
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| anyhow!("Project not found: {project_id}"))?;
+
+    let content = std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let file_path = file.to_string_lossy();
+    let spec = container
+        .specification_service
+        .import_specification_from_file(project_id.to_string(), &file_path, &content)
+        .await?;
+    println!("Imported specification {} ({})", spec.title, spec.id);
+    Ok(())
+}
+
+async fn export(
+    config: &Config,
+    project_id: &str,
+    output: Option<&Path>,
+    format: BundleFormat,
+    include_embeddings: bool,
+) -> Result<()> {
+    let container = open_container(config)?;
+    let bundle = container
+        .project_bundle_service
+        .export_project(project_id, include_embeddings)
+        .await?;
+
+    let text = bundle.render(format)?;
+    match output {
+        Some(path) => std::fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))?,
+        None => println!("{text}"),
+    }
+    Ok(())
+}
+
+async fn import_context(config: &Config, file: &Path, options: &ImportOptions) -> Result<()> {
+    let text = std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let bundle = ProjectBundle::parse(&text)?;
+    let container = open_container(config)?;
+    let report = container.project_bundle_service.import_project(&bundle, options).await?;
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.dry_run && report.changed() > 0 && !bundle.has_embeddings() {
+        eprintln!("Run rebuild_project_index for {} to make the imported context searchable", report.project_id);
+    }
+    Ok(())
+}
+
+async fn query(
+    config: &Config,
+    project_id: &str,
+    feature_area: &str,
+    task_type: &str,
+    components: &[String],
+) -> Result<()> {
+    let container = open_container(config)?;
+    let result = container
+        .context_query_service
+        .query_context(project_id, feature_area, task_type, components)
+        .await?;
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
+
+/// Report on the configuration and database without modifying either
+fn doctor(config: &Config) -> Result<()> {
+    let mut problems = 0;
+    let mut check = |ok: bool, message: String| {
+        println!("[{}] {}", if ok { "ok" } else { "FAIL" }, message);
+        if !ok {
+            problems += 1;
+        }
+    };
+
+    match &config.source {
+        Some(path) => check(true, format!("config: {}", path.display())),
+        None => check(true, "config: defaults (no config file found)".to_string()),
+    }
+
+    let db_path = config.database_path()?;
+    if !db_path.is_file() {
+        check(false, format!("database: {} does not exist, run `migrate`", db_path.display()));
+    } else {
+        match database_report(&db_path) {
+            Ok(report) => check(true, format!("database: {} ({report})", db_path.display())),
+            Err(e) => check(false, format!("database: {}: {e}", db_path.display())),
+        }
+    }
+
+    check(
+        config.server.http_address.parse::<SocketAddr>().is_ok(),
+        format!(
+            "transport: {:?} (HTTP address {})",
+            config.server.transport, config.server.http_address
+        ),
+    );
+    match &config.websocket.bind_address {
+        Some(address) => check(address.parse::<SocketAddr>().is_ok(), format!("websocket: {address}")),
+        None => check(true, "websocket: disabled".to_string()),
+    }
+    match config.embedding.embedding_config() {
+        Ok(embedding) => check(
+            embedding.embedding_dimension > 0
+                && embedding.model_path.as_deref().is_none_or(|path| Path::new(path).is_dir()),
+            format!(
+                "embedding: {} ({:?}), {} dimensions",
+                embedding.model_name, config.embedding.model_type, embedding.embedding_dimension
+            ),
+        ),
+        Err(e) => check(false, format!("embedding: {e}")),
+    }
+
+    if problems > 0 {
+        return Err(anyhow!("doctor found {problems} problem(s)"));
+    }
+    Ok(())
+}
+
+/// Integrity, schema and row counts of the database at `path`
+fn database_report(path: &Path) -> Result<String> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let integrity: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if integrity != "ok" {
+        return Err(anyhow!("integrity check failed: {integrity}"));
+    }
+
+    let schema = schema_status(&conn)?;
+    if !schema.pending.is_empty() {
+        return Err(anyhow!(
+            "schema version {} of {}, run `migrate`",
+            schema.current_version,
+            schema.latest_version
+        ));
+    }
+
+    let projects: i64 = conn.query_row("SELECT COUNT(*) FROM projects WHERE deleted_at IS NULL", [], |row| row.get(0))?;
+    Ok(format!("schema version {}, {projects} project(s)", schema.current_version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serve_is_the_default_command() {
+        let cli = Cli::try_parse_from(["context-server-rs", "--transport", "http", "--address", "0.0.0.0:9000"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.serve.transport, Some(TransportKind::Http));
+
+        let cli = Cli::try_parse_from(["context-server-rs", "serve", "--transport", "stdio"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Serve(ServeArgs { transport: Some(TransportKind::Stdio), .. }))));
+        assert!(Cli::try_parse_from(["context-server-rs", "--transport", "ws"]).is_err());
+    }
+
+    #[test]
+    fn test_subcommand_arguments() {
+        let cli = Cli::try_parse_from([
+            "context-server-rs",
+            "query",
+            "p1",
+            "--feature-area",
+            "auth",
+            "--components",
+            "login,signup",
+            "--db",
+            "/tmp/x.db",
+        ])
+        .unwrap();
+        assert_eq!(cli.db, Some(PathBuf::from("/tmp/x.db")));
+        match cli.command {
+            Some(Command::Query { project_id, task_type, components, .. }) => {
+                assert_eq!(project_id, "p1");
+                assert_eq!(task_type, "implement");
+                assert_eq!(components, ["login", "signup"]);
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+    }
+}
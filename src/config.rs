@@ -0,0 +1,434 @@
+use crate::container::AppContainer;
+use crate::models::embedding::{EmbeddingConfig, ModelType};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Per-repository config file, found by walking up from the working directory
+pub const CONFIG_FILE_NAME: &str = ".context-server.toml";
+
+/// Environment variable naming an explicit config file
+pub const CONFIG_ENV: &str = "CONTEXT_SERVER_CONFIG";
+
+/// Address the HTTP transport binds to when none is configured
+pub const DEFAULT_HTTP_ADDRESS: &str = "127.0.0.1:8080";
+
+/// Errors raised while loading configuration
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Invalid config file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("Invalid value '{value}' for {name}")]
+    InvalidValue { name: String, value: String },
+    #[error("{name} must be set: {reason}")]
+    MissingValue { name: String, reason: String },
+    #[error("Could not determine home directory")]
+    NoHomeDirectory,
+}
+
+/// Transport the server speaks MCP over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    #[default]
+    Stdio,
+    Http,
+}
+
+/// Format of log lines written to stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    /// SQLite file; relative paths are resolved against the config file's directory
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub transport: TransportKind,
+    pub http_address: String,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            transport: TransportKind::Stdio,
+            http_address: DEFAULT_HTTP_ADDRESS.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebSocketSettings {
+    /// Address of the real-time sync server; it is not started when unset
+    pub bind_address: Option<String>,
+}
+
+/// Embedding model behind search
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingModelKind {
+    /// BM25-weighted hashed terms; needs no model files
+    #[default]
+    Bm25,
+    SentenceTransformer,
+    Bert,
+    Codebert,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmbeddingSettings {
+    pub model_type: EmbeddingModelKind,
+    /// Name the vectors are stored under; changing it migrates the index.
+    /// Defaults to the model directory's name, or to `bm25`
+    pub model_name: Option<String>,
+    /// Directory of a local transformer model (`model.safetensors`, `config.json`, and
+    /// `vocab.txt` or `tokenizer.json`); relative paths are resolved against the config file's directory
+    pub model_path: Option<PathBuf>,
+    /// Dimension of the search vectors, the hidden size for transformer models;
+    /// changing it migrates the index
+    pub dimension: usize,
+}
+
+impl Default for EmbeddingSettings {
+    fn default() -> Self {
+        Self {
+            model_type: EmbeddingModelKind::Bm25,
+            model_name: None,
+            model_path: None,
+            dimension: 256,
+        }
+    }
+}
+
+impl EmbeddingSettings {
+    /// Embedding configuration for the search index
+    pub fn embedding_config(&self) -> Result<EmbeddingConfig, ConfigError> {
+        let model_type = match self.model_type {
+            EmbeddingModelKind::Bm25 => {
+                let config = AppContainer::search_embedding_config_with_dimension(self.dimension);
+                return Ok(EmbeddingConfig {
+                    model_name: self.model_name.clone().unwrap_or(config.model_name),
+                    ..config
+                });
+            }
+            EmbeddingModelKind::SentenceTransformer => ModelType::SentenceTransformer,
+            EmbeddingModelKind::Bert => ModelType::BERT,
+            EmbeddingModelKind::Codebert => ModelType::CodeBERT,
+        };
+
+        let model_path = self.model_path.as_ref().ok_or_else(|| ConfigError::MissingValue {
+            name: "embedding.model_path".to_string(),
+            reason: "transformer models are loaded from a local directory".to_string(),
+        })?;
+        let model_name = self.model_name.clone().unwrap_or_else(|| {
+            model_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| model_path.to_string_lossy().to_string())
+        });
+        Ok(EmbeddingConfig {
+            model_name,
+            model_path: Some(model_path.to_string_lossy().to_string()),
+            model_type,
+            embedding_dimension: self.dimension,
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingSettings {
+    /// Default filter directive; `RUST_LOG` takes precedence when set
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            format: LogFormat::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrashSettings {
+    /// Days deleted entities are kept before `serve` purges them; 0 disables purging
+    pub retention_days: u32,
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self { retention_days: crate::services::trash_service::DEFAULT_RETENTION_DAYS }
+    }
+}
+
+/// Server configuration: defaults, then the config file, then `CONTEXT_SERVER_*`
+/// environment variables, then command-line flags
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub database: DatabaseSettings,
+    pub server: ServerSettings,
+    pub websocket: WebSocketSettings,
+    pub embedding: EmbeddingSettings,
+    pub logging: LoggingSettings,
+    pub trash: TrashSettings,
+    /// File the configuration was read from, if any
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+impl Config {
+    /// Load the configuration for the current directory and environment.
+    /// An explicit path wins over `CONTEXT_SERVER_CONFIG`, which wins over the
+    /// nearest `.context-server.toml` and then the global config file.
+    pub fn load(explicit: Option<&Path>) -> Result<Self, ConfigError> {
+        let path = match explicit {
+            Some(path) => Some(path.to_path_buf()),
+            None => match std::env::var_os(CONFIG_ENV) {
+                Some(path) => Some(PathBuf::from(path)),
+                None => {
+                    let cwd = std::env::current_dir().ok();
+                    cwd.and_then(|cwd| Self::discover(&cwd))
+                        .or_else(|| global_config_dir().ok().map(|dir| dir.join("config.toml")).filter(|p| p.is_file()))
+                }
+            },
+        };
+
+        let mut config = match path {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        config.apply_env(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Nearest `.context-server.toml` in `start` or one of its ancestors
+    pub fn discover(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let mut config: Config = toml::from_str(&text).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        if let Some(dir) = path.parent() {
+            for relative in [&mut config.database.path, &mut config.embedding.model_path] {
+                if let Some(file) = relative.as_mut().filter(|file| file.is_relative()) {
+                    *file = dir.join(&*file);
+                }
+            }
+        }
+        config.source = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// Apply `CONTEXT_SERVER_*` overrides read through `lookup`
+    pub fn apply_env(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        if let Some(path) = lookup("CONTEXT_SERVER_DB_PATH") {
+            self.database.path = Some(PathBuf::from(path));
+        }
+        if let Some(transport) = lookup("CONTEXT_SERVER_TRANSPORT") {
+            self.server.transport = parse_value("CONTEXT_SERVER_TRANSPORT", &transport)?;
+        }
+        if let Some(address) = lookup("CONTEXT_SERVER_HTTP_ADDRESS") {
+            self.server.http_address = address;
+        }
+        if let Some(address) = lookup("CONTEXT_SERVER_WEBSOCKET_ADDRESS") {
+            self.websocket.bind_address = Some(address).filter(|a| !a.is_empty());
+        }
+        if let Some(model_type) = lookup("CONTEXT_SERVER_EMBEDDING_MODEL_TYPE") {
+            self.embedding.model_type = parse_value("CONTEXT_SERVER_EMBEDDING_MODEL_TYPE", &model_type)?;
+        }
+        if let Some(name) = lookup("CONTEXT_SERVER_EMBEDDING_MODEL_NAME") {
+            self.embedding.model_name = Some(name).filter(|n| !n.is_empty());
+        }
+        if let Some(path) = lookup("CONTEXT_SERVER_EMBEDDING_MODEL_PATH") {
+            self.embedding.model_path = Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty());
+        }
+        if let Some(dimension) = lookup("CONTEXT_SERVER_EMBEDDING_DIMENSION") {
+            self.embedding.dimension = dimension.parse().map_err(|_| ConfigError::InvalidValue {
+                name: "CONTEXT_SERVER_EMBEDDING_DIMENSION".to_string(),
+                value: dimension.clone(),
+            })?;
+        }
+        if let Some(days) = lookup("CONTEXT_SERVER_TRASH_RETENTION_DAYS") {
+            self.trash.retention_days = days.parse().map_err(|_| ConfigError::InvalidValue {
+                name: "CONTEXT_SERVER_TRASH_RETENTION_DAYS".to_string(),
+                value: days.clone(),
+            })?;
+        }
+        if let Some(level) = lookup("CONTEXT_SERVER_LOG_LEVEL") {
+            self.logging.level = level;
+        }
+        if let Some(format) = lookup("CONTEXT_SERVER_LOG_FORMAT") {
+            self.logging.format = parse_value("CONTEXT_SERVER_LOG_FORMAT", &format)?;
+        }
+        Ok(())
+    }
+
+    /// Database file to use, `~/config/context-server-rs/context.db` unless configured
+    pub fn database_path(&self) -> Result<PathBuf, ConfigError> {
+        match &self.database.path {
+            Some(path) => Ok(path.clone()),
+            None => Ok(global_config_dir()?.join("context.db")),
+        }
+    }
+}
+
+/// Global configuration directory, `~/config/context-server-rs`
+pub fn global_config_dir() -> Result<PathBuf, ConfigError> {
+    let home_dir = dirs::home_dir().ok_or(ConfigError::NoHomeDirectory)?;
+    Ok(home_dir.join("config").join("context-server-rs"))
+}
+
+/// Parse a lowercase enum value the way the config file spells it
+fn parse_value<T: serde::de::DeserializeOwned>(name: &str, value: &str) -> Result<T, ConfigError> {
+    T::deserialize(serde::de::value::StrDeserializer::<serde::de::value::Error>::new(&value.to_lowercase()))
+        .map_err(|_| ConfigError::InvalidValue {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_config_file_is_discovered_from_nested_directories() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("src").join("api");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(Config::discover(&nested), None);
+
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(
+            &path,
+            r#"
+[database]
+path = ".context/context.db"
+
+[server]
+transport = "http"
+
+[embedding]
+model_type = "sentence_transformer"
+model_path = "models/all-MiniLM-L6-v2"
+dimension = 384
+
+[logging]
+format = "json"
+"#,
+        )
+        .unwrap();
+        assert_eq!(Config::discover(&nested), Some(path.clone()));
+
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.database_path().unwrap(), dir.path().join(".context/context.db"));
+        assert_eq!(config.server.transport, TransportKind::Http);
+        assert_eq!(config.server.http_address, DEFAULT_HTTP_ADDRESS);
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(config.logging.level, "info");
+        assert_eq!(config.source, Some(path));
+
+        let embedding = config.embedding.embedding_config().unwrap();
+        assert_eq!(embedding.model_type, ModelType::SentenceTransformer);
+        assert_eq!(embedding.model_name, "all-MiniLM-L6-v2");
+        let model_path = dir.path().join("models/all-MiniLM-L6-v2");
+        assert_eq!(embedding.model_path.as_deref(), model_path.to_str());
+        assert_eq!(embedding.embedding_dimension, 384);
+    }
+
+    #[test]
+    fn test_embedding_model_selection() {
+        let bm25 = EmbeddingSettings::default().embedding_config().unwrap();
+        assert_eq!(bm25.model_name, "bm25");
+        assert_eq!(bm25.model_type, ModelType::Custom("bm25".to_string()));
+        assert_eq!(bm25.model_path, None);
+
+        let settings = EmbeddingSettings {
+            model_type: EmbeddingModelKind::Codebert,
+            ..Default::default()
+        };
+        assert!(matches!(settings.embedding_config(), Err(ConfigError::MissingValue { .. })));
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[server]\nport = 80\n").unwrap();
+        assert!(matches!(Config::from_file(&path), Err(ConfigError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_environment_overrides_the_file() {
+        let env: HashMap<&str, &str> = HashMap::from([
+            ("CONTEXT_SERVER_DB_PATH", "/tmp/other.db"),
+            ("CONTEXT_SERVER_TRANSPORT", "HTTP"),
+            ("CONTEXT_SERVER_HTTP_ADDRESS", "0.0.0.0:9000"),
+            ("CONTEXT_SERVER_WEBSOCKET_ADDRESS", "127.0.0.1:9001"),
+            ("CONTEXT_SERVER_EMBEDDING_MODEL_TYPE", "BERT"),
+            ("CONTEXT_SERVER_EMBEDDING_MODEL_NAME", "team-bert"),
+            ("CONTEXT_SERVER_EMBEDDING_MODEL_PATH", "/opt/models/bert"),
+            ("CONTEXT_SERVER_EMBEDDING_DIMENSION", "512"),
+            ("CONTEXT_SERVER_TRASH_RETENTION_DAYS", "7"),
+            ("CONTEXT_SERVER_LOG_LEVEL", "debug"),
+        ]);
+        let mut config = Config::default();
+        config.apply_env(|name| env.get(name).map(|v| v.to_string())).unwrap();
+
+        assert_eq!(config.database_path().unwrap(), PathBuf::from("/tmp/other.db"));
+        assert_eq!(config.server.transport, TransportKind::Http);
+        assert_eq!(config.server.http_address, "0.0.0.0:9000");
+        assert_eq!(config.websocket.bind_address.as_deref(), Some("127.0.0.1:9001"));
+        assert_eq!(config.embedding.dimension, 512);
+        let embedding = config.embedding.embedding_config().unwrap();
+        assert_eq!(embedding.model_type, ModelType::BERT);
+        assert_eq!(embedding.model_name, "team-bert");
+        assert_eq!(embedding.model_path.as_deref(), Some("/opt/models/bert"));
+        assert_eq!(config.trash.retention_days, 7);
+        assert_eq!(config.logging.level, "debug");
+
+        let error = config
+            .apply_env(|name| (name == "CONTEXT_SERVER_LOG_FORMAT").then(|| "xml".to_string()))
+            .unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidValue { .. }));
+    }
+}
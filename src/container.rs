@@ -4,26 +4,39 @@ use rusqlite::Connection;
 use std::sync::{Arc, Mutex};
 
 // Infrastructure layer
+use crate::db::init::init_db;
+use crate::repositories::embedding_repository::SqliteEmbeddingRepository;
+use crate::repositories::EnhancedContextRepository;
 use crate::infrastructure::{
     SqliteAnalyticsRepository,
     SqliteArchitecturalDecisionRepository,
     SqliteBusinessRuleRepository,
     SqliteDevelopmentPhaseRepository,
     SqliteEnhancedContextRepository,
+    SqliteFeatureContextRepository,
     SqliteFrameworkRepository,
     // Note: SqliteComponentRepository removed as it was identical to SqliteFrameworkRepository
     SqlitePerformanceRequirementRepository,
+    SqliteProjectConventionRepository,
     SqliteProjectRepository,
+    SqliteSecurityPolicyRepository,
     SqliteSpecificationRepository,
 };
 
 // Service layer
+use crate::models::embedding::{EmbeddingConfig, ModelType};
+use crate::models::enhanced_context::EnhancedContextItem;
+use crate::services::bm25_embedding_service::BM25_MODEL_TYPE;
+use crate::services::hybrid_search_service::HybridSearchConfig;
+use crate::services::search_index_manager::IndexManagerError;
+use crate::services::semantic_search_service::{SemanticSearchConfig, SemanticSearchServiceImpl};
 use crate::services::{
     analytics_service::{AnalyticsService, DefaultAnalyticsService},
     architecture_validation_service::ArchitectureValidationServiceImpl,
     context_crud_service::{ContextCrudService, ContextCrudServiceImpl},
     context_query_service::ContextQueryServiceImpl,
     development_phase_service::DevelopmentPhaseServiceImpl,
+    extended_context_crud_service::{ExtendedContextCrudService, ExtendedContextCrudServiceImpl},
     framework_service::FrameworkServiceImpl,
     // Note: ComponentService removed as it was identical to FrameworkService
     project_service::ProjectServiceImpl,
@@ -41,6 +54,25 @@ use crate::services::{
     SpecificationVersioningService,
     DefaultSpecificationContextLinkingService,
     SpecificationContextLinkingService,
+    EmbeddingService,
+    ChangeBroadcaster,
+    ChangeDetectionService,
+    ContextPackService,
+    DefaultContextPackService,
+    DefaultContextRelationshipEngine,
+    EmbeddingServiceFactory,
+    EntityHistoryService,
+    HybridSearchService,
+    HybridSearchServiceImpl,
+    IndexManagerConfig,
+    SearchIndexManagerImpl,
+    SemanticSearchService,
+    ProjectBundleService,
+    SqliteEntityHistoryService,
+    SqliteProjectBundleService,
+    SqliteTrashService,
+    TrashService,
+    VectorIndex,
 };
 
 /// Application container holding all dependencies
@@ -52,6 +84,7 @@ pub struct AppContainer {
     pub context_query_service: Box<dyn ContextQueryService>,
     pub architecture_validation_service: Box<dyn ArchitectureValidationService>,
     pub context_crud_service: Box<dyn ContextCrudService>,
+    pub extended_context_crud_service: Box<dyn ExtendedContextCrudService>,
     pub framework_service: Box<dyn FrameworkService>,
     pub analytics_service: Box<dyn AnalyticsService>,
     pub specification_service: Arc<dyn SpecificationService>,
@@ -59,13 +92,31 @@ pub struct AppContainer {
     pub specification_versioning_service: Arc<dyn SpecificationVersioningService>,
     pub specification_context_linking_service: Arc<dyn SpecificationContextLinkingService>,
     pub specification_analytics_service: Arc<dyn SpecificationAnalyticsService>,
+    pub entity_history_service: Arc<dyn EntityHistoryService>,
+    pub trash_service: Arc<dyn TrashService>,
+    pub project_bundle_service: Arc<dyn ProjectBundleService>,
+    pub enhanced_context_repository: Arc<dyn EnhancedContextRepository>,
+    pub semantic_search_service: Arc<dyn SemanticSearchService>,
+    pub hybrid_search_service: Arc<dyn HybridSearchService>,
+    pub context_pack_service: Arc<dyn ContextPackService>,
+    pub search_index_manager: Arc<SearchIndexManagerImpl>,
+    pub change_detection_service: Arc<ChangeDetectionService>,
     // Note: component_service removed as it was identical to framework_service
+    embedding_repository: Arc<SqliteEmbeddingRepository>,
+    embedding_service: Arc<dyn EmbeddingService>,
+    embedding_config: EmbeddingConfig,
+    search_ready: tokio::sync::OnceCell<()>,
 }
 
 impl AppContainer {
     /// Create a new application container with all dependencies injected
     pub fn new(db_path: &str) -> Result<Self> {
-        let conn = Connection::open(db_path)?;
+        Self::with_embedding_config(db_path, Self::search_embedding_config())
+    }
+
+    /// Create a container whose search index uses `embedding_config`
+    pub fn with_embedding_config(db_path: &str, embedding_config: EmbeddingConfig) -> Result<Self> {
+        let conn = init_db(db_path)?;
         let db = Arc::new(Mutex::new(conn));
 
         // Create repositories (infrastructure layer)
@@ -88,6 +139,9 @@ impl AppContainer {
             business_rule_repository,
             architectural_decision_repository,
             performance_requirement_repository,
+            SqliteSecurityPolicyRepository::new(db.clone()),
+            SqliteProjectConventionRepository::new(db.clone()),
+            SqliteFrameworkRepository::new(db.clone()),
         ));
 
         // Create framework service for architecture validation
@@ -106,19 +160,22 @@ impl AppContainer {
             SqlitePerformanceRequirementRepository::new(db.clone()),
         ));
 
+        let extended_context_crud_service = Box::new(ExtendedContextCrudServiceImpl::new(
+            SqliteSecurityPolicyRepository::new(db.clone()),
+            SqliteProjectConventionRepository::new(db.clone()),
+            SqliteFeatureContextRepository::new(db.clone()),
+        ));
+
         // Create framework service
         let framework_repository = SqliteFrameworkRepository::new(db.clone());
         let framework_service = Box::new(FrameworkServiceImpl::new(framework_repository));
 
         // Create analytics service
         let analytics_repository = SqliteAnalyticsRepository::new(db.clone());
-        // Initialize analytics tables
-        analytics_repository.init_tables()?;
         let analytics_service = Box::new(DefaultAnalyticsService::new(Box::new(analytics_repository)));
 
         // Create specification services
         let specification_repository = Arc::new(SqliteSpecificationRepository::new(db.clone()));
-        specification_repository.initialize_tables()?;
         
         let specification_service = Arc::new(DefaultSpecificationService::new(specification_repository.clone()));
         
@@ -128,19 +185,20 @@ impl AppContainer {
         ));
         
         let specification_versioning_service = Arc::new(SqliteSpecificationVersioningService::new(db.clone()));
-        specification_versioning_service.initialize_tables()?;
 
         // Create enhanced context repository and service
         let enhanced_context_repository = Arc::new(SqliteEnhancedContextRepository::new(db.clone()));
-        enhanced_context_repository.initialize_tables()?;
         
         let specification_context_linking_service = Arc::new(DefaultSpecificationContextLinkingService::new(
             specification_repository.clone(),
-            enhanced_context_repository,
+            enhanced_context_repository.clone(),
             Arc::new(ContextQueryServiceImpl::new(
                 SqliteBusinessRuleRepository::new(db.clone()),
                 SqliteArchitecturalDecisionRepository::new(db.clone()),
                 SqlitePerformanceRequirementRepository::new(db.clone()),
+                SqliteSecurityPolicyRepository::new(db.clone()),
+                SqliteProjectConventionRepository::new(db.clone()),
+                SqliteFrameworkRepository::new(db.clone()),
             )),
         ));
 
@@ -150,6 +208,68 @@ impl AppContainer {
             Arc::new(DefaultAnalyticsService::new(Box::new(SqliteAnalyticsRepository::new(db.clone())))),
         ));
 
+        // Revisions of the entities written through the entity tools
+        let entity_history_service = Arc::new(SqliteEntityHistoryService::new(db.clone()));
+
+        // Soft-deleted entities, restored or purged from the trash
+        let trash_service = Arc::new(SqliteTrashService::new(db.clone()));
+
+        // Project export and import
+        let project_bundle_service = Arc::new(SqliteProjectBundleService::new(db.clone()));
+
+        // Create search services. The embedding repository needs an async
+        // connection of its own; its tables are created by `prepare_search`.
+        let embedding_repository = Arc::new(SqliteEmbeddingRepository::new(Arc::new(
+            tokio::sync::Mutex::new(Connection::open(db_path)?),
+        )));
+        let embedding_service: Arc<dyn EmbeddingService> =
+            Arc::from(EmbeddingServiceFactory::create_service(embedding_config.clone()));
+        let index_config = IndexManagerConfig {
+            // Items mirrored from CRUD entities have not been quality scored
+            min_quality_threshold: 0.0,
+            vector_index_path: Some(format!("{db_path}.hnsw")),
+            ..Default::default()
+        };
+        let vector_index = Arc::new(VectorIndex::new(index_config.hnsw.clone(), &embedding_config.model_name));
+
+        let semantic_search_service: Arc<dyn SemanticSearchService> = Arc::new(
+            SemanticSearchServiceImpl::new(
+                embedding_service.clone(),
+                embedding_repository.clone(),
+                SemanticSearchConfig {
+                    embedding_config: embedding_config.clone(),
+                    ..Default::default()
+                },
+            )
+            .with_vector_index(vector_index.clone()),
+        );
+        let hybrid_search_service = Arc::new(HybridSearchServiceImpl::new(
+            semantic_search_service.clone(),
+            enhanced_context_repository.clone(),
+            HybridSearchConfig {
+                similarity_threshold: 0.2,
+                ..Default::default()
+            },
+        ));
+        let context_pack_service = Arc::new(DefaultContextPackService::new(
+            enhanced_context_repository.clone(),
+            hybrid_search_service.clone(),
+            // Every pair of a project's contexts is related at 0.4; only stronger links bring items closer
+            Arc::new(DefaultContextRelationshipEngine::new().with_similarity_threshold(0.5)),
+        ));
+        let search_index_manager = Arc::new(
+            SearchIndexManagerImpl::new(
+                semantic_search_service.clone(),
+                embedding_repository.clone(),
+                embedding_service.clone(),
+                index_config,
+            )
+            .with_vector_index(vector_index),
+        );
+
+        // Change events feed resource subscriptions
+        let change_detection_service = Arc::new(ChangeDetectionService::new(Arc::new(ChangeBroadcaster::new())));
+
         // Note: component_service removed as it was identical to framework_service
 
         Ok(AppContainer {
@@ -158,6 +278,7 @@ impl AppContainer {
             context_query_service,
             architecture_validation_service,
             context_crud_service,
+            extended_context_crud_service,
             framework_service,
             analytics_service,
             specification_service,
@@ -165,9 +286,92 @@ impl AppContainer {
             specification_versioning_service,
             specification_context_linking_service,
             specification_analytics_service,
+            entity_history_service,
+            trash_service,
+            project_bundle_service,
+            enhanced_context_repository,
+            semantic_search_service,
+            hybrid_search_service,
+            context_pack_service,
+            search_index_manager,
+            change_detection_service,
             // Note: component_service removed
+            embedding_repository,
+            embedding_service,
+            embedding_config,
+            search_ready: tokio::sync::OnceCell::new(),
         })
     }
+
+    /// Embedding model used for search; BM25 needs no model files
+    pub fn search_embedding_config() -> EmbeddingConfig {
+        EmbeddingConfig {
+            model_name: BM25_MODEL_TYPE.to_string(),
+            model_type: ModelType::Custom(BM25_MODEL_TYPE.to_string()),
+            embedding_dimension: 256,
+            ..Default::default()
+        }
+    }
+
+    /// BM25 search embedding with another vector dimension. The dimension is
+    /// part of the model name, so changing it migrates the index.
+    pub fn search_embedding_config_with_dimension(dimension: usize) -> EmbeddingConfig {
+        let config = Self::search_embedding_config();
+        if dimension == config.embedding_dimension {
+            return config;
+        }
+        EmbeddingConfig {
+            model_name: format!("{BM25_MODEL_TYPE}-{dimension}"),
+            embedding_dimension: dimension,
+            ..config
+        }
+    }
+
+    /// Create the embedding tables, prepare the embedding model, load the ANN index,
+    /// and start migrating vectors written by a previously configured model.
+    /// Runs once, on first use of search.
+    pub async fn prepare_search(&self) -> Result<(), IndexManagerError> {
+        self.search_ready
+            .get_or_try_init(|| async {
+                self.embedding_repository.initialize().await?;
+
+                let config = self.embedding_config.clone();
+                self.embedding_service.prepare(&self.embedding_repository).await?;
+
+                if let Some(previous) = self.search_index_manager.register_embedding_model(&config).await? {
+                    let previous_service = EmbeddingServiceFactory::create_initialized_service(previous).await?;
+                    // Queries keep being answered by the previous model, with its stored vocabularies
+                    previous_service.prepare(&self.embedding_repository).await?;
+                    self.search_index_manager
+                        .start_model_migration(Arc::from(previous_service), config)
+                        .await?;
+                    let contexts = self.all_context_items().await?;
+                    self.search_index_manager.spawn_model_migration(contexts);
+                }
+
+                let count = self.search_index_manager.initialize_vector_index().await?;
+                tracing::info!("Search ready with {} vectors", count);
+                Ok::<_, IndexManagerError>(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn all_context_items(&self) -> Result<Vec<EnhancedContextItem>, IndexManagerError> {
+        let maintenance_error = |e: rmcp::model::ErrorData| IndexManagerError::MaintenanceError {
+            message: e.message.to_string(),
+        };
+        let mut contexts = Vec::new();
+        for project in self.project_service.list_projects().await.map_err(maintenance_error)? {
+            contexts.extend(
+                self.enhanced_context_repository
+                    .find_contexts_by_project(&project.id)
+                    .await
+                    .map_err(maintenance_error)?,
+            );
+        }
+        Ok(contexts)
+    }
 }
 
 /// Factory pattern for creating the container with proper error handling
@@ -670,6 +670,7 @@ impl ServerHandler for ContextMcpServer {
                     },
                     "required": ["project_id", "feature_area", "task_type", "components"]
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
             Tool {
@@ -679,6 +680,7 @@ impl ServerHandler for ContextMcpServer {
                     "type": "object",
                     "properties": {}
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
             Tool {
@@ -702,6 +704,7 @@ impl ServerHandler for ContextMcpServer {
                     },
                     "required": ["name"]
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
             Tool {
@@ -735,6 +738,7 @@ impl ServerHandler for ContextMcpServer {
                     },
                     "required": ["project_id", "component_name", "component_type", "architecture_layer"]
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
             Tool {
@@ -750,6 +754,7 @@ impl ServerHandler for ContextMcpServer {
                     },
                     "required": ["project_id"]
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
             Tool {
@@ -777,6 +782,7 @@ impl ServerHandler for ContextMcpServer {
                     },
                     "required": ["project_id", "phase_name", "phase_order"]
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
             Tool {
@@ -792,6 +798,7 @@ impl ServerHandler for ContextMcpServer {
                     },
                     "required": ["project_id"]
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
             Tool {
@@ -807,6 +814,7 @@ impl ServerHandler for ContextMcpServer {
                     },
                     "required": ["project_id"]
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
             Tool {
@@ -816,6 +824,7 @@ impl ServerHandler for ContextMcpServer {
                     "type": "object",
                     "properties": {}
                 }).as_object().unwrap().clone()),
+                output_schema: None,
                 annotations: None,
             },
         ];
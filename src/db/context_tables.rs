@@ -0,0 +1,90 @@
+/// A table holding one type of context entity. Its rows are soft deleted by
+/// setting `deleted_at`, and every read skips rows where it is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextTable {
+    /// Entity type as the entity tools name it
+    pub entity_type: &'static str,
+    pub table: &'static str,
+    /// Column naming the entity in trash listings
+    pub name_column: &'static str,
+}
+
+/// Projects first, then the tables of entities that belong to a project
+pub const CONTEXT_TABLES: &[ContextTable] = &[
+    ContextTable { entity_type: "project", table: "projects", name_column: "name" },
+    ContextTable { entity_type: "business_rule", table: "business_rules", name_column: "rule_name" },
+    ContextTable {
+        entity_type: "architectural_decision",
+        table: "architectural_decisions",
+        name_column: "decision_title",
+    },
+    ContextTable {
+        entity_type: "performance_requirement",
+        table: "performance_requirements",
+        name_column: "requirement_type",
+    },
+    ContextTable { entity_type: "security_policy", table: "security_policies", name_column: "policy_name" },
+    ContextTable { entity_type: "project_convention", table: "project_conventions", name_column: "convention_type" },
+    ContextTable { entity_type: "framework_component", table: "framework_components", name_column: "component_name" },
+    ContextTable { entity_type: "development_phase", table: "development_phases", name_column: "phase_name" },
+    ContextTable { entity_type: "feature_context", table: "feature_context", name_column: "feature_name" },
+];
+
+impl ContextTable {
+    /// Column holding the project a row belongs to; a project belongs to itself
+    pub fn project_column(&self) -> &'static str {
+        if self.table == "projects" {
+            "id"
+        } else {
+            "project_id"
+        }
+    }
+}
+
+/// The table of an entity type
+pub fn context_table(entity_type: &str) -> Option<&'static ContextTable> {
+    CONTEXT_TABLES.iter().find(|t| t.entity_type == entity_type)
+}
+
+/// Tables whose rows belong to a project and go with it when it is deleted
+pub fn project_tables() -> &'static [ContextTable] {
+    &CONTEXT_TABLES[1..]
+}
+
+/// SQLite expression for the current time, in the fixed-width UTC form `deleted_at`
+/// is stored in, so timestamps compare correctly as text
+pub const SQL_NOW: &str = "strftime('%Y-%m-%dT%H:%M:%fZ', 'now')";
+
+/// Statement that moves the live rows of `table` matching `condition` to the trash
+pub fn soft_delete_sql(table: &str, condition: &str) -> String {
+    format!("UPDATE {table} SET deleted_at = {SQL_NOW} WHERE {condition} AND deleted_at IS NULL")
+}
+
+/// Rows of a project's specifications, the project ID being `?1`
+pub const SPEC_ROWS: &str = "spec_id IN (SELECT id FROM specifications WHERE project_id = ?1)";
+/// Rows of the requirements in a project's specifications
+pub const REQUIREMENT_ROWS: &str =
+    "requirement_id IN (SELECT id FROM requirements WHERE spec_id IN (SELECT id FROM specifications WHERE project_id = ?1))";
+/// Rows of the tasks in a project's specifications
+pub const TASK_ROWS: &str =
+    "task_id IN (SELECT id FROM tasks WHERE spec_id IN (SELECT id FROM specifications WHERE project_id = ?1))";
+/// Rows of a project's enhanced context items
+pub const CONTEXT_ITEM_ROWS: &str = "context_id IN (SELECT id FROM enhanced_context_items WHERE project_id = ?1)";
+
+/// Tables of a project's enhanced context items and specifications, parents
+/// first, each with the condition selecting the project's rows. They have no
+/// trash of their own: their rows stay while the project is in the trash and are
+/// deleted when it is purged.
+pub const PROJECT_DEPENDENT_TABLES: &[(&str, &str)] = &[
+    ("enhanced_context_items", "project_id = ?1"),
+    ("context_embeddings", CONTEXT_ITEM_ROWS),
+    ("specifications", "project_id = ?1"),
+    ("requirements", SPEC_ROWS),
+    ("acceptance_criteria", REQUIREMENT_ROWS),
+    ("tasks", SPEC_ROWS),
+    ("specification_versions", SPEC_ROWS),
+    ("task_dependencies", TASK_ROWS),
+    ("requirement_context_links", REQUIREMENT_ROWS),
+    ("task_context_links", TASK_ROWS),
+    ("task_requirement_links", TASK_ROWS),
+];
@@ -0,0 +1,937 @@
+// Versioned schema migrations
+use crate::models::embedding::vector_to_bytes;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension, Transaction, TransactionBehavior};
+
+/// A schema change, applied once per database in version order. Applied
+/// migrations are recorded with a checksum of their SQL, so a migration must
+/// never be edited once released; change the schema with a new one instead.
+#[derive(Debug)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    statements: &'static [&'static str],
+    /// Columns added after the statements ran; a table that already has one
+    /// is left alone, as SQLite cannot `ADD COLUMN IF NOT EXISTS`
+    columns: &'static [NewColumn],
+    /// Conversions SQL cannot express, run after the columns are added
+    data: &'static [DataMigration],
+}
+
+/// A column a migration adds to an existing table
+#[derive(Debug)]
+struct NewColumn {
+    table: &'static str,
+    column: &'static str,
+    definition: &'static str,
+}
+
+/// A step of a migration written in Rust
+#[derive(Debug)]
+struct DataMigration {
+    /// Stands in for the step's code in the migration's checksum
+    name: &'static str,
+    run: fn(&Connection) -> rusqlite::Result<()>,
+}
+
+impl NewColumn {
+    fn sql(&self) -> String {
+        format!("ALTER TABLE {} ADD COLUMN {} {};", self.table, self.column, self.definition)
+    }
+}
+
+impl Migration {
+    /// MD5 of the migration's SQL
+    pub fn checksum(&self) -> String {
+        let mut sql = self.statements.join("\n");
+        for column in self.columns {
+            sql.push('\n');
+            sql.push_str(&column.sql());
+        }
+        for step in self.data {
+            sql.push('\n');
+            sql.push_str(step.name);
+        }
+        format!("{:x}", md5::compute(sql))
+    }
+}
+
+/// Errors raised while bringing a database schema up to date
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Migration {version} ({name}) failed: {source}")]
+    Failed {
+        version: u32,
+        name: &'static str,
+        source: rusqlite::Error,
+    },
+    #[error("Migration {version} ({name}) was changed after it was applied to this database")]
+    ChecksumMismatch { version: u32, name: String },
+    #[error("Database has migration {version} ({name}), which this server does not know; it was written by a newer version")]
+    UnknownMigration { version: u32, name: String },
+}
+
+/// A migration recorded in `schema_migrations`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: String,
+}
+
+/// Schema version of a database against the migrations this server knows
+#[derive(Debug)]
+pub struct SchemaStatus {
+    /// Highest applied migration, 0 for databases that predate migrations
+    pub current_version: u32,
+    pub latest_version: u32,
+    pub pending: Vec<&'static Migration>,
+}
+
+const CREATE_MIGRATIONS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        checksum TEXT NOT NULL,
+        applied_at TEXT NOT NULL
+    );
+"#;
+
+/// `context_embeddings` as created by migration 2; migration 10 rebuilds
+/// pre-migration tables into this shape
+const CREATE_EMBEDDINGS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS context_embeddings (
+        id TEXT PRIMARY KEY,
+        context_id TEXT NOT NULL,
+        project_id TEXT,
+        embedding_vector BLOB NOT NULL, -- little-endian f32
+        vector_dimension INTEGER,
+        quantization TEXT, -- 'int8' or 'binary', NULL when not quantized
+        quantized_vector BLOB,
+        quantization_scale REAL,
+        chunk_index INTEGER NOT NULL DEFAULT 0,
+        chunk_start INTEGER,
+        chunk_end INTEGER,
+        chunk_heading TEXT,
+        chunk_text TEXT, -- NULL for items embedded whole
+        embedding_model TEXT NOT NULL,
+        embedding_version TEXT NOT NULL,
+        content_hash TEXT NOT NULL,
+        content_type TEXT,
+        content_length INTEGER,
+        tokenization_method TEXT,
+        preprocessing_steps TEXT, -- JSON array
+        quality_score REAL,
+        custom_metadata TEXT, -- JSON object
+        created_at TEXT NOT NULL,
+        updated_at TEXT DEFAULT (datetime('now')),
+        FOREIGN KEY (project_id) REFERENCES projects(id),
+        UNIQUE(context_id, embedding_model, embedding_version, chunk_index)
+    );
+"#;
+
+const CREATE_EMBEDDINGS_INDEXES: &str = r#"
+    CREATE INDEX IF NOT EXISTS idx_embeddings_context_id ON context_embeddings(context_id);
+    CREATE INDEX IF NOT EXISTS idx_embeddings_project_id ON context_embeddings(project_id);
+    CREATE INDEX IF NOT EXISTS idx_embeddings_model ON context_embeddings(embedding_model);
+    CREATE INDEX IF NOT EXISTS idx_embeddings_content_hash ON context_embeddings(content_hash);
+    CREATE INDEX IF NOT EXISTS idx_embeddings_created_at ON context_embeddings(created_at);
+"#;
+
+/// Every migration, in the order they are applied. Migrations 1 to 7
+/// reproduce the schema databases were given before migrations were
+/// versioned and only create what is missing, so running them adopts such a
+/// database; later migrations can rely on the schema they leave.
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "core_context",
+        statements: &[r#"
+            CREATE TABLE IF NOT EXISTS projects (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT,
+                repository_url TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS business_rules (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                rule_name TEXT NOT NULL,
+                description TEXT,
+                domain_area TEXT,
+                implementation_pattern TEXT,
+                constraints TEXT,
+                examples TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS architectural_decisions (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                decision_title TEXT NOT NULL,
+                context TEXT,
+                decision TEXT,
+                consequences TEXT,
+                alternatives_considered TEXT,
+                status TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS performance_requirements (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                component_area TEXT,
+                requirement_type TEXT,
+                target_value TEXT,
+                optimization_patterns TEXT,
+                avoid_patterns TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS security_policies (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                policy_name TEXT NOT NULL,
+                policy_area TEXT,
+                requirements TEXT,
+                implementation_pattern TEXT,
+                forbidden_patterns TEXT,
+                compliance_notes TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS project_conventions (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                convention_type TEXT,
+                convention_rule TEXT,
+                good_examples TEXT,
+                bad_examples TEXT,
+                rationale TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS feature_context (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                feature_name TEXT NOT NULL,
+                business_purpose TEXT,
+                user_personas TEXT,
+                key_workflows TEXT,
+                integration_points TEXT,
+                edge_cases TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            -- Framework-agnostic component tables
+            CREATE TABLE IF NOT EXISTS framework_components (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                component_name TEXT NOT NULL,
+                component_type TEXT NOT NULL, -- 'widget', 'provider', 'service', 'repository', 'model', 'utility'
+                architecture_layer TEXT NOT NULL, -- 'presentation', 'domain', 'data', 'core'
+                file_path TEXT,
+                dependencies TEXT, -- JSON array of dependencies
+                metadata TEXT, -- JSON metadata for framework-specific properties
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            -- Flutter-specific context tables (legacy)
+            CREATE TABLE IF NOT EXISTS flutter_components (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                component_name TEXT NOT NULL,
+                component_type TEXT NOT NULL, -- 'widget', 'provider', 'service', 'repository'
+                architecture_layer TEXT NOT NULL, -- 'presentation', 'domain', 'data', 'core'
+                file_path TEXT,
+                dependencies TEXT, -- JSON array of dependencies
+                riverpod_scope TEXT, -- 'global', 'scoped', 'local'
+                widget_type TEXT, -- 'StatelessWidget', 'StatefulWidget', 'ConsumerWidget'
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS development_phases (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                phase_name TEXT NOT NULL, -- 'Setup', 'Chat UI', 'Model Management', 'Polish'
+                phase_order INTEGER NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending', -- 'pending', 'in_progress', 'completed', 'blocked'
+                description TEXT,
+                completion_criteria TEXT, -- JSON array of criteria
+                dependencies TEXT, -- JSON array of phase dependencies
+                started_at TEXT,
+                completed_at TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS privacy_rules (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                rule_name TEXT NOT NULL,
+                rule_type TEXT NOT NULL, -- 'forbidden_import', 'required_local_storage', 'data_flow'
+                pattern TEXT NOT NULL, -- import pattern, storage key, etc.
+                description TEXT,
+                severity TEXT DEFAULT 'error', -- 'error', 'warning', 'info'
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS privacy_violations (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                rule_id TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                violation_text TEXT,
+                status TEXT DEFAULT 'open', -- 'open', 'resolved', 'suppressed'
+                detected_at TEXT DEFAULT (datetime('now')),
+                resolved_at TEXT,
+                FOREIGN KEY (project_id) REFERENCES projects(id),
+                FOREIGN KEY (rule_id) REFERENCES privacy_rules(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS architecture_layers (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                layer_name TEXT NOT NULL, -- 'presentation', 'domain', 'data', 'core'
+                allowed_dependencies TEXT, -- JSON array of allowed layer dependencies
+                forbidden_imports TEXT, -- JSON array of forbidden import patterns
+                description TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS model_context (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                model_name TEXT NOT NULL,
+                model_path TEXT,
+                model_type TEXT, -- 'GGUF', 'ONNX', etc.
+                model_size TEXT,
+                performance_metrics TEXT, -- JSON with inference times, memory usage
+                configuration TEXT, -- JSON with model settings
+                is_active BOOLEAN DEFAULT 0,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS code_templates (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                template_name TEXT NOT NULL,
+                template_type TEXT NOT NULL, -- 'widget', 'provider', 'repository', 'test'
+                template_content TEXT NOT NULL,
+                variables TEXT, -- JSON array of template variables
+                description TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                FOREIGN KEY (project_id) REFERENCES projects(id)
+            );
+        "#],
+        columns: &[],
+        data: &[],
+    },
+    Migration {
+        version: 2,
+        name: "embeddings",
+        statements: &[
+            CREATE_EMBEDDINGS_TABLE,
+            CREATE_EMBEDDINGS_INDEXES,
+            r#"
+            -- Corpus statistics for the BM25 embedding fallback ('' project_id = all projects)
+            CREATE TABLE IF NOT EXISTS embedding_vocabularies (
+                embedding_model TEXT NOT NULL,
+                project_id TEXT NOT NULL DEFAULT '',
+                document_count INTEGER NOT NULL,
+                average_document_length REAL NOT NULL,
+                built_at TEXT NOT NULL,
+                PRIMARY KEY (embedding_model, project_id)
+            );
+
+            CREATE TABLE IF NOT EXISTS embedding_vocabulary_terms (
+                embedding_model TEXT NOT NULL,
+                project_id TEXT NOT NULL DEFAULT '',
+                term TEXT NOT NULL,
+                document_frequency INTEGER NOT NULL,
+                PRIMARY KEY (embedding_model, project_id, term)
+            );
+
+            -- Model the served vectors belong to; a different configured model triggers a migration
+            CREATE TABLE IF NOT EXISTS embedding_model_state (
+                role TEXT PRIMARY KEY,
+                embedding_model TEXT NOT NULL,
+                config TEXT NOT NULL, -- JSON EmbeddingConfig
+                updated_at TEXT NOT NULL
+            );
+            "#,
+        ],
+        columns: &[],
+        data: &[],
+    },
+    Migration {
+        version: 3,
+        name: "analytics_events",
+        statements: &[r#"
+            -- Analytics events table for usage tracking
+            CREATE TABLE IF NOT EXISTS analytics_events (
+                id TEXT PRIMARY KEY,
+                event_type TEXT NOT NULL,
+                project_id TEXT,
+                entity_type TEXT,
+                entity_id TEXT,
+                user_agent TEXT,
+                metadata TEXT,
+                timestamp TEXT NOT NULL,
+                duration_ms INTEGER,
+                success BOOLEAN NOT NULL,
+                error_message TEXT
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_analytics_events_project_id ON analytics_events(project_id);
+            CREATE INDEX IF NOT EXISTS idx_analytics_events_entity ON analytics_events(entity_type, entity_id);
+            CREATE INDEX IF NOT EXISTS idx_analytics_events_timestamp ON analytics_events(timestamp);
+        "#],
+        columns: &[],
+        data: &[],
+    },
+    Migration {
+        version: 4,
+        name: "specifications",
+        statements: &[r#"
+            CREATE TABLE IF NOT EXISTS specifications (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                spec_type TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT,
+                content_format TEXT NOT NULL,
+                raw_content TEXT NOT NULL,
+                parsed_sections TEXT, -- JSON
+                content_metadata TEXT, -- JSON
+                status TEXT NOT NULL,
+                version INTEGER NOT NULL DEFAULT 1,
+                file_path TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                metadata TEXT -- JSON
+            );
+
+            CREATE TABLE IF NOT EXISTS requirements (
+                id TEXT PRIMARY KEY,
+                spec_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT NOT NULL,
+                user_story TEXT,
+                priority TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                metadata TEXT, -- JSON
+                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS acceptance_criteria (
+                id TEXT PRIMARY KEY,
+                requirement_id TEXT NOT NULL,
+                description TEXT NOT NULL,
+                criterion_type TEXT NOT NULL,
+                status TEXT NOT NULL,
+                test_cases TEXT, -- JSON array
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS tasks (
+                id TEXT PRIMARY KEY,
+                spec_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT NOT NULL,
+                status TEXT NOT NULL,
+                task_type TEXT NOT NULL,
+                parent_task TEXT,
+                estimated_effort TEXT,
+                actual_effort TEXT,
+                assigned_to TEXT,
+                progress REAL NOT NULL DEFAULT 0.0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                started_at TEXT,
+                completed_at TEXT,
+                metadata TEXT, -- JSON
+                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE,
+                FOREIGN KEY (parent_task) REFERENCES tasks (id) ON DELETE SET NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS task_dependencies (
+                task_id TEXT NOT NULL,
+                depends_on_task_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (task_id, depends_on_task_id),
+                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+                FOREIGN KEY (depends_on_task_id) REFERENCES tasks (id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS requirement_context_links (
+                requirement_id TEXT NOT NULL,
+                context_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (requirement_id, context_id),
+                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS task_context_links (
+                task_id TEXT NOT NULL,
+                context_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (task_id, context_id),
+                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS task_requirement_links (
+                task_id TEXT NOT NULL,
+                requirement_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (task_id, requirement_id),
+                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_specifications_project_id ON specifications (project_id);
+            CREATE INDEX IF NOT EXISTS idx_requirements_spec_id ON requirements (spec_id);
+            CREATE INDEX IF NOT EXISTS idx_tasks_spec_id ON tasks (spec_id);
+        "#],
+        columns: &[],
+        data: &[],
+    },
+    Migration {
+        version: 5,
+        name: "specification_versions",
+        statements: &[r#"
+            CREATE TABLE IF NOT EXISTS specification_versions (
+                id TEXT PRIMARY KEY,
+                spec_id TEXT NOT NULL,
+                version_number INTEGER NOT NULL,
+                content_hash TEXT NOT NULL,
+                raw_content TEXT NOT NULL,
+                parsed_sections TEXT, -- JSON
+                change_description TEXT NOT NULL,
+                change_type TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                created_by TEXT,
+                file_path TEXT,
+                metadata TEXT, -- JSON
+                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_spec_versions_spec_id ON specification_versions (spec_id);
+            CREATE INDEX IF NOT EXISTS idx_spec_versions_version_number ON specification_versions (spec_id, version_number);
+        "#],
+        columns: &[],
+        data: &[],
+    },
+    Migration {
+        version: 6,
+        name: "enhanced_context",
+        statements: &[r#"
+            CREATE TABLE IF NOT EXISTS enhanced_context_items (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                content_type TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT NOT NULL,
+                data TEXT NOT NULL, -- JSON data
+                source_file TEXT,
+                source_line INTEGER,
+                quality_score REAL NOT NULL DEFAULT 0.0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                version INTEGER NOT NULL DEFAULT 1,
+                -- Metadata fields
+                tags TEXT, -- JSON array
+                priority TEXT NOT NULL DEFAULT 'medium',
+                confidence REAL NOT NULL DEFAULT 1.0,
+                source TEXT NOT NULL DEFAULT 'manual',
+                validation_status TEXT NOT NULL DEFAULT 'pending',
+                last_accessed TEXT,
+                access_count INTEGER NOT NULL DEFAULT 0,
+                custom_fields TEXT -- JSON object
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_enhanced_context_project ON enhanced_context_items (project_id);
+            CREATE INDEX IF NOT EXISTS idx_enhanced_context_type ON enhanced_context_items (content_type);
+        "#],
+        columns: &[],
+        data: &[],
+    },
+    Migration {
+        version: 7,
+        name: "enhanced_context_fts",
+        statements: &[r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS enhanced_context_fts USING fts5(
+                title, description, data, tags,
+                content = 'enhanced_context_items',
+                content_rowid = 'rowid',
+                tokenize = 'porter unicode61'
+            );
+
+            CREATE TRIGGER IF NOT EXISTS enhanced_context_fts_insert AFTER INSERT ON enhanced_context_items BEGIN
+                INSERT INTO enhanced_context_fts (rowid, title, description, data, tags)
+                VALUES (new.rowid, new.title, new.description, new.data, new.tags);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS enhanced_context_fts_delete AFTER DELETE ON enhanced_context_items BEGIN
+                INSERT INTO enhanced_context_fts (enhanced_context_fts, rowid, title, description, data, tags)
+                VALUES ('delete', old.rowid, old.title, old.description, old.data, old.tags);
+            END;
+
+            -- Usage counters change often and are not indexed, so only content edits reindex
+            CREATE TRIGGER IF NOT EXISTS enhanced_context_fts_update
+            AFTER UPDATE OF title, description, data, tags ON enhanced_context_items BEGIN
+                INSERT INTO enhanced_context_fts (enhanced_context_fts, rowid, title, description, data, tags)
+                VALUES ('delete', old.rowid, old.title, old.description, old.data, old.tags);
+                INSERT INTO enhanced_context_fts (rowid, title, description, data, tags)
+                VALUES (new.rowid, new.title, new.description, new.data, new.tags);
+            END;
+
+            -- Index items written before the index existed
+            INSERT INTO enhanced_context_fts (enhanced_context_fts) VALUES ('rebuild');
+        "#],
+        columns: &[],
+        data: &[],
+    },
+    Migration {
+        version: 8,
+        name: "entity_revisions",
+        statements: &[r#"
+            -- Append-only history of the context entities written through the entity tools
+            CREATE TABLE IF NOT EXISTS entity_revisions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                entity_type TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                project_id TEXT NOT NULL,
+                revision INTEGER NOT NULL,
+                operation TEXT NOT NULL,
+                author TEXT,
+                snapshot TEXT,
+                diff TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                UNIQUE (entity_type, entity_id, revision)
+            );
+            CREATE INDEX IF NOT EXISTS idx_entity_revisions_project ON entity_revisions(project_id, created_at);
+        "#],
+        columns: &[],
+        data: &[],
+    },
+    Migration {
+        version: 9,
+        name: "soft_delete",
+        statements: &[],
+        // Deleted context stays in the trash until it is restored or purged
+        columns: &[
+            NewColumn { table: "projects", column: "deleted_at", definition: "TEXT" },
+            NewColumn { table: "business_rules", column: "deleted_at", definition: "TEXT" },
+            NewColumn { table: "architectural_decisions", column: "deleted_at", definition: "TEXT" },
+            NewColumn { table: "performance_requirements", column: "deleted_at", definition: "TEXT" },
+            NewColumn { table: "security_policies", column: "deleted_at", definition: "TEXT" },
+            NewColumn { table: "project_conventions", column: "deleted_at", definition: "TEXT" },
+            NewColumn { table: "framework_components", column: "deleted_at", definition: "TEXT" },
+            NewColumn { table: "development_phases", column: "deleted_at", definition: "TEXT" },
+            NewColumn { table: "feature_context", column: "deleted_at", definition: "TEXT" },
+        ],
+        data: &[],
+    },
+    Migration {
+        version: 10,
+        name: "binary_embeddings",
+        statements: &[],
+        columns: &[],
+        data: &[DataMigration { name: "rebuild_context_embeddings", run: rebuild_context_embeddings }],
+    },
+];
+
+/// Convert a `context_embeddings` table from before migration 2, which stores
+/// vectors as JSON TEXT and lacks the chunk columns, to the current shape
+fn rebuild_context_embeddings(conn: &Connection) -> rusqlite::Result<()> {
+    let columns: Vec<(String, String)> = {
+        let mut stmt = conn.prepare("SELECT name, type FROM pragma_table_info('context_embeddings')")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+    let json_vectors = columns
+        .iter()
+        .any(|(name, declared)| name == "embedding_vector" && declared.eq_ignore_ascii_case("TEXT"));
+    let unchunked = !columns.iter().any(|(name, _)| name == "chunk_index");
+
+    // The chunk columns are part of the UNIQUE key, so the table is rebuilt rather than altered
+    if json_vectors || unchunked {
+        tracing::info!("Rebuilding context_embeddings for binary, chunked vector storage");
+        conn.execute_batch("ALTER TABLE context_embeddings RENAME TO context_embeddings_old")?;
+        conn.execute_batch(CREATE_EMBEDDINGS_TABLE)?;
+
+        let new_columns: Vec<String> = {
+            let mut stmt = conn.prepare("SELECT name FROM pragma_table_info('context_embeddings')")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+        let shared = new_columns
+            .into_iter()
+            .filter(|name| columns.iter().any(|(old, _)| old == name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        conn.execute_batch(&format!(
+            "INSERT INTO context_embeddings ({shared}) SELECT {shared} FROM context_embeddings_old;
+             DROP TABLE context_embeddings_old;"
+        ))?;
+        conn.execute_batch(CREATE_EMBEDDINGS_INDEXES)?;
+    }
+
+    let legacy_rows: Vec<(String, String)> = {
+        let mut stmt =
+            conn.prepare("SELECT id, embedding_vector FROM context_embeddings WHERE typeof(embedding_vector) = 'text'")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+    let mut converted = 0;
+    for (id, json) in &legacy_rows {
+        match serde_json::from_str::<Vec<f32>>(json) {
+            Ok(vector) => {
+                conn.execute(
+                    "UPDATE context_embeddings SET embedding_vector = ?1, vector_dimension = ?2 WHERE id = ?3",
+                    params![vector_to_bytes(&vector), vector.len() as i64, id],
+                )?;
+                converted += 1;
+            }
+            Err(e) => {
+                // Unreadable rows would fail every search; they are regenerated on reindex
+                tracing::warn!("Dropping embedding {} with unreadable vector: {}", id, e);
+                conn.execute("DELETE FROM context_embeddings WHERE id = ?1", [id])?;
+            }
+        }
+    }
+    if converted > 0 {
+        tracing::info!("Converted {} embeddings to binary vector storage", converted);
+    }
+
+    conn.execute(
+        "UPDATE context_embeddings SET vector_dimension = length(embedding_vector) / 4 WHERE vector_dimension IS NULL",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Apply the migrations `conn` has not had yet, returning them. Runs in one
+/// immediate transaction, so concurrent callers apply each migration once and
+/// a failed migration leaves the schema as it was.
+pub fn run_migrations(conn: &Connection) -> Result<Vec<&'static Migration>, MigrationError> {
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+    tx.execute_batch(CREATE_MIGRATIONS_TABLE)?;
+    let applied = applied_migrations(&tx)?;
+    verify_applied(&applied)?;
+
+    let mut newly_applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| !applied.iter().any(|a| a.version == m.version)) {
+        let failed = |source| MigrationError::Failed {
+            version: migration.version,
+            name: migration.name,
+            source,
+        };
+        for statement in migration.statements {
+            tx.execute_batch(statement).map_err(failed)?;
+        }
+        for column in migration.columns {
+            let exists: bool = tx
+                .query_row(
+                    "SELECT EXISTS(SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)",
+                    [column.table, column.column],
+                    |row| row.get(0),
+                )
+                .map_err(failed)?;
+            if !exists {
+                tx.execute_batch(&column.sql()).map_err(failed)?;
+            }
+        }
+        for step in migration.data {
+            (step.run)(&tx).map_err(failed)?;
+        }
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name, checksum, applied_at) VALUES (?1, ?2, ?3, ?4)",
+            params![migration.version, migration.name, migration.checksum(), Utc::now().to_rfc3339()],
+        )?;
+        tracing::info!("Applied migration {} ({})", migration.version, migration.name);
+        newly_applied.push(migration);
+    }
+
+    tx.pragma_update(None, "user_version", latest_version())?;
+    tx.commit()?;
+    Ok(newly_applied)
+}
+
+/// Schema version of `conn` without modifying it
+pub fn schema_status(conn: &Connection) -> Result<SchemaStatus, MigrationError> {
+    let tracked: Option<String> = conn
+        .query_row(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let applied = match tracked {
+        Some(_) => applied_migrations(conn)?,
+        None => Vec::new(),
+    };
+    verify_applied(&applied)?;
+
+    Ok(SchemaStatus {
+        current_version: applied.iter().map(|a| a.version).max().unwrap_or(0),
+        latest_version: latest_version(),
+        pending: MIGRATIONS
+            .iter()
+            .filter(|m| !applied.iter().any(|a| a.version == m.version))
+            .collect(),
+    })
+}
+
+/// Migrations recorded in `schema_migrations`, oldest first
+pub fn applied_migrations(conn: &Connection) -> Result<Vec<AppliedMigration>, MigrationError> {
+    let mut stmt = conn.prepare("SELECT version, name, checksum, applied_at FROM schema_migrations ORDER BY version")?;
+    let applied = stmt
+        .query_map([], |row| {
+            Ok(AppliedMigration {
+                version: row.get(0)?,
+                name: row.get(1)?,
+                checksum: row.get(2)?,
+                applied_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(applied)
+}
+
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// Every applied migration must be one of ours, unchanged
+fn verify_applied(applied: &[AppliedMigration]) -> Result<(), MigrationError> {
+    for record in applied {
+        match MIGRATIONS.iter().find(|m| m.version == record.version) {
+            None => {
+                return Err(MigrationError::UnknownMigration {
+                    version: record.version,
+                    name: record.name.clone(),
+                })
+            }
+            Some(migration) if migration.checksum() != record.checksum => {
+                return Err(MigrationError::ChecksumMismatch {
+                    version: record.version,
+                    name: record.name.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_exists(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = ?1)",
+            [name],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_migration_versions_are_consecutive() {
+        let versions: Vec<u32> = MIGRATIONS.iter().map(|m| m.version).collect();
+        let expected: Vec<u32> = (1..=MIGRATIONS.len() as u32).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn test_fresh_database_is_migrated_once() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        let applied = run_migrations(&conn).unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        for table in ["projects", "context_embeddings", "analytics_events", "tasks", "specification_versions", "enhanced_context_fts"] {
+            assert!(table_exists(&conn, table), "{table} is created");
+        }
+        let user_version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(user_version, latest_version());
+
+        assert!(run_migrations(&conn).unwrap().is_empty());
+        let status = schema_status(&conn).unwrap();
+        assert_eq!(status.current_version, latest_version());
+        assert!(status.pending.is_empty());
+    }
+
+    #[test]
+    fn test_added_columns_are_skipped_where_they_exist() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        // A database whose schema is current but whose migrations were not recorded
+        conn.execute_batch("DROP TABLE schema_migrations").unwrap();
+
+        assert_eq!(run_migrations(&conn).unwrap().len(), MIGRATIONS.len());
+        conn.execute("UPDATE projects SET deleted_at = NULL", []).unwrap();
+    }
+
+    #[test]
+    fn test_edited_migrations_are_rejected() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn.execute("UPDATE schema_migrations SET checksum = 'edited' WHERE version = 2", []).unwrap();
+
+        let error = run_migrations(&conn).unwrap_err();
+        assert!(matches!(error, MigrationError::ChecksumMismatch { version: 2, .. }), "{error}");
+        assert!(schema_status(&conn).is_err());
+    }
+
+    #[test]
+    fn test_databases_from_newer_servers_are_rejected() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO schema_migrations (version, name, checksum, applied_at) VALUES (?1, 'from_the_future', '', '')",
+            [latest_version() + 1],
+        )
+        .unwrap();
+
+        let error = run_migrations(&conn).unwrap_err();
+        assert!(matches!(error, MigrationError::UnknownMigration { .. }), "{error}");
+    }
+
+    #[test]
+    fn test_failed_migration_leaves_the_schema_untouched() {
+        let conn = Connection::open_in_memory().unwrap();
+        // Migration 2 indexes content_hash, which this table lacks
+        conn.execute_batch("CREATE TABLE context_embeddings (id TEXT PRIMARY KEY, context_id TEXT)").unwrap();
+
+        let error = run_migrations(&conn).unwrap_err();
+        assert!(matches!(error, MigrationError::Failed { version: 2, .. }), "{error}");
+        assert!(!table_exists(&conn, "projects"));
+        assert!(!table_exists(&conn, "schema_migrations"));
+    }
+}
@@ -1 +1,3 @@
+pub mod context_tables;
 pub mod init;
+pub mod migrations;
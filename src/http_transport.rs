@@ -0,0 +1,34 @@
+use crate::container::AppContainer;
+use crate::enhanced_context_server::EnhancedContextMcpServer;
+use anyhow::Result;
+use rmcp::transport::streamable_http_server::{
+    session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Path of the streamable HTTP MCP endpoint
+pub const MCP_ENDPOINT: &str = "/mcp";
+
+/// Router serving MCP over streamable HTTP (POST for requests, GET for the SSE
+/// notification stream, DELETE to end a session). Every session gets its own
+/// server instance over the shared container, so agents share one database.
+pub fn router(container: Arc<AppContainer>) -> axum::Router {
+    let service = StreamableHttpService::new(
+        move || Ok(EnhancedContextMcpServer::with_container(container.clone())),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    axum::Router::new().nest_service(MCP_ENDPOINT, service)
+}
+
+/// Serve MCP over streamable HTTP on `listener` until the listener fails
+pub async fn serve_http(container: Arc<AppContainer>, listener: TcpListener) -> Result<()> {
+    tracing::info!(
+        "Serving MCP over streamable HTTP at http://{}{}",
+        listener.local_addr()?,
+        MCP_ENDPOINT
+    );
+    axum::serve(listener, router(container)).await?;
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::db::migrations::run_migrations;
 use crate::services::analytics_service::{AnalyticsEvent, AnalyticsEventType, AnalyticsRepository, UsageStatistics};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -17,44 +18,10 @@ impl SqliteAnalyticsRepository {
         Self { db }
     }
 
-    /// Initialize the analytics tables
+    /// Bring the schema, including the analytics tables, up to date
     pub fn init_tables(&self) -> Result<()> {
         let conn = self.db.lock().unwrap();
-        
-        // Create analytics_events table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS analytics_events (
-                id TEXT PRIMARY KEY,
-                event_type TEXT NOT NULL,
-                project_id TEXT,
-                entity_type TEXT,
-                entity_id TEXT,
-                user_agent TEXT,
-                metadata TEXT,
-                timestamp TEXT NOT NULL,
-                duration_ms INTEGER,
-                success BOOLEAN NOT NULL,
-                error_message TEXT
-            )",
-            [],
-        )?;
-
-        // Create indexes for better query performance
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_analytics_events_project_id ON analytics_events(project_id)",
-            [],
-        )?;
-        
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_analytics_events_entity ON analytics_events(entity_type, entity_id)",
-            [],
-        )?;
-        
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_analytics_events_timestamp ON analytics_events(timestamp)",
-            [],
-        )?;
-
+        run_migrations(&conn)?;
         Ok(())
     }
 
@@ -1,8 +1,10 @@
+use crate::db::context_tables::soft_delete_sql;
 use crate::models::context::ArchitecturalDecision;
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::ArchitecturalDecisionRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
-use rusqlite::Connection;
+use rusqlite::{Connection, Row};
 use std::sync::{Arc, Mutex};
 
 /// SQLite implementation of ArchitecturalDecisionRepository
@@ -14,6 +16,20 @@ impl SqliteArchitecturalDecisionRepository {
     pub fn new(db: Arc<Mutex<Connection>>) -> Self {
         Self { db }
     }
+
+    fn row_to_decision(row: &Row) -> rusqlite::Result<ArchitecturalDecision> {
+        Ok(ArchitecturalDecision {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            decision_title: row.get(2)?,
+            context: row.get(3)?,
+            decision: row.get(4)?,
+            consequences: row.get(5)?,
+            alternatives_considered: row.get(6)?,
+            status: row.get(7)?,
+            created_at: row.get(8)?,
+        })
+    }
 }
 
 #[async_trait]
@@ -49,23 +65,11 @@ impl ArchitecturalDecisionRepository for SqliteArchitecturalDecisionRepository {
         let db = self.db.lock().unwrap();
         let mut decisions = Vec::new();
 
-        let mut stmt = db.prepare("SELECT id, project_id, decision_title, context, decision, consequences, alternatives_considered, status, created_at FROM architectural_decisions WHERE project_id = ?")
+        let mut stmt = db.prepare("SELECT id, project_id, decision_title, context, decision, consequences, alternatives_considered, status, created_at FROM architectural_decisions WHERE deleted_at IS NULL AND project_id = ?")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let decision_rows = stmt
-            .query_map([project_id], |row| {
-                Ok(ArchitecturalDecision {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    decision_title: row.get(2)?,
-                    context: row.get(3)?,
-                    decision: row.get(4)?,
-                    consequences: row.get(5)?,
-                    alternatives_considered: row.get(6)?,
-                    status: row.get(7)?,
-                    created_at: row.get(8)?,
-                })
-            })
+            .query_map([project_id], Self::row_to_decision)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         for decision in decision_rows {
@@ -78,26 +82,34 @@ impl ArchitecturalDecisionRepository for SqliteArchitecturalDecisionRepository {
         Ok(decisions)
     }
 
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<ArchitecturalDecision>, McpError> {
+        let window = query.window(&["created_at", "decision_title", "status"])?;
+        let db = self.db.lock().unwrap();
+
+        let sql = format!("SELECT id, project_id, decision_title, context, decision, consequences, alternatives_considered, status, created_at FROM architectural_decisions WHERE deleted_at IS NULL AND project_id = ? {}", window.sql());
+        let mut stmt = db.prepare(&sql)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt
+            .query_map([project_id], Self::row_to_decision)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(window.page(rows))
+    }
+
     async fn find_by_id(&self, id: &str) -> Result<Option<ArchitecturalDecision>, McpError> {
         let db = self.db.lock().unwrap();
 
-        let mut stmt = db.prepare("SELECT id, project_id, decision_title, context, decision, consequences, alternatives_considered, status, created_at FROM architectural_decisions WHERE id = ?")
+        let mut stmt = db.prepare("SELECT id, project_id, decision_title, context, decision, consequences, alternatives_considered, status, created_at FROM architectural_decisions WHERE deleted_at IS NULL AND id = ?")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let mut decision_iter = stmt
-            .query_map([id], |row| {
-                Ok(ArchitecturalDecision {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    decision_title: row.get(2)?,
-                    context: row.get(3)?,
-                    decision: row.get(4)?,
-                    consequences: row.get(5)?,
-                    alternatives_considered: row.get(6)?,
-                    status: row.get(7)?,
-                    created_at: row.get(8)?,
-                })
-            })
+            .query_map([id], Self::row_to_decision)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         match decision_iter.next() {
@@ -137,7 +149,7 @@ impl ArchitecturalDecisionRepository for SqliteArchitecturalDecisionRepository {
         let db = self.db.lock().unwrap();
 
         let rows_affected = db
-            .execute("DELETE FROM architectural_decisions WHERE id = ?", [id])
+            .execute(&soft_delete_sql("architectural_decisions", "id = ?"), [id])
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         Ok(rows_affected > 0)
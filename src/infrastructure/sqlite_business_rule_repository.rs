@@ -1,8 +1,10 @@
+use crate::db::context_tables::soft_delete_sql;
 use crate::models::context::BusinessRule;
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::BusinessRuleRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
-use rusqlite::Connection;
+use rusqlite::{Connection, Row};
 use std::sync::{Arc, Mutex};
 
 /// SQLite implementation of BusinessRuleRepository
@@ -14,6 +16,20 @@ impl SqliteBusinessRuleRepository {
     pub fn new(db: Arc<Mutex<Connection>>) -> Self {
         Self { db }
     }
+
+    fn row_to_rule(row: &Row) -> rusqlite::Result<BusinessRule> {
+        Ok(BusinessRule {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            rule_name: row.get(2)?,
+            description: row.get(3)?,
+            domain_area: row.get(4)?,
+            implementation_pattern: row.get(5)?,
+            constraints: row.get(6)?,
+            examples: row.get(7)?,
+            created_at: row.get(8)?,
+        })
+    }
 }
 
 #[async_trait]
@@ -43,23 +59,11 @@ impl BusinessRuleRepository for SqliteBusinessRuleRepository {
         let db = self.db.lock().unwrap();
         let mut rules = Vec::new();
 
-        let mut stmt = db.prepare("SELECT id, project_id, rule_name, description, domain_area, implementation_pattern, constraints, examples, created_at FROM business_rules WHERE project_id = ?")
+        let mut stmt = db.prepare("SELECT id, project_id, rule_name, description, domain_area, implementation_pattern, constraints, examples, created_at FROM business_rules WHERE deleted_at IS NULL AND project_id = ?")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let rule_rows = stmt
-            .query_map([project_id], |row| {
-                Ok(BusinessRule {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    rule_name: row.get(2)?,
-                    description: row.get(3)?,
-                    domain_area: row.get(4)?,
-                    implementation_pattern: row.get(5)?,
-                    constraints: row.get(6)?,
-                    examples: row.get(7)?,
-                    created_at: row.get(8)?,
-                })
-            })
+            .query_map([project_id], Self::row_to_rule)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         for rule in rule_rows {
@@ -72,6 +76,26 @@ impl BusinessRuleRepository for SqliteBusinessRuleRepository {
         Ok(rules)
     }
 
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<BusinessRule>, McpError> {
+        let window = query.window(&["created_at", "rule_name", "domain_area"])?;
+        let db = self.db.lock().unwrap();
+
+        let sql = format!("SELECT id, project_id, rule_name, description, domain_area, implementation_pattern, constraints, examples, created_at FROM business_rules WHERE deleted_at IS NULL AND project_id = ? {}", window.sql());
+        let mut stmt = db.prepare(&sql)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rules = stmt
+            .query_map([project_id], Self::row_to_rule)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(window.page(rules))
+    }
+
     async fn find_by_domain_area(
         &self,
         project_id: &str,
@@ -80,23 +104,11 @@ impl BusinessRuleRepository for SqliteBusinessRuleRepository {
         let db = self.db.lock().unwrap();
         let mut rules = Vec::new();
 
-        let mut stmt = db.prepare("SELECT id, project_id, rule_name, description, domain_area, implementation_pattern, constraints, examples, created_at FROM business_rules WHERE project_id = ? AND (domain_area = ? OR domain_area IS NULL)")
+        let mut stmt = db.prepare("SELECT id, project_id, rule_name, description, domain_area, implementation_pattern, constraints, examples, created_at FROM business_rules WHERE deleted_at IS NULL AND project_id = ? AND (domain_area = ? OR domain_area IS NULL)")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let rule_rows = stmt
-            .query_map([project_id, domain_area], |row| {
-                Ok(BusinessRule {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    rule_name: row.get(2)?,
-                    description: row.get(3)?,
-                    domain_area: row.get(4)?,
-                    implementation_pattern: row.get(5)?,
-                    constraints: row.get(6)?,
-                    examples: row.get(7)?,
-                    created_at: row.get(8)?,
-                })
-            })
+            .query_map([project_id, domain_area], Self::row_to_rule)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         for rule in rule_rows {
@@ -112,23 +124,11 @@ impl BusinessRuleRepository for SqliteBusinessRuleRepository {
     async fn find_by_id(&self, id: &str) -> Result<Option<BusinessRule>, McpError> {
         let db = self.db.lock().unwrap();
 
-        let mut stmt = db.prepare("SELECT id, project_id, rule_name, description, domain_area, implementation_pattern, constraints, examples, created_at FROM business_rules WHERE id = ?")
+        let mut stmt = db.prepare("SELECT id, project_id, rule_name, description, domain_area, implementation_pattern, constraints, examples, created_at FROM business_rules WHERE deleted_at IS NULL AND id = ?")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let mut rule_iter = stmt
-            .query_map([id], |row| {
-                Ok(BusinessRule {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    rule_name: row.get(2)?,
-                    description: row.get(3)?,
-                    domain_area: row.get(4)?,
-                    implementation_pattern: row.get(5)?,
-                    constraints: row.get(6)?,
-                    examples: row.get(7)?,
-                    created_at: row.get(8)?,
-                })
-            })
+            .query_map([id], Self::row_to_rule)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         match rule_iter.next() {
@@ -165,7 +165,7 @@ impl BusinessRuleRepository for SqliteBusinessRuleRepository {
         let db = self.db.lock().unwrap();
 
         let rows_affected = db
-            .execute("DELETE FROM business_rules WHERE id = ?", [id])
+            .execute(&soft_delete_sql("business_rules", "id = ?"), [id])
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         Ok(rows_affected > 0)
@@ -1,8 +1,10 @@
+use crate::db::context_tables::soft_delete_sql;
 use crate::models::development::{DevelopmentPhase, PhaseStatus};
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::DevelopmentPhaseRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
-use rusqlite::Connection;
+use rusqlite::{Connection, Row};
 use std::sync::{Arc, Mutex};
 
 /// SQLite implementation of DevelopmentPhaseRepository  
@@ -25,6 +27,31 @@ impl SqliteDevelopmentPhaseRepository {
         }
     }
 
+    fn row_to_phase(row: &Row) -> rusqlite::Result<DevelopmentPhase> {
+        let status_str: String = row.get(4)?;
+        let completion_criteria_str: String = row.get(6)?;
+        let dependencies_str: String = row.get(7)?;
+
+        let status = Self::parse_phase_status(&status_str);
+        let completion_criteria: Vec<String> =
+            serde_json::from_str(&completion_criteria_str).unwrap_or_default();
+        let dependencies: Vec<String> = serde_json::from_str(&dependencies_str).unwrap_or_default();
+
+        Ok(DevelopmentPhase {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            phase_name: row.get(2)?,
+            phase_order: row.get(3)?,
+            status,
+            description: row.get(5)?,
+            completion_criteria,
+            dependencies,
+            started_at: row.get(8)?,
+            completed_at: row.get(9)?,
+            created_at: row.get(10)?,
+        })
+    }
+
     fn phase_status_to_string(status: &PhaseStatus) -> &'static str {
         match status {
             PhaseStatus::Pending => "pending",
@@ -75,35 +102,11 @@ impl DevelopmentPhaseRepository for SqliteDevelopmentPhaseRepository {
         let db = self.db.lock().unwrap();
         let mut phases = Vec::new();
 
-        let mut stmt = db.prepare("SELECT id, project_id, phase_name, phase_order, status, description, completion_criteria, dependencies, started_at, completed_at, created_at FROM development_phases WHERE project_id = ? ORDER BY phase_order")
+        let mut stmt = db.prepare("SELECT id, project_id, phase_name, phase_order, status, description, completion_criteria, dependencies, started_at, completed_at, created_at FROM development_phases WHERE deleted_at IS NULL AND project_id = ? ORDER BY phase_order")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let phase_rows = stmt
-            .query_map([project_id], |row| {
-                let status_str: String = row.get(4)?;
-                let completion_criteria_str: String = row.get(6)?;
-                let dependencies_str: String = row.get(7)?;
-
-                let status = Self::parse_phase_status(&status_str);
-                let completion_criteria: Vec<String> =
-                    serde_json::from_str(&completion_criteria_str).unwrap_or_default();
-                let dependencies: Vec<String> =
-                    serde_json::from_str(&dependencies_str).unwrap_or_default();
-
-                Ok(DevelopmentPhase {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    phase_name: row.get(2)?,
-                    phase_order: row.get(3)?,
-                    status,
-                    description: row.get(5)?,
-                    completion_criteria,
-                    dependencies,
-                    started_at: row.get(8)?,
-                    completed_at: row.get(9)?,
-                    created_at: row.get(10)?,
-                })
-            })
+            .query_map([project_id], Self::row_to_phase)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         for phase in phase_rows {
@@ -116,38 +119,34 @@ impl DevelopmentPhaseRepository for SqliteDevelopmentPhaseRepository {
         Ok(phases)
     }
 
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<DevelopmentPhase>, McpError> {
+        let window = query.window(&["phase_order", "phase_name", "status", "created_at"])?;
+        let db = self.db.lock().unwrap();
+
+        let sql = format!("SELECT id, project_id, phase_name, phase_order, status, description, completion_criteria, dependencies, started_at, completed_at, created_at FROM development_phases WHERE deleted_at IS NULL AND project_id = ? {}", window.sql());
+        let mut stmt = db.prepare(&sql)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let phases = stmt
+            .query_map([project_id], Self::row_to_phase)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(window.page(phases))
+    }
+
     async fn find_by_id(&self, id: &str) -> Result<Option<DevelopmentPhase>, McpError> {
         let db = self.db.lock().unwrap();
 
-        let mut stmt = db.prepare("SELECT id, project_id, phase_name, phase_order, status, description, completion_criteria, dependencies, started_at, completed_at, created_at FROM development_phases WHERE id = ?")
+        let mut stmt = db.prepare("SELECT id, project_id, phase_name, phase_order, status, description, completion_criteria, dependencies, started_at, completed_at, created_at FROM development_phases WHERE deleted_at IS NULL AND id = ?")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let mut phase_iter = stmt
-            .query_map([id], |row| {
-                let status_str: String = row.get(4)?;
-                let completion_criteria_str: String = row.get(6)?;
-                let dependencies_str: String = row.get(7)?;
-
-                let status = Self::parse_phase_status(&status_str);
-                let completion_criteria: Vec<String> =
-                    serde_json::from_str(&completion_criteria_str).unwrap_or_default();
-                let dependencies: Vec<String> =
-                    serde_json::from_str(&dependencies_str).unwrap_or_default();
-
-                Ok(DevelopmentPhase {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    phase_name: row.get(2)?,
-                    phase_order: row.get(3)?,
-                    status,
-                    description: row.get(5)?,
-                    completion_criteria,
-                    dependencies,
-                    started_at: row.get(8)?,
-                    completed_at: row.get(9)?,
-                    created_at: row.get(10)?,
-                })
-            })
+            .query_map([id], Self::row_to_phase)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         match phase_iter.next() {
@@ -194,7 +193,7 @@ impl DevelopmentPhaseRepository for SqliteDevelopmentPhaseRepository {
         let db = self.db.lock().unwrap();
 
         let rows_affected = db
-            .execute("DELETE FROM development_phases WHERE id = ?", [id])
+            .execute(&soft_delete_sql("development_phases", "id = ?"), [id])
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         Ok(rows_affected > 0)
@@ -1,4 +1,5 @@
-use crate::models::enhanced_context::{EnhancedContextItem, ContextType, ContextId, ProjectId};
+use crate::db::migrations::run_migrations;
+use crate::models::enhanced_context::{EnhancedContextItem, ContextType, ContextId, ProjectId, KeywordSearchResult};
 use crate::repositories::EnhancedContextRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
@@ -6,6 +7,11 @@ use rusqlite::{params, Connection, Row};
 use std::sync::{Arc, Mutex};
 use chrono::{DateTime, Utc};
 
+/// Column weights for `bm25()`: title, description, data, tags
+const FTS_COLUMN_WEIGHTS: &str = "10.0, 4.0, 1.0, 3.0";
+/// Tokens of context shown around matches in snippets
+const SNIPPET_TOKENS: i32 = 16;
+
 /// SQLite implementation of EnhancedContextRepository
 pub struct SqliteEnhancedContextRepository {
     db: Arc<Mutex<Connection>>,
@@ -20,45 +26,69 @@ impl SqliteEnhancedContextRepository {
         McpError::internal_error(format!("{}: {}", msg, e), None)
     }
 
-    /// Initialize the enhanced context tables
+    /// Bring the schema, including the enhanced context tables and their
+    /// full-text index, up to date
     pub fn initialize_tables(&self) -> Result<(), McpError> {
         let db = self.db.lock().map_err(|e| Self::db_error("Database lock error", e))?;
+        run_migrations(&db).map_err(|e| Self::db_error("Failed to migrate database", e))?;
+        Ok(())
+    }
 
-        // Create enhanced_context_items table
-        db.execute(
+    /// FTS5 query matching any of the words in `text`. Words are quoted so
+    /// user input cannot inject FTS operators; returns None if nothing is searchable.
+    fn fts_query<'a>(terms: impl IntoIterator<Item = &'a str>) -> Option<String> {
+        let words: Vec<String> = terms
+            .into_iter()
+            .flat_map(|term| term.split(|c: char| !c.is_alphanumeric() && c != '_'))
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("\"{}\"", word))
+            .collect();
+
+        (!words.is_empty()).then(|| words.join(" OR "))
+    }
+
+    fn search_locked(
+        &self,
+        db: &Connection,
+        project_id: Option<&str>,
+        fts_query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<KeywordSearchResult>, McpError> {
+        let query = format!(
             r#"
-            CREATE TABLE IF NOT EXISTS enhanced_context_items (
-                id TEXT PRIMARY KEY,
-                project_id TEXT NOT NULL,
-                content_type TEXT NOT NULL,
-                title TEXT NOT NULL,
-                description TEXT NOT NULL,
-                data TEXT NOT NULL, -- JSON data
-                source_file TEXT,
-                source_line INTEGER,
-                quality_score REAL NOT NULL DEFAULT 0.0,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                version INTEGER NOT NULL DEFAULT 1,
-                -- Metadata fields
-                tags TEXT, -- JSON array
-                priority TEXT NOT NULL DEFAULT 'medium',
-                confidence REAL NOT NULL DEFAULT 1.0,
-                source TEXT NOT NULL DEFAULT 'manual',
-                validation_status TEXT NOT NULL DEFAULT 'pending',
-                last_accessed TEXT,
-                access_count INTEGER NOT NULL DEFAULT 0,
-                custom_fields TEXT -- JSON object
-            )
+            SELECT e.*,
+                   bm25(enhanced_context_fts, {weights}) AS fts_rank,
+                   snippet(enhanced_context_fts, -1, '**', '**', '…', {tokens}) AS fts_snippet
+            FROM enhanced_context_fts
+            JOIN enhanced_context_items e ON e.rowid = enhanced_context_fts.rowid
+            WHERE enhanced_context_fts MATCH ?1 AND (?2 IS NULL OR e.project_id = ?2)
+            ORDER BY fts_rank
+            LIMIT ?3
             "#,
-            [],
-        ).map_err(|e| Self::db_error("Failed to create enhanced_context_items table", e))?;
+            weights = FTS_COLUMN_WEIGHTS,
+            tokens = SNIPPET_TOKENS,
+        );
 
-        // Create indexes for better performance
-        db.execute("CREATE INDEX IF NOT EXISTS idx_enhanced_context_project ON enhanced_context_items (project_id)", []).ok();
-        db.execute("CREATE INDEX IF NOT EXISTS idx_enhanced_context_type ON enhanced_context_items (content_type)", []).ok();
+        let mut stmt = db.prepare(&query).map_err(|e| Self::db_error("Failed to prepare statement", e))?;
 
-        Ok(())
+        // LIMIT -1 is unbounded
+        let limit = limit.map_or(-1, |limit| limit as i64);
+        let result_iter = stmt.query_map(params![fts_query, project_id, limit], |row| {
+            let rank: f64 = row.get("fts_rank")?;
+            Ok(KeywordSearchResult {
+                context: self.row_to_enhanced_context_item(row)?,
+                // bm25() is negative, with more relevant rows further below zero
+                score: -rank,
+                snippet: row.get("fts_snippet")?,
+            })
+        }).map_err(|e| Self::db_error("Failed to search contexts", e))?;
+
+        let mut results = Vec::new();
+        for result in result_iter {
+            results.push(result.map_err(|e| Self::db_error("Failed to parse context", e))?);
+        }
+
+        Ok(results)
     }
 
     fn row_to_enhanced_context_item(&self, row: &Row) -> Result<EnhancedContextItem, rusqlite::Error> {
@@ -299,47 +329,24 @@ impl EnhancedContextRepository for SqliteEnhancedContextRepository {
     }
 
     async fn find_contexts_by_keywords(&self, project_id: &str, keywords: &[String]) -> Result<Vec<EnhancedContextItem>, McpError> {
-        if keywords.is_empty() {
+        let Some(fts_query) = Self::fts_query(keywords.iter().map(String::as_str)) else {
             return self.find_contexts_by_project(project_id).await;
-        }
+        };
 
         let db = self.db.lock().map_err(|e| Self::db_error("Database lock error", e))?;
 
-        // Build a simple keyword search query
-        let keyword_conditions: Vec<String> = keywords.iter()
-            .map(|_| "(title LIKE ? OR description LIKE ?)".to_string())
-            .collect();
-        let where_clause = keyword_conditions.join(" OR ");
-
-        let query = format!(
-            "SELECT * FROM enhanced_context_items WHERE project_id = ? AND ({}) ORDER BY updated_at DESC",
-            where_clause
-        );
-
-        let mut stmt = db.prepare(&query).map_err(|e| Self::db_error("Failed to prepare statement", e))?;
-
-        // Build parameters
-        let mut params = vec![project_id.to_string()];
-        for keyword in keywords {
-            let pattern = format!("%{}%", keyword);
-            params.push(pattern.clone());
-            params.push(pattern);
-        }
-
-        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter()
-            .map(|p| p as &dyn rusqlite::ToSql)
-            .collect();
+        let results = self.search_locked(&db, Some(project_id), &fts_query, None)?;
+        Ok(results.into_iter().map(|result| result.context).collect())
+    }
 
-        let context_iter = stmt.query_map(&param_refs[..], |row| {
-            self.row_to_enhanced_context_item(row)
-        }).map_err(|e| Self::db_error("Failed to query contexts", e))?;
+    async fn search_contexts(&self, project_id: Option<&str>, query: &str, limit: usize) -> Result<Vec<KeywordSearchResult>, McpError> {
+        let Some(fts_query) = Self::fts_query([query]) else {
+            return Ok(Vec::new());
+        };
 
-        let mut contexts = Vec::new();
-        for context in context_iter {
-            contexts.push(context.map_err(|e| Self::db_error("Failed to parse context", e))?);
-        }
+        let db = self.db.lock().map_err(|e| Self::db_error("Database lock error", e))?;
 
-        Ok(contexts)
+        self.search_locked(&db, project_id, &fts_query, Some(limit))
     }
 
     async fn update_context(&self, context: &EnhancedContextItem) -> Result<EnhancedContextItem, McpError> {
@@ -1,8 +1,10 @@
+use crate::db::context_tables::soft_delete_sql;
 use crate::models::framework::FrameworkComponent;
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::FrameworkRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
-use rusqlite::Connection;
+use rusqlite::{Connection, Row};
 use std::sync::{Arc, Mutex};
 
 /// SQLite implementation of FrameworkRepository
@@ -14,6 +16,31 @@ impl SqliteFrameworkRepository {
     pub fn new(db: Arc<Mutex<Connection>>) -> Self {
         Self { db }
     }
+
+    fn row_to_component(row: &Row) -> rusqlite::Result<FrameworkComponent> {
+        let dependencies_str: String = row.get(6)?;
+        let dependencies: Vec<String> = serde_json::from_str(&dependencies_str).unwrap_or_default();
+
+        let metadata_str: String = row.get(7)?;
+        let metadata: Option<serde_json::Value> = if metadata_str.is_empty() {
+            None
+        } else {
+            serde_json::from_str(&metadata_str).ok()
+        };
+
+        Ok(FrameworkComponent {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            component_name: row.get(2)?,
+            component_type: row.get(3)?,
+            architecture_layer: row.get(4)?,
+            file_path: row.get(5)?,
+            dependencies,
+            metadata,
+            created_at: row.get(8)?,
+            updated_at: row.get(9)?,
+        })
+    }
 }
 
 #[async_trait]
@@ -71,37 +98,13 @@ impl FrameworkRepository for SqliteFrameworkRepository {
 
         let mut stmt = db.prepare(
             "SELECT id, project_id, component_name, component_type, architecture_layer, file_path, dependencies, metadata, created_at, updated_at 
-             FROM framework_components WHERE project_id = ?1"
+             FROM framework_components WHERE deleted_at IS NULL AND project_id = ?1"
         ).map_err(|e|
             McpError::internal_error(format!("Failed to prepare statement: {}", e), None)
         )?;
 
         let component_iter = stmt
-            .query_map([project_id], |row| {
-                let dependencies_str: String = row.get(6)?;
-                let dependencies: Vec<String> =
-                    serde_json::from_str(&dependencies_str).unwrap_or_default();
-
-                let metadata_str: String = row.get(7)?;
-                let metadata: Option<serde_json::Value> = if metadata_str.is_empty() {
-                    None
-                } else {
-                    serde_json::from_str(&metadata_str).ok()
-                };
-
-                Ok(FrameworkComponent {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    component_name: row.get(2)?,
-                    component_type: row.get(3)?,
-                    architecture_layer: row.get(4)?,
-                    file_path: row.get(5)?,
-                    dependencies,
-                    metadata,
-                    created_at: row.get(8)?,
-                    updated_at: row.get(9)?,
-                })
-            })
+            .query_map([project_id], Self::row_to_component)
             .map_err(|e| {
                 McpError::internal_error(
                     format!("Failed to query framework components: {}", e),
@@ -121,6 +124,43 @@ impl FrameworkRepository for SqliteFrameworkRepository {
 
         Ok(components)
     }
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        architecture_layer: Option<&str>,
+        query: &ListQuery,
+    ) -> Result<Page<FrameworkComponent>, McpError> {
+        let window = query.window(&[
+            "created_at",
+            "updated_at",
+            "component_name",
+            "component_type",
+            "architecture_layer",
+        ])?;
+        let db = self
+            .db
+            .lock()
+            .map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let sql = format!(
+            "SELECT id, project_id, component_name, component_type, architecture_layer, file_path, dependencies, metadata, created_at, updated_at 
+             FROM framework_components WHERE deleted_at IS NULL AND project_id = ?1 AND (?2 IS NULL OR architecture_layer = ?2) {}",
+            window.sql()
+        );
+        let mut stmt = db.prepare(&sql).map_err(|e|
+            McpError::internal_error(format!("Failed to prepare statement: {}", e), None)
+        )?;
+
+        let components = stmt
+            .query_map(rusqlite::params![project_id, architecture_layer], Self::row_to_component)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| {
+                McpError::internal_error(format!("Failed to query framework components: {}", e), None)
+            })?;
+
+        Ok(window.page(components))
+    }
+
     async fn find_by_id(&self, id: &str) -> Result<Option<FrameworkComponent>, McpError> {
         let db = self
             .db
@@ -129,37 +169,13 @@ impl FrameworkRepository for SqliteFrameworkRepository {
 
         let mut stmt = db.prepare(
             "SELECT id, project_id, component_name, component_type, architecture_layer, file_path, dependencies, metadata, created_at, updated_at 
-             FROM framework_components WHERE id = ?1"
+             FROM framework_components WHERE deleted_at IS NULL AND id = ?1"
         ).map_err(|e|
             McpError::internal_error(format!("Failed to prepare statement: {}", e), None)
         )?;
 
         let mut component_iter = stmt
-            .query_map([id], |row| {
-                let dependencies_str: String = row.get(6)?;
-                let dependencies: Vec<String> =
-                    serde_json::from_str(&dependencies_str).unwrap_or_default();
-
-                let metadata_str: String = row.get(7)?;
-                let metadata: Option<serde_json::Value> = if metadata_str.is_empty() {
-                    None
-                } else {
-                    serde_json::from_str(&metadata_str).ok()
-                };
-
-                Ok(FrameworkComponent {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    component_name: row.get(2)?,
-                    component_type: row.get(3)?,
-                    architecture_layer: row.get(4)?,
-                    file_path: row.get(5)?,
-                    dependencies,
-                    metadata,
-                    created_at: row.get(8)?,
-                    updated_at: row.get(9)?,
-                })
-            })
+            .query_map([id], Self::row_to_component)
             .map_err(|e| {
                 McpError::internal_error(
                     format!("Failed to query framework component: {}", e),
@@ -218,7 +234,7 @@ impl FrameworkRepository for SqliteFrameworkRepository {
             .map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
 
         let rows_affected = db
-            .execute("DELETE FROM framework_components WHERE id = ?1", [id])
+            .execute(&soft_delete_sql("framework_components", "id = ?1"), [id])
             .map_err(|e| {
                 McpError::internal_error(
                     format!("Failed to delete framework component: {}", e),
@@ -240,37 +256,13 @@ impl FrameworkRepository for SqliteFrameworkRepository {
 
         let mut stmt = db.prepare(
             "SELECT id, project_id, component_name, component_type, architecture_layer, file_path, dependencies, metadata, created_at, updated_at 
-             FROM framework_components WHERE project_id = ?1 AND architecture_layer = ?2"
+             FROM framework_components WHERE deleted_at IS NULL AND project_id = ?1 AND architecture_layer = ?2"
         ).map_err(|e|
             McpError::internal_error(format!("Failed to prepare statement: {}", e), None)
         )?;
 
         let component_iter = stmt
-            .query_map([project_id, layer], |row| {
-                let dependencies_str: String = row.get(6)?;
-                let dependencies: Vec<String> =
-                    serde_json::from_str(&dependencies_str).unwrap_or_default();
-
-                let metadata_str: String = row.get(7)?;
-                let metadata: Option<serde_json::Value> = if metadata_str.is_empty() {
-                    None
-                } else {
-                    serde_json::from_str(&metadata_str).ok()
-                };
-
-                Ok(FrameworkComponent {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    component_name: row.get(2)?,
-                    component_type: row.get(3)?,
-                    architecture_layer: row.get(4)?,
-                    file_path: row.get(5)?,
-                    dependencies,
-                    metadata,
-                    created_at: row.get(8)?,
-                    updated_at: row.get(9)?,
-                })
-            })
+            .query_map([project_id, layer], Self::row_to_component)
             .map_err(|e| {
                 McpError::internal_error(
                     format!("Failed to query framework components by layer: {}", e),
@@ -1,8 +1,10 @@
+use crate::db::context_tables::soft_delete_sql;
 use crate::models::context::PerformanceRequirement;
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::PerformanceRequirementRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
-use rusqlite::Connection;
+use rusqlite::{Connection, Row};
 use std::sync::{Arc, Mutex};
 
 /// SQLite implementation of PerformanceRequirementRepository
@@ -14,6 +16,19 @@ impl SqlitePerformanceRequirementRepository {
     pub fn new(db: Arc<Mutex<Connection>>) -> Self {
         Self { db }
     }
+
+    fn row_to_requirement(row: &Row) -> rusqlite::Result<PerformanceRequirement> {
+        Ok(PerformanceRequirement {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            component_area: row.get(2)?,
+            requirement_type: row.get(3)?,
+            target_value: row.get(4)?,
+            optimization_patterns: row.get(5)?,
+            avoid_patterns: row.get(6)?,
+            created_at: row.get(7)?,
+        })
+    }
 }
 
 #[async_trait]
@@ -48,22 +63,11 @@ impl PerformanceRequirementRepository for SqlitePerformanceRequirementRepository
         let db = self.db.lock().unwrap();
         let mut requirements = Vec::new();
 
-        let mut stmt = db.prepare("SELECT id, project_id, component_area, requirement_type, target_value, optimization_patterns, avoid_patterns, created_at FROM performance_requirements WHERE project_id = ?")
+        let mut stmt = db.prepare("SELECT id, project_id, component_area, requirement_type, target_value, optimization_patterns, avoid_patterns, created_at FROM performance_requirements WHERE deleted_at IS NULL AND project_id = ?")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let requirement_rows = stmt
-            .query_map([project_id], |row| {
-                Ok(PerformanceRequirement {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    component_area: row.get(2)?,
-                    requirement_type: row.get(3)?,
-                    target_value: row.get(4)?,
-                    optimization_patterns: row.get(5)?,
-                    avoid_patterns: row.get(6)?,
-                    created_at: row.get(7)?,
-                })
-            })
+            .query_map([project_id], Self::row_to_requirement)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         for requirement in requirement_rows {
@@ -76,25 +80,34 @@ impl PerformanceRequirementRepository for SqlitePerformanceRequirementRepository
         Ok(requirements)
     }
 
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<PerformanceRequirement>, McpError> {
+        let window = query.window(&["created_at", "component_area", "requirement_type"])?;
+        let db = self.db.lock().unwrap();
+
+        let sql = format!("SELECT id, project_id, component_area, requirement_type, target_value, optimization_patterns, avoid_patterns, created_at FROM performance_requirements WHERE deleted_at IS NULL AND project_id = ? {}", window.sql());
+        let mut stmt = db.prepare(&sql)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt
+            .query_map([project_id], Self::row_to_requirement)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(window.page(rows))
+    }
+
     async fn find_by_id(&self, id: &str) -> Result<Option<PerformanceRequirement>, McpError> {
         let db = self.db.lock().unwrap();
 
-        let mut stmt = db.prepare("SELECT id, project_id, component_area, requirement_type, target_value, optimization_patterns, avoid_patterns, created_at FROM performance_requirements WHERE id = ?")
+        let mut stmt = db.prepare("SELECT id, project_id, component_area, requirement_type, target_value, optimization_patterns, avoid_patterns, created_at FROM performance_requirements WHERE deleted_at IS NULL AND id = ?")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let mut requirement_iter = stmt
-            .query_map([id], |row| {
-                Ok(PerformanceRequirement {
-                    id: row.get(0)?,
-                    project_id: row.get(1)?,
-                    component_area: row.get(2)?,
-                    requirement_type: row.get(3)?,
-                    target_value: row.get(4)?,
-                    optimization_patterns: row.get(5)?,
-                    avoid_patterns: row.get(6)?,
-                    created_at: row.get(7)?,
-                })
-            })
+            .query_map([id], Self::row_to_requirement)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         match requirement_iter.next() {
@@ -133,7 +146,7 @@ impl PerformanceRequirementRepository for SqlitePerformanceRequirementRepository
         let db = self.db.lock().unwrap();
 
         let rows_affected = db
-            .execute("DELETE FROM performance_requirements WHERE id = ?", [id])
+            .execute(&soft_delete_sql("performance_requirements", "id = ?"), [id])
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         Ok(rows_affected > 0)
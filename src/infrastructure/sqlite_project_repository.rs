@@ -1,8 +1,10 @@
+use crate::db::context_tables::{project_tables, soft_delete_sql};
 use crate::models::context::Project;
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::ProjectRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
-use rusqlite::Connection;
+use rusqlite::{Connection, Row};
 use std::sync::{Arc, Mutex};
 
 /// SQLite implementation of ProjectRepository
@@ -14,6 +16,17 @@ impl SqliteProjectRepository {
     pub fn new(db: Arc<Mutex<Connection>>) -> Self {
         Self { db }
     }
+
+    fn row_to_project(row: &Row) -> rusqlite::Result<Project> {
+        Ok(Project {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            repository_url: row.get(3)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
 }
 
 #[async_trait]
@@ -39,20 +52,11 @@ impl ProjectRepository for SqliteProjectRepository {
     async fn find_by_id(&self, id: &str) -> Result<Option<Project>, McpError> {
         let db = self.db.lock().unwrap();
 
-        let mut stmt = db.prepare("SELECT id, name, description, repository_url, created_at, updated_at FROM projects WHERE id = ?")
+        let mut stmt = db.prepare("SELECT id, name, description, repository_url, created_at, updated_at FROM projects WHERE deleted_at IS NULL AND id = ?")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let mut project_iter = stmt
-            .query_map([id], |row| {
-                Ok(Project {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    description: row.get(2)?,
-                    repository_url: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
-                })
-            })
+            .query_map([id], Self::row_to_project)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         match project_iter.next() {
@@ -69,20 +73,11 @@ impl ProjectRepository for SqliteProjectRepository {
         let db = self.db.lock().unwrap();
         let mut projects = Vec::new();
 
-        let mut stmt = db.prepare("SELECT id, name, description, repository_url, created_at, updated_at FROM projects")
+        let mut stmt = db.prepare("SELECT id, name, description, repository_url, created_at, updated_at FROM projects WHERE deleted_at IS NULL")
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         let project_rows = stmt
-            .query_map([], |row| {
-                Ok(Project {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    description: row.get(2)?,
-                    repository_url: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
-                })
-            })
+            .query_map([], Self::row_to_project)
             .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
         for project in project_rows {
@@ -95,6 +90,22 @@ impl ProjectRepository for SqliteProjectRepository {
         Ok(projects)
     }
 
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Project>, McpError> {
+        let window = query.window(&["created_at", "updated_at", "name"])?;
+        let db = self.db.lock().unwrap();
+
+        let sql = format!("SELECT id, name, description, repository_url, created_at, updated_at FROM projects WHERE deleted_at IS NULL {}", window.sql());
+        let mut stmt = db.prepare(&sql)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let projects = stmt
+            .query_map([], Self::row_to_project)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(window.page(projects))
+    }
+
     async fn update(&self, project: &Project) -> Result<Project, McpError> {
         let db = self.db.lock().unwrap();
 
@@ -112,13 +123,31 @@ impl ProjectRepository for SqliteProjectRepository {
         Ok(project.clone())
     }
 
+    /// Move the project and everything that belongs to it to the trash. The
+    /// children share the project's `deleted_at`, which is how restoring the
+    /// project knows which of them to bring back. Its enhanced context items and
+    /// specifications have no trash of their own and stay until it is purged.
     async fn delete(&self, id: &str) -> Result<bool, McpError> {
         let db = self.db.lock().unwrap();
+        let db_error = |e: rusqlite::Error| McpError::internal_error(format!("Database error: {}", e), None);
+        let tx = db.unchecked_transaction().map_err(db_error)?;
 
-        let rows_affected = db
-            .execute("DELETE FROM projects WHERE id = ?", [id])
-            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        let rows_affected = tx.execute(&soft_delete_sql("projects", "id = ?"), [id]).map_err(db_error)?;
+        if rows_affected == 0 {
+            return Ok(false);
+        }
+        let deleted_at: String = tx
+            .query_row("SELECT deleted_at FROM projects WHERE id = ?", [id], |row| row.get(0))
+            .map_err(db_error)?;
+        for child in project_tables() {
+            tx.execute(
+                &format!("UPDATE {} SET deleted_at = ?1 WHERE project_id = ?2 AND deleted_at IS NULL", child.table),
+                [deleted_at.as_str(), id],
+            )
+            .map_err(db_error)?;
+        }
+        tx.commit().map_err(db_error)?;
 
-        Ok(rows_affected > 0)
+        Ok(true)
     }
 }
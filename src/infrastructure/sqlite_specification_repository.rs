@@ -1,8 +1,10 @@
+use crate::db::migrations::run_migrations;
 use crate::models::specification::{
     AcceptanceCriterion, CriterionStatus, CriterionType, Priority, ProjectSpecification,
     Requirement, RequirementStatus, SpecFormat, SpecStatus, SpecType, Task, TaskStatus, TaskType,
     SpecContent, RequirementMetadata, TaskMetadata,
 };
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::SpecificationRepository;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -22,179 +24,24 @@ impl SqliteSpecificationRepository {
         Self { db }
     }
 
-    /// Initialize database tables for specifications
+    /// Bring the schema, including the specification tables, up to date
     pub fn initialize_tables(&self) -> Result<(), McpError> {
         let db = self.db.lock().unwrap();
-
-        // Create specifications table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS specifications (
-                id TEXT PRIMARY KEY,
-                project_id TEXT NOT NULL,
-                spec_type TEXT NOT NULL,
-                title TEXT NOT NULL,
-                description TEXT,
-                content_format TEXT NOT NULL,
-                raw_content TEXT NOT NULL,
-                parsed_sections TEXT, -- JSON
-                content_metadata TEXT, -- JSON
-                status TEXT NOT NULL,
-                version INTEGER NOT NULL DEFAULT 1,
-                file_path TEXT,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                metadata TEXT -- JSON
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create specifications table: {}", e), None))?;
-
-        // Create requirements table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS requirements (
-                id TEXT PRIMARY KEY,
-                spec_id TEXT NOT NULL,
-                title TEXT NOT NULL,
-                description TEXT NOT NULL,
-                user_story TEXT,
-                priority TEXT NOT NULL,
-                status TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                metadata TEXT, -- JSON
-                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create requirements table: {}", e), None))?;
-
-        // Create acceptance_criteria table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS acceptance_criteria (
-                id TEXT PRIMARY KEY,
-                requirement_id TEXT NOT NULL,
-                description TEXT NOT NULL,
-                criterion_type TEXT NOT NULL,
-                status TEXT NOT NULL,
-                test_cases TEXT, -- JSON array
-                created_at TEXT NOT NULL,
-                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create acceptance_criteria table: {}", e), None))?;
-
-        // Create tasks table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS tasks (
-                id TEXT PRIMARY KEY,
-                spec_id TEXT NOT NULL,
-                title TEXT NOT NULL,
-                description TEXT NOT NULL,
-                status TEXT NOT NULL,
-                task_type TEXT NOT NULL,
-                parent_task TEXT,
-                estimated_effort TEXT,
-                actual_effort TEXT,
-                assigned_to TEXT,
-                progress REAL NOT NULL DEFAULT 0.0,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                started_at TEXT,
-                completed_at TEXT,
-                metadata TEXT, -- JSON
-                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE,
-                FOREIGN KEY (parent_task) REFERENCES tasks (id) ON DELETE SET NULL
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create tasks table: {}", e), None))?;
-
-        // Create task_dependencies table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS task_dependencies (
-                task_id TEXT NOT NULL,
-                depends_on_task_id TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                PRIMARY KEY (task_id, depends_on_task_id),
-                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
-                FOREIGN KEY (depends_on_task_id) REFERENCES tasks (id) ON DELETE CASCADE
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create task_dependencies table: {}", e), None))?;
-
-        // Create requirement_context_links table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS requirement_context_links (
-                requirement_id TEXT NOT NULL,
-                context_id TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                PRIMARY KEY (requirement_id, context_id),
-                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create requirement_context_links table: {}", e), None))?;
-
-        // Create task_context_links table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS task_context_links (
-                task_id TEXT NOT NULL,
-                context_id TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                PRIMARY KEY (task_id, context_id),
-                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create task_context_links table: {}", e), None))?;
-
-        // Create task_requirement_links table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS task_requirement_links (
-                task_id TEXT NOT NULL,
-                requirement_id TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                PRIMARY KEY (task_id, requirement_id),
-                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
-                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create task_requirement_links table: {}", e), None))?;
-
-        // Create indexes for better performance
-        db.execute("CREATE INDEX IF NOT EXISTS idx_specifications_project_id ON specifications (project_id)", [])
-            .map_err(|e| McpError::internal_error(format!("Failed to create index: {}", e), None))?;
-        
-        db.execute("CREATE INDEX IF NOT EXISTS idx_requirements_spec_id ON requirements (spec_id)", [])
-            .map_err(|e| McpError::internal_error(format!("Failed to create index: {}", e), None))?;
-        
-        db.execute("CREATE INDEX IF NOT EXISTS idx_tasks_spec_id ON tasks (spec_id)", [])
-            .map_err(|e| McpError::internal_error(format!("Failed to create index: {}", e), None))?;
-
+        run_migrations(&db)
+            .map_err(|e| McpError::internal_error(format!("Failed to migrate database: {}", e), None))?;
         Ok(())
     }
 
     fn row_to_specification(row: &Row) -> Result<ProjectSpecification, rusqlite::Error> {
-        let parsed_sections: HashMap<String, String> = row.get::<_, Option<String>>(8)?
+        let parsed_sections: HashMap<String, String> = row.get::<_, Option<String>>(7)?
             .and_then(|s| serde_json::from_str(&s).ok())
             .unwrap_or_default();
 
-        let content_metadata: HashMap<String, serde_json::Value> = row.get::<_, Option<String>>(9)?
+        let content_metadata: HashMap<String, serde_json::Value> = row.get::<_, Option<String>>(8)?
             .and_then(|s| serde_json::from_str(&s).ok())
             .unwrap_or_default();
 
-        let spec_metadata = row.get::<_, Option<String>>(15)?
+        let spec_metadata = row.get::<_, Option<String>>(14)?
             .and_then(|s| serde_json::from_str(&s).ok())
             .unwrap_or_default();
 
@@ -214,19 +61,68 @@ impl SqliteSpecificationRepository {
             content,
             requirements: Vec::new(), // Will be loaded separately
             tasks: Vec::new(), // Will be loaded separately
-            status: Self::parse_spec_status(&row.get::<_, String>(10)?),
-            version: row.get::<_, i64>(11)? as u32,
-            file_path: row.get(12)?,
-            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(13)?)
-                .map_err(|_| rusqlite::Error::InvalidColumnType(13, "created_at".to_string(), rusqlite::types::Type::Text))?
+            status: Self::parse_spec_status(&row.get::<_, String>(9)?),
+            version: row.get::<_, i64>(10)? as u32,
+            file_path: row.get(11)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(12)?)
+                .map_err(|_| rusqlite::Error::InvalidColumnType(12, "created_at".to_string(), rusqlite::types::Type::Text))?
                 .with_timezone(&Utc),
-            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(14)?)
-                .map_err(|_| rusqlite::Error::InvalidColumnType(14, "updated_at".to_string(), rusqlite::types::Type::Text))?
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(13)?)
+                .map_err(|_| rusqlite::Error::InvalidColumnType(13, "updated_at".to_string(), rusqlite::types::Type::Text))?
                 .with_timezone(&Utc),
             metadata: spec_metadata,
         })
     }
 
+    /// Acceptance criteria of a requirement
+    fn acceptance_criteria(db: &Connection, requirement_id: &str) -> Result<Vec<AcceptanceCriterion>, McpError> {
+        let mut criteria = Vec::new();
+
+        let mut stmt = db.prepare(
+            "SELECT id, description, criterion_type, status, test_cases, created_at FROM acceptance_criteria WHERE requirement_id = ?"
+        ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let criteria_rows = stmt.query_map([requirement_id], |row| {
+            let test_cases: Vec<String> = row.get::<_, Option<String>>(4)?
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default();
+
+            Ok(AcceptanceCriterion {
+                id: row.get(0)?,
+                description: row.get(1)?,
+                criterion_type: CriterionType::Functional, // Simplified for now
+                status: CriterionStatus::Pending, // Simplified for now
+                test_cases,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .map_err(|_| rusqlite::Error::InvalidColumnType(5, "created_at".to_string(), rusqlite::types::Type::Text))?
+                    .with_timezone(&Utc),
+            })
+        }).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        for criterion in criteria_rows {
+            match criterion {
+                Ok(criterion) => criteria.push(criterion),
+                Err(e) => tracing::warn!("Failed to parse acceptance criterion: {}", e),
+            }
+        }
+
+        Ok(criteria)
+    }
+
+    /// Context ids linked to a requirement
+    fn requirement_context_links(db: &Connection, requirement_id: &str) -> Result<Vec<String>, McpError> {
+        let mut stmt = db.prepare(
+            "SELECT context_id FROM requirement_context_links WHERE requirement_id = ? ORDER BY created_at ASC"
+        ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let links = stmt.query_map([requirement_id], |row| row.get::<_, String>(0))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(links)
+    }
+
     fn row_to_requirement(row: &Row) -> Result<Requirement, rusqlite::Error> {
         let metadata: RequirementMetadata = row.get::<_, Option<String>>(9)?
             .and_then(|s| serde_json::from_str(&s).ok())
@@ -464,6 +360,28 @@ impl SpecificationRepository for SqliteSpecificationRepository {
         Ok(specifications)
     }
 
+    async fn find_specifications_page(&self, project_id: &str, spec_type: Option<&str>, query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError> {
+        let window = query.window(&["created_at", "updated_at", "title", "spec_type", "status"])?;
+        let db = self.db.lock().unwrap();
+
+        let sql = format!(
+            r#"
+            SELECT id, project_id, spec_type, title, description, content_format, raw_content,
+                   parsed_sections, content_metadata, status, version, file_path, created_at, updated_at, metadata
+            FROM specifications WHERE project_id = ?1 AND (?2 IS NULL OR spec_type = ?2) {}
+            "#,
+            window.sql()
+        );
+        let mut stmt = db.prepare(&sql)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let specifications = stmt.query_map(params![project_id, spec_type], Self::row_to_specification)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(window.page(specifications))
+    }
+
     async fn find_specifications_by_type(&self, project_id: &str, spec_type: &str) -> Result<Vec<ProjectSpecification>, McpError> {
         let db = self.db.lock().unwrap();
         let mut specifications = Vec::new();
@@ -606,34 +524,8 @@ impl SpecificationRepository for SqliteSpecificationRepository {
 
         match req_iter.next() {
             Some(Ok(mut requirement)) => {
-                // Load acceptance criteria
-                let mut criteria_stmt = db.prepare(
-                    "SELECT id, description, criterion_type, status, test_cases, created_at FROM acceptance_criteria WHERE requirement_id = ?"
-                ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
-
-                let criteria_rows = criteria_stmt.query_map([&requirement.id], |row| {
-                    let test_cases: Vec<String> = row.get::<_, Option<String>>(4)?
-                        .and_then(|s| serde_json::from_str(&s).ok())
-                        .unwrap_or_default();
-
-                    Ok(AcceptanceCriterion {
-                        id: row.get(0)?,
-                        description: row.get(1)?,
-                        criterion_type: CriterionType::Functional, // Simplified for now
-                        status: CriterionStatus::Pending, // Simplified for now
-                        test_cases,
-                        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
-                            .map_err(|_| rusqlite::Error::InvalidColumnType(5, "created_at".to_string(), rusqlite::types::Type::Text))?
-                            .with_timezone(&Utc),
-                    })
-                }).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
-
-                for criterion in criteria_rows {
-                    match criterion {
-                        Ok(criterion) => requirement.acceptance_criteria.push(criterion),
-                        Err(e) => tracing::warn!("Failed to parse acceptance criterion: {}", e),
-                    }
-                }
+                requirement.acceptance_criteria = Self::acceptance_criteria(&db, &requirement.id)?;
+                requirement.linked_context = Self::requirement_context_links(&db, &requirement.id)?;
 
                 Ok(Some(requirement))
             }
@@ -664,6 +556,11 @@ impl SpecificationRepository for SqliteSpecificationRepository {
             }
         }
 
+        for requirement in &mut requirements {
+            requirement.acceptance_criteria = Self::acceptance_criteria(&db, &requirement.id)?;
+            requirement.linked_context = Self::requirement_context_links(&db, &requirement.id)?;
+        }
+
         Ok(requirements)
     }
 
@@ -1,7 +1,10 @@
 pub mod api;
+pub mod cli;
+pub mod config;
 pub mod container;
 pub mod db;
 pub mod enhanced_context_server;
+pub mod http_transport;
 pub mod infrastructure;
 pub mod models;
 pub mod repositories;
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -10,7 +11,7 @@ pub struct Project {
     pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BusinessRule {
     pub id: String,
     pub project_id: String,
@@ -23,7 +24,7 @@ pub struct BusinessRule {
     pub created_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ArchitecturalDecision {
     pub id: String,
     pub project_id: String,
@@ -36,7 +37,7 @@ pub struct ArchitecturalDecision {
     pub created_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PerformanceRequirement {
     pub id: String,
     pub project_id: String,
@@ -48,7 +49,7 @@ pub struct PerformanceRequirement {
     pub created_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SecurityPolicy {
     pub id: String,
     pub project_id: String,
@@ -61,7 +62,7 @@ pub struct SecurityPolicy {
     pub created_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectConvention {
     pub id: String,
     pub project_id: String,
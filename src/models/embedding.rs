@@ -1,18 +1,28 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use schemars::JsonSchema;
 
 /// Vector embedding for context content
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextEmbedding {
     pub id: String,
     pub context_id: String,
+    /// Project of the embedded context, used to scope project searches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
     pub embedding_vector: Vec<f32>,
     pub embedding_model: String,
     pub embedding_version: String,
     pub content_hash: String,
     pub metadata: EmbeddingMetadata,
     pub created_at: DateTime<Utc>,
+    /// Compact copy of `embedding_vector` used to pre-filter candidates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantized_vector: Option<QuantizedVector>,
+    /// Set when this vector covers one chunk of a long context item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<EmbeddingChunk>,
 }
 
 impl ContextEmbedding {
@@ -26,15 +36,33 @@ impl ContextEmbedding {
         Self {
             id: uuid::Uuid::new_v4().to_string(),
             context_id,
+            project_id: None,
             embedding_vector,
             embedding_model,
             embedding_version,
             content_hash,
             metadata: EmbeddingMetadata::default(),
             created_at: Utc::now(),
+            quantized_vector: None,
+            chunk: None,
         }
     }
 
+    /// Position among the context's chunks; unchunked embeddings are chunk 0
+    pub fn chunk_index(&self) -> usize {
+        self.chunk.as_ref().map_or(0, |chunk| chunk.index)
+    }
+
+    /// Attach (or drop, for `VectorQuantization::None`) the quantized form of the vector
+    pub fn quantize(&mut self, method: VectorQuantization) {
+        self.quantized_vector = QuantizedVector::quantize(&self.embedding_vector, method);
+    }
+
+    /// Full precision vector as little-endian f32 bytes
+    pub fn vector_bytes(&self) -> Vec<u8> {
+        vector_to_bytes(&self.embedding_vector)
+    }
+
     /// Calculate cosine similarity with another embedding
     pub fn cosine_similarity(&self, other: &ContextEmbedding) -> f32 {
         if self.embedding_vector.len() != other.embedding_vector.len() {
@@ -73,6 +101,160 @@ impl ContextEmbedding {
     }
 }
 
+/// Part of a long context item embedded on its own
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EmbeddingChunk {
+    pub index: usize,
+    /// Byte range of the chunk within the item's searchable text
+    pub start: usize,
+    pub end: usize,
+    /// Markdown heading path (e.g. "Auth > Tokens") the chunk falls under
+    pub heading: Option<String>,
+    pub text: String,
+}
+
+/// Encode a vector as little-endian f32 bytes, the `context_embeddings` storage format
+pub fn vector_to_bytes(vector: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(vector.len() * 4);
+    for value in vector {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+/// Decode little-endian f32 bytes; `None` if the length is not a multiple of 4
+pub fn vector_from_bytes(bytes: &[u8]) -> Option<Vec<f32>> {
+    if !bytes.len().is_multiple_of(4) {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect(),
+    )
+}
+
+/// Optional compact representation stored next to the full vector
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorQuantization {
+    #[default]
+    None,
+    /// One signed byte per dimension, scaled by the largest absolute component
+    Int8,
+    /// One bit per dimension (sign), compared with Hamming distance
+    Binary,
+}
+
+impl VectorQuantization {
+    pub fn as_str(&self) -> &str {
+        match self {
+            VectorQuantization::None => "none",
+            VectorQuantization::Int8 => "int8",
+            VectorQuantization::Binary => "binary",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(VectorQuantization::None),
+            "int8" => Some(VectorQuantization::Int8),
+            "binary" => Some(VectorQuantization::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// Quantized vector with what is needed to approximate the original
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuantizedVector {
+    pub method: VectorQuantization,
+    pub dimension: usize,
+    /// Int8: value = byte as i8 * scale; unused for binary
+    pub scale: f32,
+    pub data: Vec<u8>,
+}
+
+impl QuantizedVector {
+    /// Quantize a vector; returns `None` for `VectorQuantization::None`
+    pub fn quantize(vector: &[f32], method: VectorQuantization) -> Option<Self> {
+        let (scale, data) = match method {
+            VectorQuantization::None => return None,
+            VectorQuantization::Int8 => {
+                let max = vector.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+                let scale = if max > 0.0 { max / 127.0 } else { 1.0 };
+                let data = vector
+                    .iter()
+                    .map(|v| (v / scale).round().clamp(-127.0, 127.0) as i8 as u8)
+                    .collect();
+                (scale, data)
+            }
+            VectorQuantization::Binary => {
+                let mut data = vec![0u8; vector.len().div_ceil(8)];
+                for (i, value) in vector.iter().enumerate() {
+                    if *value > 0.0 {
+                        data[i / 8] |= 1 << (i % 8);
+                    }
+                }
+                (1.0, data)
+            }
+        };
+
+        Some(Self {
+            method,
+            dimension: vector.len(),
+            scale,
+            data,
+        })
+    }
+
+    /// Approximate reconstruction of the original vector
+    pub fn dequantize(&self) -> Vec<f32> {
+        match self.method {
+            VectorQuantization::None => Vec::new(),
+            VectorQuantization::Int8 => self.data.iter().map(|b| *b as i8 as f32 * self.scale).collect(),
+            VectorQuantization::Binary => (0..self.dimension)
+                .map(|i| if self.data[i / 8] & (1 << (i % 8)) != 0 { 1.0 } else { -1.0 })
+                .collect(),
+        }
+    }
+
+    /// Cheap similarity estimate in [-1, 1] between two vectors quantized the same way
+    pub fn approximate_similarity(&self, other: &QuantizedVector) -> f32 {
+        if self.method != other.method || self.dimension != other.dimension || self.dimension == 0 {
+            return 0.0;
+        }
+
+        match self.method {
+            VectorQuantization::None => 0.0,
+            VectorQuantization::Int8 => {
+                let (mut dot, mut norm_a, mut norm_b) = (0i64, 0i64, 0i64);
+                for (a, b) in self.data.iter().zip(other.data.iter()) {
+                    let (a, b) = (*a as i8 as i64, *b as i8 as i64);
+                    dot += a * b;
+                    norm_a += a * a;
+                    norm_b += b * b;
+                }
+                if norm_a == 0 || norm_b == 0 {
+                    0.0
+                } else {
+                    dot as f32 / ((norm_a as f32).sqrt() * (norm_b as f32).sqrt())
+                }
+            }
+            VectorQuantization::Binary => {
+                let differing: u32 = self
+                    .data
+                    .iter()
+                    .zip(other.data.iter())
+                    .map(|(a, b)| (a ^ b).count_ones())
+                    .sum();
+                1.0 - 2.0 * differing as f32 / self.dimension as f32
+            }
+        }
+    }
+}
+
 /// Metadata for embeddings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingMetadata {
@@ -141,6 +323,18 @@ impl Default for VectorSearchQuery {
     }
 }
 
+impl VectorSearchQuery {
+    /// Select results by maximal marginal relevance when `diversity` is above 0
+    pub fn with_diversity(mut self, diversity: f32) -> Self {
+        self.ranking_method = if diversity > 0.0 {
+            RankingMethod::MaximalMarginalRelevance { diversity: diversity.min(1.0) }
+        } else {
+            RankingMethod::CosineSimilarity
+        };
+        self
+    }
+}
+
 /// Filters for vector search
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchFilters {
@@ -149,6 +343,9 @@ pub struct SearchFilters {
     pub date_range: Option<DateRange>,
     pub quality_threshold: Option<f32>,
     pub tags: Option<Vec<String>>,
+    /// Only match vectors of this model; set by the search service to the serving model
+    #[serde(default)]
+    pub embedding_model: Option<String>,
 }
 
 impl Default for SearchFilters {
@@ -159,6 +356,7 @@ impl Default for SearchFilters {
             date_range: None,
             quality_threshold: None,
             tags: None,
+            embedding_model: None,
         }
     }
 }
@@ -176,6 +374,10 @@ pub enum RankingMethod {
     CosineSimilarity,
     EuclideanDistance,
     Hybrid,
+    /// Maximal marginal relevance: each pick trades similarity to the query
+    /// against similarity to results already picked. `diversity` runs from 0
+    /// (plain relevance) to 1 (only novelty counts).
+    MaximalMarginalRelevance { diversity: f32 },
     Custom(String),
 }
 
@@ -185,13 +387,22 @@ impl RankingMethod {
             RankingMethod::CosineSimilarity => "cosine_similarity",
             RankingMethod::EuclideanDistance => "euclidean_distance",
             RankingMethod::Hybrid => "hybrid",
+            RankingMethod::MaximalMarginalRelevance { .. } => "mmr",
             RankingMethod::Custom(name) => name,
         }
     }
+
+    /// Diversity setting, if results are selected by maximal marginal relevance
+    pub fn diversity(&self) -> Option<f32> {
+        match self {
+            RankingMethod::MaximalMarginalRelevance { diversity } => Some(diversity.clamp(0.0, 1.0)),
+            _ => None,
+        }
+    }
 }
 
 /// Result of vector similarity search
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VectorSearchResult {
     pub context_id: String,
     pub similarity_score: f32,
@@ -201,12 +412,35 @@ pub struct VectorSearchResult {
 }
 
 /// Metadata for search results
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ResultMetadata {
     pub content_type: String,
     pub content_preview: String,
     pub match_explanation: String,
     pub quality_indicators: Vec<String>,
+    /// Best matching chunk when the item was embedded in several chunks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<ChunkMatch>,
+}
+
+/// Chunk of a long context item that produced a search hit
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ChunkMatch {
+    pub chunk_index: usize,
+    pub heading: Option<String>,
+    /// Chunks of the same item that matched the query
+    pub matched_chunks: usize,
+}
+
+impl ChunkMatch {
+    /// Chunk details of a single matching embedding, `None` when it is not chunked
+    pub fn from_embedding(embedding: &ContextEmbedding) -> Option<Self> {
+        embedding.chunk.as_ref().map(|chunk| Self {
+            chunk_index: chunk.index,
+            heading: chunk.heading.clone(),
+            matched_chunks: 1,
+        })
+    }
 }
 
 /// Batch of embeddings for efficient processing
@@ -230,8 +464,9 @@ pub struct ModelInfo {
 }
 
 /// Types of embedding models
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum ModelType {
+    #[default]
     SentenceTransformer,
     BERT,
     CodeBERT,
@@ -252,7 +487,10 @@ pub struct BatchStats {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingConfig {
     pub model_name: String,
+    /// Directory (or `.safetensors` file) of a local sentence-transformer model
     pub model_path: Option<String>,
+    #[serde(default)]
+    pub model_type: ModelType,
     pub embedding_dimension: usize,
     pub max_sequence_length: usize,
     pub batch_size: usize,
@@ -266,6 +504,7 @@ impl Default for EmbeddingConfig {
         Self {
             model_name: "all-MiniLM-L6-v2".to_string(),
             model_path: None,
+            model_type: ModelType::SentenceTransformer,
             embedding_dimension: 384,
             max_sequence_length: 512,
             batch_size: 32,
@@ -274,4 +513,60 @@ impl Default for EmbeddingConfig {
             cache_embeddings: true,
         }
     }
-}
\ No newline at end of file
+}
+/// Corpus statistics backing the BM25 embedding fallback
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EmbeddingVocabulary {
+    /// Project the vocabulary was built from, `None` for the whole database
+    pub project_id: Option<String>,
+    pub document_count: u64,
+    pub average_document_length: f32,
+    /// Number of documents each term appears in
+    pub document_frequencies: HashMap<String, u64>,
+    pub built_at: DateTime<Utc>,
+}
+
+impl EmbeddingVocabulary {
+    /// Build a vocabulary from already tokenized documents
+    pub fn from_documents<I>(project_id: Option<String>, documents: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<String>>,
+    {
+        let mut document_frequencies: HashMap<String, u64> = HashMap::new();
+        let mut document_count = 0u64;
+        let mut total_length = 0u64;
+
+        for terms in documents {
+            document_count += 1;
+            total_length += terms.len() as u64;
+
+            let unique: std::collections::HashSet<String> = terms.into_iter().collect();
+            for term in unique {
+                *document_frequencies.entry(term).or_insert(0) += 1;
+            }
+        }
+
+        Self {
+            project_id,
+            document_count,
+            average_document_length: if document_count == 0 {
+                0.0
+            } else {
+                total_length as f32 / document_count as f32
+            },
+            document_frequencies,
+            built_at: Utc::now(),
+        }
+    }
+
+    /// BM25 inverse document frequency; unseen terms get the maximum weight
+    pub fn idf(&self, term: &str) -> f32 {
+        let n = self.document_count as f32;
+        let df = self.document_frequencies.get(term).copied().unwrap_or(0) as f32;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.document_count == 0
+    }
+}
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
+use schemars::JsonSchema;
 
 // Type aliases for better readability
 pub type ContextId = String;
@@ -9,7 +10,7 @@ pub type ProjectId = String;
 pub type RelationshipId = String;
 
 /// Enhanced context item with intelligence features
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EnhancedContextItem {
     pub id: ContextId,
     pub project_id: ProjectId,
@@ -92,7 +93,7 @@ impl Default for EnhancedContextItem {
 }
 
 /// Content of a context item with type information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ContextContent {
     pub content_type: ContextType,
     pub title: String,
@@ -103,7 +104,7 @@ pub struct ContextContent {
 }
 
 /// Types of context that can be stored
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
 pub enum ContextType {
     BusinessRule,
     ArchitecturalDecision,
@@ -139,7 +140,7 @@ impl ContextType {
 }
 
 /// Relationship between context items
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ContextRelationship {
     pub id: RelationshipId,
     pub target_id: ContextId,
@@ -182,7 +183,7 @@ impl ContextRelationship {
 }
 
 /// Types of relationships between context items
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum RelationshipType {
     DependsOn,
     Conflicts,
@@ -220,7 +221,7 @@ impl RelationshipType {
 }
 
 /// Metadata associated with context items
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ContextMetadata {
     pub tags: Vec<String>,
     pub priority: Priority,
@@ -248,7 +249,7 @@ impl Default for ContextMetadata {
 }
 
 /// Priority levels for context items
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum Priority {
     Critical,
     High,
@@ -277,7 +278,7 @@ impl Priority {
 }
 
 /// Source of context information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum ContextSource {
     Manual,
     AutoDetected,
@@ -301,7 +302,7 @@ impl ContextSource {
 }
 
 /// Validation status of context items
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum ValidationStatus {
     Pending,
     Valid,
@@ -323,7 +324,7 @@ impl ValidationStatus {
 }
 
 /// Usage statistics for context items
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UsageStatistics {
     pub total_queries: u64,
     pub successful_queries: u64,
@@ -388,7 +389,7 @@ impl UsageStatistics {
 }
 
 /// Usage frequency categories
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum UsageFrequency {
     Never,
     Rare,
@@ -398,7 +399,7 @@ pub enum UsageFrequency {
 }
 
 /// Query patterns for usage analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct QueryPattern {
     pub pattern_type: String,
     pub frequency: u32,
@@ -407,7 +408,7 @@ pub struct QueryPattern {
 }
 
 /// Semantic tags for context categorization
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SemanticTag {
     pub tag: String,
     pub confidence: f64,
@@ -427,7 +428,7 @@ impl SemanticTag {
 }
 
 /// Source of semantic tags
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum TagSource {
     Manual,
     NLP,
@@ -530,4 +531,14 @@ pub enum ImprovementType {
     AddRelationships,
     UpdateTags,
     ArchiveUnused,
-}
\ No newline at end of file
+}
+
+/// Full-text match of a context item, best first
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KeywordSearchResult {
+    pub context: EnhancedContextItem,
+    /// BM25 relevance; higher is better
+    pub score: f64,
+    /// Matching excerpt with the query terms wrapped in `**`
+    pub snippet: String,
+}
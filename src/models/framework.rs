@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FrameworkComponent {
     pub id: String,
     pub project_id: String,
@@ -15,7 +16,7 @@ pub struct FrameworkComponent {
 }
 
 // Generic server metadata structures (framework-agnostic)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ServerCapabilitiesInfo {
     pub server_info: ServerMetadata,
     pub features: Vec<FeatureInfo>,
@@ -25,7 +26,7 @@ pub struct ServerCapabilitiesInfo {
     pub recommended_workflow: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ServerMetadata {
     pub name: String,
     pub version: String,
@@ -33,7 +34,7 @@ pub struct ServerMetadata {
     pub config_directory: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FeatureInfo {
     pub name: String,
     pub description: String,
@@ -41,14 +42,14 @@ pub struct FeatureInfo {
     pub tools: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum FeatureStatus {
     Implemented,
     Framework, // Database structure exists, tools being added
     Planned,   // Not yet implemented
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TableInfo {
     pub name: String,
     pub description: String,
@@ -56,7 +57,7 @@ pub struct TableInfo {
     pub example_use: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ToolInfo {
     pub name: String,
     pub description: String,
@@ -65,7 +66,7 @@ pub struct ToolInfo {
     pub example_use: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UsageExample {
     pub scenario: String,
     pub steps: Vec<String>,
@@ -7,4 +7,5 @@ pub mod embedding;
 pub mod enhanced_context;
 pub mod flutter;
 pub mod framework;
+pub mod pagination;
 pub mod specification;
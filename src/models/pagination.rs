@@ -0,0 +1,206 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rmcp::model::ErrorData as McpError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Page size of list queries that do not set a limit
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+/// Largest page a list query may request
+pub const MAX_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// Which page of a listing to return and how the listing is ordered
+#[derive(Debug, Clone, Default)]
+pub struct ListQuery {
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    pub sort_by: Option<String>,
+    pub sort_order: SortOrder,
+}
+
+/// Position in a listing, handed to clients as an opaque string
+#[derive(Debug, Serialize, Deserialize)]
+struct Cursor {
+    offset: usize,
+    sort_by: String,
+    sort_order: SortOrder,
+}
+
+impl Cursor {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+impl ListQuery {
+    /// Resolve the query against the columns a listing can be sorted by.
+    /// `sortable[0]` is the default sort column.
+    pub fn window(&self, sortable: &[&'static str]) -> Result<PageWindow, McpError> {
+        let sort_by = match &self.sort_by {
+            Some(column) => sortable.iter().find(|c| **c == column.as_str()).copied().ok_or_else(|| {
+                McpError::invalid_params(
+                    format!("Cannot sort by {column}; expected one of: {}", sortable.join(", ")),
+                    None,
+                )
+            })?,
+            None => sortable[0],
+        };
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if limit == 0 || limit > MAX_PAGE_SIZE {
+            return Err(McpError::invalid_params(
+                format!("limit must be between 1 and {MAX_PAGE_SIZE}"),
+                None,
+            ));
+        }
+
+        let offset = match &self.cursor {
+            Some(cursor) => {
+                let cursor = Cursor::decode(cursor)
+                    .ok_or_else(|| McpError::invalid_params("Invalid cursor", None))?;
+                if cursor.sort_by != sort_by || cursor.sort_order != self.sort_order {
+                    return Err(McpError::invalid_params(
+                        "Cursor belongs to a listing with a different sort_by or sort_order",
+                        None,
+                    ));
+                }
+                cursor.offset
+            }
+            None => 0,
+        };
+
+        Ok(PageWindow {
+            sort_by,
+            sort_order: self.sort_order,
+            offset,
+            limit,
+        })
+    }
+}
+
+/// A validated [`ListQuery`]: the rows to fetch and their order
+#[derive(Debug, Clone)]
+pub struct PageWindow {
+    sort_by: &'static str,
+    sort_order: SortOrder,
+    offset: usize,
+    limit: usize,
+}
+
+impl PageWindow {
+    /// `ORDER BY ... LIMIT ... OFFSET ...` clause selecting the page plus one row,
+    /// which tells [`PageWindow::page`] whether another page follows. Ties are
+    /// broken by `id` so pages never overlap.
+    pub fn sql(&self) -> String {
+        format!(
+            "ORDER BY {} {}, id ASC LIMIT {} OFFSET {}",
+            self.sort_by,
+            self.sort_order.sql(),
+            self.limit + 1,
+            self.offset
+        )
+    }
+
+    /// The page made of rows fetched with [`PageWindow::sql`]
+    pub fn page<T>(&self, mut rows: Vec<T>) -> Page<T> {
+        let next_cursor = (rows.len() > self.limit).then(|| {
+            rows.truncate(self.limit);
+            Cursor {
+                offset: self.offset + self.limit,
+                sort_by: self.sort_by.to_string(),
+                sort_order: self.sort_order,
+            }
+            .encode()
+        });
+        Page { items: rows, next_cursor }
+    }
+}
+
+/// One page of a listing
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor of the following page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SORTABLE: &[&str] = &["created_at", "rule_name"];
+
+    #[test]
+    fn test_window_defaults_to_first_sortable_column() {
+        let window = ListQuery::default().window(SORTABLE).unwrap();
+        assert_eq!(window.sql(), "ORDER BY created_at ASC, id ASC LIMIT 51 OFFSET 0");
+    }
+
+    #[test]
+    fn test_cursor_continues_where_the_page_ended() {
+        let query = ListQuery {
+            limit: Some(2),
+            sort_by: Some("rule_name".to_string()),
+            sort_order: SortOrder::Desc,
+            ..Default::default()
+        };
+        let page = query.window(SORTABLE).unwrap().page(vec![1, 2, 3]);
+        assert_eq!(page.items, vec![1, 2]);
+
+        let next = ListQuery { cursor: page.next_cursor, ..query };
+        let window = next.window(SORTABLE).unwrap();
+        assert_eq!(window.sql(), "ORDER BY rule_name DESC, id ASC LIMIT 3 OFFSET 2");
+        assert!(window.page(vec![3]).next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_invalid_queries_are_rejected() {
+        let unknown_column = ListQuery { sort_by: Some("id; DROP TABLE x".to_string()), ..Default::default() };
+        assert!(unknown_column.window(SORTABLE).is_err());
+
+        let too_large = ListQuery { limit: Some(MAX_PAGE_SIZE + 1), ..Default::default() };
+        assert!(too_large.window(SORTABLE).is_err());
+
+        let garbage = ListQuery { cursor: Some("not a cursor".to_string()), ..Default::default() };
+        assert!(garbage.window(SORTABLE).is_err());
+
+        let page = ListQuery { limit: Some(1), ..Default::default() }.window(SORTABLE).unwrap().page(vec![1, 2]);
+        let resorted = ListQuery {
+            cursor: page.next_cursor,
+            sort_by: Some("rule_name".to_string()),
+            ..Default::default()
+        };
+        assert!(resorted.window(SORTABLE).is_err());
+    }
+}
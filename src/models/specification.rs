@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
+use schemars::JsonSchema;
 
 // Type aliases for better readability
 pub type SpecId = String;
@@ -11,7 +12,7 @@ pub type ProjectId = String;
 pub type ContextId = String;
 
 /// Project specification containing requirements, design, and tasks
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectSpecification {
     pub id: SpecId,
     pub project_id: ProjectId,
@@ -82,7 +83,7 @@ impl ProjectSpecification {
 }
 
 /// Types of specifications
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum SpecType {
     Feature,
     Architecture,
@@ -128,7 +129,7 @@ impl SpecType {
 }
 
 /// Content of a specification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SpecContent {
     pub format: SpecFormat,
     pub raw_content: String,
@@ -158,7 +159,7 @@ impl SpecContent {
 }
 
 /// Format of specification content
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum SpecFormat {
     Markdown,
     YAML,
@@ -190,7 +191,7 @@ impl SpecFormat {
 }
 
 /// Status of a specification
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum SpecStatus {
     Draft,
     InReview,
@@ -216,7 +217,7 @@ impl SpecStatus {
 }
 
 /// Metadata for specifications
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SpecMetadata {
     pub tags: Vec<String>,
     pub priority: Priority,
@@ -246,7 +247,7 @@ impl Default for SpecMetadata {
 }
 
 /// Priority levels
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum Priority {
     Critical,
     High,
@@ -275,7 +276,7 @@ impl Priority {
 }
 
 /// Complexity levels
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum Complexity {
     Simple,
     Medium,
@@ -420,7 +421,7 @@ pub enum CriterionStatus {
 }
 
 /// Status of requirements
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum RequirementStatus {
     Draft,
     Defined,
@@ -615,7 +616,7 @@ impl TaskType {
 }
 
 /// Status of tasks
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum TaskStatus {
     NotStarted,
     InProgress,
@@ -1,4 +1,5 @@
 use crate::models::context::ArchitecturalDecision;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -13,6 +14,12 @@ pub trait ArchitecturalDecisionRepository: Send + Sync {
         &self,
         project_id: &str,
     ) -> Result<Vec<ArchitecturalDecision>, McpError>;
+    /// One page of a project's decisions, sortable by `created_at`, `decision_title` or `status`
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<ArchitecturalDecision>, McpError>;
     async fn find_by_id(&self, id: &str) -> Result<Option<ArchitecturalDecision>, McpError>;
     async fn update(
         &self,
@@ -1,4 +1,5 @@
 use crate::models::context::BusinessRule;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -7,6 +8,12 @@ use rmcp::model::ErrorData as McpError;
 pub trait BusinessRuleRepository: Send + Sync {
     async fn create(&self, rule: &BusinessRule) -> Result<BusinessRule, McpError>;
     async fn find_by_project_id(&self, project_id: &str) -> Result<Vec<BusinessRule>, McpError>;
+    /// One page of a project's rules, sortable by `created_at`, `rule_name` or `domain_area`
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<BusinessRule>, McpError>;
     async fn find_by_domain_area(
         &self,
         project_id: &str,
@@ -1,4 +1,5 @@
 use crate::models::development::DevelopmentPhase;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -8,6 +9,12 @@ pub trait DevelopmentPhaseRepository: Send + Sync {
     async fn create(&self, phase: &DevelopmentPhase) -> Result<DevelopmentPhase, McpError>;
     async fn find_by_project_id(&self, project_id: &str)
         -> Result<Vec<DevelopmentPhase>, McpError>;
+    /// One page of a project's phases, sortable by `phase_order`, `phase_name`, `status` or `created_at`
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<DevelopmentPhase>, McpError>;
     async fn find_by_id(&self, id: &str) -> Result<Option<DevelopmentPhase>, McpError>;
     async fn update(&self, phase: &DevelopmentPhase) -> Result<DevelopmentPhase, McpError>;
     async fn delete(&self, id: &str) -> Result<bool, McpError>;
@@ -1,7 +1,12 @@
-use crate::models::embedding::{ContextEmbedding, VectorSearchQuery, VectorSearchResult};
+use crate::db::migrations::{run_migrations, MigrationError};
+use crate::models::embedding::{
+    vector_from_bytes, ContextEmbedding, EmbeddingConfig, EmbeddingVocabulary, QuantizedVector,
+    VectorQuantization, VectorSearchQuery, VectorSearchResult,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, Result as SqliteResult, Row};
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult, Row};
 use serde_json;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -31,6 +36,14 @@ impl From<rusqlite::Error> for EmbeddingRepositoryError {
     }
 }
 
+impl From<MigrationError> for EmbeddingRepositoryError {
+    fn from(error: MigrationError) -> Self {
+        EmbeddingRepositoryError::DatabaseError {
+            message: error.to_string(),
+        }
+    }
+}
+
 impl From<serde_json::Error> for EmbeddingRepositoryError {
     fn from(error: serde_json::Error) -> Self {
         EmbeddingRepositoryError::SerializationError { source: error }
@@ -49,9 +62,38 @@ pub trait EmbeddingRepository: Send + Sync {
     /// Retrieve embedding by context ID
     async fn get_embedding_by_context_id(&self, context_id: &str) -> Result<Option<ContextEmbedding>, EmbeddingRepositoryError>;
     
+    /// Retrieve every stored chunk of a context, across models, in chunk order
+    async fn get_embeddings_by_context_id(&self, context_id: &str) -> Result<Vec<ContextEmbedding>, EmbeddingRepositoryError> {
+        Ok(self.get_embedding_by_context_id(context_id).await?.into_iter().collect())
+    }
+    
+    /// Replace the stored chunks of each context in `embeddings` for the same model,
+    /// so a context that now has fewer chunks leaves none behind
+    async fn replace_context_embeddings(&self, embeddings: &[ContextEmbedding]) -> Result<(), EmbeddingRepositoryError> {
+        let context_ids: std::collections::HashSet<&str> = embeddings.iter().map(|e| e.context_id.as_str()).collect();
+        for context_id in context_ids {
+            self.delete_embedding(context_id).await?;
+        }
+        self.store_embeddings_batch(embeddings).await
+    }
+    
     /// Retrieve all embeddings for a project
     async fn get_embeddings_by_project(&self, project_id: &str) -> Result<Vec<ContextEmbedding>, EmbeddingRepositoryError>;
     
+    /// Retrieve all stored embeddings, optionally restricted to one model
+    async fn get_all_embeddings(&self, embedding_model: Option<&str>) -> Result<Vec<ContextEmbedding>, EmbeddingRepositoryError>;
+    
+    /// Contexts with `embedding_model` vectors and when their vectors were last written,
+    /// without reading the vectors themselves
+    async fn get_embedding_write_times(&self, embedding_model: &str) -> Result<Vec<(String, DateTime<Utc>)>, EmbeddingRepositoryError> {
+        let mut write_times: std::collections::HashMap<String, DateTime<Utc>> = std::collections::HashMap::new();
+        for embedding in self.get_all_embeddings(Some(embedding_model)).await? {
+            let written = write_times.entry(embedding.context_id).or_insert(embedding.created_at);
+            *written = (*written).max(embedding.created_at);
+        }
+        Ok(write_times.into_iter().collect())
+    }
+    
     /// Find embeddings by similarity (brute force for SQLite)
     async fn find_similar_embeddings(&self, query: &VectorSearchQuery, project_id: Option<&str>) -> Result<Vec<VectorSearchResult>, EmbeddingRepositoryError>;
     
@@ -69,75 +111,363 @@ pub trait EmbeddingRepository: Send + Sync {
     
     /// Check if embedding exists for context
     async fn embedding_exists(&self, context_id: &str) -> Result<bool, EmbeddingRepositoryError>;
+    
+    /// Configuration of the model the served vectors were built with
+    async fn get_active_embedding_config(&self) -> Result<Option<EmbeddingConfig>, EmbeddingRepositoryError>;
+    
+    /// Record the model the served vectors are built with
+    async fn set_active_embedding_config(&self, config: &EmbeddingConfig) -> Result<(), EmbeddingRepositoryError>;
+    
+    /// How many contexts embedded with `source_model` also have `target_model` vectors
+    async fn get_model_coverage(&self, source_model: &str, target_model: &str) -> Result<ModelCoverage, EmbeddingRepositoryError>;
+    
+    /// Contexts embedded with `source_model` that have no `target_model` vectors yet
+    async fn get_contexts_missing_model(&self, source_model: &str, target_model: &str) -> Result<Vec<String>, EmbeddingRepositoryError>;
+    
+    /// Delete every vector of one model, returning the number of rows removed
+    async fn delete_embeddings_by_model(&self, embedding_model: &str) -> Result<usize, EmbeddingRepositoryError>;
+}
+
+/// Progress of re-embedding one model's contexts under another
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModelCoverage {
+    pub source_contexts: usize,
+    pub covered_contexts: usize,
+}
+
+impl ModelCoverage {
+    /// Fraction of source contexts covered; an empty source counts as complete
+    pub fn ratio(&self) -> f32 {
+        if self.source_contexts == 0 {
+            1.0
+        } else {
+            self.covered_contexts as f32 / self.source_contexts as f32
+        }
+    }
+    
+    pub fn is_complete(&self) -> bool {
+        self.covered_contexts >= self.source_contexts
+    }
 }
 
 /// Statistics about embeddings
 #[derive(Debug, Clone)]
 pub struct EmbeddingStats {
     pub total_embeddings: u64,
+    /// Distinct contexts; lower than `total_embeddings` when items are chunked
+    pub indexed_contexts: u64,
     pub embeddings_by_model: std::collections::HashMap<String, u64>,
     pub average_vector_dimension: f64,
     pub oldest_embedding: Option<DateTime<Utc>>,
     pub newest_embedding: Option<DateTime<Utc>>,
+    /// Number of embeddings carrying a quantized copy, keyed by method ("int8", "binary")
+    pub quantized_embeddings: std::collections::HashMap<String, u64>,
+    /// Rows holding a JSON vector; migration 10 converts those written by older versions
+    pub legacy_json_embeddings: u64,
+    /// Bytes used by full precision vectors
+    pub vector_storage_bytes: u64,
+    /// Bytes used by quantized vectors
+    pub quantized_storage_bytes: u64,
 }
 
 /// SQLite implementation of EmbeddingRepository
+#[derive(Clone)]
 pub struct SqliteEmbeddingRepository {
     connection: Arc<Mutex<Connection>>,
+    quantization: VectorQuantization,
 }
 
+/// Columns read by `row_to_embedding`
+const EMBEDDING_COLUMNS: &str = "id, context_id, project_id, embedding_vector, embedding_model, embedding_version,
+    content_hash, content_type, content_length, tokenization_method,
+    preprocessing_steps, quality_score, custom_metadata, created_at,
+    quantization, quantized_vector, quantization_scale,
+    chunk_index, chunk_start, chunk_end, chunk_heading, chunk_text";
+
+/// Quantized pre-filtering keeps this many candidates per requested result
+const PREFILTER_CANDIDATE_MULTIPLIER: usize = 4;
+const MIN_PREFILTER_CANDIDATES: usize = 32;
+
 impl SqliteEmbeddingRepository {
     pub fn new(connection: Arc<Mutex<Connection>>) -> Self {
-        Self { connection }
+        Self {
+            connection,
+            quantization: VectorQuantization::None,
+        }
+    }
+
+    /// Store a quantized copy of every vector and use it to pre-filter similarity searches
+    pub fn with_quantization(mut self, quantization: VectorQuantization) -> Self {
+        self.quantization = quantization;
+        self
+    }
+
+    pub fn quantization(&self) -> VectorQuantization {
+        self.quantization
     }
     
-    /// Initialize the embeddings table
+    /// Bring the schema up to date and quantize vectors stored without the
+    /// configured quantization
     pub async fn initialize(&self) -> Result<(), EmbeddingRepositoryError> {
         let conn = self.connection.lock().await;
         
-        conn.execute_batch(
+        run_migrations(&conn)?;
+        Self::backfill_quantized_vectors_locked(&conn, self.quantization)?;
+        
+        info!("Embedding repository initialized successfully");
+        Ok(())
+    }
+    
+    /// Store a quantized copy of every vector that lacks one in the configured
+    /// method, returning how many were quantized; safe to run repeatedly
+    pub async fn backfill_quantized_vectors(&self) -> Result<usize, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        Self::backfill_quantized_vectors_locked(&conn, self.quantization)
+    }
+    
+    fn backfill_quantized_vectors_locked(
+        conn: &Connection,
+        quantization: VectorQuantization,
+    ) -> Result<usize, EmbeddingRepositoryError> {
+        if quantization == VectorQuantization::None {
+            return Ok(0);
+        }
+        let tx = conn.unchecked_transaction()?;
+        let pending: Vec<(String, Vec<u8>)> = {
+            let mut stmt = tx.prepare(
+                "SELECT id, embedding_vector FROM context_embeddings WHERE quantization IS NULL OR quantization != ?1",
+            )?;
+            let rows = stmt
+                .query_map([quantization.as_str()], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+        
+        let mut quantized_count = 0;
+        for (id, bytes) in &pending {
+            let quantized = vector_from_bytes(bytes)
+                .and_then(|vector| QuantizedVector::quantize(&vector, quantization));
+            if let Some(quantized) = quantized {
+                tx.execute(
+                    "UPDATE context_embeddings SET quantization = ?1, quantized_vector = ?2, quantization_scale = ?3 WHERE id = ?4",
+                    params![quantization.as_str(), quantized.data, quantized.scale, id],
+                )?;
+                quantized_count += 1;
+            }
+        }
+        tx.commit()?;
+        if quantized_count > 0 {
+            info!("Quantized {} embeddings as {}", quantized_count, quantization.as_str());
+        }
+        Ok(quantized_count)
+    }
+    
+    /// Text (title, description, data and tags) of the context items a vocabulary is built from
+    pub async fn get_context_item_texts(&self, project_id: Option<&str>) -> Result<Vec<String>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        let table_exists: bool = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'enhanced_context_items'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )? > 0;
+        if !table_exists {
+            return Ok(Vec::new());
+        }
+        
+        let mut stmt = conn.prepare(
             r#"
-            CREATE TABLE IF NOT EXISTS context_embeddings (
-                id TEXT PRIMARY KEY,
-                context_id TEXT NOT NULL,
-                project_id TEXT,
-                embedding_vector TEXT NOT NULL, -- JSON array of floats
-                embedding_model TEXT NOT NULL,
-                embedding_version TEXT NOT NULL,
-                content_hash TEXT NOT NULL,
-                content_type TEXT,
-                content_length INTEGER,
-                tokenization_method TEXT,
-                preprocessing_steps TEXT, -- JSON array
-                quality_score REAL,
-                custom_metadata TEXT, -- JSON object
-                created_at TEXT NOT NULL,
-                updated_at TEXT DEFAULT (datetime('now')),
-                FOREIGN KEY (project_id) REFERENCES projects(id),
-                UNIQUE(context_id, embedding_model, embedding_version)
-            );
-            
-            CREATE INDEX IF NOT EXISTS idx_embeddings_context_id ON context_embeddings(context_id);
-            CREATE INDEX IF NOT EXISTS idx_embeddings_project_id ON context_embeddings(project_id);
-            CREATE INDEX IF NOT EXISTS idx_embeddings_model ON context_embeddings(embedding_model);
-            CREATE INDEX IF NOT EXISTS idx_embeddings_content_hash ON context_embeddings(content_hash);
-            CREATE INDEX IF NOT EXISTS idx_embeddings_created_at ON context_embeddings(created_at);
+            SELECT title, description, data, COALESCE(tags, '')
+            FROM enhanced_context_items
+            WHERE ?1 IS NULL OR project_id = ?1
             "#,
         )?;
         
-        info!("Embedding repository initialized successfully");
+        let texts = stmt
+            .query_map([project_id], |row| {
+                Ok([
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ]
+                .join(" "))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(texts)
+    }
+
+    /// How many context items a vocabulary would be built from now, and how
+    /// many of them were created or updated after `since`
+    pub async fn get_context_item_changes(
+        &self,
+        project_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<(u64, u64), EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+
+        let table_exists: bool = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'enhanced_context_items'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )? > 0;
+        if !table_exists {
+            return Ok((0, 0));
+        }
+
+        let (count, changed) = conn.query_row(
+            r#"
+            SELECT COUNT(*), COALESCE(SUM(julianday(updated_at) > julianday(?2)), 0)
+            FROM enhanced_context_items
+            WHERE ?1 IS NULL OR project_id = ?1
+            "#,
+            params![project_id, since.to_rfc3339()],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+        )?;
+
+        Ok((count as u64, changed as u64))
+    }
+
+    /// Persist a vocabulary, replacing any previous one for the same model and project
+    pub async fn save_vocabulary(&self, embedding_model: &str, vocabulary: &EmbeddingVocabulary) -> Result<(), EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        let project_id = vocabulary.project_id.as_deref().unwrap_or("");
+        
+        let tx = conn.unchecked_transaction()?;
+        
+        tx.execute(
+            "DELETE FROM embedding_vocabulary_terms WHERE embedding_model = ?1 AND project_id = ?2",
+            params![embedding_model, project_id],
+        )?;
+        tx.execute(
+            r#"
+            INSERT OR REPLACE INTO embedding_vocabularies (
+                embedding_model, project_id, document_count, average_document_length, built_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+            params![
+                embedding_model,
+                project_id,
+                vocabulary.document_count as i64,
+                vocabulary.average_document_length,
+                vocabulary.built_at.to_rfc3339(),
+            ],
+        )?;
+        
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO embedding_vocabulary_terms (embedding_model, project_id, term, document_frequency) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (term, frequency) in &vocabulary.document_frequencies {
+                stmt.execute(params![embedding_model, project_id, term, *frequency as i64])?;
+            }
+        }
+        
+        tx.commit()?;
+        info!(
+            "Stored vocabulary of {} terms for model {}",
+            vocabulary.document_frequencies.len(),
+            embedding_model
+        );
         Ok(())
     }
     
+    /// Load a previously stored vocabulary
+    pub async fn load_vocabulary(&self, embedding_model: &str, project_id: Option<&str>) -> Result<Option<EmbeddingVocabulary>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        let scope = project_id.unwrap_or("");
+        
+        let header = conn
+            .query_row(
+                r#"
+                SELECT document_count, average_document_length, built_at
+                FROM embedding_vocabularies
+                WHERE embedding_model = ?1 AND project_id = ?2
+                "#,
+                params![embedding_model, scope],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, f64>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
+            .optional()?;
+        
+        let Some((document_count, average_document_length, built_at)) = header else {
+            return Ok(None);
+        };
+        
+        let mut stmt = conn.prepare(
+            "SELECT term, document_frequency FROM embedding_vocabulary_terms WHERE embedding_model = ?1 AND project_id = ?2",
+        )?;
+        let document_frequencies = stmt
+            .query_map(params![embedding_model, scope], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })?
+            .collect::<Result<std::collections::HashMap<_, _>, _>>()?;
+        
+        let built_at = DateTime::parse_from_rfc3339(&built_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+        
+        Ok(Some(EmbeddingVocabulary {
+            project_id: project_id.map(str::to_string),
+            document_count: document_count as u64,
+            average_document_length: average_document_length as f32,
+            document_frequencies,
+            built_at,
+        }))
+    }
+
+    /// Load every stored vocabulary of a model, the database-wide one included
+    pub async fn load_vocabularies(&self, embedding_model: &str) -> Result<Vec<EmbeddingVocabulary>, EmbeddingRepositoryError> {
+        let scopes: Vec<String> = {
+            let conn = self.connection.lock().await;
+            let mut stmt = conn.prepare("SELECT project_id FROM embedding_vocabularies WHERE embedding_model = ?1")?;
+            let rows = stmt
+                .query_map([embedding_model], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+
+        let mut vocabularies = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            let project_id = (!scope.is_empty()).then_some(scope.as_str());
+            if let Some(vocabulary) = self.load_vocabulary(embedding_model, project_id).await? {
+                vocabularies.push(vocabulary);
+            }
+        }
+        Ok(vocabularies)
+    }
+    
     /// Convert database row to ContextEmbedding
     fn row_to_embedding(&self, row: &Row) -> SqliteResult<ContextEmbedding> {
-        let embedding_vector_json: String = row.get("embedding_vector")?;
-        let embedding_vector: Vec<f32> = serde_json::from_str(&embedding_vector_json)
-            .map_err(|e| rusqlite::Error::InvalidColumnType(
-                0, 
-                "embedding_vector".to_string(), 
-                rusqlite::types::Type::Text
-            ))?;
+        // JSON text vectors are converted by migration 10, but are still readable
+        let embedding_vector = match row.get_ref("embedding_vector")? {
+            ValueRef::Blob(bytes) => vector_from_bytes(bytes),
+            ValueRef::Text(text) => serde_json::from_slice(text).ok(),
+            _ => None,
+        }
+        .ok_or_else(|| rusqlite::Error::InvalidColumnType(
+            2,
+            "embedding_vector".to_string(),
+            rusqlite::types::Type::Blob
+        ))?;
+        
+        let quantization: Option<String> = row.get("quantization")?;
+        let quantized_data: Option<Vec<u8>> = row.get("quantized_vector")?;
+        let quantized_vector = match (quantization.as_deref().and_then(VectorQuantization::parse), quantized_data) {
+            (Some(method), Some(data)) => Some(QuantizedVector {
+                method,
+                dimension: embedding_vector.len(),
+                scale: row.get::<_, Option<f32>>("quantization_scale")?.unwrap_or(1.0),
+                data,
+            }),
+            _ => None,
+        };
         
         let preprocessing_steps_json: String = row.get("preprocessing_steps")?;
         let preprocessing_steps: Vec<String> = serde_json::from_str(&preprocessing_steps_json)
@@ -173,48 +503,59 @@ impl SqliteEmbeddingRepository {
             custom_fields,
         };
         
+        let chunk = match row.get::<_, Option<String>>("chunk_text")? {
+            Some(text) => Some(crate::models::embedding::EmbeddingChunk {
+                index: row.get::<_, i64>("chunk_index")? as usize,
+                start: row.get::<_, Option<i64>>("chunk_start")?.unwrap_or(0) as usize,
+                end: row.get::<_, Option<i64>>("chunk_end")?.unwrap_or(0) as usize,
+                heading: row.get("chunk_heading")?,
+                text,
+            }),
+            None => None,
+        };
+        
         Ok(ContextEmbedding {
             id: row.get("id")?,
             context_id: row.get("context_id")?,
+            project_id: row.get("project_id")?,
             embedding_vector,
             embedding_model: row.get("embedding_model")?,
             embedding_version: row.get("embedding_version")?,
             content_hash: row.get("content_hash")?,
             metadata,
             created_at,
+            quantized_vector,
+            chunk,
         })
     }
     
-    /// Calculate cosine similarity between two vectors
-    fn cosine_similarity(&self, vec1: &[f32], vec2: &[f32]) -> f32 {
-        if vec1.len() != vec2.len() {
-            return 0.0;
-        }
-
-        let dot_product: f32 = vec1.iter().zip(vec2.iter()).map(|(a, b)| a * b).sum();
-        let norm_a: f32 = vec1.iter().map(|x| x * x).sum::<f32>().sqrt();
-        let norm_b: f32 = vec2.iter().map(|x| x * x).sum::<f32>().sqrt();
-
-        if norm_a == 0.0 || norm_b == 0.0 {
-            0.0
-        } else {
-            dot_product / (norm_a * norm_b)
+    /// The project id when it names a stored project, so the foreign key holds
+    /// for contexts that were never saved under a project
+    fn known_project_id(conn: &Connection, project_id: Option<&str>) -> Result<Option<String>, EmbeddingRepositoryError> {
+        let Some(project_id) = project_id else {
+            return Ok(None);
+        };
+        let projects_exist: bool = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'projects'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )? > 0;
+        if !projects_exist {
+            return Ok(None);
         }
-    }
-}
-
-#[async_trait]
-impl EmbeddingRepository for SqliteEmbeddingRepository {
-    async fn store_embedding(&self, embedding: &ContextEmbedding) -> Result<(), EmbeddingRepositoryError> {
-        let conn = self.connection.lock().await;
         
-        let embedding_vector_json = serde_json::to_string(&embedding.embedding_vector)?;
+        Ok(conn
+            .query_row("SELECT id FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+            .optional()?)
+    }
+    
+    /// Write one embedding row; shared by single, batch and replace operations
+    fn insert_embedding(&self, conn: &Connection, embedding: &ContextEmbedding) -> Result<(), EmbeddingRepositoryError> {
+        let quantized = self.quantized_form(embedding);
         let preprocessing_steps_json = serde_json::to_string(&embedding.metadata.preprocessing_steps)?;
         let custom_metadata_json = serde_json::to_string(&embedding.metadata.custom_fields)?;
-        
-        // Extract project_id from context (this would need to be passed or looked up)
-        // For now, we'll leave it as None and handle it in the service layer
-        let project_id: Option<String> = None;
+        let chunk = embedding.chunk.as_ref();
+        let project_id = Self::known_project_id(conn, embedding.project_id.as_deref())?;
         
         conn.execute(
             r#"
@@ -222,14 +563,16 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                 id, context_id, project_id, embedding_vector, embedding_model, 
                 embedding_version, content_hash, content_type, content_length,
                 tokenization_method, preprocessing_steps, quality_score, 
-                custom_metadata, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                custom_metadata, created_at, vector_dimension, quantization,
+                quantized_vector, quantization_scale, chunk_index, chunk_start,
+                chunk_end, chunk_heading, chunk_text
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)
             "#,
             params![
                 embedding.id,
                 embedding.context_id,
                 project_id,
-                embedding_vector_json,
+                embedding.vector_bytes(),
                 embedding.embedding_model,
                 embedding.embedding_version,
                 embedding.content_hash,
@@ -240,9 +583,113 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                 embedding.metadata.quality_score,
                 custom_metadata_json,
                 embedding.created_at.to_rfc3339(),
+                embedding.embedding_vector.len() as i64,
+                quantized.as_ref().map(|q| q.method.as_str()),
+                quantized.as_ref().map(|q| q.data.as_slice()),
+                quantized.as_ref().map(|q| q.scale),
+                embedding.chunk_index() as i64,
+                chunk.map(|c| c.start as i64),
+                chunk.map(|c| c.end as i64),
+                chunk.and_then(|c| c.heading.as_deref()),
+                chunk.map(|c| c.text.as_str()),
             ],
         )?;
         
+        Ok(())
+    }
+    
+    /// Quantized copy to store: recomputed with the configured method, otherwise the embedding's own
+    fn quantized_form(&self, embedding: &ContextEmbedding) -> Option<QuantizedVector> {
+        match self.quantization {
+            VectorQuantization::None => embedding.quantized_vector.clone(),
+            method => QuantizedVector::quantize(&embedding.embedding_vector, method),
+        }
+    }
+    
+    /// Rank rows by their quantized vectors and keep the best few for exact scoring.
+    /// `None` means no pre-filtering applies and every row must be scored.
+    fn prefilter_candidates(
+        &self,
+        conn: &Connection,
+        query_embedding: &[f32],
+        max_results: usize,
+        project_id: Option<&str>,
+        embedding_model: Option<&str>,
+    ) -> Result<Option<Vec<String>>, EmbeddingRepositoryError> {
+        let Some(query_quantized) = QuantizedVector::quantize(query_embedding, self.quantization) else {
+            return Ok(None);
+        };
+        
+        let mut stmt = conn.prepare(
+            "SELECT id, quantization, quantized_vector, quantization_scale FROM context_embeddings
+             WHERE (?1 IS NULL OR project_id = ?1) AND (?2 IS NULL OR embedding_model = ?2)",
+        )?;
+        let rows = stmt
+            .query_map(params![project_id, embedding_model], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?,
+                    row.get::<_, Option<f32>>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        let keep = (max_results * PREFILTER_CANDIDATE_MULTIPLIER).max(MIN_PREFILTER_CANDIDATES);
+        if rows.len() <= keep {
+            return Ok(None);
+        }
+        
+        let mut candidates = Vec::new();
+        let mut scored = Vec::new();
+        for (id, method, data, scale) in rows {
+            match (method.as_deref().and_then(VectorQuantization::parse), data) {
+                (Some(method), Some(data)) if method == self.quantization => {
+                    let quantized = QuantizedVector {
+                        method,
+                        dimension: query_quantized.dimension,
+                        scale: scale.unwrap_or(1.0),
+                        data,
+                    };
+                    scored.push((query_quantized.approximate_similarity(&quantized), id));
+                }
+                // Rows without a matching quantized copy are always scored exactly
+                _ => candidates.push(id),
+            }
+        }
+        
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.extend(scored.into_iter().take(keep).map(|(_, id)| id));
+        
+        debug!("Quantized pre-filter kept {} candidates", candidates.len());
+        Ok(Some(candidates))
+    }
+    
+    /// Calculate cosine similarity between two vectors
+    fn cosine_similarity(&self, vec1: &[f32], vec2: &[f32]) -> f32 {
+        if vec1.len() != vec2.len() {
+            return 0.0;
+        }
+
+        let dot_product: f32 = vec1.iter().zip(vec2.iter()).map(|(a, b)| a * b).sum();
+        let norm_a: f32 = vec1.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let norm_b: f32 = vec2.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+        if norm_a == 0.0 || norm_b == 0.0 {
+            0.0
+        } else {
+            dot_product / (norm_a * norm_b)
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingRepository for SqliteEmbeddingRepository {
+    async fn store_embedding(&self, embedding: &ContextEmbedding) -> Result<(), EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        self.insert_embedding(&conn, embedding)?;
+        
         debug!("Stored embedding for context: {}", embedding.context_id);
         Ok(())
     }
@@ -253,38 +700,7 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         let tx = conn.unchecked_transaction()?;
         
         for embedding in embeddings {
-            let embedding_vector_json = serde_json::to_string(&embedding.embedding_vector)?;
-            let preprocessing_steps_json = serde_json::to_string(&embedding.metadata.preprocessing_steps)?;
-            let custom_metadata_json = serde_json::to_string(&embedding.metadata.custom_fields)?;
-            
-            let project_id: Option<String> = None; // Would be determined by service layer
-            
-            tx.execute(
-                r#"
-                INSERT OR REPLACE INTO context_embeddings (
-                    id, context_id, project_id, embedding_vector, embedding_model, 
-                    embedding_version, content_hash, content_type, content_length,
-                    tokenization_method, preprocessing_steps, quality_score, 
-                    custom_metadata, created_at
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
-                "#,
-                params![
-                    embedding.id,
-                    embedding.context_id,
-                    project_id,
-                    embedding_vector_json,
-                    embedding.embedding_model,
-                    embedding.embedding_version,
-                    embedding.content_hash,
-                    embedding.metadata.content_type,
-                    embedding.metadata.content_length as i64,
-                    embedding.metadata.tokenization_method.as_str(),
-                    preprocessing_steps_json,
-                    embedding.metadata.quality_score,
-                    custom_metadata_json,
-                    embedding.created_at.to_rfc3339(),
-                ],
-            )?;
+            self.insert_embedding(&tx, embedding)?;
         }
         
         tx.commit()?;
@@ -292,20 +708,37 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         Ok(())
     }
     
+    async fn replace_context_embeddings(&self, embeddings: &[ContextEmbedding]) -> Result<(), EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        let tx = conn.unchecked_transaction()?;
+        
+        let mut replaced = std::collections::HashSet::new();
+        for embedding in embeddings {
+            if replaced.insert((embedding.context_id.as_str(), embedding.embedding_model.as_str())) {
+                tx.execute(
+                    "DELETE FROM context_embeddings WHERE context_id = ?1 AND embedding_model = ?2",
+                    params![embedding.context_id, embedding.embedding_model],
+                )?;
+            }
+            self.insert_embedding(&tx, embedding)?;
+        }
+        
+        tx.commit()?;
+        debug!("Replaced embeddings for {} contexts", replaced.len());
+        Ok(())
+    }
+    
     async fn get_embedding_by_context_id(&self, context_id: &str) -> Result<Option<ContextEmbedding>, EmbeddingRepositoryError> {
         let conn = self.connection.lock().await;
         
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT id, context_id, embedding_vector, embedding_model, embedding_version,
-                   content_hash, content_type, content_length, tokenization_method,
-                   preprocessing_steps, quality_score, custom_metadata, created_at
-            FROM context_embeddings 
-            WHERE context_id = ?1
-            ORDER BY created_at DESC
-            LIMIT 1
-            "#,
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM context_embeddings
+             WHERE context_id = ?1
+             ORDER BY chunk_index, created_at DESC
+             LIMIT 1",
+            EMBEDDING_COLUMNS
+        ))?;
         
         let embedding_iter = stmt.query_map([context_id], |row| {
             self.row_to_embedding(row)
@@ -318,19 +751,32 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         Ok(None)
     }
     
+    async fn get_embeddings_by_context_id(&self, context_id: &str) -> Result<Vec<ContextEmbedding>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM context_embeddings
+             WHERE context_id = ?1
+             ORDER BY embedding_model, chunk_index",
+            EMBEDDING_COLUMNS
+        ))?;
+        
+        let embeddings = stmt
+            .query_map([context_id], |row| self.row_to_embedding(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(embeddings)
+    }
+    
     async fn get_embeddings_by_project(&self, project_id: &str) -> Result<Vec<ContextEmbedding>, EmbeddingRepositoryError> {
         let conn = self.connection.lock().await;
         
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT id, context_id, embedding_vector, embedding_model, embedding_version,
-                   content_hash, content_type, content_length, tokenization_method,
-                   preprocessing_steps, quality_score, custom_metadata, created_at
-            FROM context_embeddings 
-            WHERE project_id = ?1
-            ORDER BY created_at DESC
-            "#,
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM context_embeddings
+             WHERE project_id = ?1
+             ORDER BY created_at DESC",
+            EMBEDDING_COLUMNS
+        ))?;
         
         let embedding_iter = stmt.query_map([project_id], |row| {
             self.row_to_embedding(row)
@@ -344,40 +790,52 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         Ok(embeddings)
     }
     
-    async fn find_similar_embeddings(&self, query: &VectorSearchQuery, project_id: Option<&str>) -> Result<Vec<VectorSearchResult>, EmbeddingRepositoryError> {
+    async fn get_all_embeddings(&self, embedding_model: Option<&str>) -> Result<Vec<ContextEmbedding>, EmbeddingRepositoryError> {
         let conn = self.connection.lock().await;
         
-        // Build query with optional project filter
-        let sql = if project_id.is_some() {
-            r#"
-            SELECT id, context_id, embedding_vector, embedding_model, embedding_version,
-                   content_hash, content_type, content_length, tokenization_method,
-                   preprocessing_steps, quality_score, custom_metadata, created_at
-            FROM context_embeddings 
-            WHERE project_id = ?1
-            "#
-        } else {
-            r#"
-            SELECT id, context_id, embedding_vector, embedding_model, embedding_version,
-                   content_hash, content_type, content_length, tokenization_method,
-                   preprocessing_steps, quality_score, custom_metadata, created_at
-            FROM context_embeddings
-            "#
-        };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM context_embeddings
+             WHERE ?1 IS NULL OR embedding_model = ?1
+             ORDER BY created_at",
+            EMBEDDING_COLUMNS
+        ))?;
         
-        let mut stmt = conn.prepare(sql)?;
+        let embeddings = stmt
+            .query_map([embedding_model], |row| self.row_to_embedding(row))?
+            .collect::<Result<Vec<_>, _>>()?;
         
-        let embeddings: Result<Vec<_>, _> = if let Some(pid) = project_id {
-            stmt.query_map([pid], |row| self.row_to_embedding(row))?
-                .collect()
-        } else {
-            stmt.query_map([], |row| self.row_to_embedding(row))?
-                .collect()
-        };
+        Ok(embeddings)
+    }
+    
+    async fn get_embedding_write_times(&self, embedding_model: &str) -> Result<Vec<(String, DateTime<Utc>)>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
         
-        let embeddings = embeddings?;
+        // updated_at is reset to datetime('now') whenever a row is replaced
+        let mut stmt = conn.prepare(
+            "SELECT context_id, MAX(COALESCE(datetime(updated_at), datetime(created_at)))
+             FROM context_embeddings
+             WHERE embedding_model = ?1
+             GROUP BY context_id",
+        )?;
+        let rows = stmt
+            .query_map([embedding_model], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
         
-        let mut results = Vec::new();
+        Ok(rows
+            .into_iter()
+            .map(|(context_id, written)| {
+                let written = written
+                    .and_then(|w| chrono::NaiveDateTime::parse_from_str(&w, "%Y-%m-%d %H:%M:%S").ok())
+                    .map(|w| w.and_utc())
+                    // Unparseable times count as just written, so the context is re-read
+                    .unwrap_or_else(Utc::now);
+                (context_id, written)
+            })
+            .collect())
+    }
+    
+    async fn find_similar_embeddings(&self, query: &VectorSearchQuery, project_id: Option<&str>) -> Result<Vec<VectorSearchResult>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
         
         // Get query embedding (this would be provided by the service layer)
         let query_embedding = query.query_embedding.as_ref()
@@ -385,6 +843,23 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                 message: "Query embedding not provided".to_string()
             })?;
         
+        let embedding_model = query.filters.embedding_model.as_deref();
+        let candidate_ids = self.prefilter_candidates(&conn, query_embedding, query.max_results, project_id, embedding_model)?;
+        
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM context_embeddings
+             WHERE (?1 IS NULL OR project_id = ?1)
+               AND (?2 IS NULL OR id IN (SELECT value FROM json_each(?2)))
+               AND (?3 IS NULL OR embedding_model = ?3)",
+            EMBEDDING_COLUMNS
+        ))?;
+        let candidate_ids = candidate_ids.map(|ids| serde_json::to_string(&ids)).transpose()?;
+        let embeddings = stmt
+            .query_map(params![project_id, candidate_ids, embedding_model], |row| self.row_to_embedding(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        let mut results = Vec::new();
+        
         for embedding in embeddings {
             
             // Calculate similarity
@@ -393,6 +868,7 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
             if similarity >= query.similarity_threshold {
                 let distance = 1.0 - similarity;
                 
+                let chunk = crate::models::embedding::ChunkMatch::from_embedding(&embedding);
                 results.push(VectorSearchResult {
                     context_id: embedding.context_id,
                     similarity_score: similarity,
@@ -400,12 +876,16 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                     rank: 0, // Will be set after sorting
                     metadata: crate::models::embedding::ResultMetadata {
                         content_type: embedding.metadata.content_type,
-                        content_preview: "Preview not available".to_string(),
+                        content_preview: embedding
+                            .chunk
+                            .map(|chunk| chunk.text)
+                            .unwrap_or_else(|| "Preview not available".to_string()),
                         match_explanation: format!("Cosine similarity: {:.3}", similarity),
                         quality_indicators: vec![
                             format!("Quality: {:.2}", embedding.metadata.quality_score),
                             format!("Model: {}", embedding.embedding_model),
                         ],
+                        chunk,
                     },
                 });
             }
@@ -462,88 +942,89 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
     async fn get_embedding_stats(&self, project_id: Option<&str>) -> Result<EmbeddingStats, EmbeddingRepositoryError> {
         let conn = self.connection.lock().await;
         
-        let (where_clause, params): (&str, Vec<&str>) = if let Some(pid) = project_id {
-            ("WHERE project_id = ?1", vec![pid])
-        } else {
-            ("", vec![])
-        };
+        let where_clause = "WHERE (?1 IS NULL OR project_id = ?1)";
         
-        // Get total count
-        let total_embeddings: u64 = if let Some(pid) = project_id {
-            conn.query_row(
-                "SELECT COUNT(*) FROM context_embeddings WHERE project_id = ?1",
-                [pid],
-                |row| row.get::<_, i64>(0)
-            )? as u64
-        } else {
-            conn.query_row(
-                "SELECT COUNT(*) FROM context_embeddings",
-                [],
-                |row| row.get::<_, i64>(0)
-            )? as u64
-        };
+        // Counts, storage sizes, dimension and date range in one pass
+        let (
+            total_embeddings,
+            indexed_contexts,
+            legacy_json_embeddings,
+            vector_storage_bytes,
+            quantized_storage_bytes,
+            average_vector_dimension,
+            oldest_str,
+            newest_str,
+        ) = conn.query_row(
+                &format!(
+                    "SELECT COUNT(*),
+                            COUNT(DISTINCT context_id),
+                            COALESCE(SUM(typeof(embedding_vector) = 'text'), 0),
+                            COALESCE(SUM(CASE WHEN typeof(embedding_vector) = 'blob' THEN length(embedding_vector) ELSE 0 END), 0),
+                            COALESCE(SUM(length(quantized_vector)), 0),
+                            AVG(vector_dimension),
+                            MIN(created_at), MAX(created_at)
+                     FROM context_embeddings {}",
+                    where_clause
+                ),
+                [project_id],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)? as u64,
+                        row.get::<_, i64>(1)? as u64,
+                        row.get::<_, i64>(2)? as u64,
+                        row.get::<_, i64>(3)? as u64,
+                        row.get::<_, i64>(4)? as u64,
+                        row.get::<_, Option<f64>>(5)?.unwrap_or(0.0),
+                        row.get::<_, Option<String>>(6)?,
+                        row.get::<_, Option<String>>(7)?,
+                    ))
+                },
+            )?;
         
         // Get embeddings by model
         let mut embeddings_by_model = std::collections::HashMap::new();
-        if let Some(pid) = project_id {
-            let mut stmt = conn.prepare(
-                "SELECT embedding_model, COUNT(*) FROM context_embeddings WHERE project_id = ?1 GROUP BY embedding_model"
-            )?;
-            let model_iter = stmt.query_map([pid], |row| {
-                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
-            })?;
-            
-            for result in model_iter {
-                let (model, count) = result?;
-                embeddings_by_model.insert(model, count);
-            }
-        } else {
-            let mut stmt = conn.prepare(
-                "SELECT embedding_model, COUNT(*) FROM context_embeddings GROUP BY embedding_model"
-            )?;
-            let model_iter = stmt.query_map([], |row| {
-                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
-            })?;
-            
-            for result in model_iter {
-                let (model, count) = result?;
-                embeddings_by_model.insert(model, count);
-            }
+        let mut stmt = conn.prepare(&format!(
+            "SELECT embedding_model, COUNT(*) FROM context_embeddings {} GROUP BY embedding_model",
+            where_clause
+        ))?;
+        let model_iter = stmt.query_map([project_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+        })?;
+        for result in model_iter {
+            let (model, count) = result?;
+            embeddings_by_model.insert(model, count);
         }
         
-        // Get date range
-        let (oldest_str, newest_str): (Option<String>, Option<String>) = if let Some(pid) = project_id {
-            conn.query_row(
-                "SELECT MIN(created_at), MAX(created_at) FROM context_embeddings WHERE project_id = ?1",
-                [pid],
-                |row| {
-                    Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?))
-                }
-            )?
-        } else {
-            conn.query_row(
-                "SELECT MIN(created_at), MAX(created_at) FROM context_embeddings",
-                [],
-                |row| {
-                    Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?))
-                }
-            )?
-        };
+        // Get quantized embeddings by method
+        let mut quantized_embeddings = std::collections::HashMap::new();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT quantization, COUNT(*) FROM context_embeddings {} AND quantized_vector IS NOT NULL GROUP BY quantization",
+            where_clause
+        ))?;
+        let quantization_iter = stmt.query_map([project_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+        })?;
+        for result in quantization_iter {
+            let (method, count) = result?;
+            quantized_embeddings.insert(method, count);
+        }
         
         let oldest_embedding = oldest_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
             .map(|dt| dt.with_timezone(&Utc));
         let newest_embedding = newest_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
             .map(|dt| dt.with_timezone(&Utc));
         
-        // Calculate average dimension (this is a rough estimate)
-        let average_vector_dimension = 384.0; // Default for most sentence transformers
-        
         Ok(EmbeddingStats {
             total_embeddings,
+            indexed_contexts,
             embeddings_by_model,
             average_vector_dimension,
             oldest_embedding,
             newest_embedding,
+            quantized_embeddings,
+            legacy_json_embeddings,
+            vector_storage_bytes,
+            quantized_storage_bytes,
         })
     }
     
@@ -558,4 +1039,83 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         
         Ok(count > 0)
     }
+    
+    async fn get_active_embedding_config(&self) -> Result<Option<EmbeddingConfig>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        let config: Option<String> = conn
+            .query_row(
+                "SELECT config FROM embedding_model_state WHERE role = 'active'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        
+        Ok(config.map(|config| serde_json::from_str(&config)).transpose()?)
+    }
+    
+    async fn set_active_embedding_config(&self, config: &EmbeddingConfig) -> Result<(), EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        conn.execute(
+            "INSERT OR REPLACE INTO embedding_model_state (role, embedding_model, config, updated_at)
+             VALUES ('active', ?1, ?2, ?3)",
+            params![config.model_name, serde_json::to_string(config)?, Utc::now().to_rfc3339()],
+        )?;
+        
+        info!("Active embedding model set to {}", config.model_name);
+        Ok(())
+    }
+    
+    async fn get_model_coverage(&self, source_model: &str, target_model: &str) -> Result<ModelCoverage, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        let (source_contexts, covered_contexts): (i64, i64) = conn.query_row(
+            "SELECT COUNT(DISTINCT s.context_id),
+                    COUNT(DISTINCT CASE WHEN EXISTS (
+                        SELECT 1 FROM context_embeddings t
+                        WHERE t.context_id = s.context_id AND t.embedding_model = ?2
+                    ) THEN s.context_id END)
+             FROM context_embeddings s
+             WHERE s.embedding_model = ?1",
+            params![source_model, target_model],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        
+        Ok(ModelCoverage {
+            source_contexts: source_contexts as usize,
+            covered_contexts: covered_contexts as usize,
+        })
+    }
+    
+    async fn get_contexts_missing_model(&self, source_model: &str, target_model: &str) -> Result<Vec<String>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT s.context_id FROM context_embeddings s
+             WHERE s.embedding_model = ?1
+               AND NOT EXISTS (
+                   SELECT 1 FROM context_embeddings t
+                   WHERE t.context_id = s.context_id AND t.embedding_model = ?2
+               )
+             ORDER BY s.context_id",
+        )?;
+        let context_ids = stmt
+            .query_map(params![source_model, target_model], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        
+        Ok(context_ids)
+    }
+    
+    async fn delete_embeddings_by_model(&self, embedding_model: &str) -> Result<usize, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+        
+        let rows_affected = conn.execute(
+            "DELETE FROM context_embeddings WHERE embedding_model = ?1",
+            [embedding_model],
+        )?;
+        
+        info!("Deleted {} embeddings of model {}", rows_affected, embedding_model);
+        Ok(rows_affected)
+    }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::models::enhanced_context::{EnhancedContextItem, ContextType, ContextId, ProjectId};
+use crate::models::enhanced_context::{EnhancedContextItem, ContextType, ContextId, ProjectId, KeywordSearchResult};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -17,9 +17,12 @@ pub trait EnhancedContextRepository: Send + Sync {
     /// Find context items by type
     async fn find_contexts_by_type(&self, project_id: &str, context_type: ContextType) -> Result<Vec<EnhancedContextItem>, McpError>;
     
-    /// Find context items by keywords (simple text search)
+    /// Find context items matching any of the keywords, most relevant first
     async fn find_contexts_by_keywords(&self, project_id: &str, keywords: &[String]) -> Result<Vec<EnhancedContextItem>, McpError>;
     
+    /// Full-text search over title, description, data and tags, ranked by BM25
+    async fn search_contexts(&self, project_id: Option<&str>, query: &str, limit: usize) -> Result<Vec<KeywordSearchResult>, McpError>;
+    
     /// Update an existing context item
     async fn update_context(&self, context: &EnhancedContextItem) -> Result<EnhancedContextItem, McpError>;
     
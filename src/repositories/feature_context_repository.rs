@@ -1,4 +1,5 @@
 use crate::models::context::FeatureContext;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -9,6 +10,12 @@ pub trait FeatureContextRepository: Send + Sync {
     async fn update(&self, feature_context: &FeatureContext) -> Result<FeatureContext, McpError>;
     async fn delete(&self, id: &str) -> Result<bool, McpError>;
     async fn list_by_project(&self, project_id: &str) -> Result<Vec<FeatureContext>, McpError>;
+    /// One page of a project's feature contexts, sortable by `created_at` or `feature_name`
+    async fn list_page_by_project(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<FeatureContext>, McpError>;
     async fn get_by_feature_name(
         &self,
         project_id: &str,
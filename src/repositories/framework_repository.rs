@@ -1,4 +1,5 @@
 use crate::models::framework::FrameworkComponent;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -10,6 +11,14 @@ pub trait FrameworkRepository: Send + Sync {
         &self,
         project_id: &str,
     ) -> Result<Vec<FrameworkComponent>, McpError>;
+    /// One page of a project's components, optionally in one architecture layer.
+    /// Sortable by `created_at`, `updated_at`, `component_name`, `component_type` or `architecture_layer`.
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        architecture_layer: Option<&str>,
+        query: &ListQuery,
+    ) -> Result<Page<FrameworkComponent>, McpError>;
     async fn find_by_id(&self, id: &str) -> Result<Option<FrameworkComponent>, McpError>;
     async fn update(&self, component: &FrameworkComponent) -> Result<FrameworkComponent, McpError>;
     async fn delete(&self, id: &str) -> Result<bool, McpError>;
@@ -1,4 +1,5 @@
 use crate::models::context::PerformanceRequirement;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -13,6 +14,12 @@ pub trait PerformanceRequirementRepository: Send + Sync {
         &self,
         project_id: &str,
     ) -> Result<Vec<PerformanceRequirement>, McpError>;
+    /// One page of a project's requirements, sortable by `created_at`, `component_area` or `requirement_type`
+    async fn find_page_by_project_id(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<PerformanceRequirement>, McpError>;
     async fn find_by_id(&self, id: &str) -> Result<Option<PerformanceRequirement>, McpError>;
     async fn update(
         &self,
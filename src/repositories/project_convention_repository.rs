@@ -1,4 +1,5 @@
 use crate::models::context::ProjectConvention;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -9,6 +10,12 @@ pub trait ProjectConventionRepository: Send + Sync {
     async fn update(&self, convention: &ProjectConvention) -> Result<ProjectConvention, McpError>;
     async fn delete(&self, id: &str) -> Result<bool, McpError>;
     async fn list_by_project(&self, project_id: &str) -> Result<Vec<ProjectConvention>, McpError>;
+    /// One page of a project's conventions, sortable by `created_at` or `convention_type`
+    async fn list_page_by_project(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<ProjectConvention>, McpError>;
     async fn list_by_convention_type(
         &self,
         project_id: &str,
@@ -1,4 +1,5 @@
 use crate::models::context::Project;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -8,6 +9,8 @@ pub trait ProjectRepository: Send + Sync {
     async fn create(&self, project: &Project) -> Result<Project, McpError>;
     async fn find_by_id(&self, id: &str) -> Result<Option<Project>, McpError>;
     async fn find_all(&self) -> Result<Vec<Project>, McpError>;
+    /// One page of all projects, sortable by `created_at`, `updated_at` or `name`
+    async fn find_page(&self, query: &ListQuery) -> Result<Page<Project>, McpError>;
     async fn update(&self, project: &Project) -> Result<Project, McpError>;
     async fn delete(&self, id: &str) -> Result<bool, McpError>;
 }
@@ -1,4 +1,5 @@
 use crate::models::context::SecurityPolicy;
+use crate::models::pagination::{ListQuery, Page};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -9,6 +10,12 @@ pub trait SecurityPolicyRepository: Send + Sync {
     async fn update(&self, security_policy: &SecurityPolicy) -> Result<SecurityPolicy, McpError>;
     async fn delete(&self, id: &str) -> Result<bool, McpError>;
     async fn list_by_project(&self, project_id: &str) -> Result<Vec<SecurityPolicy>, McpError>;
+    /// One page of a project's policies, sortable by `created_at`, `policy_name` or `policy_area`
+    async fn list_page_by_project(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<SecurityPolicy>, McpError>;
     async fn list_by_policy_area(
         &self,
         project_id: &str,
@@ -1,3 +1,4 @@
+use crate::models::pagination::{ListQuery, Page};
 use crate::models::specification::{ProjectSpecification, Requirement, Task};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
@@ -9,6 +10,9 @@ pub trait SpecificationRepository: Send + Sync {
     async fn find_specification_by_id(&self, id: &str) -> Result<Option<ProjectSpecification>, McpError>;
     async fn find_specifications_by_project(&self, project_id: &str) -> Result<Vec<ProjectSpecification>, McpError>;
     async fn find_specifications_by_type(&self, project_id: &str, spec_type: &str) -> Result<Vec<ProjectSpecification>, McpError>;
+    /// One page of a project's specifications, optionally of one type.
+    /// Sortable by `created_at`, `updated_at`, `title`, `spec_type` or `status`.
+    async fn find_specifications_page(&self, project_id: &str, spec_type: Option<&str>, query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError>;
     async fn update_specification(&self, spec: &ProjectSpecification) -> Result<ProjectSpecification, McpError>;
     async fn delete_specification(&self, id: &str) -> Result<bool, McpError>;
     
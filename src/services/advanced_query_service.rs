@@ -64,6 +64,12 @@ pub struct AdvancedSearchFilters {
     pub validation_status: Option<Vec<String>>,
     pub cross_project: bool,
     pub include_archived: bool,
+    /// Priorities to match, e.g. from `priority:>=high`
+    #[serde(default)]
+    pub priorities: Option<Vec<String>>,
+    /// Values that rule a result out, e.g. from `-tag:legacy` or `-deprecated`
+    #[serde(default)]
+    pub excluded: SearchExclusions,
 }
 
 impl Default for AdvancedSearchFilters {
@@ -77,10 +83,127 @@ impl Default for AdvancedSearchFilters {
             validation_status: None,
             cross_project: false,
             include_archived: false,
+            priorities: None,
+            excluded: SearchExclusions::default(),
         }
     }
 }
 
+impl AdvancedSearchFilters {
+    /// Whether a context item passes every filter; `usage_frequency` and
+    /// `relationship_types` need search statistics and are not checked here
+    pub fn matches(&self, item: &EnhancedContextItem) -> bool {
+        self.rejection(item).is_none()
+    }
+
+    /// The first filter a context item fails, if any
+    pub fn rejection(&self, item: &EnhancedContextItem) -> Option<FilterRejection> {
+        let any_of = |values: &Option<Vec<String>>, value: &str| {
+            values.as_ref().is_none_or(|values| values.iter().any(|v| v.eq_ignore_ascii_case(value)))
+        };
+        let base = &self.base_filters;
+
+        if !any_of(&base.project_ids, &item.project_id) {
+            return Some(FilterRejection::Project);
+        }
+        if !any_of(&base.content_types, item.content.content_type.as_str()) {
+            return Some(FilterRejection::ContentType);
+        }
+        if !any_of(&self.priorities, item.metadata.priority.as_str())
+            || !any_of(&self.validation_status, item.metadata.validation_status.as_str())
+            || !any_of(&self.source_types, item.metadata.source.as_str())
+        {
+            return Some(FilterRejection::Metadata);
+        }
+        if let Some(tags) = &base.tags {
+            if !tags.iter().any(|tag| has_tag(item, tag)) {
+                return Some(FilterRejection::Metadata);
+            }
+        }
+        if let Some(range) = &base.date_range {
+            if item.created_at < range.start || item.created_at > range.end {
+                return Some(FilterRejection::Date);
+            }
+        }
+        if let Some(range) = &self.quality_range {
+            let score = item.quality_score as f32;
+            if score < range.min_score || score > range.max_score {
+                return Some(FilterRejection::Quality);
+            }
+        }
+        if let Some(threshold) = base.quality_threshold {
+            if (item.quality_score as f32) < threshold {
+                return Some(FilterRejection::Quality);
+            }
+        }
+
+        self.excluded.matches(item).then_some(FilterRejection::Excluded)
+    }
+}
+
+/// Why a context item failed `AdvancedSearchFilters`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterRejection {
+    Project,
+    ContentType,
+    /// Priority, validation status, source or tags
+    Metadata,
+    Date,
+    Quality,
+    /// Ruled out by a negated clause such as `-tag:legacy`
+    Excluded,
+}
+
+/// Values that disqualify a result; each list matches if any value applies
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchExclusions {
+    pub project_ids: Vec<String>,
+    pub content_types: Vec<String>,
+    pub tags: Vec<String>,
+    pub priorities: Vec<String>,
+    pub validation_status: Vec<String>,
+    pub source_types: Vec<String>,
+    /// Words or phrases that must not appear in the title, description or data
+    pub terms: Vec<String>,
+}
+
+impl SearchExclusions {
+    pub fn is_empty(&self) -> bool {
+        self.project_ids.is_empty()
+            && self.content_types.is_empty()
+            && self.tags.is_empty()
+            && self.priorities.is_empty()
+            && self.validation_status.is_empty()
+            && self.source_types.is_empty()
+            && self.terms.is_empty()
+    }
+
+    /// Whether any exclusion applies to the item
+    pub fn matches(&self, item: &EnhancedContextItem) -> bool {
+        let contains = |values: &[String], value: &str| values.iter().any(|v| v.eq_ignore_ascii_case(value));
+
+        contains(&self.project_ids, &item.project_id)
+            || contains(&self.content_types, item.content.content_type.as_str())
+            || contains(&self.priorities, item.metadata.priority.as_str())
+            || contains(&self.validation_status, item.metadata.validation_status.as_str())
+            || contains(&self.source_types, item.metadata.source.as_str())
+            || self.tags.iter().any(|tag| has_tag(item, tag))
+            || self.terms.iter().any(|term| contains_text(item, term))
+    }
+}
+
+fn has_tag(item: &EnhancedContextItem, tag: &str) -> bool {
+    item.metadata.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
+/// Case-insensitive match against the searchable text of an item
+pub(crate) fn contains_text(item: &EnhancedContextItem, text: &str) -> bool {
+    let needle = text.to_lowercase();
+    item.content.title.to_lowercase().contains(&needle)
+        || item.content.description.to_lowercase().contains(&needle)
+        || item.content.data.to_string().to_lowercase().contains(&needle)
+}
+
 /// Quality score range filter
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualityRange {
@@ -259,6 +382,13 @@ pub trait AdvancedQueryService: Send + Sync {
         filters: &AdvancedSearchFilters,
     ) -> Result<(Vec<String>, FilterStatistics), AdvancedQueryError>;
     
+    /// Drop the context items that fail the filters, counting why they were dropped
+    async fn filter_context_items(
+        &self,
+        items: Vec<EnhancedContextItem>,
+        filters: &AdvancedSearchFilters,
+    ) -> Result<(Vec<EnhancedContextItem>, FilterStatistics), AdvancedQueryError>;
+    
     /// Get filter suggestions based on current query and results
     async fn suggest_filters(
         &self,
@@ -473,6 +603,38 @@ impl AdvancedQueryService for AdvancedQueryServiceImpl {
         Ok((results, stats))
     }
     
+    async fn filter_context_items(
+        &self,
+        items: Vec<EnhancedContextItem>,
+        filters: &AdvancedSearchFilters,
+    ) -> Result<(Vec<EnhancedContextItem>, FilterStatistics), AdvancedQueryError> {
+        self.validate_filters(filters)?;
+        debug!("Filtering {} context items", items.len());
+        
+        let mut stats = FilterStatistics {
+            total_candidates: items.len(),
+            filtered_by_quality: 0,
+            filtered_by_date: 0,
+            filtered_by_type: 0,
+            filtered_by_project: 0,
+            final_results: 0,
+        };
+        let mut kept = Vec::with_capacity(items.len());
+        for item in items {
+            match filters.rejection(&item) {
+                None => kept.push(item),
+                Some(FilterRejection::Quality) => stats.filtered_by_quality += 1,
+                Some(FilterRejection::Date) => stats.filtered_by_date += 1,
+                Some(FilterRejection::ContentType) => stats.filtered_by_type += 1,
+                Some(FilterRejection::Project) => stats.filtered_by_project += 1,
+                Some(FilterRejection::Metadata | FilterRejection::Excluded) => {}
+            }
+        }
+        stats.final_results = kept.len();
+        
+        Ok((kept, stats))
+    }
+    
     async fn suggest_filters(
         &self,
         _query: &str,
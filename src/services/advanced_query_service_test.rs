@@ -5,9 +5,9 @@ use crate::services::{
 };
 use crate::models::embedding::{VectorSearchQuery, SearchFilters, VectorSearchResult, ResultMetadata};
 use crate::models::enhanced_context::{EnhancedContextItem, ContextType};
-use crate::services::hybrid_search_service::{HybridSearchService, HybridSearchResult, HybridSearchError, SearchStrategy};
+use crate::services::hybrid_search_service::{HybridSearchService, HybridSearchResult, HybridSearchError, SearchStrategy, FusionMethod};
 use crate::services::semantic_search_service::{SemanticSearchService, EnhancedSearchResult, SearchMetadata, SemanticSearchError, SearchIndexStats};
-use crate::services::context_query_service::ContextQueryResult;
+use crate::models::enhanced_context::KeywordSearchResult;
 use async_trait::async_trait;
 use chrono::Utc;
 use std::collections::HashMap;
@@ -54,15 +54,12 @@ impl HybridSearchService for MockHybridSearchService {
 
         Ok(HybridSearchResult {
             semantic_results: filtered_results,
-            traditional_results: ContextQueryResult {
-                business_rules: Vec::new(),
-                architectural_decisions: Vec::new(),
-                performance_requirements: Vec::new(),
-                security_policies: Vec::new(),
-                project_conventions: Vec::new(),
-            },
+            traditional_results: Vec::new(),
+            ranked_results: Vec::new(),
             combined_score: 0.85,
             search_strategy: SearchStrategy::Hybrid,
+            fusion_method: FusionMethod::WeightedSum,
+            reranker: None,
             total_results: self.mock_results.len(),
         })
     }
@@ -77,17 +74,10 @@ impl HybridSearchService for MockHybridSearchService {
     async fn traditional_search(
         &self,
         _project_id: &str,
-        _feature_area: &str,
-        _task_type: &str,
-        _components: &[String],
-    ) -> Result<ContextQueryResult, HybridSearchError> {
-        Ok(ContextQueryResult {
-            business_rules: Vec::new(),
-            architectural_decisions: Vec::new(),
-            performance_requirements: Vec::new(),
-            security_policies: Vec::new(),
-            project_conventions: Vec::new(),
-        })
+        _query_text: &str,
+        _max_results: usize,
+    ) -> Result<Vec<KeywordSearchResult>, HybridSearchError> {
+        Ok(Vec::new())
     }
 
     async fn get_search_suggestions(
@@ -161,6 +151,7 @@ impl SemanticSearchService for MockSemanticSearchService {
             average_embedding_quality: 0.8,
             index_freshness_score: 0.9,
             last_updated: Utc::now(),
+            vector_index: None,
         })
     }
 
@@ -183,6 +174,7 @@ fn create_mock_enhanced_results() -> Vec<EnhancedSearchResult> {
                     content_preview: "Authentication security policy".to_string(),
                     match_explanation: "High similarity match for authentication".to_string(),
                     quality_indicators: vec!["High quality".to_string(), "Recently updated".to_string()],
+                    chunk: None,
                 },
             },
             context_item: None,
@@ -207,6 +199,7 @@ fn create_mock_enhanced_results() -> Vec<EnhancedSearchResult> {
                     content_preview: "User database schema".to_string(),
                     match_explanation: "Good match for database query".to_string(),
                     quality_indicators: vec!["Good quality".to_string()],
+                    chunk: None,
                 },
             },
             context_item: None,
@@ -231,6 +224,7 @@ fn create_mock_enhanced_results() -> Vec<EnhancedSearchResult> {
                     content_preview: "REST API specification".to_string(),
                     match_explanation: "Moderate match for API query".to_string(),
                     quality_indicators: vec!["Medium quality".to_string()],
+                    chunk: None,
                 },
             },
             context_item: None,
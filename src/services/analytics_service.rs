@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use schemars::JsonSchema;
 
 /// Analytics event types for tracking context usage
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,7 +34,7 @@ pub struct AnalyticsEvent {
 }
 
 /// Usage statistics for a specific context or entity
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UsageStatistics {
     pub total_queries: u64,
     pub successful_queries: u64,
@@ -44,7 +45,7 @@ pub struct UsageStatistics {
 }
 
 /// Project-level analytics insights
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectInsights {
     pub project_id: String,
     pub total_events: u64,
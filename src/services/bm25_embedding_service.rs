@@ -0,0 +1,418 @@
+use crate::models::embedding::{
+    ContextEmbedding, EmbeddingConfig, EmbeddingMetadata, EmbeddingVocabulary, ModelInfo, ModelType,
+    VectorSearchQuery, VectorSearchResult,
+};
+use crate::repositories::embedding_repository::{EmbeddingRepositoryError, SqliteEmbeddingRepository};
+use crate::services::embedding_service::{rank_by_similarity, EmbeddingError, EmbeddingService};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use chrono::Utc;
+use std::sync::{OnceLock, RwLock};
+use tracing::{debug, info};
+
+/// Model type name selecting the BM25 fallback in `EmbeddingServiceFactory`
+pub const BM25_MODEL_TYPE: &str = "bm25";
+
+/// Term frequency saturation
+const K1: f32 = 1.2;
+/// Document length normalization
+const B: f32 = 0.75;
+/// Share of a vocabulary's documents that may be added, removed or edited
+/// before it is rebuilt
+const VOCABULARY_REBUILD_THRESHOLD: f32 = 0.2;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "have", "in", "is",
+    "it", "its", "of", "on", "or", "that", "the", "this", "to", "was", "were", "will", "with",
+];
+
+/// Embedding service that needs no model files: BM25-weighted terms are
+/// projected into `embedding_dimension` with signed feature hashing.
+///
+/// Term weights come from an `EmbeddingVocabulary` built over a project's
+/// `enhanced_context_items`, so common boilerplate words contribute little and
+/// distinctive domain terms dominate the similarity. Each project has its own
+/// vocabulary; the database-wide one (keyed by `None`) weights texts of projects
+/// without one.
+pub struct Bm25EmbeddingService {
+    config: EmbeddingConfig,
+    /// Project the service was created for, weighting texts embedded without one
+    scope: Option<String>,
+    vocabularies: RwLock<HashMap<Option<String>, EmbeddingVocabulary>>,
+    /// Where vocabularies are rebuilt from, once the service is prepared
+    repository: OnceLock<SqliteEmbeddingRepository>,
+}
+
+impl Bm25EmbeddingService {
+    pub fn new(config: EmbeddingConfig) -> Self {
+        Self {
+            config,
+            scope: None,
+            vocabularies: RwLock::new(HashMap::new()),
+            repository: OnceLock::new(),
+        }
+    }
+
+    pub fn with_vocabulary(config: EmbeddingConfig, vocabulary: EmbeddingVocabulary) -> Self {
+        let mut service = Self::new(config);
+        service.scope = vocabulary.project_id.clone();
+        service.set_vocabulary(vocabulary);
+        service
+    }
+
+    /// Create a service using the persisted vocabularies, building and storing
+    /// one for `project_id` from `enhanced_context_items` when none exists yet
+    pub async fn from_repository(
+        config: EmbeddingConfig,
+        repository: &SqliteEmbeddingRepository,
+        project_id: Option<&str>,
+    ) -> Result<Self, EmbeddingError> {
+        let mut service = Self::new(config);
+        service.scope = project_id.map(str::to_string);
+        service.load_vocabularies(repository, project_id).await?;
+
+        let vocabulary = service.vocabulary(project_id);
+        info!(
+            "BM25 embedding service ready with {} terms from {} documents",
+            vocabulary.document_frequencies.len(),
+            vocabulary.document_count
+        );
+        Ok(service)
+    }
+
+    /// Use the persisted vocabularies, building and storing the one for
+    /// `project_id` when none exists yet
+    async fn load_vocabularies(
+        &self,
+        repository: &SqliteEmbeddingRepository,
+        project_id: Option<&str>,
+    ) -> Result<(), EmbeddingError> {
+        let _ = self.repository.set(repository.clone());
+
+        let stored = repository
+            .load_vocabularies(&self.config.model_name)
+            .await
+            .map_err(vocabulary_error)?;
+        for vocabulary in stored {
+            self.set_vocabulary(vocabulary);
+        }
+
+        let missing = !self
+            .vocabularies
+            .read()
+            .unwrap()
+            .contains_key(&project_id.map(str::to_string));
+        if missing {
+            self.rebuild_vocabulary(repository, project_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild a vocabulary from the current `enhanced_context_items` and persist it.
+    /// Existing embeddings should be regenerated afterwards to pick up the new weights.
+    pub async fn rebuild_vocabulary(
+        &self,
+        repository: &SqliteEmbeddingRepository,
+        project_id: Option<&str>,
+    ) -> Result<(), EmbeddingError> {
+        let texts = repository
+            .get_context_item_texts(project_id)
+            .await
+            .map_err(vocabulary_error)?;
+
+        let vocabulary = EmbeddingVocabulary::from_documents(
+            project_id.map(str::to_string),
+            texts.iter().map(|text| tokenize(text)),
+        );
+        repository
+            .save_vocabulary(&self.config.model_name, &vocabulary)
+            .await
+            .map_err(vocabulary_error)?;
+
+        info!(
+            "Rebuilt BM25 vocabulary for {} from {} documents",
+            project_id.unwrap_or("all projects"),
+            vocabulary.document_count
+        );
+        self.set_vocabulary(vocabulary);
+        Ok(())
+    }
+
+    /// Replace the corpus statistics of the vocabulary's project, e.g. after a rebuild
+    pub fn set_vocabulary(&self, vocabulary: EmbeddingVocabulary) {
+        self.vocabularies
+            .write()
+            .unwrap()
+            .insert(vocabulary.project_id.clone(), vocabulary);
+    }
+
+    /// The vocabulary weighting a project's texts: its own, else the service's
+    /// scope, else the database-wide one
+    pub fn vocabulary(&self, project_id: Option<&str>) -> EmbeddingVocabulary {
+        let vocabularies = self.vocabularies.read().unwrap();
+        [project_id.map(str::to_string), self.scope.clone(), None]
+            .iter()
+            .find_map(|key| vocabularies.get(key))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Whether the corpus has drifted far enough from a vocabulary to rebuild it
+    async fn is_stale(
+        &self,
+        repository: &SqliteEmbeddingRepository,
+        project_id: Option<&str>,
+    ) -> Result<bool, EmbeddingError> {
+        let built = self
+            .vocabularies
+            .read()
+            .unwrap()
+            .get(&project_id.map(str::to_string))
+            .map(|vocabulary| (vocabulary.document_count, vocabulary.built_at));
+
+        let since = built.map(|(_, built_at)| built_at).unwrap_or_else(Utc::now);
+        let (count, changed) = repository
+            .get_context_item_changes(project_id, since)
+            .await
+            .map_err(vocabulary_error)?;
+
+        Ok(match built {
+            None => count > 0,
+            Some((document_count, _)) => {
+                let drift = count.abs_diff(document_count).max(changed);
+                drift > 0 && drift as f32 >= document_count as f32 * VOCABULARY_REBUILD_THRESHOLD
+            }
+        })
+    }
+
+    /// Compute the BM25-weighted, hashed and L2 normalized vector for a text
+    fn embed(&self, text: &str, project_id: Option<&str>) -> Vec<f32> {
+        let dimension = self.config.embedding_dimension.max(1);
+        let mut embedding = vec![0.0f32; dimension];
+
+        let terms = tokenize(text);
+        if terms.is_empty() {
+            return embedding;
+        }
+
+        let mut term_frequencies: HashMap<&str, f32> = HashMap::new();
+        for term in &terms {
+            *term_frequencies.entry(term.as_str()).or_insert(0.0) += 1.0;
+        }
+
+        let vocabulary = self.vocabulary(project_id);
+        let document_length = terms.len() as f32;
+        let average_length = if vocabulary.average_document_length > 0.0 {
+            vocabulary.average_document_length
+        } else {
+            document_length
+        };
+
+        for (term, tf) in term_frequencies {
+            let saturation =
+                tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * document_length / average_length));
+            let weight = vocabulary.idf(term) * saturation;
+
+            let (index, sign) = hash_term(term, dimension);
+            embedding[index] += sign * weight;
+        }
+
+        let norm: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for value in &mut embedding {
+                *value /= norm;
+            }
+        }
+
+        embedding
+    }
+}
+
+fn vocabulary_error(error: EmbeddingRepositoryError) -> EmbeddingError {
+    EmbeddingError::ModelLoadError {
+        message: format!("BM25 vocabulary unavailable: {}", error),
+    }
+}
+
+/// Lowercased alphanumeric terms with stop words and single characters removed
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(|word| word.to_lowercase())
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Stable bucket and sign for a term; md5 keeps vectors identical across builds
+fn hash_term(term: &str, dimension: usize) -> (usize, f32) {
+    let digest = md5::compute(term.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    let hash = u64::from_le_bytes(bytes);
+
+    let sign = if digest[8] & 1 == 0 { 1.0 } else { -1.0 };
+    ((hash % dimension as u64) as usize, sign)
+}
+
+#[async_trait]
+impl EmbeddingService for Bm25EmbeddingService {
+    async fn generate_embedding(&self, text: &str, content_type: &str) -> Result<ContextEmbedding, EmbeddingError> {
+        self.generate_project_embedding(text, content_type, None).await
+    }
+
+    async fn generate_project_embedding(
+        &self,
+        text: &str,
+        content_type: &str,
+        project_id: Option<&str>,
+    ) -> Result<ContextEmbedding, EmbeddingError> {
+        debug!("Generating BM25 embedding for content type: {}", content_type);
+
+        let embedding_vector = self.embed(text, project_id);
+        let content_hash = format!("{:x}", md5::compute(text.as_bytes()));
+
+        let mut embedding = ContextEmbedding::new(
+            String::new(), // context_id will be set by caller
+            embedding_vector,
+            self.config.model_name.clone(),
+            "1.0".to_string(),
+            content_hash,
+        );
+
+        embedding.metadata = EmbeddingMetadata {
+            content_type: content_type.to_string(),
+            content_length: text.len(),
+            tokenization_method: self.config.tokenization_method.clone(),
+            preprocessing_steps: vec!["bm25_weighting".to_string(), "feature_hashing".to_string()],
+            quality_score: if text.trim().len() < 10 { 0.5 } else { 1.0 },
+            custom_fields: HashMap::new(),
+        };
+
+        Ok(embedding)
+    }
+
+    async fn generate_embeddings_batch(&self, texts: Vec<(&str, &str, &str)>) -> Result<Vec<ContextEmbedding>, EmbeddingError> {
+        debug!("Generating batch of {} BM25 embeddings", texts.len());
+
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for (context_id, text, content_type) in texts {
+            let mut embedding = self.generate_embedding(text, content_type).await?;
+            embedding.context_id = context_id.to_string();
+            embeddings.push(embedding);
+        }
+
+        Ok(embeddings)
+    }
+
+    fn calculate_similarity(&self, embedding1: &ContextEmbedding, embedding2: &ContextEmbedding) -> f32 {
+        embedding1.cosine_similarity(embedding2)
+    }
+
+    async fn find_similar(&self, query: &VectorSearchQuery, embeddings: &[ContextEmbedding]) -> Result<Vec<VectorSearchResult>, EmbeddingError> {
+        let query_embedding = match query.query_embedding {
+            Some(ref embedding) => embedding.clone(),
+            None => {
+                let project_id = match query.filters.project_ids.as_deref() {
+                    Some([project_id]) => Some(project_id.as_str()),
+                    _ => None,
+                };
+                self.embed(&query.query_text, project_id)
+            }
+        };
+
+        Ok(rank_by_similarity(&query_embedding, embeddings, query))
+    }
+
+    fn get_model_info(&self) -> ModelInfo {
+        ModelInfo {
+            model_name: self.config.model_name.clone(),
+            model_version: "1.0".to_string(),
+            embedding_dimension: self.config.embedding_dimension,
+            max_sequence_length: self.config.max_sequence_length,
+            model_type: ModelType::Custom(BM25_MODEL_TYPE.to_string()),
+        }
+    }
+
+    async fn update_config(&mut self, config: EmbeddingConfig) -> Result<(), EmbeddingError> {
+        info!("Updating BM25 embedding service configuration");
+        self.config = config;
+        Ok(())
+    }
+
+    async fn prepare(&self, repository: &SqliteEmbeddingRepository) -> Result<(), EmbeddingError> {
+        self.load_vocabularies(repository, None).await
+    }
+
+    async fn refresh_vocabulary(&self, project_id: Option<&str>, force: bool) -> Result<bool, EmbeddingError> {
+        let Some(repository) = self.repository.get() else {
+            return Ok(false);
+        };
+        if !force && !self.is_stale(repository, project_id).await? {
+            return Ok(false);
+        }
+        self.rebuild_vocabulary(repository, project_id).await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> EmbeddingConfig {
+        EmbeddingConfig {
+            model_name: "bm25".to_string(),
+            model_type: ModelType::Custom(BM25_MODEL_TYPE.to_string()),
+            embedding_dimension: 256,
+            ..Default::default()
+        }
+    }
+
+    fn corpus() -> EmbeddingVocabulary {
+        let documents = [
+            "Access tokens must be rotated every day",
+            "The checkout page must render in under a second",
+            "All passwords must be hashed with argon2",
+            "Database migrations must be reversible",
+        ];
+        EmbeddingVocabulary::from_documents(None, documents.iter().map(|d| tokenize(d)))
+    }
+
+    #[test]
+    fn test_tokenize_drops_stop_words_and_punctuation() {
+        assert_eq!(
+            tokenize("The API-key is rotated, daily!"),
+            vec!["api", "key", "rotated", "daily"]
+        );
+    }
+
+    #[test]
+    fn test_vocabulary_idf_prefers_rare_terms() {
+        let vocabulary = corpus();
+        assert_eq!(vocabulary.document_count, 4);
+        assert!(vocabulary.idf("tokens") > vocabulary.idf("must"));
+        assert!(vocabulary.idf("unseen") >= vocabulary.idf("tokens"));
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_are_deterministic_and_meaningful() {
+        let service = Bm25EmbeddingService::with_vocabulary(config(), corpus());
+
+        let a = service.generate_embedding("rotate access tokens daily", "business_rule").await.unwrap();
+        let a_again = service.generate_embedding("rotate access tokens daily", "business_rule").await.unwrap();
+        let b = service.generate_embedding("access tokens must be rotated every day", "business_rule").await.unwrap();
+        let c = service.generate_embedding("database migrations must be reversible", "business_rule").await.unwrap();
+
+        assert_eq!(a.embedding_vector, a_again.embedding_vector);
+        assert_eq!(a.embedding_vector.len(), 256);
+        assert!(service.calculate_similarity(&a, &b) > service.calculate_similarity(&a, &c));
+        assert!(service.calculate_similarity(&a, &c) < 0.1);
+    }
+
+    #[tokio::test]
+    async fn test_empty_text_yields_zero_vector() {
+        let service = Bm25EmbeddingService::new(config());
+        let embedding = service.generate_embedding("   ", "business_rule").await.unwrap();
+        assert!(embedding.embedding_vector.iter().all(|v| *v == 0.0));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::models::context::{ArchitecturalDecision, BusinessRule, PerformanceRequirement};
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::{
     ArchitecturalDecisionRepository, BusinessRuleRepository, PerformanceRequirementRepository,
 };
@@ -22,6 +23,11 @@ pub trait ContextCrudService: Send + Sync {
     async fn update_business_rule(&self, rule: &BusinessRule) -> Result<BusinessRule, McpError>;
     async fn delete_business_rule(&self, id: &str) -> Result<bool, McpError>;
     async fn list_business_rules(&self, project_id: &str) -> Result<Vec<BusinessRule>, McpError>;
+    async fn list_business_rules_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<BusinessRule>, McpError>;
     async fn list_business_rules_by_domain(
         &self,
         project_id: &str,
@@ -49,6 +55,11 @@ pub trait ContextCrudService: Send + Sync {
         &self,
         project_id: &str,
     ) -> Result<Vec<ArchitecturalDecision>, McpError>;
+    async fn list_architectural_decisions_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<ArchitecturalDecision>, McpError>;
 
     // Performance Requirements CRUD
     async fn create_performance_requirement(
@@ -71,6 +82,11 @@ pub trait ContextCrudService: Send + Sync {
         &self,
         project_id: &str,
     ) -> Result<Vec<PerformanceRequirement>, McpError>;
+    async fn list_performance_requirements_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<PerformanceRequirement>, McpError>;
 
     // Bulk operations
     async fn bulk_create_business_rules(
@@ -167,6 +183,16 @@ where
             .await
     }
 
+    async fn list_business_rules_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<BusinessRule>, McpError> {
+        self.business_rule_repository
+            .find_page_by_project_id(project_id, query)
+            .await
+    }
+
     async fn list_business_rules_by_domain(
         &self,
         project_id: &str,
@@ -234,6 +260,16 @@ where
             .await
     }
 
+    async fn list_architectural_decisions_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<ArchitecturalDecision>, McpError> {
+        self.architectural_decision_repository
+            .find_page_by_project_id(project_id, query)
+            .await
+    }
+
     // Performance Requirements CRUD Implementation
     async fn create_performance_requirement(
         &self,
@@ -290,6 +326,16 @@ where
             .await
     }
 
+    async fn list_performance_requirements_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<PerformanceRequirement>, McpError> {
+        self.performance_requirement_repository
+            .find_page_by_project_id(project_id, query)
+            .await
+    }
+
     // Bulk Operations Implementation
     async fn bulk_create_business_rules(
         &self,
@@ -0,0 +1,537 @@
+use crate::models::enhanced_context::{EnhancedContextItem, Priority};
+use crate::repositories::EnhancedContextRepository;
+use crate::services::context_relationship_engine::ContextRelationshipEngine;
+use crate::services::hybrid_search_service::HybridSearchService;
+use crate::services::text_chunker::snippet;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Weight of each ranking signal in an item's pack score
+const PRIORITY_WEIGHT: f64 = 0.3;
+const QUALITY_WEIGHT: f64 = 0.15;
+const PROXIMITY_WEIGHT: f64 = 0.25;
+const RELEVANCE_WEIGHT: f64 = 0.3;
+/// Quality assumed for items that have not been quality scored
+const UNSCORED_QUALITY: f64 = 0.5;
+/// Relationship hops followed from the focus items
+const PROXIMITY_DEPTH: usize = 2;
+/// Rough size of a token, used to estimate the cost of text
+const CHARS_PER_TOKEN: usize = 4;
+/// Longest summary of an item that did not fit whole
+const SUMMARY_CHARS: usize = 280;
+/// Summaries shorter than this are not worth including
+const MIN_SUMMARY_CHARS: usize = 40;
+
+/// What a context pack is assembled for
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ContextPackRequest {
+    /// The ID of the project
+    pub project_id: String,
+    /// Description of the task the context is for
+    pub task: String,
+    /// Maximum number of tokens the packed items may take
+    #[schemars(range(min = 1))]
+    pub token_budget: usize,
+    /// Files the task touches
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Components the task touches
+    #[serde(default)]
+    pub components: Vec<String>,
+}
+
+/// Context items selected for a task, within a token budget
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContextPack {
+    pub project_id: String,
+    pub task: String,
+    pub token_budget: usize,
+    /// Estimated tokens taken by the packed items
+    pub tokens_used: usize,
+    /// Packed items, best first
+    pub items: Vec<PackedContextItem>,
+    /// Candidates left out of the pack, best first
+    pub dropped: Vec<DroppedContextItem>,
+}
+
+/// A context item in a pack
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PackedContextItem {
+    pub context_id: String,
+    pub context_type: String,
+    pub title: String,
+    /// The item as text, or a summary of it when `summarised`
+    pub content: String,
+    /// Estimated tokens of `content`
+    pub tokens: usize,
+    /// Whether `content` was cut down to fit the budget
+    pub summarised: bool,
+    pub score: f64,
+    pub scores: PackScores,
+}
+
+/// Ranking signals of a candidate, each in 0..1
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PackScores {
+    pub priority: f64,
+    pub quality: f64,
+    /// Closeness in the relationship graph to items about the focus files and components
+    pub proximity: f64,
+    /// Search relevance to the task, relative to the best match
+    pub relevance: f64,
+}
+
+/// A candidate left out of a pack
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DroppedContextItem {
+    pub context_id: String,
+    pub context_type: String,
+    pub title: String,
+    /// Estimated tokens of the whole item
+    pub tokens: usize,
+    pub score: f64,
+    pub reason: DropReason,
+}
+
+/// Why a candidate was left out of a pack
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DropReason {
+    /// Not even a summary fit in the budget left
+    OverBudget,
+    /// Neither matched by the task nor related to the focus, and not critical
+    Unrelated,
+}
+
+/// Service assembling token-budgeted context packs
+#[async_trait]
+pub trait ContextPackService: Send + Sync {
+    async fn build_context_pack(&self, request: &ContextPackRequest) -> Result<ContextPack, McpError>;
+}
+
+/// Ranks a project's context items by priority, quality score, relationship
+/// proximity to the focus and search relevance, then packs them greedily,
+/// summarising items that do not fit whole
+pub struct DefaultContextPackService {
+    context_repository: Arc<dyn EnhancedContextRepository>,
+    hybrid_search_service: Arc<dyn HybridSearchService>,
+    relationship_engine: Arc<dyn ContextRelationshipEngine>,
+}
+
+impl DefaultContextPackService {
+    pub fn new(
+        context_repository: Arc<dyn EnhancedContextRepository>,
+        hybrid_search_service: Arc<dyn HybridSearchService>,
+        relationship_engine: Arc<dyn ContextRelationshipEngine>,
+    ) -> Self {
+        Self {
+            context_repository,
+            hybrid_search_service,
+            relationship_engine,
+        }
+    }
+
+    /// Search relevance of each matching context, relative to the best match
+    async fn relevance(&self, request: &ContextPackRequest) -> Result<HashMap<String, f64>, McpError> {
+        let result = self
+            .hybrid_search_service
+            .hybrid_search(&request.project_id, &request.task, None, None, &request.components)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Context search failed: {e}"), None))?;
+
+        let best = result.ranked_results.iter().map(|r| r.scores.final_score).fold(0.0, f64::max);
+        if best <= 0.0 {
+            return Ok(HashMap::new());
+        }
+        Ok(result
+            .ranked_results
+            .into_iter()
+            .map(|r| (r.context_id, r.scores.final_score / best))
+            .collect())
+    }
+
+    /// Proximity of each context to the items about the focus files and
+    /// components: 1 for those items, multiplied by the relationship strength
+    /// at every hop away from them
+    async fn proximity(
+        &self,
+        request: &ContextPackRequest,
+        candidates: &[EnhancedContextItem],
+    ) -> Result<HashMap<String, f64>, McpError> {
+        let mut proximity: HashMap<String, f64> = candidates
+            .iter()
+            .filter(|item| is_focus(item, request))
+            .map(|item| (item.id.clone(), 1.0))
+            .collect();
+        if proximity.is_empty() {
+            return Ok(proximity);
+        }
+
+        let graph = self
+            .relationship_engine
+            .build_relationship_graph(candidates)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to relate contexts: {e}"), None))?;
+        // The graph only links each pair one way, so walk its edges both ways
+        let mut neighbours: HashMap<&str, Vec<(&str, f64)>> = HashMap::new();
+        for item in candidates {
+            for relationship in graph.get_relationships(&item.id).into_iter().flatten() {
+                let strength = relationship.strength.clamp(0.0, 1.0);
+                neighbours.entry(&item.id).or_default().push((&relationship.target_id, strength));
+                neighbours.entry(&relationship.target_id).or_default().push((&item.id, strength));
+            }
+        }
+
+        let mut frontier: Vec<(String, f64)> = proximity.iter().map(|(id, closeness)| (id.clone(), *closeness)).collect();
+        for _ in 0..PROXIMITY_DEPTH {
+            let mut next = Vec::new();
+            for (id, closeness) in &frontier {
+                for (neighbour, strength) in neighbours.get(id.as_str()).into_iter().flatten() {
+                    let reached = closeness * strength;
+                    let best = proximity.entry(neighbour.to_string()).or_insert(0.0);
+                    if reached > *best {
+                        *best = reached;
+                        next.push((neighbour.to_string(), reached));
+                    }
+                }
+            }
+            frontier = next;
+        }
+        Ok(proximity)
+    }
+}
+
+#[async_trait]
+impl ContextPackService for DefaultContextPackService {
+    async fn build_context_pack(&self, request: &ContextPackRequest) -> Result<ContextPack, McpError> {
+        if request.token_budget == 0 {
+            return Err(McpError::invalid_params("token_budget must be at least 1", None));
+        }
+        if request.task.trim().is_empty() {
+            return Err(McpError::invalid_params("task must describe the work to be done", None));
+        }
+
+        let candidates = self.context_repository.find_contexts_by_project(&request.project_id).await?;
+        let relevance = self.relevance(request).await?;
+        let proximity = self.proximity(request, &candidates).await?;
+
+        let mut ranked: Vec<(EnhancedContextItem, PackScores, f64)> = candidates
+            .into_iter()
+            .map(|item| {
+                let scores = PackScores {
+                    priority: item.metadata.priority.score(),
+                    quality: if item.quality_score > 0.0 { item.quality_score } else { UNSCORED_QUALITY },
+                    proximity: proximity.get(&item.id).copied().unwrap_or(0.0),
+                    relevance: relevance.get(&item.id).copied().unwrap_or(0.0),
+                };
+                let score = PRIORITY_WEIGHT * scores.priority
+                    + QUALITY_WEIGHT * scores.quality
+                    + PROXIMITY_WEIGHT * scores.proximity
+                    + RELEVANCE_WEIGHT * scores.relevance;
+                (item, scores, score)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(Ordering::Equal).then_with(|| a.0.id.cmp(&b.0.id)));
+
+        let mut pack = ContextPack {
+            project_id: request.project_id.clone(),
+            task: request.task.clone(),
+            token_budget: request.token_budget,
+            tokens_used: 0,
+            items: Vec::new(),
+            dropped: Vec::new(),
+        };
+        for (item, scores, score) in ranked {
+            let header = item_header(&item);
+            let details = item_details(&item);
+            let full = if details.is_empty() { header.clone() } else { format!("{header}\n{details}") };
+            let full_tokens = estimate_tokens(&full);
+            let drop = |reason| DroppedContextItem {
+                context_id: item.id.clone(),
+                context_type: item.content.content_type.as_str().to_string(),
+                title: item.content.title.clone(),
+                tokens: full_tokens,
+                score,
+                reason,
+            };
+
+            let unrelated = scores.relevance == 0.0 && scores.proximity == 0.0 && item.metadata.priority != Priority::Critical;
+            if unrelated {
+                pack.dropped.push(drop(DropReason::Unrelated));
+                continue;
+            }
+
+            let remaining = request.token_budget - pack.tokens_used;
+            let (content, summarised) = if full_tokens <= remaining {
+                (full, false)
+            } else {
+                // Room for the summary text once the header and the ellipsis `snippet` adds are paid for
+                let room = (remaining * CHARS_PER_TOKEN).saturating_sub(header.len() + 1 + '…'.len_utf8());
+                if details.is_empty() || room < MIN_SUMMARY_CHARS {
+                    pack.dropped.push(drop(DropReason::OverBudget));
+                    continue;
+                }
+                (format!("{header}\n{}", snippet(&details, room.min(SUMMARY_CHARS))), true)
+            };
+
+            let tokens = estimate_tokens(&content);
+            pack.tokens_used += tokens;
+            pack.items.push(PackedContextItem {
+                context_id: item.id,
+                context_type: item.content.content_type.as_str().to_string(),
+                title: item.content.title,
+                content,
+                tokens,
+                summarised,
+                score,
+                scores,
+            });
+        }
+        Ok(pack)
+    }
+}
+
+/// Whether `item` is about one of the files or components the task touches
+fn is_focus(item: &EnhancedContextItem, request: &ContextPackRequest) -> bool {
+    let touches_file = request.files.iter().any(|file| {
+        item.content.source_file.as_deref().is_some_and(|source| source.ends_with(file.as_str()) || file.ends_with(source))
+    });
+    if touches_file {
+        return true;
+    }
+
+    let text = format!(
+        "{} {} {} {}",
+        item.content.title,
+        item.content.description,
+        item.metadata.tags.join(" "),
+        item.content.data
+    )
+    .to_lowercase();
+    request.components.iter().any(|component| text.contains(&component.to_lowercase()))
+}
+
+/// Estimated number of tokens in `text`
+fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(CHARS_PER_TOKEN)
+}
+
+fn item_header(item: &EnhancedContextItem) -> String {
+    format!("[{}] {}", item.content.content_type.as_str(), item.content.title)
+}
+
+/// Description of an item followed by its set data fields, one per line
+fn item_details(item: &EnhancedContextItem) -> String {
+    let mut lines = Vec::new();
+    if !item.content.description.trim().is_empty() {
+        lines.push(item.content.description.trim().to_string());
+    }
+    if let Value::Object(fields) = &item.content.data {
+        for (key, value) in fields {
+            match value {
+                Value::Null => {}
+                Value::String(text) if text.trim().is_empty() => {}
+                Value::String(text) => lines.push(format!("{key}: {}", text.trim())),
+                other => lines.push(format!("{key}: {other}")),
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::SqliteEnhancedContextRepository;
+    use crate::models::embedding::VectorSearchQuery;
+    use crate::models::enhanced_context::{ContextContent, ContextType, KeywordSearchResult};
+    use crate::services::context_relationship_engine::DefaultContextRelationshipEngine;
+    use crate::services::hybrid_search_service::{
+        FusionMethod, HybridSearchError, HybridSearchResult, RankedSearchResult, SearchStrategy, StageScores,
+    };
+    use crate::services::semantic_search_service::EnhancedSearchResult;
+    use serde_json::json;
+
+    /// Hybrid search ranking contexts by fixed scores
+    struct FixedSearch(Vec<(&'static str, f64)>);
+
+    #[async_trait]
+    impl HybridSearchService for FixedSearch {
+        async fn hybrid_search(
+            &self,
+            _project_id: &str,
+            _query_text: &str,
+            _feature_area: Option<&str>,
+            _task_type: Option<&str>,
+            _components: &[String],
+        ) -> Result<HybridSearchResult, HybridSearchError> {
+            let ranked_results: Vec<RankedSearchResult> = self
+                .0
+                .iter()
+                .map(|(context_id, score)| RankedSearchResult {
+                    context_id: context_id.to_string(),
+                    context: None,
+                    preview: String::new(),
+                    scores: StageScores { final_score: *score, ..Default::default() },
+                })
+                .collect();
+            Ok(HybridSearchResult {
+                semantic_results: Vec::new(),
+                traditional_results: Vec::new(),
+                total_results: ranked_results.len(),
+                ranked_results,
+                combined_score: 0.0,
+                search_strategy: SearchStrategy::Hybrid,
+                fusion_method: FusionMethod::WeightedSum,
+                reranker: None,
+            })
+        }
+
+        async fn semantic_search(&self, _query: &VectorSearchQuery) -> Result<Vec<EnhancedSearchResult>, HybridSearchError> {
+            Ok(Vec::new())
+        }
+
+        async fn traditional_search(
+            &self,
+            _project_id: &str,
+            _query_text: &str,
+            _max_results: usize,
+        ) -> Result<Vec<KeywordSearchResult>, HybridSearchError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_search_suggestions(
+            &self,
+            _partial_query: &str,
+            _project_id: Option<&str>,
+        ) -> Result<Vec<String>, HybridSearchError> {
+            Ok(Vec::new())
+        }
+    }
+
+    async fn service(contexts: Vec<EnhancedContextItem>, search: FixedSearch) -> DefaultContextPackService {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let repository = SqliteEnhancedContextRepository::new(Arc::new(std::sync::Mutex::new(conn)));
+        repository.initialize_tables().unwrap();
+        for context in &contexts {
+            repository.create_context(context).await.unwrap();
+        }
+        DefaultContextPackService::new(
+            Arc::new(repository),
+            Arc::new(search),
+            Arc::new(DefaultContextRelationshipEngine::new().with_similarity_threshold(0.5)),
+        )
+    }
+
+    fn context(id: &str, content_type: ContextType, title: &str, description: &str, priority: Priority) -> EnhancedContextItem {
+        let content = ContextContent {
+            content_type,
+            title: title.to_string(),
+            description: description.to_string(),
+            data: json!({ "domain_area": "payments" }),
+            source_file: None,
+            source_line: None,
+        };
+        let mut item = EnhancedContextItem::new("project".to_string(), content);
+        item.id = id.to_string();
+        item.metadata.priority = priority;
+        item
+    }
+
+    fn request(token_budget: usize) -> ContextPackRequest {
+        ContextPackRequest {
+            project_id: "project".to_string(),
+            task: "Implement refunds".to_string(),
+            token_budget,
+            files: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pack_ranks_by_priority_and_relevance() {
+        let service = service(
+            vec![
+                context("rule-low", ContextType::BusinessRule, "Refund notes", "Refunds are logged", Priority::Low),
+                context("rule-high", ContextType::BusinessRule, "Refund window", "Refunds within 30 days", Priority::High),
+                context("adr-unrelated", ContextType::ArchitecturalDecision, "Use SQLite", "Store data in SQLite", Priority::High),
+            ],
+            FixedSearch(vec![("rule-high", 2.0), ("rule-low", 2.0)]),
+        )
+        .await;
+
+        let pack = service.build_context_pack(&request(1000)).await.unwrap();
+        let packed: Vec<&str> = pack.items.iter().map(|i| i.context_id.as_str()).collect();
+        assert_eq!(packed, vec!["rule-high", "rule-low"]);
+        assert!(pack.items.iter().all(|i| !i.summarised && i.scores.relevance == 1.0));
+        assert_eq!(pack.tokens_used, pack.items.iter().map(|i| i.tokens).sum::<usize>());
+        assert!(pack.items[0].content.contains("Refunds within 30 days"));
+
+        assert_eq!(pack.dropped.len(), 1);
+        assert_eq!(pack.dropped[0].context_id, "adr-unrelated");
+        assert_eq!(pack.dropped[0].reason, DropReason::Unrelated);
+    }
+
+    #[tokio::test]
+    async fn test_items_are_summarised_then_dropped_to_fit_the_budget() {
+        let long = "Refunds are issued to the original payment method after review. ".repeat(20);
+        let service = service(
+            vec![
+                context("rule-long", ContextType::BusinessRule, "Refund method", &long, Priority::Critical),
+                context("rule-next", ContextType::BusinessRule, "Refund approval", &long, Priority::High),
+            ],
+            FixedSearch(vec![("rule-long", 1.0), ("rule-next", 1.0)]),
+        )
+        .await;
+
+        let pack = service.build_context_pack(&request(60)).await.unwrap();
+        assert_eq!(pack.items.len(), 1);
+        let item = &pack.items[0];
+        assert_eq!(item.context_id, "rule-long");
+        assert!(item.summarised);
+        assert!(item.content.starts_with("[business_rule] Refund method\n"));
+        assert!(item.content.ends_with('…'));
+        assert!(pack.tokens_used <= 60);
+
+        assert_eq!(pack.dropped[0].context_id, "rule-next");
+        assert_eq!(pack.dropped[0].reason, DropReason::OverBudget);
+        assert!(pack.dropped[0].tokens > 60);
+    }
+
+    #[tokio::test]
+    async fn test_focus_components_pull_in_related_items() {
+        let mut component_rule = context("rule-gateway", ContextType::BusinessRule, "Gateway retries", "Retry failed charges", Priority::Medium);
+        component_rule.content.source_file = Some("src/payments/gateway.rs".to_string());
+        let service = service(
+            vec![
+                component_rule,
+                context("adr-idempotency", ContextType::ArchitecturalDecision, "Idempotency keys", "Charges carry idempotency keys", Priority::Medium),
+                context("perf-other", ContextType::PerformanceRequirement, "Invoice rendering", "Invoices render within 200ms", Priority::Medium),
+            ],
+            FixedSearch(Vec::new()),
+        )
+        .await;
+
+        let mut request = request(1000);
+        request.files = vec!["payments/gateway.rs".to_string()];
+        let pack = service.build_context_pack(&request).await.unwrap();
+
+        assert_eq!(pack.items[0].context_id, "rule-gateway");
+        assert_eq!(pack.items[0].scores.proximity, 1.0);
+        let adr = pack.items.iter().find(|i| i.context_id == "adr-idempotency").expect("related ADR is packed");
+        assert!(adr.scores.proximity > 0.0 && adr.scores.proximity < 1.0);
+        assert!(pack.dropped.iter().any(|d| d.context_id == "perf-other" && d.reason == DropReason::Unrelated));
+    }
+
+    #[tokio::test]
+    async fn test_empty_budget_is_rejected() {
+        let service = service(Vec::new(), FixedSearch(Vec::new())).await;
+        assert!(service.build_context_pack(&request(0)).await.is_err());
+    }
+}
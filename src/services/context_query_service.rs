@@ -2,13 +2,16 @@ use crate::models::context::{
     ArchitecturalDecision, BusinessRule, PerformanceRequirement, ProjectConvention, SecurityPolicy,
 };
 use crate::repositories::{
-    ArchitecturalDecisionRepository, BusinessRuleRepository, PerformanceRequirementRepository,
+    ArchitecturalDecisionRepository, BusinessRuleRepository, FrameworkRepository,
+    PerformanceRequirementRepository, ProjectConventionRepository, SecurityPolicyRepository,
 };
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
+use schemars::JsonSchema;
+use std::collections::HashSet;
 
 /// Result of context query
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, JsonSchema)]
 pub struct ContextQueryResult {
     pub business_rules: Vec<BusinessRule>,
     pub architectural_decisions: Vec<ArchitecturalDecision>,
@@ -29,56 +32,129 @@ pub trait ContextQueryService: Send + Sync {
     ) -> Result<ContextQueryResult, McpError>;
 }
 
+/// Task types that widen the query to every security policy of the project
+const SECURITY_TASK_WORDS: &[&str] = &["api", "endpoint", "security", "auth", "authentication"];
+/// Task types that widen the query to every convention of the project
+const TESTING_TASK_WORDS: &[&str] = &["test", "tests", "testing"];
+
 /// Implementation of ContextQueryService
-pub struct ContextQueryServiceImpl<BR, ADR, PR>
+///
+/// Business rules, security policies and conventions are returned when their
+/// area is unset (project-wide) or names the feature area, one of the
+/// requested components, or a component those depend on. `task_type` widens
+/// this: API and security tasks get every security policy, testing tasks every
+/// convention.
+pub struct ContextQueryServiceImpl<BR, ADR, PR, SPR, PCR, FR>
 where
     BR: BusinessRuleRepository,
     ADR: ArchitecturalDecisionRepository,
     PR: PerformanceRequirementRepository,
+    SPR: SecurityPolicyRepository,
+    PCR: ProjectConventionRepository,
+    FR: FrameworkRepository,
 {
     business_rule_repository: BR,
     architectural_decision_repository: ADR,
     performance_requirement_repository: PR,
+    security_policy_repository: SPR,
+    project_convention_repository: PCR,
+    framework_repository: FR,
 }
 
-impl<BR, ADR, PR> ContextQueryServiceImpl<BR, ADR, PR>
+impl<BR, ADR, PR, SPR, PCR, FR> ContextQueryServiceImpl<BR, ADR, PR, SPR, PCR, FR>
 where
     BR: BusinessRuleRepository,
     ADR: ArchitecturalDecisionRepository,
     PR: PerformanceRequirementRepository,
+    SPR: SecurityPolicyRepository,
+    PCR: ProjectConventionRepository,
+    FR: FrameworkRepository,
 {
     pub fn new(
         business_rule_repository: BR,
         architectural_decision_repository: ADR,
         performance_requirement_repository: PR,
+        security_policy_repository: SPR,
+        project_convention_repository: PCR,
+        framework_repository: FR,
     ) -> Self {
         Self {
             business_rule_repository,
             architectural_decision_repository,
             performance_requirement_repository,
+            security_policy_repository,
+            project_convention_repository,
+            framework_repository,
+        }
+    }
+
+    /// Normalised names of `components` and, transitively, the project
+    /// components they depend on
+    async fn component_scope(&self, project_id: &str, components: &[String]) -> Result<HashSet<String>, McpError> {
+        let mut scope: HashSet<String> = components.iter().map(|name| normalise(name)).collect();
+        if scope.is_empty() {
+            return Ok(scope);
+        }
+
+        let project_components = self.framework_repository.find_by_project_id(project_id).await?;
+        let mut pending: Vec<String> = scope.iter().cloned().collect();
+        while let Some(name) = pending.pop() {
+            for component in project_components.iter().filter(|c| normalise(&c.component_name) == name) {
+                for dependency in &component.dependencies {
+                    let dependency = normalise(dependency);
+                    if scope.insert(dependency.clone()) {
+                        pending.push(dependency);
+                    }
+                }
+            }
         }
+        Ok(scope)
     }
 }
 
+fn normalise(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Whether an entity scoped to `area` belongs in the result; unscoped entities always do
+fn in_scope(area: Option<&str>, scope: &HashSet<String>) -> bool {
+    area.is_none_or(|area| scope.contains(&normalise(area)))
+}
+
+fn task_mentions(task_type: &str, words: &[&str]) -> bool {
+    task_type
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| words.contains(&word.to_lowercase().as_str()))
+}
+
 #[async_trait]
-impl<BR, ADR, PR> ContextQueryService for ContextQueryServiceImpl<BR, ADR, PR>
+impl<BR, ADR, PR, SPR, PCR, FR> ContextQueryService for ContextQueryServiceImpl<BR, ADR, PR, SPR, PCR, FR>
 where
     BR: BusinessRuleRepository,
     ADR: ArchitecturalDecisionRepository,
     PR: PerformanceRequirementRepository,
+    SPR: SecurityPolicyRepository,
+    PCR: ProjectConventionRepository,
+    FR: FrameworkRepository,
 {
     async fn query_context(
         &self,
         project_id: &str,
         feature_area: &str,
-        _task_type: &str,
-        _components: &[String],
+        task_type: &str,
+        components: &[String],
     ) -> Result<ContextQueryResult, McpError> {
-        // Query business rules for the feature area
+        let mut scope = self.component_scope(project_id, components).await?;
+        scope.insert(normalise(feature_area));
+
+        // Query business rules for the feature area and components
         let business_rules = self
             .business_rule_repository
-            .find_by_domain_area(project_id, feature_area)
-            .await?;
+            .find_by_project_id(project_id)
+            .await?
+            .into_iter()
+            .filter(|rule| in_scope(rule.domain_area.as_deref(), &scope))
+            .collect();
 
         // Query architectural decisions
         let architectural_decisions = self
@@ -92,12 +168,32 @@ where
             .find_by_project_id(project_id)
             .await?;
 
+        let all_policies = task_mentions(task_type, SECURITY_TASK_WORDS);
+        let security_policies = self
+            .security_policy_repository
+            .list_by_project(project_id)
+            .await?
+            .into_iter()
+            .filter(|policy| all_policies || in_scope(policy.policy_area.as_deref(), &scope))
+            .collect();
+
+        // Conventions may also be typed by the task they apply to
+        let all_conventions = task_mentions(task_type, TESTING_TASK_WORDS);
+        scope.insert(normalise(task_type));
+        let project_conventions = self
+            .project_convention_repository
+            .list_by_project(project_id)
+            .await?
+            .into_iter()
+            .filter(|convention| all_conventions || in_scope(convention.convention_type.as_deref(), &scope))
+            .collect();
+
         Ok(ContextQueryResult {
             business_rules,
             architectural_decisions,
             performance_requirements,
-            security_policies: Vec::new(), // TODO: Implement when security repository is available
-            project_conventions: Vec::new(), // TODO: Implement when convention repository is available
+            security_policies,
+            project_conventions,
         })
     }
 }
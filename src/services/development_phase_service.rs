@@ -1,4 +1,5 @@
 use crate::models::development::{DevelopmentPhase, PhaseStatus};
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::DevelopmentPhaseRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
@@ -18,6 +19,11 @@ pub trait DevelopmentPhaseService: Send + Sync {
 
     async fn get_phase(&self, id: &str) -> Result<Option<DevelopmentPhase>, McpError>;
     async fn list_phases(&self, project_id: &str) -> Result<Vec<DevelopmentPhase>, McpError>;
+    async fn list_phases_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<DevelopmentPhase>, McpError>;
     async fn update_phase(&self, phase: &DevelopmentPhase) -> Result<DevelopmentPhase, McpError>;
     async fn delete_phase(&self, id: &str) -> Result<bool, McpError>;
     async fn start_phase(&self, id: &str) -> Result<DevelopmentPhase, McpError>;
@@ -72,6 +78,14 @@ impl<R: DevelopmentPhaseRepository> DevelopmentPhaseService for DevelopmentPhase
         self.repository.find_by_project_id(project_id).await
     }
 
+    async fn list_phases_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<DevelopmentPhase>, McpError> {
+        self.repository.find_page_by_project_id(project_id, query).await
+    }
+
     async fn update_phase(&self, phase: &DevelopmentPhase) -> Result<DevelopmentPhase, McpError> {
         self.repository.update(phase).await
     }
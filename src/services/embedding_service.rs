@@ -2,6 +2,9 @@ use crate::models::embedding::{
     ContextEmbedding, EmbeddingConfig, EmbeddingMetadata, ModelInfo, ModelType, TokenizationMethod,
     VectorSearchQuery, VectorSearchResult,
 };
+use crate::repositories::embedding_repository::SqliteEmbeddingRepository;
+use crate::services::bm25_embedding_service::{Bm25EmbeddingService, BM25_MODEL_TYPE};
+use crate::services::transformer_embedding_service::TransformerEmbeddingService;
 use async_trait::async_trait;
 use nalgebra::DVector;
 use regex::Regex;
@@ -51,6 +54,30 @@ pub trait EmbeddingService: Send + Sync {
     
     /// Update configuration
     async fn update_config(&mut self, config: EmbeddingConfig) -> Result<(), EmbeddingError>;
+
+    /// Load what the service needs before its first embedding, such as a
+    /// vocabulary or model files
+    async fn prepare(&self, _repository: &SqliteEmbeddingRepository) -> Result<(), EmbeddingError> {
+        Ok(())
+    }
+
+    /// Generate an embedding weighted for a project's corpus; services
+    /// without corpus statistics ignore the project
+    async fn generate_project_embedding(
+        &self,
+        text: &str,
+        content_type: &str,
+        _project_id: Option<&str>,
+    ) -> Result<ContextEmbedding, EmbeddingError> {
+        self.generate_embedding(text, content_type).await
+    }
+
+    /// Rebuild the corpus statistics of a project (`None` for the whole
+    /// database) when forced or when the corpus has drifted from them.
+    /// Returns whether they changed, in which case its embeddings are stale.
+    async fn refresh_vocabulary(&self, _project_id: Option<&str>, _force: bool) -> Result<bool, EmbeddingError> {
+        Ok(false)
+    }
 }
 
 /// Implementation of EmbeddingService using simple hash-based embeddings
@@ -319,39 +346,7 @@ impl EmbeddingService for SimpleEmbeddingService {
             temp_embedding.embedding_vector
         };
         
-        let mut results = Vec::new();
-        
-        for embedding in embeddings {
-            // Calculate similarity
-            let similarity = self.calculate_cosine_similarity(&query_embedding, &embedding.embedding_vector);
-            
-            if similarity >= query.similarity_threshold {
-                let distance = 1.0 - similarity; // Convert similarity to distance
-                
-                results.push(VectorSearchResult {
-                    context_id: embedding.context_id.clone(),
-                    similarity_score: similarity,
-                    distance,
-                    rank: 0, // Will be set after sorting
-                    metadata: crate::models::embedding::ResultMetadata {
-                        content_type: embedding.metadata.content_type.clone(),
-                        content_preview: "Preview not available".to_string(), // Would be filled by caller
-                        match_explanation: format!("Similarity: {:.3}", similarity),
-                        quality_indicators: vec![format!("Quality: {:.2}", embedding.metadata.quality_score)],
-                    },
-                });
-            }
-        }
-        
-        // Sort by similarity (descending)
-        results.sort_by(|a, b| b.similarity_score.partial_cmp(&a.similarity_score).unwrap());
-        
-        // Set ranks and limit results
-        for (i, result) in results.iter_mut().enumerate() {
-            result.rank = i + 1;
-        }
-        
-        results.truncate(query.max_results);
+        let results = rank_by_similarity(&query_embedding, embeddings, query);
         
         debug!("Found {} similar embeddings", results.len());
         Ok(results)
@@ -376,36 +371,170 @@ impl EmbeddingService for SimpleEmbeddingService {
     }
 }
 
-impl SimpleEmbeddingService {
-    /// Calculate cosine similarity between two vectors
-    fn calculate_cosine_similarity(&self, vec1: &[f32], vec2: &[f32]) -> f32 {
-        if vec1.len() != vec2.len() {
-            return 0.0;
+/// Calculate cosine similarity between two vectors
+pub(crate) fn cosine_similarity(vec1: &[f32], vec2: &[f32]) -> f32 {
+    if vec1.len() != vec2.len() {
+        return 0.0;
+    }
+
+    let dot_product: f32 = vec1.iter().zip(vec2.iter()).map(|(a, b)| a * b).sum();
+    let norm_a: f32 = vec1.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = vec2.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot_product / (norm_a * norm_b)
+    }
+}
+
+/// Greedy maximal marginal relevance: picks up to `k` candidates, each time the
+/// one maximising `(1 - diversity) * relevance - diversity * max similarity to
+/// the picks so far`. Candidates without a vector are never seen as redundant.
+/// Returns indices into `relevance` in pick order.
+pub(crate) fn maximal_marginal_relevance(
+    relevance: &[f32],
+    vectors: &[Option<Vec<f32>>],
+    k: usize,
+    diversity: f32,
+) -> Vec<usize> {
+    let diversity = diversity.clamp(0.0, 1.0);
+    let mut remaining: Vec<usize> = (0..relevance.len()).collect();
+    let mut selected: Vec<usize> = Vec::with_capacity(k.min(relevance.len()));
+    // Highest similarity of each candidate to any pick so far
+    let mut redundancy = vec![0.0f32; relevance.len()];
+
+    while selected.len() < k && !remaining.is_empty() {
+        let (position, &best) = remaining
+            .iter()
+            .enumerate()
+            .max_by(|(_, &a), (_, &b)| {
+                let score = |i: usize| (1.0 - diversity) * relevance[i] - diversity * redundancy[i];
+                score(a).total_cmp(&score(b)).then(b.cmp(&a))
+            })
+            .expect("remaining is not empty");
+        remaining.swap_remove(position);
+        selected.push(best);
+
+        if let Some(picked) = &vectors[best] {
+            for &i in &remaining {
+                if let Some(vector) = &vectors[i] {
+                    redundancy[i] = redundancy[i].max(cosine_similarity(picked, vector));
+                }
+            }
         }
+    }
+
+    selected
+}
 
-        let dot_product: f32 = vec1.iter().zip(vec2.iter()).map(|(a, b)| a * b).sum();
-        let norm_a: f32 = vec1.iter().map(|x| x * x).sum::<f32>().sqrt();
-        let norm_b: f32 = vec2.iter().map(|x| x * x).sum::<f32>().sqrt();
+/// Score candidate embeddings against a query vector, keeping those above the
+/// query threshold, ranked by similarity and truncated to `max_results`
+pub(crate) fn rank_by_similarity(
+    query_embedding: &[f32],
+    embeddings: &[ContextEmbedding],
+    query: &VectorSearchQuery,
+) -> Vec<VectorSearchResult> {
+    let mut results = Vec::new();
 
-        if norm_a == 0.0 || norm_b == 0.0 {
-            0.0
-        } else {
-            dot_product / (norm_a * norm_b)
+    for embedding in embeddings {
+        let similarity = cosine_similarity(query_embedding, &embedding.embedding_vector);
+
+        if similarity >= query.similarity_threshold {
+            let distance = 1.0 - similarity; // Convert similarity to distance
+
+            results.push(VectorSearchResult {
+                context_id: embedding.context_id.clone(),
+                similarity_score: similarity,
+                distance,
+                rank: 0, // Will be set after sorting
+                metadata: crate::models::embedding::ResultMetadata {
+                    content_type: embedding.metadata.content_type.clone(),
+                    content_preview: embedding
+                        .chunk
+                        .as_ref()
+                        .map(|chunk| chunk.text.clone())
+                        .unwrap_or_else(|| "Preview not available".to_string()), // Would be filled by caller
+                    match_explanation: format!("Similarity: {:.3}", similarity),
+                    quality_indicators: vec![format!("Quality: {:.2}", embedding.metadata.quality_score)],
+                    chunk: crate::models::embedding::ChunkMatch::from_embedding(embedding),
+                },
+            });
         }
     }
+
+    // Sort by similarity (descending)
+    results.sort_by(|a, b| b.similarity_score.partial_cmp(&a.similarity_score).unwrap());
+
+    // Set ranks and limit results
+    for (i, result) in results.iter_mut().enumerate() {
+        result.rank = i + 1;
+    }
+
+    results.truncate(query.max_results);
+    results
 }
 
 /// Factory for creating embedding services
+///
+/// A configured `model_path` with a transformer `model_type` selects the local
+/// `TransformerEmbeddingService`, `ModelType::Custom("bm25")` selects the
+/// model-free `Bm25EmbeddingService`; anything else falls back to the hash-based
+/// `SimpleEmbeddingService`.
 pub struct EmbeddingServiceFactory;
 
 impl EmbeddingServiceFactory {
     pub fn create_service(config: EmbeddingConfig) -> Box<dyn EmbeddingService> {
-        Box::new(SimpleEmbeddingService::new(config))
+        if Self::uses_local_model(&config) {
+            Box::new(TransformerEmbeddingService::new(config))
+        } else if Self::uses_bm25(&config) {
+            Box::new(Bm25EmbeddingService::new(config))
+        } else {
+            Box::new(SimpleEmbeddingService::new(config))
+        }
+    }
+    
+    /// Like `create_initialized_service`, but the BM25 fallback loads (or builds
+    /// and persists) its vocabulary through the embedding repository
+    pub async fn create_service_with_repository(
+        config: EmbeddingConfig,
+        repository: &SqliteEmbeddingRepository,
+        project_id: Option<&str>,
+    ) -> Result<Box<dyn EmbeddingService>, EmbeddingError> {
+        if Self::uses_bm25(&config) {
+            let service = Bm25EmbeddingService::from_repository(config, repository, project_id).await?;
+            return Ok(Box::new(service));
+        }
+        
+        Self::create_initialized_service(config).await
     }
     
     pub async fn create_initialized_service(config: EmbeddingConfig) -> Result<Box<dyn EmbeddingService>, EmbeddingError> {
+        if Self::uses_bm25(&config) {
+            return Ok(Box::new(Bm25EmbeddingService::new(config)));
+        }
+        
+        if Self::uses_local_model(&config) {
+            let service = TransformerEmbeddingService::new(config);
+            service.initialize().await?;
+            return Ok(Box::new(service));
+        }
+        
         let mut service = SimpleEmbeddingService::new(config);
         service.initialize().await?;
         Ok(Box::new(service))
     }
-}
\ No newline at end of file
+    
+    fn uses_bm25(config: &EmbeddingConfig) -> bool {
+        matches!(&config.model_type, ModelType::Custom(name) if name.eq_ignore_ascii_case(BM25_MODEL_TYPE))
+    }
+    
+    /// Whether the configuration points at an on-disk transformer model
+    fn uses_local_model(config: &EmbeddingConfig) -> bool {
+        config.model_path.is_some()
+            && matches!(
+                config.model_type,
+                ModelType::SentenceTransformer | ModelType::BERT | ModelType::CodeBERT
+            )
+    }
+}
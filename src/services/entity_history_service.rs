@@ -0,0 +1,411 @@
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rmcp::model::ErrorData as McpError;
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// Append-only revision history of the context entities
+#[async_trait]
+pub trait EntityHistoryService: Send + Sync {
+    /// Append a revision for a write. An entity written before history was
+    /// kept first gets a baseline revision holding `previous`.
+    async fn record_revision(&self, revision: NewRevision) -> Result<EntityRevision, McpError>;
+
+    /// Every revision of an entity, oldest first
+    async fn list_revisions(&self, entity_type: &str, entity_id: &str) -> Result<Vec<EntityRevision>, McpError>;
+
+    /// One revision of an entity
+    async fn get_revision(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        revision: u32,
+    ) -> Result<Option<EntityRevision>, McpError>;
+
+    /// The latest revision at or before `as_of` of every entity of a project
+    /// that existed at that time, including the project itself
+    async fn project_as_of(&self, project_id: &str, as_of: DateTime<Utc>) -> Result<Vec<EntityRevision>, McpError>;
+}
+
+/// What a revision did to its entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionOperation {
+    /// State of an entity written before its history was kept
+    Baseline,
+    Create,
+    Update,
+    Delete,
+    Restore,
+}
+
+impl RevisionOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevisionOperation::Baseline => "baseline",
+            RevisionOperation::Create => "create",
+            RevisionOperation::Update => "update",
+            RevisionOperation::Delete => "delete",
+            RevisionOperation::Restore => "restore",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "baseline" => RevisionOperation::Baseline,
+            "create" => RevisionOperation::Create,
+            "delete" => RevisionOperation::Delete,
+            "restore" => RevisionOperation::Restore,
+            _ => RevisionOperation::Update,
+        }
+    }
+}
+
+/// A top-level field that differs between two snapshots; a missing side
+/// means the field was absent or null
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// One recorded state of an entity
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EntityRevision {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub project_id: String,
+    /// Revision number, counting from 1 per entity
+    pub revision: u32,
+    pub operation: RevisionOperation,
+    pub author: Option<String>,
+    /// The entity as written; absent once it was deleted
+    pub snapshot: Option<Value>,
+    /// Fields changed since the previous revision
+    pub diff: Vec<FieldChange>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A write to record in an entity's history
+#[derive(Debug, Clone)]
+pub struct NewRevision {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub project_id: String,
+    pub operation: RevisionOperation,
+    pub author: Option<String>,
+    /// The entity before the write, if it existed
+    pub previous: Option<Value>,
+    /// The entity after the write; `None` for deletions
+    pub snapshot: Option<Value>,
+}
+
+/// Field-level differences between two entity snapshots, in field order
+pub fn diff_snapshots(before: Option<&Value>, after: Option<&Value>) -> Vec<FieldChange> {
+    let field = |snapshot: Option<&Value>, name: &str| {
+        snapshot.and_then(|s| s.get(name)).filter(|v| !v.is_null()).cloned()
+    };
+    let names = |snapshot: Option<&Value>| {
+        snapshot
+            .and_then(|s| s.as_object())
+            .map(|object| object.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+
+    let mut fields = names(before);
+    for name in names(after) {
+        if !fields.contains(&name) {
+            fields.push(name);
+        }
+    }
+    fields
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (field(before, &name), field(after, &name));
+            (old != new).then_some(FieldChange { field: name, before: old, after: new })
+        })
+        .collect()
+}
+
+/// Timestamps are stored at a fixed precision so they compare as text
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn db_error(action: &str, e: impl std::fmt::Display) -> McpError {
+    McpError::internal_error(format!("Failed to {action}: {e}"), None)
+}
+
+const REVISION_COLUMNS: &str =
+    "entity_type, entity_id, project_id, revision, operation, author, snapshot, diff, created_at";
+
+/// SQLite implementation of EntityHistoryService
+pub struct SqliteEntityHistoryService {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteEntityHistoryService {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn json_column<T: DeserializeOwned>(row: &Row, index: usize) -> rusqlite::Result<Option<T>> {
+        row.get::<_, Option<String>>(index)?
+            .map(|text| serde_json::from_str(&text))
+            .transpose()
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+    }
+
+    fn row_to_revision(row: &Row) -> rusqlite::Result<EntityRevision> {
+        let created_at: String = row.get(8)?;
+        Ok(EntityRevision {
+            entity_type: row.get(0)?,
+            entity_id: row.get(1)?,
+            project_id: row.get(2)?,
+            revision: row.get(3)?,
+            operation: RevisionOperation::parse(&row.get::<_, String>(4)?),
+            author: row.get(5)?,
+            snapshot: Self::json_column(row, 6)?,
+            diff: Self::json_column(row, 7)?.unwrap_or_default(),
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(8, Type::Text, Box::new(e)))?,
+        })
+    }
+
+    fn insert(conn: &Connection, revision: &EntityRevision) -> Result<(), McpError> {
+        let snapshot = revision.snapshot.as_ref().map(Value::to_string);
+        let diff = serde_json::to_string(&revision.diff).map_err(|e| db_error("serialize revision diff", e))?;
+        conn.execute(
+            &format!("INSERT INTO entity_revisions ({REVISION_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"),
+            params![
+                revision.entity_type,
+                revision.entity_id,
+                revision.project_id,
+                revision.revision,
+                revision.operation.as_str(),
+                revision.author,
+                snapshot,
+                diff,
+                timestamp(revision.created_at),
+            ],
+        )
+        .map_err(|e| db_error("record revision", e))?;
+        Ok(())
+    }
+
+    fn query(&self, sql: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<EntityRevision>, McpError> {
+        let db = self.db.lock().map_err(|e| db_error("lock database", e))?;
+        let mut stmt = db.prepare(sql).map_err(|e| db_error("prepare statement", e))?;
+        let revisions = stmt
+            .query_map(params, Self::row_to_revision)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| db_error("query revisions", e))?;
+        Ok(revisions)
+    }
+}
+
+#[async_trait]
+impl EntityHistoryService for SqliteEntityHistoryService {
+    async fn record_revision(&self, new: NewRevision) -> Result<EntityRevision, McpError> {
+        let db = self.db.lock().map_err(|e| db_error("lock database", e))?;
+        let tx = db.unchecked_transaction().map_err(|e| db_error("begin transaction", e))?;
+
+        let latest = tx
+            .query_row(
+                &format!(
+                    "SELECT {REVISION_COLUMNS} FROM entity_revisions
+                     WHERE entity_type = ?1 AND entity_id = ?2 ORDER BY revision DESC LIMIT 1"
+                ),
+                params![new.entity_type, new.entity_id],
+                Self::row_to_revision,
+            )
+            .optional()
+            .map_err(|e| db_error("read latest revision", e))?;
+        let now = Utc::now();
+        let (number, before) = match latest {
+            Some(latest) => (latest.revision + 1, latest.snapshot),
+            None => match new.previous {
+                Some(previous) => {
+                    let baseline = EntityRevision {
+                        entity_type: new.entity_type.clone(),
+                        entity_id: new.entity_id.clone(),
+                        project_id: new.project_id.clone(),
+                        revision: 1,
+                        operation: RevisionOperation::Baseline,
+                        author: None,
+                        diff: diff_snapshots(None, Some(&previous)),
+                        snapshot: Some(previous),
+                        created_at: now,
+                    };
+                    Self::insert(&tx, &baseline)?;
+                    (2, baseline.snapshot)
+                }
+                None => (1, None),
+            },
+        };
+
+        let revision = EntityRevision {
+            entity_type: new.entity_type,
+            entity_id: new.entity_id,
+            project_id: new.project_id,
+            revision: number,
+            operation: new.operation,
+            author: new.author,
+            diff: diff_snapshots(before.as_ref(), new.snapshot.as_ref()),
+            snapshot: new.snapshot,
+            created_at: now,
+        };
+        Self::insert(&tx, &revision)?;
+        tx.commit().map_err(|e| db_error("commit revision", e))?;
+        Ok(revision)
+    }
+
+    async fn list_revisions(&self, entity_type: &str, entity_id: &str) -> Result<Vec<EntityRevision>, McpError> {
+        self.query(
+            &format!(
+                "SELECT {REVISION_COLUMNS} FROM entity_revisions
+                 WHERE entity_type = ?1 AND entity_id = ?2 ORDER BY revision"
+            ),
+            &[&entity_type, &entity_id],
+        )
+    }
+
+    async fn get_revision(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        revision: u32,
+    ) -> Result<Option<EntityRevision>, McpError> {
+        let revisions = self.query(
+            &format!(
+                "SELECT {REVISION_COLUMNS} FROM entity_revisions
+                 WHERE entity_type = ?1 AND entity_id = ?2 AND revision = ?3"
+            ),
+            &[&entity_type, &entity_id, &revision],
+        )?;
+        Ok(revisions.into_iter().next())
+    }
+
+    async fn project_as_of(&self, project_id: &str, as_of: DateTime<Utc>) -> Result<Vec<EntityRevision>, McpError> {
+        let revisions = self.query(
+            &format!(
+                "SELECT {REVISION_COLUMNS} FROM entity_revisions r
+                 WHERE r.project_id = ?1 AND r.created_at <= ?2
+                   AND r.revision = (
+                       SELECT MAX(revision) FROM entity_revisions
+                       WHERE entity_type = r.entity_type AND entity_id = r.entity_id AND created_at <= ?2
+                   )
+                 ORDER BY r.entity_type, r.created_at, r.entity_id"
+            ),
+            &[&project_id, &timestamp(as_of)],
+        )?;
+        Ok(revisions.into_iter().filter(|r| r.snapshot.is_some()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+    use serde_json::json;
+
+    fn service() -> SqliteEntityHistoryService {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        SqliteEntityHistoryService::new(Arc::new(Mutex::new(conn)))
+    }
+
+    fn write(operation: RevisionOperation, previous: Option<Value>, snapshot: Option<Value>) -> NewRevision {
+        NewRevision {
+            entity_type: "business_rule".to_string(),
+            entity_id: "rule-1".to_string(),
+            project_id: "shop".to_string(),
+            operation,
+            author: Some("alice".to_string()),
+            previous,
+            snapshot,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_changed_fields_only() {
+        let before = json!({"id": "r", "rule_name": "Refunds", "description": null, "domain_area": "billing"});
+        let after =
+            json!({"id": "r", "rule_name": "Refund window", "description": "30 days", "domain_area": "billing"});
+
+        let diff = diff_snapshots(Some(&before), Some(&after));
+        let fields: Vec<&str> = diff.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, ["rule_name", "description"]);
+        assert_eq!(diff[1].before, None);
+        assert_eq!(diff[1].after, Some(json!("30 days")));
+        assert!(diff_snapshots(Some(&before), Some(&before)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_revisions_are_numbered_and_diffed_against_the_previous_one() {
+        let history = service();
+        let v1 = json!({"id": "rule-1", "rule_name": "Refunds"});
+        let v2 = json!({"id": "rule-1", "rule_name": "Refund window"});
+
+        history.record_revision(write(RevisionOperation::Create, None, Some(v1.clone()))).await.unwrap();
+        history.record_revision(write(RevisionOperation::Update, Some(v1.clone()), Some(v2.clone()))).await.unwrap();
+        let deleted = history.record_revision(write(RevisionOperation::Delete, Some(v2), None)).await.unwrap();
+        assert_eq!(deleted.revision, 3);
+        assert_eq!(deleted.diff.len(), 2);
+
+        let revisions = history.list_revisions("business_rule", "rule-1").await.unwrap();
+        let operations: Vec<_> = revisions.iter().map(|r| r.operation).collect();
+        assert_eq!(operations, [RevisionOperation::Create, RevisionOperation::Update, RevisionOperation::Delete]);
+        assert_eq!(revisions[1].diff, vec![FieldChange {
+            field: "rule_name".to_string(),
+            before: Some(json!("Refunds")),
+            after: Some(json!("Refund window")),
+        }]);
+        assert_eq!(revisions[1].author.as_deref(), Some("alice"));
+        let first = history.get_revision("business_rule", "rule-1", 1).await.unwrap().unwrap();
+        assert_eq!(first.snapshot, Some(v1));
+    }
+
+    #[tokio::test]
+    async fn test_entities_written_before_history_get_a_baseline() {
+        let history = service();
+        let old = json!({"id": "rule-1", "rule_name": "Refunds"});
+        let new = json!({"id": "rule-1", "rule_name": "Refund window"});
+
+        let update = write(RevisionOperation::Update, Some(old.clone()), Some(new));
+        let revision = history.record_revision(update).await.unwrap();
+        assert_eq!(revision.revision, 2);
+        let baseline = history.get_revision("business_rule", "rule-1", 1).await.unwrap().unwrap();
+        assert_eq!(baseline.operation, RevisionOperation::Baseline);
+        assert_eq!(baseline.snapshot, Some(old));
+    }
+
+    #[tokio::test]
+    async fn test_project_as_of_skips_later_writes_and_deleted_entities() {
+        let history = service();
+        let v1 = json!({"id": "rule-1", "rule_name": "Refunds"});
+        let v2 = json!({"id": "rule-1", "rule_name": "Refund window"});
+
+        history.record_revision(write(RevisionOperation::Create, None, Some(v1.clone()))).await.unwrap();
+        let after_create = Utc::now();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        history.record_revision(write(RevisionOperation::Update, Some(v1.clone()), Some(v2.clone()))).await.unwrap();
+        let after_update = Utc::now();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        history.record_revision(write(RevisionOperation::Delete, Some(v2.clone()), None)).await.unwrap();
+
+        let snapshot =
+            |revisions: Vec<EntityRevision>| revisions.into_iter().map(|r| r.snapshot.unwrap()).collect::<Vec<_>>();
+        assert_eq!(snapshot(history.project_as_of("shop", after_create).await.unwrap()), vec![v1]);
+        assert_eq!(snapshot(history.project_as_of("shop", after_update).await.unwrap()), vec![v2]);
+        assert!(history.project_as_of("shop", Utc::now()).await.unwrap().is_empty());
+        assert!(history.project_as_of("other", Utc::now()).await.unwrap().is_empty());
+    }
+}
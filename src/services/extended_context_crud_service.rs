@@ -1,4 +1,5 @@
 use crate::models::context::{FeatureContext, ProjectConvention, SecurityPolicy};
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::{
     FeatureContextRepository, ProjectConventionRepository, SecurityPolicyRepository,
 };
@@ -27,6 +28,11 @@ pub trait ExtendedContextCrudService: Send + Sync {
         &self,
         project_id: &str,
     ) -> Result<Vec<SecurityPolicy>, McpError>;
+    async fn list_security_policies_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<SecurityPolicy>, McpError>;
     async fn list_security_policies_by_area(
         &self,
         project_id: &str,
@@ -51,6 +57,11 @@ pub trait ExtendedContextCrudService: Send + Sync {
         &self,
         project_id: &str,
     ) -> Result<Vec<ProjectConvention>, McpError>;
+    async fn list_project_conventions_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<ProjectConvention>, McpError>;
     async fn list_project_conventions_by_type(
         &self,
         project_id: &str,
@@ -79,6 +90,11 @@ pub trait ExtendedContextCrudService: Send + Sync {
         &self,
         project_id: &str,
     ) -> Result<Vec<FeatureContext>, McpError>;
+    async fn list_feature_contexts_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<FeatureContext>, McpError>;
 
     // Bulk operations
     async fn bulk_create_security_policies(
@@ -130,7 +146,6 @@ where
     PCR: ProjectConventionRepository,
     FCR: FeatureContextRepository,
 {
-    #[allow(dead_code)]
     pub fn new(
         security_policy_repository: SPR,
         project_convention_repository: PCR,
@@ -196,6 +211,16 @@ where
             .await
     }
 
+    async fn list_security_policies_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<SecurityPolicy>, McpError> {
+        self.security_policy_repository
+            .list_page_by_project(project_id, query)
+            .await
+    }
+
     async fn list_security_policies_by_area(
         &self,
         project_id: &str,
@@ -253,6 +278,16 @@ where
             .await
     }
 
+    async fn list_project_conventions_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<ProjectConvention>, McpError> {
+        self.project_convention_repository
+            .list_page_by_project(project_id, query)
+            .await
+    }
+
     async fn list_project_conventions_by_type(
         &self,
         project_id: &str,
@@ -322,6 +357,16 @@ where
             .await
     }
 
+    async fn list_feature_contexts_page(
+        &self,
+        project_id: &str,
+        query: &ListQuery,
+    ) -> Result<Page<FeatureContext>, McpError> {
+        self.feature_context_repository
+            .list_page_by_project(project_id, query)
+            .await
+    }
+
     // Bulk Operations Implementation
     async fn bulk_create_security_policies(
         &self,
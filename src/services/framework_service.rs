@@ -1,4 +1,5 @@
 use crate::models::framework::FrameworkComponent;
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::FrameworkRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
@@ -18,6 +19,12 @@ pub trait FrameworkService: Send + Sync {
 
     async fn get_component(&self, id: &str) -> Result<Option<FrameworkComponent>, McpError>;
     async fn list_components(&self, project_id: &str) -> Result<Vec<FrameworkComponent>, McpError>;
+    async fn list_components_page(
+        &self,
+        project_id: &str,
+        layer: Option<&str>,
+        query: &ListQuery,
+    ) -> Result<Page<FrameworkComponent>, McpError>;
     async fn list_components_by_layer(
         &self,
         project_id: &str,
@@ -76,6 +83,17 @@ impl<R: FrameworkRepository + Send + Sync> FrameworkService for FrameworkService
         self.repository.find_by_project_id(project_id).await
     }
 
+    async fn list_components_page(
+        &self,
+        project_id: &str,
+        layer: Option<&str>,
+        query: &ListQuery,
+    ) -> Result<Page<FrameworkComponent>, McpError> {
+        self.repository
+            .find_page_by_project_id(project_id, layer, query)
+            .await
+    }
+
     async fn list_components_by_layer(
         &self,
         project_id: &str,
@@ -1,9 +1,7 @@
-use crate::models::context::{
-    ArchitecturalDecision, BusinessRule, PerformanceRequirement,
-};
-use crate::models::enhanced_context::{EnhancedContextItem, ContextType};
+use crate::models::enhanced_context::{EnhancedContextItem, KeywordSearchResult};
 use crate::models::embedding::VectorSearchQuery;
-use crate::services::context_query_service::{ContextQueryService, ContextQueryResult};
+use crate::repositories::EnhancedContextRepository;
+use crate::services::reranker::{LexicalReranker, Reranker};
 use crate::services::semantic_search_service::{
     SemanticSearchService, EnhancedSearchResult, SemanticSearchError,
 };
@@ -13,19 +11,59 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info};
+use schemars::JsonSchema;
 
 /// Hybrid search result combining traditional and semantic search
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HybridSearchResult {
     pub semantic_results: Vec<EnhancedSearchResult>,
-    pub traditional_results: ContextQueryResult,
+    /// BM25-ranked full-text matches
+    pub traditional_results: Vec<KeywordSearchResult>,
+    /// Semantic and keyword results merged per context, best first
+    pub ranked_results: Vec<RankedSearchResult>,
     pub combined_score: f64,
     pub search_strategy: SearchStrategy,
+    pub fusion_method: FusionMethod,
+    /// Reranker applied to the top results, if any
+    pub reranker: Option<String>,
     pub total_results: usize,
 }
 
+/// One context in the merged hybrid ranking
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RankedSearchResult {
+    pub context_id: String,
+    pub context: Option<EnhancedContextItem>,
+    /// Chunk preview or keyword snippet
+    pub preview: String,
+    pub scores: StageScores,
+}
+
+/// Scores a result received at each ranking stage, for debugging ranking
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct StageScores {
+    pub semantic: Option<f64>,
+    pub semantic_rank: Option<usize>,
+    /// BM25 score, higher is better
+    pub keyword: Option<f64>,
+    pub keyword_rank: Option<usize>,
+    pub fusion: f64,
+    /// Reranker relevance in 0..1, for results within `rerank_top_n`
+    pub rerank: Option<f64>,
+    pub final_score: f64,
+}
+
+/// How semantic and keyword rankings are merged
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub enum FusionMethod {
+    /// `semantic_weight * similarity + traditional_weight * BM25 / best BM25`
+    WeightedSum,
+    /// Sum of `1 / (rrf_k + rank)` over the rankings a result appears in
+    ReciprocalRankFusion,
+}
+
 /// Search strategy used for hybrid search
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum SearchStrategy {
     SemanticOnly,
     TraditionalOnly,
@@ -40,8 +78,18 @@ pub struct HybridSearchConfig {
     pub traditional_weight: f32,
     pub enable_intent_routing: bool,
     pub max_semantic_results: usize,
+    pub max_keyword_results: usize,
     pub similarity_threshold: f32,
     pub enable_result_fusion: bool,
+    pub fusion_method: FusionMethod,
+    /// Damping constant for reciprocal rank fusion
+    pub rrf_k: f64,
+    /// Number of merged results passed to the reranker; 0 disables reranking
+    pub rerank_top_n: usize,
+    /// Share of the final score taken from the reranker, the rest from fusion
+    pub rerank_weight: f32,
+    /// Maximal marginal relevance diversity for the semantic stage; 0 disables it
+    pub diversity: f32,
 }
 
 impl Default for HybridSearchConfig {
@@ -51,8 +99,14 @@ impl Default for HybridSearchConfig {
             traditional_weight: 0.3,
             enable_intent_routing: true,
             max_semantic_results: 20,
+            max_keyword_results: 20,
             similarity_threshold: 0.6,
             enable_result_fusion: true,
+            fusion_method: FusionMethod::WeightedSum,
+            rrf_k: 60.0,
+            rerank_top_n: 20,
+            rerank_weight: 0.5,
+            diversity: 0.0,
         }
     }
 }
@@ -69,6 +123,9 @@ pub enum HybridSearchError {
     #[error("Result fusion error: {message}")]
     ResultFusionError { message: String },
     
+    #[error("Rerank error: {message}")]
+    RerankError { message: String },
+    
     #[error("Configuration error: {message}")]
     ConfigurationError { message: String },
 }
@@ -104,14 +161,13 @@ pub trait HybridSearchService: Send + Sync {
         query: &VectorSearchQuery,
     ) -> Result<Vec<EnhancedSearchResult>, HybridSearchError>;
     
-    /// Perform traditional context query
+    /// Perform full-text keyword search, ranked by BM25
     async fn traditional_search(
         &self,
         project_id: &str,
-        feature_area: &str,
-        task_type: &str,
-        components: &[String],
-    ) -> Result<ContextQueryResult, HybridSearchError>;
+        query_text: &str,
+        max_results: usize,
+    ) -> Result<Vec<KeywordSearchResult>, HybridSearchError>;
     
     /// Get search suggestions based on query intent
     async fn get_search_suggestions(
@@ -124,23 +180,31 @@ pub trait HybridSearchService: Send + Sync {
 /// Implementation of HybridSearchService
 pub struct HybridSearchServiceImpl {
     semantic_search_service: Arc<dyn SemanticSearchService>,
-    context_query_service: Arc<dyn ContextQueryService>,
+    context_repository: Arc<dyn EnhancedContextRepository>,
+    reranker: Arc<dyn Reranker>,
     config: HybridSearchConfig,
 }
 
 impl HybridSearchServiceImpl {
     pub fn new(
         semantic_search_service: Arc<dyn SemanticSearchService>,
-        context_query_service: Arc<dyn ContextQueryService>,
+        context_repository: Arc<dyn EnhancedContextRepository>,
         config: HybridSearchConfig,
     ) -> Self {
         Self {
             semantic_search_service,
-            context_query_service,
+            context_repository,
+            reranker: Arc::new(LexicalReranker::default()),
             config,
         }
     }
     
+    /// Replace the default lexical reranker, e.g. with a `CrossEncoderReranker`
+    pub fn with_reranker(mut self, reranker: Arc<dyn Reranker>) -> Self {
+        self.reranker = reranker;
+        self
+    }
+    
     /// Determine search strategy based on query intent and configuration
     fn determine_search_strategy(&self, query_text: &str) -> SearchStrategy {
         if !self.config.enable_intent_routing {
@@ -176,15 +240,18 @@ impl HybridSearchServiceImpl {
     fn fuse_results(
         &self,
         semantic_results: Vec<EnhancedSearchResult>,
-        traditional_results: ContextQueryResult,
+        traditional_results: Vec<KeywordSearchResult>,
         strategy: SearchStrategy,
     ) -> HybridSearchResult {
         if !self.config.enable_result_fusion {
             return HybridSearchResult {
                 semantic_results,
                 traditional_results,
+                ranked_results: Vec::new(),
                 combined_score: 0.0,
                 search_strategy: strategy,
+                fusion_method: self.config.fusion_method,
+                reranker: None,
                 total_results: 0,
             };
         }
@@ -202,22 +269,133 @@ impl HybridSearchServiceImpl {
             }
         };
         
-        let total_results = semantic_results.len() + 
-                          traditional_results.business_rules.len() +
-                          traditional_results.architectural_decisions.len() +
-                          traditional_results.performance_requirements.len() +
-                          traditional_results.security_policies.len() +
-                          traditional_results.project_conventions.len();
+        let ranked_results = self.merge_rankings(&semantic_results, &traditional_results);
+        let total_results = ranked_results.len();
         
         HybridSearchResult {
             semantic_results,
             traditional_results,
+            ranked_results,
             combined_score,
             search_strategy: strategy,
+            fusion_method: self.config.fusion_method,
+            reranker: None,
             total_results,
         }
     }
     
+    /// Merge both rankings into one entry per context, ordered by fusion score
+    fn merge_rankings(
+        &self,
+        semantic_results: &[EnhancedSearchResult],
+        traditional_results: &[KeywordSearchResult],
+    ) -> Vec<RankedSearchResult> {
+        let mut merged: Vec<RankedSearchResult> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut entry = |context_id: &str| -> usize {
+            *positions.entry(context_id.to_string()).or_insert_with(|| {
+                merged.push(RankedSearchResult {
+                    context_id: context_id.to_string(),
+                    context: None,
+                    preview: String::new(),
+                    scores: StageScores::default(),
+                });
+                merged.len() - 1
+            })
+        };
+        
+        let mut semantic_entries = Vec::new();
+        for (rank, result) in semantic_results.iter().enumerate() {
+            semantic_entries.push((entry(&result.vector_result.context_id), rank, result));
+        }
+        let mut keyword_entries = Vec::new();
+        for (rank, result) in traditional_results.iter().enumerate() {
+            keyword_entries.push((entry(&result.context.id), rank, result));
+        }
+        
+        for (index, rank, result) in semantic_entries {
+            let ranked = &mut merged[index];
+            if ranked.scores.semantic_rank.is_none() {
+                ranked.scores.semantic = Some(result.vector_result.similarity_score as f64);
+                ranked.scores.semantic_rank = Some(rank + 1);
+                ranked.context = result.context_item.clone();
+                ranked.preview = result.vector_result.metadata.content_preview.clone();
+            }
+        }
+        for (index, rank, result) in keyword_entries {
+            let ranked = &mut merged[index];
+            if ranked.scores.keyword_rank.is_none() {
+                ranked.scores.keyword = Some(result.score);
+                ranked.scores.keyword_rank = Some(rank + 1);
+                ranked.context.get_or_insert_with(|| result.context.clone());
+                if ranked.preview.is_empty() {
+                    ranked.preview = result.snippet.clone();
+                }
+            }
+        }
+        
+        let best_keyword = traditional_results.iter().map(|r| r.score).fold(0.0, f64::max);
+        for ranked in &mut merged {
+            let scores = &mut ranked.scores;
+            scores.fusion = match self.config.fusion_method {
+                FusionMethod::WeightedSum => {
+                    let keyword = match (scores.keyword, best_keyword > 0.0) {
+                        (Some(score), true) => score / best_keyword,
+                        _ => 0.0,
+                    };
+                    scores.semantic.unwrap_or(0.0) * self.config.semantic_weight as f64
+                        + keyword * self.config.traditional_weight as f64
+                }
+                FusionMethod::ReciprocalRankFusion => [scores.semantic_rank, scores.keyword_rank]
+                    .iter()
+                    .flatten()
+                    .map(|rank| 1.0 / (self.config.rrf_k + *rank as f64))
+                    .sum(),
+            };
+            scores.final_score = scores.fusion;
+        }
+        
+        sort_by_final_score(&mut merged);
+        merged
+    }
+    
+    /// Rescore the top merged results with the reranker, blending its score with
+    /// the normalized fusion score. Returns the reranker name if it ran.
+    async fn rerank(
+        &self,
+        query_text: &str,
+        ranked_results: &mut [RankedSearchResult],
+    ) -> Result<Option<String>, HybridSearchError> {
+        let top_n = self.config.rerank_top_n.min(ranked_results.len());
+        if top_n == 0 {
+            return Ok(None);
+        }
+        
+        // Semantic matches may come without their context item
+        for ranked in ranked_results[..top_n].iter_mut().filter(|r| r.context.is_none()) {
+            ranked.context = self.context_repository.find_context_by_id(&ranked.context_id).await?;
+        }
+        
+        let rerank_scores = self.reranker.rerank(query_text, &ranked_results[..top_n]).await?;
+        if rerank_scores.len() != top_n {
+            return Err(HybridSearchError::RerankError {
+                message: format!("Reranker returned {} scores for {} results", rerank_scores.len(), top_n),
+            });
+        }
+        
+        let best_fusion = ranked_results.iter().map(|r| r.scores.fusion).fold(0.0, f64::max);
+        let weight = self.config.rerank_weight.clamp(0.0, 1.0) as f64;
+        for (i, ranked) in ranked_results.iter_mut().enumerate() {
+            let fusion = if best_fusion > 0.0 { ranked.scores.fusion / best_fusion } else { 0.0 };
+            ranked.scores.rerank = rerank_scores.get(i).copied();
+            // Results past the top N keep their order below the reranked ones
+            ranked.scores.final_score = (1.0 - weight) * fusion + weight * ranked.scores.rerank.unwrap_or(0.0);
+        }
+        sort_by_final_score(ranked_results);
+        
+        Ok(Some(self.reranker.name().to_string()))
+    }
+    
     /// Calculate quality score for semantic results
     fn calculate_semantic_results_score(&self, results: &[EnhancedSearchResult]) -> f64 {
         if results.is_empty() {
@@ -231,79 +409,24 @@ impl HybridSearchServiceImpl {
         (total_score / results.len() as f32) as f64
     }
     
-    /// Calculate quality score for traditional results
-    fn calculate_traditional_results_score(&self, results: &ContextQueryResult) -> f64 {
-        let total_items = results.business_rules.len() +
-                         results.architectural_decisions.len() +
-                         results.performance_requirements.len() +
-                         results.security_policies.len() +
-                         results.project_conventions.len();
-        
-        if total_items == 0 {
-            0.0
-        } else {
-            // Simple scoring based on number of results found
-            // In a real implementation, this would consider result quality and relevance
-            (total_items as f64 / 10.0).min(1.0)
-        }
-    }
-    
-    /// Convert traditional context results to enhanced context items for unified processing
-    fn convert_traditional_to_enhanced(&self, traditional_results: &ContextQueryResult, project_id: &str) -> Vec<EnhancedContextItem> {
-        let mut enhanced_items = Vec::new();
-        
-        // Convert business rules
-        for rule in &traditional_results.business_rules {
-            let content = crate::models::enhanced_context::ContextContent {
-                content_type: ContextType::BusinessRule,
-                title: rule.rule_name.clone(),
-                description: rule.description.clone().unwrap_or_default(),
-                data: serde_json::to_value(rule).unwrap_or_default(),
-                source_file: None,
-                source_line: None,
-            };
-            
-            let mut item = EnhancedContextItem::new(project_id.to_string(), content);
-            item.id = rule.id.clone();
-            enhanced_items.push(item);
-        }
-        
-        // Convert architectural decisions
-        for decision in &traditional_results.architectural_decisions {
-            let content = crate::models::enhanced_context::ContextContent {
-                content_type: ContextType::ArchitecturalDecision,
-                title: decision.decision_title.clone(),
-                description: decision.context.clone().unwrap_or_default(),
-                data: serde_json::to_value(decision).unwrap_or_default(),
-                source_file: None,
-                source_line: None,
-            };
-            
-            let mut item = EnhancedContextItem::new(project_id.to_string(), content);
-            item.id = decision.id.clone();
-            enhanced_items.push(item);
-        }
-        
-        // Convert performance requirements
-        for requirement in &traditional_results.performance_requirements {
-            let content = crate::models::enhanced_context::ContextContent {
-                content_type: ContextType::PerformanceRequirement,
-                title: requirement.requirement_type.clone().unwrap_or_default(),
-                description: requirement.target_value.clone().unwrap_or_default(),
-                data: serde_json::to_value(requirement).unwrap_or_default(),
-                source_file: None,
-                source_line: None,
-            };
-            
-            let mut item = EnhancedContextItem::new(project_id.to_string(), content);
-            item.id = requirement.id.clone();
-            enhanced_items.push(item);
+    /// Calculate quality score for keyword results, mapping unbounded BM25 scores into 0..1
+    fn calculate_traditional_results_score(&self, results: &[KeywordSearchResult]) -> f64 {
+        if results.is_empty() {
+            return 0.0;
         }
         
-        enhanced_items
+        let total_score: f64 = results.iter()
+            .map(|r| r.score / (1.0 + r.score))
+            .sum();
+        
+        total_score / results.len() as f64
     }
 }
 
+fn sort_by_final_score(results: &mut [RankedSearchResult]) {
+    results.sort_by(|a, b| b.scores.final_score.total_cmp(&a.scores.final_score));
+}
+
 #[async_trait]
 impl HybridSearchService for HybridSearchServiceImpl {
     async fn hybrid_search(
@@ -320,13 +443,7 @@ impl HybridSearchService for HybridSearchServiceImpl {
         debug!("Selected search strategy: {:?}", strategy);
         
         let mut semantic_results = Vec::new();
-        let mut traditional_results = ContextQueryResult {
-            business_rules: Vec::new(),
-            architectural_decisions: Vec::new(),
-            performance_requirements: Vec::new(),
-            security_policies: Vec::new(),
-            project_conventions: Vec::new(),
-        };
+        let mut traditional_results = Vec::new();
         
         match strategy {
             SearchStrategy::SemanticOnly | SearchStrategy::Hybrid | SearchStrategy::IntentBased => {
@@ -340,7 +457,8 @@ impl HybridSearchService for HybridSearchServiceImpl {
                         ..Default::default()
                     },
                     ..Default::default()
-                };
+                }
+                .with_diversity(self.config.diversity);
                 
                 semantic_results = self.semantic_search_service.search(&query).await?;
                 debug!("Semantic search returned {} results", semantic_results.len());
@@ -350,23 +468,25 @@ impl HybridSearchService for HybridSearchServiceImpl {
         
         match strategy {
             SearchStrategy::TraditionalOnly | SearchStrategy::Hybrid => {
-                // Perform traditional search if we have the required parameters
-                if let (Some(feature_area), Some(task_type)) = (feature_area, task_type) {
-                    traditional_results = self.context_query_service
-                        .query_context(project_id, feature_area, task_type, components)
-                        .await?;
-                    
-                    debug!("Traditional search returned {} business rules, {} architectural decisions, {} performance requirements",
-                           traditional_results.business_rules.len(),
-                           traditional_results.architectural_decisions.len(),
-                           traditional_results.performance_requirements.len());
-                }
+                // Feature area, task type and components widen the keyword query
+                let keyword_query = std::iter::once(query_text)
+                    .chain(feature_area)
+                    .chain(task_type)
+                    .chain(components.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                traditional_results = self
+                    .traditional_search(project_id, &keyword_query, self.config.max_keyword_results)
+                    .await?;
+                
+                debug!("Keyword search returned {} results", traditional_results.len());
             }
             _ => {}
         }
         
-        // Fuse results
-        let hybrid_result = self.fuse_results(semantic_results, traditional_results, strategy);
+        // Fuse results, then rerank the best of them
+        let mut hybrid_result = self.fuse_results(semantic_results, traditional_results, strategy);
+        hybrid_result.reranker = self.rerank(query_text, &mut hybrid_result.ranked_results).await?;
         
         info!("Hybrid search completed: {} total results with combined score {:.3}",
               hybrid_result.total_results, hybrid_result.combined_score);
@@ -386,13 +506,12 @@ impl HybridSearchService for HybridSearchServiceImpl {
     async fn traditional_search(
         &self,
         project_id: &str,
-        feature_area: &str,
-        task_type: &str,
-        components: &[String],
-    ) -> Result<ContextQueryResult, HybridSearchError> {
-        debug!("Performing traditional-only search");
-        let results = self.context_query_service
-            .query_context(project_id, feature_area, task_type, components)
+        query_text: &str,
+        max_results: usize,
+    ) -> Result<Vec<KeywordSearchResult>, HybridSearchError> {
+        debug!("Performing keyword-only search");
+        let results = self.context_repository
+            .search_contexts(Some(project_id), query_text, max_results)
             .await?;
         Ok(results)
     }
@@ -441,6 +560,8 @@ mod tests {
     use crate::models::embedding::VectorSearchQuery;
     use crate::services::semantic_search_service::{EnhancedSearchResult, SearchMetadata};
     use crate::models::embedding::{VectorSearchResult, ResultMetadata};
+    use crate::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem};
+    use crate::infrastructure::SqliteEnhancedContextRepository;
     use std::sync::Arc;
 
     
@@ -470,6 +591,7 @@ mod tests {
                         content_preview: "Test business rule".to_string(),
                         match_explanation: "High similarity match".to_string(),
                         quality_indicators: vec!["High quality".to_string()],
+                        chunk: None,
                     },
                 },
                 context_item: None,
@@ -511,6 +633,7 @@ mod tests {
                 average_embedding_quality: 0.8,
                 index_freshness_score: 0.9,
                 last_updated: chrono::Utc::now(),
+                vector_index: None,
             })
         }
         
@@ -519,36 +642,37 @@ mod tests {
         }
     }
     
-    struct MockContextQueryService;
+    fn context_repository() -> Arc<SqliteEnhancedContextRepository> {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let repository = SqliteEnhancedContextRepository::new(Arc::new(std::sync::Mutex::new(conn)));
+        repository.initialize_tables().unwrap();
+        Arc::new(repository)
+    }
     
-    #[async_trait]
-    impl ContextQueryService for MockContextQueryService {
-        async fn query_context(
-            &self,
-            _project_id: &str,
-            _feature_area: &str,
-            _task_type: &str,
-            _components: &[String],
-        ) -> Result<ContextQueryResult, McpError> {
-            Ok(ContextQueryResult {
-                business_rules: Vec::new(),
-                architectural_decisions: Vec::new(),
-                performance_requirements: Vec::new(),
-                security_policies: Vec::new(),
-                project_conventions: Vec::new(),
-            })
-        }
+    async fn add_context(repository: &SqliteEnhancedContextRepository, id: &str, title: &str, description: &str) {
+        let content = ContextContent {
+            content_type: ContextType::SecurityPolicy,
+            title: title.to_string(),
+            description: description.to_string(),
+            data: serde_json::json!({ "scope": "api" }),
+            source_file: None,
+            source_line: None,
+        };
+        let mut context = EnhancedContextItem::new("test-project".to_string(), content);
+        context.id = id.to_string();
+        repository.create_context(&context).await.unwrap();
     }
     
     #[tokio::test]
     async fn test_hybrid_search() {
         let semantic_service = Arc::new(MockSemanticSearchService);
-        let context_service = Arc::new(MockContextQueryService);
+        let context_repository = context_repository();
+        add_context(&context_repository, "policy-1", "Authentication", "All endpoints require authentication").await;
         let config = HybridSearchConfig::default();
         
         let hybrid_service = HybridSearchServiceImpl::new(
             semantic_service,
-            context_service,
+            context_repository,
             config,
         );
         
@@ -562,17 +686,109 @@ mod tests {
         
         assert_eq!(result.search_strategy, SearchStrategy::Hybrid);
         assert!(!result.semantic_results.is_empty());
+        assert_eq!(result.traditional_results[0].context.id, "policy-1");
+        assert_eq!(result.total_results, 2);
+    }
+    
+    #[tokio::test]
+    async fn test_traditional_search_ranks_with_bm25() {
+        let context_repository = context_repository();
+        add_context(&context_repository, "policy-1", "Session cookies", "Cookies are marked secure and httpOnly").await;
+        add_context(&context_repository, "policy-2", "Token rotation", "Rotate access tokens; rotated tokens are revoked").await;
+        add_context(&context_repository, "policy-3", "Rate limiting", "Login attempts are limited per token bucket").await;
+        
+        let hybrid_service = HybridSearchServiceImpl::new(
+            Arc::new(MockSemanticSearchService),
+            context_repository.clone(),
+            HybridSearchConfig::default(),
+        );
+        
+        // Stemming matches "rotating" to "rotate"/"rotated"; the title match ranks first
+        let results = hybrid_service.traditional_search("test-project", "rotating tokens", 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].context.id, "policy-2");
+        assert!(results[0].score > results[1].score);
+        assert!(results[0].snippet.contains("**"));
+        
+        // Edits are picked up by the triggers
+        let mut updated = context_repository.find_context_by_id("policy-1").await.unwrap().unwrap();
+        updated.content.description = "Session tokens live in secure cookies".to_string();
+        context_repository.update_context(&updated).await.unwrap();
+        context_repository.delete_context("policy-3").await.unwrap();
+        
+        let results = hybrid_service.traditional_search("test-project", "tokens", 10).await.unwrap();
+        let ids: Vec<_> = results.iter().map(|r| r.context.id.as_str()).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&"policy-1") && !ids.contains(&"policy-3"));
+        
+        // FTS syntax in user input is treated as plain words
+        assert!(hybrid_service.traditional_search("test-project", "\"unbalanced AND (", 10).await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_ranked_results_record_stage_scores() {
+        let context_repository = context_repository();
+        add_context(&context_repository, "test-context-1", "Business rule", "Orders over the limit need approval").await;
+        add_context(&context_repository, "policy-1", "Token rotation", "Rotate access tokens on every use").await;
+        add_context(&context_repository, "policy-2", "Token storage", "Tokens are kept in secure storage").await;
+        
+        for fusion_method in [FusionMethod::WeightedSum, FusionMethod::ReciprocalRankFusion] {
+            let hybrid_service = HybridSearchServiceImpl::new(
+                Arc::new(MockSemanticSearchService),
+                context_repository.clone(),
+                HybridSearchConfig { fusion_method, ..Default::default() },
+            );
+            
+            let result = hybrid_service.hybrid_search("test-project", "token rotation", None, None, &[]).await.unwrap();
+            
+            assert_eq!(result.fusion_method, fusion_method);
+            assert_eq!(result.reranker.as_deref(), Some("lexical"));
+            assert_eq!(result.total_results, 3);
+            let ids: Vec<_> = result.ranked_results.iter().map(|r| r.context_id.as_str()).collect();
+            assert_eq!(ids[0], "policy-1", "{:?}", fusion_method);
+            
+            // The semantic-only match was loaded from the repository for reranking
+            let semantic = result.ranked_results.iter().find(|r| r.context_id == "test-context-1").unwrap();
+            assert_eq!(semantic.scores.semantic_rank, Some(1));
+            assert!(semantic.scores.keyword.is_none());
+            assert!(semantic.context.is_some());
+            
+            let top = &result.ranked_results[0].scores;
+            assert_eq!(top.keyword_rank, Some(1));
+            assert!(top.keyword.unwrap() > 0.0);
+            assert!(top.fusion > 0.0);
+            assert!(top.rerank.unwrap() > 0.0);
+            assert!(result.ranked_results.windows(2).all(|w| w[0].scores.final_score >= w[1].scores.final_score));
+        }
+        
+        // Without reranking the final score is the fusion score
+        let hybrid_service = HybridSearchServiceImpl::new(
+            Arc::new(MockSemanticSearchService),
+            context_repository.clone(),
+            HybridSearchConfig {
+                fusion_method: FusionMethod::ReciprocalRankFusion,
+                rerank_top_n: 0,
+                ..Default::default()
+            },
+        );
+        let result = hybrid_service.hybrid_search("test-project", "token rotation", None, None, &[]).await.unwrap();
+        assert!(result.reranker.is_none());
+        for ranked in &result.ranked_results {
+            assert!(ranked.scores.rerank.is_none());
+            assert_eq!(ranked.scores.final_score, ranked.scores.fusion);
+        }
+        let first = &result.ranked_results[0].scores;
+        assert!((first.fusion - 1.0 / 61.0).abs() < 1e-9);
     }
     
     #[tokio::test]
     async fn test_search_suggestions() {
         let semantic_service = Arc::new(MockSemanticSearchService);
-        let context_service = Arc::new(MockContextQueryService);
         let config = HybridSearchConfig::default();
         
         let hybrid_service = HybridSearchServiceImpl::new(
             semantic_service,
-            context_service,
+            context_repository(),
             config,
         );
         
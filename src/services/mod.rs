@@ -3,21 +3,31 @@
 pub mod advanced_query_service;
 pub mod analytics_helper;
 pub mod analytics_service;
+pub mod bm25_embedding_service;
 pub mod architecture_validation_service;
 pub mod context_crud_service;
 pub mod context_intelligence_service;
+pub mod context_pack_service;
 pub mod context_quality_service;
 pub mod context_query_service;
 pub mod context_relationship_engine;
 pub mod development_phase_service;
 pub mod embedding_service;
+pub mod entity_history_service;
 pub mod extended_context_crud_service;
 pub mod framework_service;
+pub mod project_bundle_service;
 pub mod project_service;
 pub mod semantic_search_service;
 pub mod hybrid_search_service;
+pub mod reranker;
 pub mod search_index_manager;
+pub mod transformer_embedding_service;
+pub mod vector_index;
+pub mod text_chunker;
+pub mod trash_service;
 pub mod specification_parser;
+pub mod search_query_parser;
 pub mod specification_service;
 pub mod specification_import_service;
 pub mod specification_versioning_service;
@@ -62,17 +72,27 @@ pub use analytics_helper::AnalyticsHelper;
 pub use analytics_service::{AnalyticsService, DefaultAnalyticsService, AnalyticsEvent, AnalyticsEventType, UsageStatistics, ProjectInsights};
 pub use architecture_validation_service::ArchitectureValidationService;
 pub use context_intelligence_service::{ContextIntelligenceService, DefaultContextIntelligenceService};
+pub use context_pack_service::{ContextPackService, DefaultContextPackService};
 pub use context_quality_service::{ContextQualityService, DefaultContextQualityService};
 pub use context_query_service::ContextQueryService;
 pub use context_relationship_engine::{ContextRelationshipEngine, DefaultContextRelationshipEngine};
 pub use development_phase_service::DevelopmentPhaseService;
 pub use embedding_service::{EmbeddingService, EmbeddingServiceFactory};
+pub use entity_history_service::{EntityHistoryService, SqliteEntityHistoryService, EntityRevision, NewRevision, RevisionOperation, FieldChange};
+pub use trash_service::{TrashService, SqliteTrashService, TrashedEntity};
+pub use project_bundle_service::{ProjectBundleService, SqliteProjectBundleService, ProjectBundle, BundleFormat, ConflictPolicy, ImportOptions, ImportReport};
+pub use bm25_embedding_service::Bm25EmbeddingService;
+pub use transformer_embedding_service::TransformerEmbeddingService;
 pub use framework_service::FrameworkService;
 pub use project_service::ProjectService;
 pub use semantic_search_service::SemanticSearchService;
-pub use hybrid_search_service::{HybridSearchService, HybridSearchServiceImpl};
-pub use search_index_manager::{SearchIndexManager, SearchIndexManagerImpl, IndexManagerConfig};
+pub use hybrid_search_service::{HybridSearchService, HybridSearchServiceImpl, FusionMethod, RankedSearchResult, StageScores};
+pub use reranker::{Reranker, LexicalReranker, CrossEncoderReranker, FieldBoosts};
+pub use search_index_manager::{SearchIndexManager, SearchIndexManagerImpl, IndexManagerConfig, ModelMigrationProgress, ModelMigrationStatus};
+pub use vector_index::{VectorIndex, VectorIndexStats, HnswParams};
+pub use text_chunker::{snippet, ChunkingConfig, TextChunker};
 pub use specification_parser::SpecificationParser;
+pub use search_query_parser::{SearchQueryParser, ParsedSearchQuery, QueryParseError};
 pub use specification_import_service::{SpecificationImportService, DefaultSpecificationImportService, SpecificationChange, ChangeType};
 pub use specification_versioning_service::{SpecificationVersioningService, SqliteSpecificationVersioningService, SpecificationVersion, VersionChangeType, VersionComparison, VersionDifference, DifferenceType};
 pub use specification_service::{SpecificationService, DefaultSpecificationService};
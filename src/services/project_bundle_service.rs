@@ -0,0 +1,585 @@
+use crate::db::context_tables::{
+    project_tables, CONTEXT_ITEM_ROWS, CONTEXT_TABLES, REQUIREMENT_ROWS, SPEC_ROWS, TASK_ROWS,
+};
+use crate::db::migrations::latest_version;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rmcp::model::{ErrorData as McpError, JsonObject};
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{params_from_iter, Connection, OptionalExtension};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// Layout version of the bundles this server writes; newer bundles are refused
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Moving a project's context between databases as a self-contained bundle
+#[async_trait]
+pub trait ProjectBundleService: Send + Sync {
+    /// Every row that belongs to a live project: its entities, enhanced
+    /// context items, specifications with their requirements, tasks, versions
+    /// and links, and optionally its embeddings
+    async fn export_project(&self, project_id: &str, include_embeddings: bool) -> Result<ProjectBundle, McpError>;
+
+    /// Write a bundle's rows into this database. A dry run reports what would
+    /// happen and leaves the database untouched.
+    async fn import_project(&self, bundle: &ProjectBundle, options: &ImportOptions) -> Result<ImportReport, McpError>;
+}
+
+/// A project's rows, by table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectBundle {
+    pub format_version: u32,
+    /// Schema version of the database the bundle was exported from
+    pub schema_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub project_id: String,
+    /// Rows as column-value objects; BLOBs are written as `{"base64": ...}`
+    pub tables: BTreeMap<String, Vec<JsonObject>>,
+}
+
+impl ProjectBundle {
+    /// Whether the bundle carries the project's embeddings
+    pub fn has_embeddings(&self) -> bool {
+        self.tables.get("context_embeddings").is_some_and(|rows| !rows.is_empty())
+    }
+
+    /// Read a bundle written as JSON or YAML
+    pub fn parse(text: &str) -> Result<Self, McpError> {
+        let bundle: Self = match serde_json::from_str(text) {
+            Ok(bundle) => bundle,
+            Err(_) => serde_yaml::from_str(text)
+                .map_err(|e| McpError::invalid_params(format!("Not a project bundle: {e}"), None))?,
+        };
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Bundle format version {} is newer than this server supports ({BUNDLE_FORMAT_VERSION})",
+                    bundle.format_version
+                ),
+                None,
+            ));
+        }
+        Ok(bundle)
+    }
+
+    pub fn render(&self, format: BundleFormat) -> Result<String, McpError> {
+        let rendered = match format {
+            BundleFormat::Json => serde_json::to_string_pretty(self).map_err(|e| e.to_string()),
+            BundleFormat::Yaml => serde_yaml::to_string(self).map_err(|e| e.to_string()),
+        };
+        rendered.map_err(|e| McpError::internal_error(format!("Failed to write bundle: {e}"), None))
+    }
+}
+
+/// Text format of a bundle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+/// What happens to a bundled row whose ID already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Keep the existing row
+    #[default]
+    Skip,
+    /// Replace the existing row with the bundled one
+    Overwrite,
+    /// Keep the existing row, filling in its empty columns from the bundle
+    Merge,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    pub conflict_policy: ConflictPolicy,
+    /// Give every imported row a new ID, importing the project as a copy
+    pub remap_ids: bool,
+    /// Import the bundled project under this ID instead of its own
+    pub target_project_id: Option<String>,
+    pub dry_run: bool,
+}
+
+/// Outcome of importing a bundle, or of a dry run of it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub conflict_policy: ConflictPolicy,
+    /// Project the bundle was imported into
+    pub project_id: String,
+    /// What happened to the rows of each table
+    pub tables: BTreeMap<String, TableImport>,
+    /// Bundle contents that were not imported
+    pub warnings: Vec<String>,
+}
+
+impl ImportReport {
+    /// Rows created, overwritten or merged
+    pub fn changed(&self) -> usize {
+        self.tables.values().map(|t| t.created + t.overwritten + t.merged).sum()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct TableImport {
+    pub created: usize,
+    pub overwritten: usize,
+    pub merged: usize,
+    pub skipped: usize,
+}
+
+/// A table whose rows go into bundles
+struct BundleTable {
+    table: &'static str,
+    /// Condition selecting a project's rows, the project ID being `?1`
+    scope: &'static str,
+    /// Columns identifying a row; `id` everywhere but the link tables
+    key: &'static [&'static str],
+    /// Columns holding IDs of bundled rows, rewritten when IDs are remapped
+    references: &'static [&'static str],
+}
+
+/// Bundled tables, parents before the rows that reference them
+fn bundle_tables(include_embeddings: bool) -> Vec<BundleTable> {
+    let mut tables = vec![BundleTable {
+        table: CONTEXT_TABLES[0].table,
+        scope: "id = ?1 AND deleted_at IS NULL",
+        key: &["id"],
+        references: &[],
+    }];
+    tables.extend(project_tables().iter().map(|t| BundleTable {
+        table: t.table,
+        scope: "project_id = ?1 AND deleted_at IS NULL",
+        key: &["id"],
+        references: &["project_id"],
+    }));
+    let spec_table = |table: &'static str, scope: &'static str, references: &'static [&'static str]| BundleTable {
+        table,
+        scope,
+        key: &["id"],
+        references,
+    };
+    let link_table = |table: &'static str, scope: &'static str, key: &'static [&'static str]| BundleTable {
+        table,
+        scope,
+        key,
+        references: key,
+    };
+    tables.extend([
+        spec_table("enhanced_context_items", "project_id = ?1", &["project_id"]),
+        spec_table("specifications", "project_id = ?1", &["project_id"]),
+        spec_table("requirements", SPEC_ROWS, &["spec_id"]),
+        spec_table("acceptance_criteria", REQUIREMENT_ROWS, &["requirement_id"]),
+        spec_table("tasks", SPEC_ROWS, &["spec_id", "parent_task"]),
+        spec_table("specification_versions", SPEC_ROWS, &["spec_id"]),
+        link_table("task_dependencies", TASK_ROWS, &["task_id", "depends_on_task_id"]),
+        link_table("requirement_context_links", REQUIREMENT_ROWS, &["requirement_id", "context_id"]),
+        link_table("task_context_links", TASK_ROWS, &["task_id", "context_id"]),
+        link_table("task_requirement_links", TASK_ROWS, &["task_id", "requirement_id"]),
+    ]);
+    if include_embeddings {
+        tables.push(spec_table(
+            "context_embeddings",
+            CONTEXT_ITEM_ROWS,
+            &["project_id", "context_id"],
+        ));
+    }
+    tables
+}
+
+pub struct SqliteProjectBundleService {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteProjectBundleService {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn export_rows(conn: &Connection, table: &BundleTable, project_id: &str) -> rusqlite::Result<Vec<JsonObject>> {
+        let mut stmt = conn.prepare(&format!("SELECT * FROM {} WHERE {}", table.table, table.scope))?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(str::to_string).collect();
+        let rows = stmt.query_map([project_id], |row| {
+            let mut object = JsonObject::new();
+            for (index, column) in columns.iter().enumerate() {
+                object.insert(column.clone(), to_json(row.get_ref(index)?));
+            }
+            Ok(object)
+        })?;
+        rows.collect()
+    }
+
+    fn import_table(
+        tx: &Connection,
+        table: &BundleTable,
+        rows: &[JsonObject],
+        ids: &HashMap<String, String>,
+        policy: ConflictPolicy,
+        warnings: &mut Vec<String>,
+    ) -> Result<TableImport, McpError> {
+        let columns = table_columns(tx, table.table).map_err(db_error)?;
+        if columns.is_empty() {
+            warnings.push(format!("Table {} does not exist in this database; its rows were not imported", table.table));
+            return Ok(TableImport { skipped: rows.len(), ..Default::default() });
+        }
+        let mut unknown = HashSet::new();
+        let mut outcome = TableImport::default();
+
+        for row in rows {
+            let mut values: Vec<(&str, SqlValue)> = Vec::new();
+            for (column, value) in row {
+                if !columns.contains(column) {
+                    unknown.insert(column.clone());
+                    continue;
+                }
+                let value = match value {
+                    Value::String(id) if column == "id" || table.references.contains(&column.as_str()) => {
+                        Value::String(ids.get(id).cloned().unwrap_or_else(|| id.clone()))
+                    }
+                    other => other.clone(),
+                };
+                values.push((column.as_str(), to_sql(&value)?));
+            }
+            let key: Vec<SqlValue> = table
+                .key
+                .iter()
+                .map(|k| values.iter().find(|(column, _)| column == k).map(|(_, v)| v.clone()))
+                .collect::<Option<_>>()
+                .ok_or_else(|| {
+                    McpError::invalid_params(
+                        format!("A row of {} is missing its key column ({})", table.table, table.key.join(", ")),
+                        None,
+                    )
+                })?;
+
+            let key_condition = table.key.iter().map(|k| format!("{k} = ?")).collect::<Vec<_>>().join(" AND ");
+            let existing = Self::existing_row(tx, table.table, &key_condition, &key).map_err(db_error)?;
+            let names = values.iter().map(|(column, _)| *column).collect::<Vec<_>>().join(", ");
+            let placeholders = vec!["?"; values.len()].join(", ");
+            // UPDATE rather than REPLACE, which deletes without firing delete triggers
+            // and would leave stale full-text entries behind
+            let update = |assigned: &[&(&str, SqlValue)]| {
+                let assignments = assigned.iter().map(|(column, _)| format!("{column} = ?")).collect::<Vec<_>>();
+                tx.execute(
+                    &format!("UPDATE {} SET {} WHERE {key_condition}", table.table, assignments.join(", ")),
+                    params_from_iter(assigned.iter().map(|(_, v)| v).chain(key.iter())),
+                )
+                .map_err(db_error)
+            };
+
+            match (existing, policy) {
+                // A row can still collide on a unique column other than its key
+                (None, ConflictPolicy::Overwrite) => {
+                    let assignments =
+                        values.iter().map(|(column, _)| format!("{column} = excluded.{column}")).collect::<Vec<_>>();
+                    let upsert = format!(
+                        "INSERT INTO {} ({names}) VALUES ({placeholders}) ON CONFLICT DO UPDATE SET {}",
+                        table.table,
+                        assignments.join(", ")
+                    );
+                    match tx.execute(&upsert, params_from_iter(values.iter().map(|(_, v)| v))).map_err(db_error)? {
+                        0 => outcome.skipped += 1,
+                        _ => outcome.created += 1,
+                    }
+                }
+                (None, _) => match tx
+                    .execute(
+                        &format!("INSERT OR IGNORE INTO {} ({names}) VALUES ({placeholders})", table.table),
+                        params_from_iter(values.iter().map(|(_, v)| v)),
+                    )
+                    .map_err(db_error)?
+                {
+                    0 => outcome.skipped += 1,
+                    _ => outcome.created += 1,
+                },
+                (Some(_), ConflictPolicy::Skip) => outcome.skipped += 1,
+                (Some(_), ConflictPolicy::Overwrite) => {
+                    update(&values.iter().collect::<Vec<_>>())?;
+                    outcome.overwritten += 1;
+                }
+                (Some(current), ConflictPolicy::Merge) => {
+                    let gaps: Vec<&(&str, SqlValue)> = values
+                        .iter()
+                        .filter(|(column, value)| {
+                            *value != SqlValue::Null && current.get(*column).is_none_or(|v| *v == SqlValue::Null)
+                        })
+                        .collect();
+                    if gaps.is_empty() {
+                        outcome.skipped += 1;
+                        continue;
+                    }
+                    update(&gaps)?;
+                    outcome.merged += 1;
+                }
+            }
+        }
+
+        let mut unknown: Vec<_> = unknown.into_iter().collect();
+        unknown.sort();
+        for column in unknown {
+            warnings.push(format!("Column {column} of {} is not in this database's schema; it was not imported", table.table));
+        }
+        Ok(outcome)
+    }
+
+    fn existing_row(
+        conn: &Connection,
+        table: &str,
+        key_condition: &str,
+        key: &[SqlValue],
+    ) -> rusqlite::Result<Option<HashMap<String, SqlValue>>> {
+        let mut stmt = conn.prepare(&format!("SELECT * FROM {table} WHERE {key_condition}"))?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(str::to_string).collect();
+        stmt.query_row(params_from_iter(key.iter()), |row| {
+            let mut values = HashMap::new();
+            for (index, column) in columns.iter().enumerate() {
+                values.insert(column.clone(), row.get::<_, SqlValue>(index)?);
+            }
+            Ok(values)
+        })
+        .optional()
+    }
+}
+
+fn db_error(e: rusqlite::Error) -> McpError {
+    McpError::internal_error(format!("Database error: {}", e), None)
+}
+
+fn table_columns(conn: &Connection, table: &str) -> rusqlite::Result<HashSet<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1)")?;
+    let columns = stmt.query_map([table], |row| row.get(0))?;
+    columns.collect()
+}
+
+fn to_json(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => json!(i),
+        ValueRef::Real(f) => json!(f),
+        ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+        ValueRef::Blob(bytes) => json!({"base64": STANDARD.encode(bytes)}),
+    }
+}
+
+fn to_sql(value: &Value) -> Result<SqlValue, McpError> {
+    Ok(match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(i64::from(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        Value::Object(object) if object.len() == 1 && object.contains_key("base64") => {
+            let encoded = object["base64"].as_str().unwrap_or_default();
+            SqlValue::Blob(
+                STANDARD
+                    .decode(encoded)
+                    .map_err(|e| McpError::invalid_params(format!("Invalid base64 in bundle: {e}"), None))?,
+            )
+        }
+        other => SqlValue::Text(other.to_string()),
+    })
+}
+
+#[async_trait]
+impl ProjectBundleService for SqliteProjectBundleService {
+    async fn export_project(&self, project_id: &str, include_embeddings: bool) -> Result<ProjectBundle, McpError> {
+        let conn = self.db.lock().unwrap();
+        let mut tables = BTreeMap::new();
+        for table in bundle_tables(include_embeddings) {
+            let rows = Self::export_rows(&conn, &table, project_id).map_err(db_error)?;
+            if table.table == "projects" && rows.is_empty() {
+                return Err(McpError::invalid_params(format!("Project not found: {project_id}"), None));
+            }
+            if !rows.is_empty() {
+                tables.insert(table.table.to_string(), rows);
+            }
+        }
+        Ok(ProjectBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            schema_version: latest_version(),
+            exported_at: Utc::now(),
+            project_id: project_id.to_string(),
+            tables,
+        })
+    }
+
+    async fn import_project(&self, bundle: &ProjectBundle, options: &ImportOptions) -> Result<ImportReport, McpError> {
+        let tables = bundle_tables(true);
+        let mut warnings = Vec::new();
+        for name in bundle.tables.keys() {
+            if !tables.iter().any(|t| t.table == name) {
+                warnings.push(format!("Table {name} is not part of a project bundle; its rows were not imported"));
+            }
+        }
+        if bundle.schema_version > latest_version() {
+            warnings.push(format!(
+                "Bundle was exported from schema version {}, newer than this database's {}",
+                bundle.schema_version,
+                latest_version()
+            ));
+        }
+
+        // Old ID to the ID the row is imported under
+        let mut ids = HashMap::new();
+        let project_id = match &options.target_project_id {
+            Some(target) => target.clone(),
+            None if options.remap_ids => uuid::Uuid::new_v4().to_string(),
+            None => bundle.project_id.clone(),
+        };
+        ids.insert(bundle.project_id.clone(), project_id.clone());
+        if options.remap_ids {
+            let bundled_ids = tables
+                .iter()
+                .filter(|t| t.key == ["id"])
+                .filter_map(|t| bundle.tables.get(t.table))
+                .flatten()
+                .filter_map(|row| row.get("id").and_then(Value::as_str));
+            for id in bundled_ids {
+                ids.entry(id.to_string()).or_insert_with(|| uuid::Uuid::new_v4().to_string());
+            }
+        }
+
+        let conn = self.db.lock().unwrap();
+        let tx = conn.unchecked_transaction().map_err(db_error)?;
+        let mut report = ImportReport {
+            dry_run: options.dry_run,
+            conflict_policy: options.conflict_policy,
+            project_id,
+            tables: BTreeMap::new(),
+            warnings: Vec::new(),
+        };
+        for table in &tables {
+            let Some(rows) = bundle.tables.get(table.table).filter(|rows| !rows.is_empty()) else {
+                continue;
+            };
+            let outcome = Self::import_table(&tx, table, rows, &ids, options.conflict_policy, &mut warnings)?;
+            report.tables.insert(table.table.to_string(), outcome);
+        }
+        report.warnings = warnings;
+
+        if options.dry_run {
+            tx.rollback().map_err(db_error)?;
+        } else {
+            tx.commit().map_err(db_error)?;
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init::init_db;
+
+    fn service() -> (SqliteProjectBundleService, Arc<Mutex<Connection>>) {
+        let db = Arc::new(Mutex::new(init_db(":memory:").unwrap()));
+        (SqliteProjectBundleService::new(db.clone()), db)
+    }
+
+    fn seed(db: &Arc<Mutex<Connection>>) {
+        db.lock()
+            .unwrap()
+            .execute_batch(
+                "INSERT INTO projects (id, name) VALUES ('p1', 'Shop');
+                 INSERT INTO business_rules (id, project_id, rule_name) VALUES ('r1', 'p1', 'Refunds');
+                 INSERT INTO business_rules (id, project_id, rule_name, deleted_at)
+                     VALUES ('r2', 'p1', 'Trashed', '2026-01-01T00:00:00.000Z');
+                 INSERT INTO specifications (id, project_id, spec_type, title, content_format, raw_content, status,
+                     created_at, updated_at)
+                     VALUES ('s1', 'p1', 'feature', 'Checkout', 'markdown', '# Checkout', 'draft', 'now', 'now');
+                 INSERT INTO tasks (id, spec_id, title, description, status, task_type, created_at, updated_at)
+                     VALUES ('t1', 's1', 'Pay', 'Take payment', 'todo', 'implementation', 'now', 'now');
+                 INSERT INTO task_context_links (task_id, context_id, created_at) VALUES ('t1', 'r1', 'now');",
+            )
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_leaves_out_trashed_rows_and_other_projects() {
+        let (bundles, db) = service();
+        seed(&db);
+        db.lock().unwrap().execute_batch("INSERT INTO projects (id, name) VALUES ('p2', 'Blog')").unwrap();
+
+        let bundle = bundles.export_project("p1", false).await.unwrap();
+        assert_eq!(bundle.tables["projects"].len(), 1);
+        assert_eq!(bundle.tables["business_rules"].len(), 1);
+        assert_eq!(bundle.tables["task_context_links"][0]["context_id"], "r1");
+        assert!(!bundle.has_embeddings());
+
+        let yaml = bundle.render(BundleFormat::Yaml).unwrap();
+        assert_eq!(ProjectBundle::parse(&yaml).unwrap(), bundle);
+        assert!(bundles.export_project("missing", false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remapped_import_rewrites_references() {
+        let (bundles, db) = service();
+        seed(&db);
+        let bundle = bundles.export_project("p1", false).await.unwrap();
+
+        let options = ImportOptions { remap_ids: true, ..Default::default() };
+        let report = bundles.import_project(&bundle, &options).await.unwrap();
+        assert_ne!(report.project_id, "p1");
+        assert_eq!(report.tables["business_rules"].created, 1);
+
+        let conn = db.lock().unwrap();
+        let (rule_id, task_id): (String, String) = conn
+            .query_row(
+                "SELECT r.id, t.id FROM business_rules r, tasks t JOIN specifications s ON s.id = t.spec_id
+                 WHERE r.project_id = ?1 AND s.project_id = ?1",
+                [&report.project_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        let linked: String = conn
+            .query_row("SELECT context_id FROM task_context_links WHERE task_id = ?1", [&task_id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(linked, rule_id);
+    }
+
+    #[tokio::test]
+    async fn test_conflict_policies_and_dry_run() {
+        let (bundles, db) = service();
+        seed(&db);
+        let mut bundle = bundles.export_project("p1", false).await.unwrap();
+        let rule = &mut bundle.tables.get_mut("business_rules").unwrap()[0];
+        rule.insert("rule_name".to_string(), json!("Refund window"));
+        rule.insert("description".to_string(), json!("Within 14 days"));
+        let rule_name = |db: &Arc<Mutex<Connection>>| -> (String, Option<String>) {
+            db.lock()
+                .unwrap()
+                .query_row("SELECT rule_name, description FROM business_rules WHERE id = 'r1'", [], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })
+                .unwrap()
+        };
+
+        let skip = bundles.import_project(&bundle, &ImportOptions::default()).await.unwrap();
+        assert_eq!(skip.tables["business_rules"].skipped, 1);
+        assert_eq!(rule_name(&db), ("Refunds".to_string(), None));
+
+        let merge = ImportOptions { conflict_policy: ConflictPolicy::Merge, ..Default::default() };
+        bundles.import_project(&bundle, &merge).await.unwrap();
+        assert_eq!(rule_name(&db), ("Refunds".to_string(), Some("Within 14 days".to_string())));
+
+        let dry_run = ImportOptions { conflict_policy: ConflictPolicy::Overwrite, dry_run: true, ..Default::default() };
+        let report = bundles.import_project(&bundle, &dry_run).await.unwrap();
+        assert_eq!(report.tables["business_rules"].overwritten, 1);
+        assert_eq!(rule_name(&db).0, "Refunds");
+
+        let overwrite = ImportOptions { conflict_policy: ConflictPolicy::Overwrite, ..Default::default() };
+        bundles.import_project(&bundle, &overwrite).await.unwrap();
+        assert_eq!(rule_name(&db).0, "Refund window");
+    }
+}
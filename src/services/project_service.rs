@@ -1,4 +1,5 @@
 use crate::models::context::Project;
+use crate::models::pagination::{ListQuery, Page};
 use crate::repositories::ProjectRepository;
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
@@ -16,6 +17,7 @@ pub trait ProjectService: Send + Sync {
     #[allow(dead_code)]
     async fn get_project(&self, id: &str) -> Result<Option<Project>, McpError>;
     async fn list_projects(&self) -> Result<Vec<Project>, McpError>;
+    async fn list_projects_page(&self, query: &ListQuery) -> Result<Page<Project>, McpError>;
     #[allow(dead_code)]
     async fn update_project(&self, project: &Project) -> Result<Project, McpError>;
     #[allow(dead_code)]
@@ -64,6 +66,10 @@ impl<R: ProjectRepository> ProjectService for ProjectServiceImpl<R> {
         self.repository.find_all().await
     }
 
+    async fn list_projects_page(&self, query: &ListQuery) -> Result<Page<Project>, McpError> {
+        self.repository.find_page(query).await
+    }
+
     async fn update_project(&self, project: &Project) -> Result<Project, McpError> {
         self.repository.update(project).await
     }
@@ -0,0 +1,269 @@
+use crate::models::enhanced_context::EnhancedContextItem;
+use crate::services::embedding_service::EmbeddingError;
+use crate::services::hybrid_search_service::{HybridSearchError, RankedSearchResult};
+use crate::services::transformer_embedding_service::CrossEncoder;
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use tracing::info;
+
+/// Second-stage scorer applied to the top merged hybrid search candidates
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    /// Name recorded on search results
+    fn name(&self) -> &str;
+
+    /// Relevance of each candidate to the query in 0..1, in candidate order
+    async fn rerank(
+        &self,
+        query: &str,
+        candidates: &[RankedSearchResult],
+    ) -> Result<Vec<f64>, HybridSearchError>;
+}
+
+/// Weight of each field when measuring query term coverage
+#[derive(Debug, Clone)]
+pub struct FieldBoosts {
+    pub title: f64,
+    pub tags: f64,
+    pub description: f64,
+    pub data: f64,
+    /// Added when the whole query appears verbatim in the title or description
+    pub exact_phrase: f64,
+}
+
+impl Default for FieldBoosts {
+    fn default() -> Self {
+        Self {
+            title: 3.0,
+            tags: 2.0,
+            description: 1.5,
+            data: 0.5,
+            exact_phrase: 1.0,
+        }
+    }
+}
+
+/// Reranker scoring the share of query terms found in each field, weighted by
+/// field. Needs no model, so it is the default.
+#[derive(Debug, Clone, Default)]
+pub struct LexicalReranker {
+    boosts: FieldBoosts,
+}
+
+impl LexicalReranker {
+    pub fn new(boosts: FieldBoosts) -> Self {
+        Self { boosts }
+    }
+
+    fn score(&self, query_terms: &[String], query_phrase: &str, candidate: &RankedSearchResult) -> f64 {
+        let boosts = &self.boosts;
+        let total = boosts.title + boosts.tags + boosts.description + boosts.data + boosts.exact_phrase;
+        if query_terms.is_empty() || total <= 0.0 {
+            return 0.0;
+        }
+
+        let coverage = |text: &str| {
+            let field_terms: HashSet<String> = terms(text).into_iter().collect();
+            query_terms.iter().filter(|t| field_terms.contains(*t)).count() as f64 / query_terms.len() as f64
+        };
+
+        let (title, tags, description, data) = match &candidate.context {
+            Some(context) => (
+                context.content.title.clone(),
+                context.metadata.tags.join(" "),
+                context.content.description.clone(),
+                context.content.data.to_string(),
+            ),
+            None => (String::new(), String::new(), candidate.preview.clone(), String::new()),
+        };
+
+        let has_phrase = query_terms.len() > 1
+            && [&title, &description].iter().any(|field| field.to_lowercase().contains(query_phrase));
+
+        let score = boosts.title * coverage(&title)
+            + boosts.tags * coverage(&tags)
+            + boosts.description * coverage(&description)
+            + boosts.data * coverage(&data)
+            + if has_phrase { boosts.exact_phrase } else { 0.0 };
+
+        (score / total).clamp(0.0, 1.0)
+    }
+}
+
+#[async_trait]
+impl Reranker for LexicalReranker {
+    fn name(&self) -> &str {
+        "lexical"
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        candidates: &[RankedSearchResult],
+    ) -> Result<Vec<f64>, HybridSearchError> {
+        let query_terms = terms(query);
+        let query_phrase = query.trim().to_lowercase();
+        Ok(candidates.iter().map(|c| self.score(&query_terms, &query_phrase, c)).collect())
+    }
+}
+
+/// Reranker running a local cross-encoder over each (query, candidate) pair.
+/// The model is loaded on first use; see `CrossEncoder` for the expected files.
+pub struct CrossEncoderReranker {
+    model_path: PathBuf,
+    max_length: usize,
+    model: OnceCell<Arc<CrossEncoder>>,
+}
+
+impl CrossEncoderReranker {
+    pub fn new(model_path: impl Into<PathBuf>) -> Self {
+        Self {
+            model_path: model_path.into(),
+            max_length: 256,
+            model: OnceCell::new(),
+        }
+    }
+
+    /// Token limit for each pair; longer candidates are truncated
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    async fn model(&self) -> Result<Arc<CrossEncoder>, EmbeddingError> {
+        let model = self
+            .model
+            .get_or_try_init(|| async {
+                info!("Loading cross-encoder model from {}", self.model_path.display());
+                let model_path = self.model_path.clone();
+                let model = tokio::task::spawn_blocking(move || CrossEncoder::load(&model_path))
+                    .await
+                    .map_err(|e| EmbeddingError::ModelLoadError {
+                        message: format!("Model loading task failed: {}", e),
+                    })??;
+                Ok::<_, EmbeddingError>(Arc::new(model))
+            })
+            .await?;
+
+        Ok(model.clone())
+    }
+}
+
+#[async_trait]
+impl Reranker for CrossEncoderReranker {
+    fn name(&self) -> &str {
+        "cross_encoder"
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        candidates: &[RankedSearchResult],
+    ) -> Result<Vec<f64>, HybridSearchError> {
+        let model = self.model().await.map_err(rerank_error)?;
+        let query = query.to_string();
+        let passages: Vec<String> = candidates.iter().map(passage).collect();
+        let max_length = self.max_length;
+
+        tokio::task::spawn_blocking(move || {
+            passages
+                .iter()
+                .map(|passage| {
+                    let logit = model.score(&query, passage, max_length)?;
+                    Ok(1.0 / (1.0 + (-logit as f64).exp()))
+                })
+                .collect::<Result<Vec<f64>, EmbeddingError>>()
+        })
+        .await
+        .map_err(|e| HybridSearchError::RerankError { message: format!("Reranking task failed: {}", e) })?
+        .map_err(rerank_error)
+    }
+}
+
+fn rerank_error(error: EmbeddingError) -> HybridSearchError {
+    HybridSearchError::RerankError { message: error.to_string() }
+}
+
+/// Text a cross-encoder reads for a candidate
+fn passage(candidate: &RankedSearchResult) -> String {
+    match &candidate.context {
+        Some(EnhancedContextItem { content, .. }) => format!("{}\n{}", content.title, content.description),
+        None => candidate.preview.clone(),
+    }
+}
+
+/// Lower-cased words of two or more characters with common suffixes removed
+fn terms(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(|word| stem(&word.to_lowercase()))
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
+}
+
+fn stem(word: &str) -> String {
+    for suffix in ["ing", "ed", "es", "s"] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.chars().count() >= 3 {
+                return stem.to_string();
+            }
+        }
+    }
+    word.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::enhanced_context::{ContextContent, ContextType};
+    use crate::services::hybrid_search_service::StageScores;
+
+    fn candidate(title: &str, description: &str, tags: &[&str]) -> RankedSearchResult {
+        let mut context = EnhancedContextItem::new(
+            "project-1".to_string(),
+            ContextContent {
+                content_type: ContextType::SecurityPolicy,
+                title: title.to_string(),
+                description: description.to_string(),
+                data: serde_json::json!({}),
+                source_file: None,
+                source_line: None,
+            },
+        );
+        context.metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+        RankedSearchResult {
+            context_id: context.id.clone(),
+            context: Some(context),
+            preview: String::new(),
+            scores: StageScores::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lexical_reranker_boosts_title_and_tags() {
+        let candidates = vec![
+            candidate("Session cookies", "Cookies are secure; tokens are not stored in them", &[]),
+            candidate("Token rotation", "Refresh tokens are rotated on every use", &["auth"]),
+            candidate("Rate limiting", "Requests are limited per client", &[]),
+        ];
+
+        let scores = LexicalReranker::default().rerank("token rotation auth", &candidates).await.unwrap();
+
+        assert_eq!(scores.len(), 3);
+        assert!(scores[1] > scores[0]);
+        assert!(scores[0] > scores[2]);
+        assert_eq!(scores[2], 0.0);
+        assert!(scores.iter().all(|s| (0.0..=1.0).contains(s)));
+    }
+
+    #[tokio::test]
+    async fn test_missing_cross_encoder_model_is_an_error() {
+        let reranker = CrossEncoderReranker::new("/nonexistent/cross-encoder");
+        let result = reranker.rerank("query", &[candidate("a", "b", &[])]).await;
+        assert!(matches!(result, Err(HybridSearchError::RerankError { .. })));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::models::enhanced_context::{EnhancedContextItem, ContextContent, ContextType};
-use crate::models::embedding::{ContextEmbedding, VectorSearchQuery, VectorSearchResult};
-use crate::repositories::embedding_repository::{EmbeddingRepository, EmbeddingRepositoryError, EmbeddingStats};
+use crate::models::embedding::{ContextEmbedding, EmbeddingConfig, VectorSearchQuery, VectorSearchResult};
+use crate::repositories::embedding_repository::{EmbeddingRepository, EmbeddingRepositoryError, EmbeddingStats, ModelCoverage};
 use crate::services::embedding_service::{EmbeddingService, EmbeddingError};
 use crate::services::semantic_search_service::{SemanticSearchService, SemanticSearchError, SearchIndexStats, EnhancedSearchResult};
 use crate::services::search_index_manager::{
@@ -84,6 +84,15 @@ impl EmbeddingRepository for MockEmbeddingRepository {
         Ok(embeddings.values().cloned().collect())
     }
     
+    async fn get_all_embeddings(&self, embedding_model: Option<&str>) -> Result<Vec<ContextEmbedding>, EmbeddingRepositoryError> {
+        let embeddings = self.embeddings.lock().await;
+        Ok(embeddings
+            .values()
+            .filter(|e| embedding_model.map_or(true, |model| e.embedding_model == model))
+            .cloned()
+            .collect())
+    }
+    
     async fn find_similar_embeddings(&self, _query: &VectorSearchQuery, _project_id: Option<&str>) -> Result<Vec<VectorSearchResult>, EmbeddingRepositoryError> {
         Ok(Vec::new())
     }
@@ -108,10 +117,15 @@ impl EmbeddingRepository for MockEmbeddingRepository {
         let embeddings = self.embeddings.lock().await;
         Ok(EmbeddingStats {
             total_embeddings: embeddings.len() as u64,
+            indexed_contexts: embeddings.len() as u64,
             embeddings_by_model: HashMap::new(),
             average_vector_dimension: 384.0,
             oldest_embedding: Some(Utc::now() - Duration::days(1)),
             newest_embedding: Some(Utc::now()),
+            quantized_embeddings: HashMap::new(),
+            legacy_json_embeddings: 0,
+            vector_storage_bytes: embeddings.len() as u64 * 384 * 4,
+            quantized_storage_bytes: 0,
         })
     }
     
@@ -119,6 +133,37 @@ impl EmbeddingRepository for MockEmbeddingRepository {
         let responses = self.embedding_exists_responses.lock().await;
         Ok(responses.get(context_id).copied().unwrap_or(false))
     }
+    
+    async fn get_active_embedding_config(&self) -> Result<Option<EmbeddingConfig>, EmbeddingRepositoryError> {
+        Ok(None)
+    }
+    
+    async fn set_active_embedding_config(&self, _config: &EmbeddingConfig) -> Result<(), EmbeddingRepositoryError> {
+        Ok(())
+    }
+    
+    async fn get_model_coverage(&self, source_model: &str, target_model: &str) -> Result<ModelCoverage, EmbeddingRepositoryError> {
+        let embeddings = self.embeddings.lock().await;
+        let source_contexts = embeddings.values().filter(|e| e.embedding_model == source_model).count();
+        let covered_contexts = if source_model == target_model { source_contexts } else { 0 };
+        Ok(ModelCoverage { source_contexts, covered_contexts })
+    }
+    
+    async fn get_contexts_missing_model(&self, source_model: &str, _target_model: &str) -> Result<Vec<String>, EmbeddingRepositoryError> {
+        let embeddings = self.embeddings.lock().await;
+        Ok(embeddings
+            .values()
+            .filter(|e| e.embedding_model == source_model)
+            .map(|e| e.context_id.clone())
+            .collect())
+    }
+    
+    async fn delete_embeddings_by_model(&self, embedding_model: &str) -> Result<usize, EmbeddingRepositoryError> {
+        let mut embeddings = self.embeddings.lock().await;
+        let before = embeddings.len();
+        embeddings.retain(|_, e| e.embedding_model != embedding_model);
+        Ok(before - embeddings.len())
+    }
 }
 
 /// Mock semantic search service for testing
@@ -252,6 +297,7 @@ impl SemanticSearchService for MockSemanticSearchService {
             average_embedding_quality: 0.85,
             index_freshness_score: 0.9,
             last_updated: Utc::now(),
+            vector_index: None,
         })
     }
     
@@ -0,0 +1,758 @@
+use crate::models::embedding::DateRange;
+use crate::models::enhanced_context::EnhancedContextItem;
+use crate::services::advanced_query_service::{contains_text, AdvancedSearchFilters, QualityRange};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rmcp::model::ErrorData as McpError;
+use std::collections::HashSet;
+
+const CONTENT_TYPES: &[&str] = &[
+    "business_rule",
+    "architectural_decision",
+    "performance_requirement",
+    "security_policy",
+    "project_convention",
+    "feature_context",
+    "code_pattern",
+    "api_specification",
+    "database_schema",
+    "test_case",
+    "documentation",
+];
+const PRIORITIES: &[&str] = &["low", "medium", "high", "critical"];
+const VALIDATION_STATUSES: &[&str] = &["pending", "valid", "invalid", "needs_review", "outdated"];
+const FIELDS: &str = "type, tag, project, priority, status, source, quality, created";
+
+/// Error in a search query, with the character column (from 1) it was found at
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{message} (at column {column})")]
+pub struct QueryParseError {
+    pub message: String,
+    pub column: usize,
+}
+
+impl QueryParseError {
+    fn new(message: impl Into<String>, position: usize) -> Self {
+        Self { message: message.into(), column: position + 1 }
+    }
+}
+
+impl From<QueryParseError> for McpError {
+    fn from(error: QueryParseError) -> Self {
+        McpError::invalid_params(
+            format!("Invalid search query: {}", error),
+            Some(serde_json::json!({ "column": error.column })),
+        )
+    }
+}
+
+/// A search query split into text for ranking and filters for matching
+#[derive(Debug, Clone, Default)]
+pub struct ParsedSearchQuery {
+    /// Free text and phrases, phrases quoted and `OR` kept, for embedding and keyword search
+    pub text: String,
+    /// Phrases a result must contain
+    pub phrases: Vec<String>,
+    pub filters: AdvancedSearchFilters,
+}
+
+impl ParsedSearchQuery {
+    /// Whether an item passes the filters and contains every required phrase
+    pub fn matches(&self, item: &EnhancedContextItem) -> bool {
+        self.filters.matches(item) && self.phrases.iter().all(|phrase| contains_text(item, phrase))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Field {
+    Type,
+    Tag,
+    Project,
+    Priority,
+    Status,
+    Source,
+    Quality,
+    Created,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "type" | "content_type" => Some(Field::Type),
+            "tag" | "tags" => Some(Field::Tag),
+            "project" | "project_id" => Some(Field::Project),
+            "priority" => Some(Field::Priority),
+            "status" | "validation_status" => Some(Field::Status),
+            "source" => Some(Field::Source),
+            "quality" => Some(Field::Quality),
+            "created" | "date" => Some(Field::Created),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Field::Type => "type",
+            Field::Tag => "tag",
+            Field::Project => "project",
+            Field::Priority => "priority",
+            Field::Status => "status",
+            Field::Source => "source",
+            Field::Quality => "quality",
+            Field::Created => "created",
+        }
+    }
+
+    fn is_range(self) -> bool {
+        matches!(self, Field::Quality | Field::Created)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum TermKind {
+    Word(String),
+    Phrase(String),
+    Filter { field: Field, value: String },
+}
+
+#[derive(Debug, Clone)]
+struct Term {
+    kind: TermKind,
+    negated: bool,
+    position: usize,
+}
+
+#[derive(Debug)]
+enum Token {
+    Term(Term),
+    Or(usize),
+}
+
+/// Parser for the context search query language:
+///
+/// ```text
+/// type:security_policy tag:auth priority:>=high "token rotation" -deprecated
+/// ```
+///
+/// - `field:value` filters on `type`, `tag`, `project`, `priority`, `status`,
+///   `source`, `quality` and `created`; `field:a,b` matches either value
+/// - `"quoted phrases"` must appear verbatim, bare words are free text
+/// - a leading `-` excludes a word, phrase or filter value
+/// - `OR` joins free text, or filters on the same field
+/// - `quality` and `created` take ranges: `>=0.8`, `<2024-06-01`, `0.5..0.9`,
+///   `2024-01-01..2024-03-31`; `created:>=7d` is relative to now. `priority`
+///   takes comparisons such as `>=high`
+///
+/// Terms are ANDed. A list field may only be given once (use `OR` or commas),
+/// since a filter value list always matches any of its values.
+pub struct SearchQueryParser;
+
+impl SearchQueryParser {
+    pub fn parse(input: &str) -> Result<ParsedSearchQuery, QueryParseError> {
+        Self::parse_at(input, Utc::now())
+    }
+
+    /// Parse with relative dates such as `created:>=7d` resolved against `now`
+    pub fn parse_at(input: &str, now: DateTime<Utc>) -> Result<ParsedSearchQuery, QueryParseError> {
+        let groups = Self::group(Self::tokenize(input)?)?;
+        let mut compiler = Compiler { now, query: ParsedSearchQuery::default(), text: Vec::new(), seen: HashSet::new() };
+        for group in groups {
+            compiler.add_group(group)?;
+        }
+        compiler.query.text = compiler.text.join(" ");
+        Ok(compiler.query)
+    }
+
+    fn tokenize(input: &str) -> Result<Vec<Token>, QueryParseError> {
+        let chars: Vec<char> = input.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i].is_whitespace() {
+                i += 1;
+                continue;
+            }
+
+            let position = i;
+            let negated = chars[i] == '-' && chars.get(i + 1).is_some_and(|c| !c.is_whitespace());
+            if negated {
+                i += 1;
+            }
+
+            if chars[i] == '"' {
+                let (phrase, end) = Self::read_quoted(&chars, i)?;
+                i = end;
+                if phrase.trim().is_empty() {
+                    return Err(QueryParseError::new("Empty phrase", position));
+                }
+                tokens.push(Token::Term(Term { kind: TermKind::Phrase(phrase), negated, position }));
+                continue;
+            }
+
+            let start = i;
+            while i < chars.len() && !chars[i].is_whitespace() && chars[i] != ':' {
+                i += 1;
+            }
+            let head: String = chars[start..i].iter().collect();
+
+            if i < chars.len() && chars[i] == ':' {
+                let next = chars.get(i + 1).copied();
+                // `note: tokens` is prose, but `tag:` with no value is a mistake
+                let has_value = next.is_some_and(|c| !c.is_whitespace());
+                let is_field_like = !head.is_empty()
+                    && head.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !matches!(next, Some(':') | Some('/'))
+                    && (has_value || Field::parse(&head).is_some());
+                if is_field_like {
+                    let field = Field::parse(&head).ok_or_else(|| {
+                        QueryParseError::new(format!("Unknown field '{}'; expected one of {}", head, FIELDS), start)
+                    })?;
+                    i += 1;
+                    let value = if next == Some('"') {
+                        let (value, end) = Self::read_quoted(&chars, i)?;
+                        i = end;
+                        value
+                    } else {
+                        let value_start = i;
+                        while i < chars.len() && !chars[i].is_whitespace() {
+                            i += 1;
+                        }
+                        chars[value_start..i].iter().collect()
+                    };
+                    if value.trim().is_empty() {
+                        return Err(QueryParseError::new(format!("Missing value for '{}'", head), start));
+                    }
+                    tokens.push(Token::Term(Term { kind: TermKind::Filter { field, value }, negated, position }));
+                    continue;
+                }
+                // Not a filter, e.g. `std::sync` or a URL; keep the whole word as text
+                while i < chars.len() && !chars[i].is_whitespace() {
+                    i += 1;
+                }
+            }
+
+            let word: String = chars[start..i].iter().collect();
+            if word == "OR" && !negated {
+                tokens.push(Token::Or(position));
+            } else {
+                tokens.push(Token::Term(Term { kind: TermKind::Word(word), negated, position }));
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    /// Read a `"…"` string starting at the opening quote; `\"` and `\\` are escapes
+    fn read_quoted(chars: &[char], open: usize) -> Result<(String, usize), QueryParseError> {
+        let mut value = String::new();
+        let mut i = open + 1;
+        while i < chars.len() {
+            match chars[i] {
+                '\\' if matches!(chars.get(i + 1), Some('"') | Some('\\')) => {
+                    value.push(chars[i + 1]);
+                    i += 2;
+                }
+                '"' => return Ok((value, i + 1)),
+                c => {
+                    value.push(c);
+                    i += 1;
+                }
+            }
+        }
+        Err(QueryParseError::new("Unterminated quote", open))
+    }
+
+    /// Split tokens into AND-ed groups of OR-ed terms
+    fn group(tokens: Vec<Token>) -> Result<Vec<Vec<Term>>, QueryParseError> {
+        let mut groups: Vec<Vec<Term>> = Vec::new();
+        let mut pending_or: Option<usize> = None;
+
+        for token in tokens {
+            match token {
+                Token::Or(position) => {
+                    if groups.is_empty() || pending_or.is_some() {
+                        return Err(QueryParseError::new("OR must be placed between two terms", position));
+                    }
+                    pending_or = Some(position);
+                }
+                Token::Term(term) => match (pending_or.take(), groups.last_mut()) {
+                    (Some(_), Some(group)) => group.push(term),
+                    _ => groups.push(vec![term]),
+                },
+            }
+        }
+
+        if let Some(position) = pending_or {
+            return Err(QueryParseError::new("OR must be placed between two terms", position));
+        }
+        Ok(groups)
+    }
+}
+
+struct Compiler {
+    now: DateTime<Utc>,
+    query: ParsedSearchQuery,
+    text: Vec<String>,
+    /// Positive filters already given, to reject an ambiguous repeat
+    seen: HashSet<Field>,
+}
+
+impl Compiler {
+    fn add_group(&mut self, group: Vec<Term>) -> Result<(), QueryParseError> {
+        if group.len() == 1 {
+            let term = group.into_iter().next().expect("group has one term");
+            return self.add_term(term);
+        }
+
+        if let Some(term) = group.iter().find(|t| t.negated) {
+            return Err(QueryParseError::new("A negated term cannot be part of OR", term.position));
+        }
+
+        let fields: Vec<Option<Field>> = group
+            .iter()
+            .map(|t| match t.kind {
+                TermKind::Filter { field, .. } => Some(field),
+                _ => None,
+            })
+            .collect();
+
+        if fields.iter().all(Option::is_none) {
+            let parts: Vec<String> = group.iter().map(|t| self.text_of(t)).collect();
+            self.text.push(parts.join(" OR "));
+            // Phrases under OR are alternatives, so none of them is required
+            return Ok(());
+        }
+
+        let first = group[0].position;
+        let field = match fields[0] {
+            Some(field) if fields.iter().all(|f| *f == Some(field)) => field,
+            _ => {
+                return Err(QueryParseError::new(
+                    "OR can only join free text, or filters on the same field",
+                    first,
+                ))
+            }
+        };
+        if field.is_range() {
+            return Err(QueryParseError::new(
+                format!("'{}' ranges cannot be joined with OR", field.name()),
+                first,
+            ));
+        }
+
+        self.claim(field, first)?;
+        for term in group {
+            if let TermKind::Filter { value, .. } = term.kind {
+                self.add_values(field, &value, term.position)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn add_term(&mut self, term: Term) -> Result<(), QueryParseError> {
+        match (&term.kind, term.negated) {
+            (TermKind::Word(word), true) | (TermKind::Phrase(word), true) => {
+                self.query.filters.excluded.terms.push(word.clone());
+            }
+            (TermKind::Word(_), false) => self.text.push(self.text_of(&term)),
+            (TermKind::Phrase(phrase), false) => {
+                self.text.push(self.text_of(&term));
+                self.query.phrases.push(phrase.clone());
+            }
+            (TermKind::Filter { field, value }, true) => self.exclude_values(*field, value, term.position)?,
+            (TermKind::Filter { field, value }, false) => {
+                self.claim(*field, term.position)?;
+                match field {
+                    Field::Quality => self.set_quality(value, term.position)?,
+                    Field::Created => self.set_created(value, term.position)?,
+                    _ => self.add_values(*field, value, term.position)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn text_of(&self, term: &Term) -> String {
+        match &term.kind {
+            TermKind::Phrase(phrase) => format!("\"{}\"", phrase),
+            TermKind::Word(word) => word.clone(),
+            TermKind::Filter { .. } => String::new(),
+        }
+    }
+
+    fn claim(&mut self, field: Field, position: usize) -> Result<(), QueryParseError> {
+        if self.seen.insert(field) {
+            return Ok(());
+        }
+        let message = if field.is_range() {
+            format!("'{}' is given more than once; use a range such as {}", field.name(), range_example(field))
+        } else {
+            format!(
+                "'{0}' is given more than once; use OR or commas to match any of several values, e.g. {0}:a,b",
+                field.name()
+            )
+        };
+        Err(QueryParseError::new(message, position))
+    }
+
+    fn add_values(&mut self, field: Field, value: &str, position: usize) -> Result<(), QueryParseError> {
+        let values = self.list_values(field, value, position)?;
+        let filters = &mut self.query.filters;
+        let target = match field {
+            Field::Type => &mut filters.base_filters.content_types,
+            Field::Tag => &mut filters.base_filters.tags,
+            Field::Project => &mut filters.base_filters.project_ids,
+            Field::Priority => &mut filters.priorities,
+            Field::Status => &mut filters.validation_status,
+            Field::Source => &mut filters.source_types,
+            Field::Quality | Field::Created => unreachable!("range fields are not lists"),
+        };
+        let target = target.get_or_insert_with(Vec::new);
+        for value in values {
+            if !target.contains(&value) {
+                target.push(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn exclude_values(&mut self, field: Field, value: &str, position: usize) -> Result<(), QueryParseError> {
+        if field.is_range() {
+            return Err(QueryParseError::new(
+                format!("'{}' cannot be negated; use a range such as {}", field.name(), range_example(field)),
+                position,
+            ));
+        }
+        let values = self.list_values(field, value, position)?;
+        let excluded = &mut self.query.filters.excluded;
+        let target = match field {
+            Field::Type => &mut excluded.content_types,
+            Field::Tag => &mut excluded.tags,
+            Field::Project => &mut excluded.project_ids,
+            Field::Priority => &mut excluded.priorities,
+            Field::Status => &mut excluded.validation_status,
+            Field::Source => &mut excluded.source_types,
+            Field::Quality | Field::Created => unreachable!("range fields are not lists"),
+        };
+        target.extend(values);
+        Ok(())
+    }
+
+    /// Comma separated values of a list field, checked against the known values
+    fn list_values(&self, field: Field, value: &str, position: usize) -> Result<Vec<String>, QueryParseError> {
+        if field == Field::Priority {
+            if let Some(values) = priority_comparison(value, position)? {
+                return Ok(values);
+            }
+        }
+
+        let mut values = Vec::new();
+        for raw in value.split(',') {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                return Err(QueryParseError::new(format!("Empty value in '{}' list", field.name()), position));
+            }
+            let known = match field {
+                Field::Type => Some(CONTENT_TYPES),
+                Field::Priority => Some(PRIORITIES),
+                Field::Status => Some(VALIDATION_STATUSES),
+                _ => None,
+            };
+            let value = match known {
+                Some(known) => {
+                    let normalized = raw.to_lowercase().replace('-', "_");
+                    if !known.contains(&normalized.as_str()) {
+                        return Err(QueryParseError::new(
+                            format!("Unknown {} '{}'; expected one of {}", field.name(), raw, known.join(", ")),
+                            position,
+                        ));
+                    }
+                    normalized
+                }
+                None => raw.to_string(),
+            };
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    fn set_quality(&mut self, value: &str, position: usize) -> Result<(), QueryParseError> {
+        let parse = |raw: &str| -> Result<f32, QueryParseError> {
+            let score: f32 = raw.trim().parse().map_err(|_| {
+                QueryParseError::new(format!("Invalid quality score '{}'; expected a number from 0 to 1", raw), position)
+            })?;
+            if !(0.0..=1.0).contains(&score) {
+                return Err(QueryParseError::new(
+                    format!("Quality score {} is out of range; expected a number from 0 to 1", raw),
+                    position,
+                ));
+            }
+            Ok(score)
+        };
+
+        let (min_score, max_score) = match parse_range(value) {
+            Range::Between(start, end) => (parse(start)?, parse(end)?),
+            Range::AtLeast(bound, _) => (parse(bound)?, 1.0),
+            Range::AtMost(bound, _) => (0.0, parse(bound)?),
+            Range::Exact(_) => {
+                return Err(QueryParseError::new(
+                    format!("'quality' needs a comparison or range, e.g. {}", range_example(Field::Quality)),
+                    position,
+                ))
+            }
+        };
+        if min_score > max_score {
+            return Err(QueryParseError::new("Quality range starts after it ends", position));
+        }
+
+        self.query.filters.quality_range = Some(QualityRange { min_score, max_score });
+        Ok(())
+    }
+
+    fn set_created(&mut self, value: &str, position: usize) -> Result<(), QueryParseError> {
+        let (start, end) = match parse_range(value) {
+            Range::Between(start, end) => {
+                (self.parse_date(start, false, position)?, self.parse_date(end, true, position)?)
+            }
+            Range::AtLeast(bound, inclusive) => {
+                let start = if inclusive {
+                    self.parse_date(bound, false, position)?
+                } else {
+                    self.parse_date(bound, true, position)? + Duration::nanoseconds(1)
+                };
+                (start, self.now)
+            }
+            Range::AtMost(bound, inclusive) => {
+                let end = if inclusive {
+                    self.parse_date(bound, true, position)?
+                } else {
+                    self.parse_date(bound, false, position)? - Duration::nanoseconds(1)
+                };
+                (DateTime::<Utc>::UNIX_EPOCH, end)
+            }
+            Range::Exact(day) => (self.parse_date(day, false, position)?, self.parse_date(day, true, position)?),
+        };
+        if start > end {
+            return Err(QueryParseError::new("Date range starts after it ends", position));
+        }
+
+        self.query.filters.base_filters.date_range = Some(DateRange { start, end });
+        Ok(())
+    }
+
+    /// `YYYY-MM-DD`, RFC 3339 or an age such as `7d`, `12h` or `2w`. Dates
+    /// without a time resolve to the first or, with `end_of_day`, last instant.
+    fn parse_date(&self, raw: &str, end_of_day: bool, position: usize) -> Result<DateTime<Utc>, QueryParseError> {
+        let raw = raw.trim();
+        if let Ok(date) = DateTime::parse_from_rfc3339(raw) {
+            return Ok(date.with_timezone(&Utc));
+        }
+        if let Ok(day) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+            let start = day.and_hms_opt(0, 0, 0).expect("midnight is valid").and_utc();
+            return Ok(if end_of_day { start + Duration::days(1) - Duration::nanoseconds(1) } else { start });
+        }
+        if let Some(unit) = raw.chars().last().filter(|c| matches!(c, 'h' | 'd' | 'w')) {
+            if let Ok(amount) = raw[..raw.len() - 1].parse::<i64>() {
+                let age = match unit {
+                    'h' => Duration::try_hours(amount),
+                    'd' => Duration::try_days(amount),
+                    _ => Duration::try_weeks(amount),
+                };
+                return age
+                    .and_then(|age| self.now.checked_sub_signed(age))
+                    .ok_or_else(|| QueryParseError::new(format!("Date age '{}' is out of range", raw), position));
+            }
+        }
+        Err(QueryParseError::new(
+            format!("Invalid date '{}'; expected YYYY-MM-DD, an RFC 3339 timestamp or an age such as 7d", raw),
+            position,
+        ))
+    }
+}
+
+enum Range<'a> {
+    Between(&'a str, &'a str),
+    /// Lower bound, and whether it is inclusive
+    AtLeast(&'a str, bool),
+    /// Upper bound, and whether it is inclusive
+    AtMost(&'a str, bool),
+    Exact(&'a str),
+}
+
+fn parse_range(value: &str) -> Range<'_> {
+    if let Some(bound) = value.strip_prefix(">=") {
+        Range::AtLeast(bound, true)
+    } else if let Some(bound) = value.strip_prefix("<=") {
+        Range::AtMost(bound, true)
+    } else if let Some(bound) = value.strip_prefix('>') {
+        Range::AtLeast(bound, false)
+    } else if let Some(bound) = value.strip_prefix('<') {
+        Range::AtMost(bound, false)
+    } else if let Some((start, end)) = value.split_once("..") {
+        Range::Between(start, end)
+    } else {
+        Range::Exact(value)
+    }
+}
+
+/// Priorities selected by `>=high`, `<medium` or `low..high`; None for a plain list
+fn priority_comparison(value: &str, position: usize) -> Result<Option<Vec<String>>, QueryParseError> {
+    let rank = |raw: &str| {
+        PRIORITIES.iter().position(|p| p.eq_ignore_ascii_case(raw.trim())).ok_or_else(|| {
+            QueryParseError::new(
+                format!("Unknown priority '{}'; expected one of {}", raw, PRIORITIES.join(", ")),
+                position,
+            )
+        })
+    };
+
+    let (low, high) = match parse_range(value) {
+        Range::Exact(_) => return Ok(None),
+        Range::Between(start, end) => (rank(start)?, rank(end)?),
+        Range::AtLeast(bound, inclusive) => (rank(bound)? + usize::from(!inclusive), PRIORITIES.len() - 1),
+        Range::AtMost(bound, inclusive) => {
+            let bound = rank(bound)?;
+            if !inclusive && bound == 0 {
+                return Err(QueryParseError::new("No priority is below 'low'", position));
+            }
+            (0, bound - usize::from(!inclusive))
+        }
+    };
+    if low > high || low >= PRIORITIES.len() {
+        return Err(QueryParseError::new(format!("Priority range '{}' matches nothing", value), position));
+    }
+
+    Ok(Some(PRIORITIES[low..=high].iter().map(|p| p.to_string()).collect()))
+}
+
+fn range_example(field: Field) -> &'static str {
+    match field {
+        Field::Created => "created:2024-01-01..2024-03-31",
+        _ => "quality:>=0.8",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::enhanced_context::{ContextContent, ContextType, Priority};
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap()
+    }
+
+    fn parse(input: &str) -> ParsedSearchQuery {
+        SearchQueryParser::parse_at(input, now()).unwrap()
+    }
+
+    fn error(input: &str) -> QueryParseError {
+        SearchQueryParser::parse_at(input, now()).unwrap_err()
+    }
+
+    #[test]
+    fn test_compiles_filters_and_text() {
+        let query = parse(r#"type:security_policy tag:auth priority:>=high "token rotation" -deprecated refresh"#);
+
+        assert_eq!(query.text, "\"token rotation\" refresh");
+        assert_eq!(query.phrases, vec!["token rotation"]);
+        let filters = &query.filters;
+        assert_eq!(filters.base_filters.content_types, Some(vec!["security_policy".to_string()]));
+        assert_eq!(filters.base_filters.tags, Some(vec!["auth".to_string()]));
+        assert_eq!(filters.priorities, Some(vec!["high".to_string(), "critical".to_string()]));
+        assert_eq!(filters.excluded.terms, vec!["deprecated"]);
+    }
+
+    #[test]
+    fn test_or_lists_and_negated_filters() {
+        let query = parse("type:business_rule OR type:security_policy tag:a,b -tag:legacy cache OR redis");
+
+        assert_eq!(
+            query.filters.base_filters.content_types,
+            Some(vec!["business_rule".to_string(), "security_policy".to_string()])
+        );
+        assert_eq!(query.filters.base_filters.tags, Some(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(query.filters.excluded.tags, vec!["legacy"]);
+        assert_eq!(query.text, "cache OR redis");
+    }
+
+    #[test]
+    fn test_ranges() {
+        let query = parse("quality:0.5..0.9 created:>=7d");
+        let quality = query.filters.quality_range.unwrap();
+        assert_eq!((quality.min_score, quality.max_score), (0.5, 0.9));
+        let dates = query.filters.base_filters.date_range.unwrap();
+        assert_eq!(dates.start, now() - Duration::days(7));
+        assert_eq!(dates.end, now());
+
+        let dates = parse("created:2024-01-01..2024-01-31").filters.base_filters.date_range.unwrap();
+        assert_eq!(dates.start, Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        assert!(dates.end > Utc.with_ymd_and_hms(2024, 1, 31, 23, 59, 59).unwrap());
+        assert!(dates.end < Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap());
+
+        assert_eq!(parse("quality:<0.3").filters.quality_range.unwrap().max_score, 0.3);
+        assert_eq!(parse("priority:<high").filters.priorities, Some(vec!["low".to_string(), "medium".to_string()]));
+    }
+
+    #[test]
+    fn test_colons_in_text_are_not_filters() {
+        let query = parse("std::sync https://example.com/docs Note: retry");
+        assert_eq!(query.text, "std::sync https://example.com/docs Note: retry");
+    }
+
+    #[test]
+    fn test_errors_point_at_the_problem() {
+        let cases = [
+            ("tpye:security_policy", "Unknown field 'tpye'", 1),
+            ("auth type:policy", "Unknown type 'policy'", 6),
+            ("\"token rotation", "Unterminated quote", 1),
+            ("tag:auth tag:jwt", "'tag' is given more than once", 10),
+            ("quality:1.5", "needs a comparison", 1),
+            ("quality:>=1.5", "out of range", 1),
+            ("created:>=yesterday", "Invalid date 'yesterday'", 1),
+            ("created:<100000000d", "Date age '100000000d' is out of range", 1),
+            ("created:>=9223372036854775807w", "out of range", 1),
+            ("OR auth", "OR must be placed between two terms", 1),
+            ("auth OR", "OR must be placed between two terms", 6),
+            ("tag:auth OR cache", "OR can only join free text", 1),
+            ("cache OR -redis", "negated term cannot be part of OR", 10),
+            ("-quality:>0.5", "cannot be negated", 1),
+            ("priority:<low", "No priority is below 'low'", 1),
+            ("tag:", "Missing value for 'tag'", 1),
+        ];
+        for (input, message, column) in cases {
+            let error = error(input);
+            assert!(error.message.contains(message), "{}: {}", input, error);
+            assert_eq!(error.column, column, "{}", input);
+        }
+
+        let mcp: McpError = error("tag:").into();
+        assert_eq!(mcp.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(mcp.message.contains("Missing value for 'tag'"));
+    }
+
+    #[test]
+    fn test_matches_context_items() {
+        let mut item = EnhancedContextItem::new(
+            "project-1".to_string(),
+            ContextContent {
+                content_type: ContextType::SecurityPolicy,
+                title: "Token rotation".to_string(),
+                description: "Refresh tokens are rotated on every use".to_string(),
+                data: serde_json::json!({}),
+                source_file: None,
+                source_line: None,
+            },
+        );
+        item.metadata.tags = vec!["Auth".to_string()];
+        item.metadata.priority = Priority::Critical;
+        item.quality_score = 0.9;
+
+        assert!(parse(r#"type:security_policy tag:auth priority:>=high "token rotation""#).matches(&item));
+        assert!(parse("quality:>=0.8 project:project-1").matches(&item));
+        assert!(!parse("-refresh").matches(&item));
+        assert!(!parse("-tag:auth").matches(&item));
+        assert!(!parse("priority:low,medium").matches(&item));
+        assert!(!parse(r#""session cookie""#).matches(&item));
+    }
+}
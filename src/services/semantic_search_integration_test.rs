@@ -10,9 +10,7 @@ use crate::services::semantic_search_service::{
 use crate::services::hybrid_search_service::{
     HybridSearchServiceImpl, HybridSearchConfig, HybridSearchService,
 };
-use crate::services::context_query_service::{ContextQueryService, ContextQueryResult};
-use async_trait::async_trait;
-use rmcp::model::ErrorData as McpError;
+use crate::infrastructure::SqliteEnhancedContextRepository;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{info, debug};
@@ -55,8 +53,11 @@ impl SemanticSearchIntegrationTest {
             search_config.clone(),
         );
         
-        // Create mock context query service for hybrid search
-        let context_query_service = Arc::new(MockContextQueryService);
+        // Keyword search for hybrid search runs over an empty context table
+        let context_repository = SqliteEnhancedContextRepository::new(Arc::new(std::sync::Mutex::new(
+            rusqlite::Connection::open_in_memory()?,
+        )));
+        context_repository.initialize_tables()?;
         
         // Create another embedding service instance for hybrid search
         let embedding_service_for_hybrid = EmbeddingServiceFactory::create_initialized_service(embedding_config.clone()).await?;
@@ -72,7 +73,7 @@ impl SemanticSearchIntegrationTest {
         let hybrid_config = HybridSearchConfig::default();
         let hybrid_search_service = HybridSearchServiceImpl::new(
             Arc::new(semantic_search_service_for_hybrid) as Arc<dyn SemanticSearchService>,
-            context_query_service,
+            Arc::new(context_repository),
             hybrid_config,
         );
         
@@ -346,29 +347,6 @@ impl SemanticSearchIntegrationTest {
     }
 }
 
-/// Mock context query service for testing hybrid search
-struct MockContextQueryService;
-
-#[async_trait]
-impl ContextQueryService for MockContextQueryService {
-    async fn query_context(
-        &self,
-        _project_id: &str,
-        _feature_area: &str,
-        _task_type: &str,
-        _components: &[String],
-    ) -> Result<ContextQueryResult, McpError> {
-        // Return empty results for simplicity in testing
-        Ok(ContextQueryResult {
-            business_rules: Vec::new(),
-            architectural_decisions: Vec::new(),
-            performance_requirements: Vec::new(),
-            security_policies: Vec::new(),
-            project_conventions: Vec::new(),
-        })
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
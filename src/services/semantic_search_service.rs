@@ -1,14 +1,20 @@
 use crate::models::embedding::{
-    ContextEmbedding, EmbeddingConfig, VectorSearchQuery, VectorSearchResult,
+    ChunkMatch, ContextEmbedding, EmbeddingConfig, VectorSearchQuery, VectorSearchResult,
 };
 use crate::models::enhanced_context::{EnhancedContextItem, ContextType};
 use crate::repositories::embedding_repository::{EmbeddingRepository, EmbeddingRepositoryError};
-use crate::services::embedding_service::{EmbeddingService, EmbeddingError};
+use crate::services::embedding_service::{maximal_marginal_relevance, EmbeddingService, EmbeddingError};
+use crate::services::text_chunker::{snippet, ChunkingConfig, TextChunker};
+use crate::services::vector_index::{VectorHit, VectorIndex, VectorIndexStats, DEFAULT_RECALL_SAMPLE_SIZE};
 use async_trait::async_trait;
 use regex::Regex;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, error, info};
+use schemars::JsonSchema;
+
+/// How many ANN neighbours a project-scoped search fetches per wanted result
+const PROJECT_SEARCH_OVERFETCH: usize = 4;
 
 /// Error types for semantic search operations
 #[derive(Debug, thiserror::Error)]
@@ -54,8 +60,20 @@ pub struct SemanticSearchConfig {
     pub recency_weight: f32,
     pub usage_weight: f32,
     pub quality_weight: f32,
+    /// Splitting of long items into separately embedded chunks
+    pub chunking: ChunkingConfig,
 }
 
+/// Chunk hits fetched per requested result, so aggregation still fills the page
+const CHUNK_CANDIDATE_MULTIPLIER: usize = 3;
+/// Score added for each additional matching chunk of the same item
+const CHUNK_SUPPORT_BONUS: f32 = 0.02;
+const MAX_CHUNK_SUPPORT_BONUS: f32 = 0.1;
+/// Length of the best-chunk snippet returned as `content_preview`
+const SNIPPET_CHARS: usize = 300;
+/// Extra candidates fetched for diversity-aware selection to choose from
+const MMR_CANDIDATE_MULTIPLIER: usize = 2;
+
 impl Default for SemanticSearchConfig {
     fn default() -> Self {
         Self {
@@ -69,12 +87,13 @@ impl Default for SemanticSearchConfig {
             recency_weight: 0.2,
             usage_weight: 0.15,
             quality_weight: 0.15,
+            chunking: ChunkingConfig::default(),
         }
     }
 }
 
 /// Enhanced search result with context information
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, JsonSchema)]
 pub struct EnhancedSearchResult {
     pub vector_result: VectorSearchResult,
     pub context_item: Option<EnhancedContextItem>,
@@ -83,7 +102,7 @@ pub struct EnhancedSearchResult {
 }
 
 /// Metadata about the search process
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, JsonSchema)]
 pub struct SearchMetadata {
     pub query_processing_time_ms: u64,
     pub embedding_generation_time_ms: u64,
@@ -102,6 +121,8 @@ pub struct SearchIndexStats {
     pub average_embedding_quality: f32,
     pub index_freshness_score: f32,
     pub last_updated: chrono::DateTime<chrono::Utc>,
+    /// Present when searches are served by an ANN index
+    pub vector_index: Option<VectorIndexStats>,
 }
 
 /// Query intent detected from natural language processing
@@ -175,14 +196,59 @@ pub trait SemanticSearchService: Send + Sync {
     
     /// Rebuild search index for a project
     async fn rebuild_index(&self, project_id: &str, contexts: &[EnhancedContextItem]) -> Result<(), SemanticSearchError>;
+    
+    /// Model queries are currently embedded with, when the service tracks one
+    fn serving_model(&self) -> Option<String> {
+        None
+    }
+    
+    /// Rebuild a project's corpus statistics when forced or when its items
+    /// have drifted from them. Returns whether its embeddings are now stale.
+    async fn refresh_vocabulary(&self, _project_id: &str, _force: bool) -> Result<bool, SemanticSearchError> {
+        Ok(false)
+    }
+    
+    /// Keep answering queries with `serving` while every write is also embedded with `target`
+    fn begin_model_migration(
+        &self,
+        _serving: Arc<dyn EmbeddingService>,
+        _target: Arc<dyn EmbeddingService>,
+    ) -> Result<(), SemanticSearchError> {
+        Err(SemanticSearchError::ConfigurationError {
+            message: "Model migration is not supported by this search service".to_string(),
+        })
+    }
+    
+    /// Embed a context with the migration target model only
+    async fn index_context_for_target_model(&self, _context: &EnhancedContextItem) -> Result<(), SemanticSearchError> {
+        Err(SemanticSearchError::IndexNotReady {
+            message: "No model migration in progress".to_string(),
+        })
+    }
+    
+    /// Switch queries to the target model in one step, returning the retired model
+    async fn complete_model_migration(&self) -> Result<String, SemanticSearchError> {
+        Err(SemanticSearchError::IndexNotReady {
+            message: "No model migration in progress".to_string(),
+        })
+    }
+}
+
+/// Embedding models in use. `target` is set while a model migration re-embeds
+/// the index; it receives every write but answers no queries until promoted.
+struct EmbeddingModels {
+    serving: Arc<dyn EmbeddingService>,
+    target: Option<Arc<dyn EmbeddingService>>,
 }
 
 /// Implementation of SemanticSearchService
 pub struct SemanticSearchServiceImpl {
-    embedding_service: Arc<dyn EmbeddingService>,
+    models: std::sync::RwLock<EmbeddingModels>,
     embedding_repository: Arc<dyn EmbeddingRepository>,
     config: SemanticSearchConfig,
     query_cache: Arc<tokio::sync::Mutex<HashMap<String, Vec<f32>>>>,
+    vector_index: Option<Arc<VectorIndex>>,
+    chunker: TextChunker,
 }
 
 impl SemanticSearchServiceImpl {
@@ -192,11 +258,100 @@ impl SemanticSearchServiceImpl {
         config: SemanticSearchConfig,
     ) -> Self {
         Self {
-            embedding_service,
+            models: std::sync::RwLock::new(EmbeddingModels {
+                serving: embedding_service,
+                target: None,
+            }),
             embedding_repository,
+            chunker: TextChunker::new(config.chunking.clone()),
             config,
             query_cache: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            vector_index: None,
+        }
+    }
+    
+    /// Serve searches from an ANN index kept up to date by `SearchIndexManagerImpl`
+    pub fn with_vector_index(mut self, vector_index: Arc<VectorIndex>) -> Self {
+        self.vector_index = Some(vector_index);
+        self
+    }
+    
+    /// Embedding service queries are answered with
+    fn serving_service(&self) -> Arc<dyn EmbeddingService> {
+        self.models.read().unwrap().serving.clone()
+    }
+    
+    fn target_service(&self) -> Option<Arc<dyn EmbeddingService>> {
+        self.models.read().unwrap().target.clone()
+    }
+    
+    /// Embeddings of a context for the serving model and, during a migration, the target model
+    async fn generate_all_model_embeddings(&self, context: &EnhancedContextItem) -> Result<Vec<ContextEmbedding>, SemanticSearchError> {
+        let mut embeddings = self.generate_context_embeddings(self.serving_service().as_ref(), context).await?;
+        if let Some(target) = self.target_service() {
+            embeddings.extend(self.generate_context_embeddings(target.as_ref(), context).await?);
         }
+        Ok(embeddings)
+    }
+    
+    /// Find candidate vectors through the ANN index when it covers the active
+    /// model, falling back to a full repository scan otherwise
+    async fn find_candidates(&self, query: &VectorSearchQuery, project_id: Option<&str>) -> Result<Vec<VectorSearchResult>, SemanticSearchError> {
+        let index = self.vector_index.as_ref().filter(|index| {
+            !index.is_empty() && index.embedding_model() == self.serving_service().get_model_info().model_name
+        });
+        
+        let (Some(index), Some(query_embedding)) = (index, query.query_embedding.as_ref()) else {
+            return Ok(self.embedding_repository.find_similar_embeddings(query, project_id).await?);
+        };
+        
+        let hits = match project_id {
+            None => index.search(query_embedding, query.max_results),
+            Some(project_id) => {
+                // Other projects' vectors crowd the neighbourhood, so fetch more and
+                // keep this project's; too few left means scanning the project instead
+                let fetch = query.max_results * PROJECT_SEARCH_OVERFETCH;
+                let fetched = index.search(query_embedding, fetch);
+                let exhaustive = fetch >= index.len()
+                    || fetched.last().is_none_or(|hit| hit.similarity < query.similarity_threshold);
+                let hits: Vec<VectorHit> = fetched
+                    .into_iter()
+                    .filter(|hit| hit.project_id.as_deref() == Some(project_id))
+                    .take(query.max_results)
+                    .collect();
+                if hits.len() < query.max_results && !exhaustive {
+                    debug!("ANN index held {} of {} results for project {}, scanning", hits.len(), query.max_results, project_id);
+                    return Ok(self.embedding_repository.find_similar_embeddings(query, Some(project_id)).await?);
+                }
+                hits
+            }
+        };
+        
+        let results = hits
+            .into_iter()
+            .filter(|hit| hit.similarity >= query.similarity_threshold)
+            .enumerate()
+            .map(|(i, hit)| VectorSearchResult {
+                context_id: hit.context_id,
+                similarity_score: hit.similarity,
+                distance: 1.0 - hit.similarity,
+                rank: i + 1,
+                metadata: crate::models::embedding::ResultMetadata {
+                    content_type: hit.content_type,
+                    content_preview: "Preview not available".to_string(),
+                    match_explanation: format!("Cosine similarity: {:.3}", hit.similarity),
+                    quality_indicators: vec![format!("Quality: {:.2}", hit.quality_score)],
+                    // Heading and text are looked up by `fill_chunk_snippets`
+                    chunk: hit.chunk_index.map(|chunk_index| ChunkMatch {
+                        chunk_index,
+                        heading: None,
+                        matched_chunks: 1,
+                    }),
+                },
+            })
+            .collect();
+        
+        Ok(results)
     }
     
     /// Process natural language query with intent detection
@@ -447,27 +602,167 @@ impl SemanticSearchServiceImpl {
             }
         }
         
-        text_parts.join(" ")
+        // Newlines keep Markdown headings at line starts for the chunker
+        text_parts.join("\n")
     }
     
     /// Generate embedding for context with caching
-    async fn generate_context_embedding(&self, context: &EnhancedContextItem) -> Result<ContextEmbedding, SemanticSearchError> {
+    async fn generate_context_embedding(&self, embedding_service: &dyn EmbeddingService, context: &EnhancedContextItem) -> Result<ContextEmbedding, SemanticSearchError> {
         let searchable_text = self.extract_searchable_text(context);
         let content_type = context.content.content_type.as_str();
         
-        let mut embedding = self.embedding_service
-            .generate_embedding(&searchable_text, content_type)
+        let mut embedding = embedding_service
+            .generate_project_embedding(&searchable_text, content_type, Some(&context.project_id))
             .await?;
         
         embedding.context_id = context.id.clone();
+        embedding.project_id = Some(context.project_id.clone());
         
         Ok(embedding)
     }
     
-    /// Get or generate query embedding with caching
-    async fn get_query_embedding(&self, query_text: &str) -> Result<Vec<f32>, SemanticSearchError> {
+    /// Embed a context whole, or chunk by chunk when its text exceeds one chunk
+    async fn generate_context_embeddings(&self, embedding_service: &dyn EmbeddingService, context: &EnhancedContextItem) -> Result<Vec<ContextEmbedding>, SemanticSearchError> {
+        let searchable_text = self.extract_searchable_text(context);
+        let chunks = self.chunker.chunk(&searchable_text);
+        if chunks.len() <= 1 {
+            return Ok(vec![self.generate_context_embedding(embedding_service, context).await?]);
+        }
+        
+        let content_type = context.content.content_type.as_str();
+        let mut embeddings = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            // Prefix the title so every chunk carries what the item is about
+            let text = format!("{}\n{}", context.content.title, chunk.text);
+            let mut embedding = embedding_service
+                .generate_project_embedding(&text, content_type, Some(&context.project_id))
+                .await?;
+            embedding.context_id = context.id.clone();
+            embedding.project_id = Some(context.project_id.clone());
+            embedding.chunk = Some(chunk);
+            embeddings.push(embedding);
+        }
+        
+        debug!("Embedded context {} as {} chunks", context.id, embeddings.len());
+        Ok(embeddings)
+    }
+    
+    /// Collapse chunk hits into one result per context: the best chunk supplies
+    /// the score and snippet, and further matching chunks add a small bonus
+    fn aggregate_chunk_results(&self, results: Vec<VectorSearchResult>) -> Vec<VectorSearchResult> {
+        let mut order: Vec<String> = Vec::new();
+        let mut by_context: HashMap<String, (VectorSearchResult, usize)> = HashMap::new();
+        
+        for result in results {
+            match by_context.get_mut(&result.context_id) {
+                Some((best, matched)) => {
+                    *matched += 1;
+                    if result.similarity_score > best.similarity_score {
+                        *best = result;
+                    }
+                }
+                None => {
+                    order.push(result.context_id.clone());
+                    by_context.insert(result.context_id.clone(), (result, 1));
+                }
+            }
+        }
+        
+        let mut aggregated: Vec<VectorSearchResult> = order
+            .into_iter()
+            .filter_map(|context_id| by_context.remove(&context_id))
+            .map(|(mut best, matched)| {
+                if let Some(chunk) = best.metadata.chunk.as_mut() {
+                    let bonus = (CHUNK_SUPPORT_BONUS * (matched - 1) as f32).min(MAX_CHUNK_SUPPORT_BONUS);
+                    best.similarity_score = (best.similarity_score + bonus).min(1.0);
+                    best.distance = 1.0 - best.similarity_score;
+                    chunk.matched_chunks = matched;
+                    best.metadata.content_preview = snippet(&best.metadata.content_preview, SNIPPET_CHARS);
+                }
+                best
+            })
+            .collect();
+        
+        aggregated.sort_by(|a, b| b.similarity_score.partial_cmp(&a.similarity_score).unwrap_or(std::cmp::Ordering::Equal));
+        for (i, result) in aggregated.iter_mut().enumerate() {
+            result.rank = i + 1;
+        }
+        aggregated
+    }
+    
+    /// Reorder relevance-ranked results by maximal marginal relevance, comparing
+    /// items by the centroid of their serving-model vectors
+    async fn diversify_results(
+        &self,
+        results: Vec<VectorSearchResult>,
+        diversity: f32,
+        max_results: usize,
+    ) -> Result<Vec<VectorSearchResult>, SemanticSearchError> {
+        let model_name = self.serving_service().get_model_info().model_name;
+        
+        let mut vectors = Vec::with_capacity(results.len());
+        for result in &results {
+            let embeddings: Vec<ContextEmbedding> = self.embedding_repository
+                .get_embeddings_by_context_id(&result.context_id)
+                .await?
+                .into_iter()
+                .filter(|e| e.embedding_model == model_name)
+                .collect();
+            vectors.push((!embeddings.is_empty()).then(|| centroid(&embeddings)));
+        }
+        
+        let relevance: Vec<f32> = results.iter().map(|r| r.similarity_score).collect();
+        let order = maximal_marginal_relevance(&relevance, &vectors, max_results, diversity);
+        
+        let mut results: Vec<Option<VectorSearchResult>> = results.into_iter().map(Some).collect();
+        let mut diversified: Vec<VectorSearchResult> = order
+            .into_iter()
+            .filter_map(|i| results[i].take())
+            .collect();
+        for (i, result) in diversified.iter_mut().enumerate() {
+            result.rank = i + 1;
+        }
+        
+        Ok(diversified)
+    }
+    
+    /// Load the text and heading of best chunks that came from the ANN index
+    async fn fill_chunk_snippets(&self, results: &mut [VectorSearchResult]) -> Result<(), SemanticSearchError> {
+        let model_name = self.serving_service().get_model_info().model_name;
+        
+        for result in results.iter_mut() {
+            let Some(chunk_index) = result.metadata.chunk.as_ref().filter(|c| c.heading.is_none()).map(|c| c.chunk_index) else {
+                continue;
+            };
+            if result.metadata.content_preview != "Preview not available" {
+                continue;
+            }
+            
+            let chunk = self.embedding_repository
+                .get_embeddings_by_context_id(&result.context_id)
+                .await?
+                .into_iter()
+                .filter(|e| e.embedding_model == model_name)
+                .find_map(|e| e.chunk.filter(|c| c.index == chunk_index));
+            
+            if let (Some(chunk), Some(chunk_match)) = (chunk, result.metadata.chunk.as_mut()) {
+                chunk_match.heading = chunk.heading;
+                result.metadata.content_preview = snippet(&chunk.text, SNIPPET_CHARS);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Get or generate query embedding with caching; the project selects the
+    /// corpus statistics the query is weighted with
+    async fn get_query_embedding(&self, query_text: &str, project_id: Option<&str>) -> Result<Vec<f32>, SemanticSearchError> {
         if self.config.cache_query_embeddings {
-            let cache_key = format!("{:x}", md5::compute(query_text.as_bytes()));
+            let cache_key = format!(
+                "{}:{:x}",
+                project_id.unwrap_or(""),
+                md5::compute(query_text.as_bytes())
+            );
             
             // Check cache first
             {
@@ -479,8 +774,8 @@ impl SemanticSearchServiceImpl {
             }
             
             // Generate new embedding
-            let embedding = self.embedding_service
-                .generate_embedding(query_text, "query")
+            let embedding = self.serving_service()
+                .generate_project_embedding(query_text, "query", project_id)
                 .await?;
             
             // Cache the result
@@ -491,8 +786,8 @@ impl SemanticSearchServiceImpl {
             
             Ok(embedding.embedding_vector)
         } else {
-            let embedding = self.embedding_service
-                .generate_embedding(query_text, "query")
+            let embedding = self.serving_service()
+                .generate_project_embedding(query_text, "query", project_id)
                 .await?;
             Ok(embedding.embedding_vector)
         }
@@ -605,8 +900,8 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
     async fn index_context(&self, context: &EnhancedContextItem) -> Result<(), SemanticSearchError> {
         debug!("Indexing context: {}", context.id);
         
-        let embedding = self.generate_context_embedding(context).await?;
-        self.embedding_repository.store_embedding(&embedding).await?;
+        let embeddings = self.generate_all_model_embeddings(context).await?;
+        self.embedding_repository.replace_context_embeddings(&embeddings).await?;
         
         info!("Successfully indexed context: {}", context.id);
         Ok(())
@@ -618,11 +913,10 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
         let mut embeddings = Vec::new();
         
         for context in contexts {
-            let embedding = self.generate_context_embedding(context).await?;
-            embeddings.push(embedding);
+            embeddings.extend(self.generate_all_model_embeddings(context).await?);
         }
         
-        self.embedding_repository.store_embeddings_batch(&embeddings).await?;
+        self.embedding_repository.replace_context_embeddings(&embeddings).await?;
         
         info!("Successfully indexed {} contexts in batch", contexts.len());
         Ok(())
@@ -651,14 +945,26 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
         debug!("Query processed - Intent: {:?}, Confidence: {:.2}", 
                processed_query.intent, processed_query.confidence);
         
+        let project_filter = query.filters.project_ids.as_ref()
+            .and_then(|ids| ids.first())
+            .map(|s| s.as_str());
+        
         // Generate query embedding using processed query
         let embedding_start = std::time::Instant::now();
-        let query_embedding = self.get_query_embedding(&processed_query.processed_query).await?;
+        let query_embedding = self.get_query_embedding(&processed_query.processed_query, project_filter).await?;
         let embedding_time = embedding_start.elapsed().as_millis() as u64;
         
         // Create enhanced query with embedding and filters based on intent
         let mut enhanced_query = query.clone();
         enhanced_query.query_embedding = Some(query_embedding);
+        // Several hits may be chunks of one item, so fetch extra before aggregating
+        enhanced_query.max_results = query.max_results * CHUNK_CANDIDATE_MULTIPLIER;
+        let diversity = query.ranking_method.diversity();
+        if diversity.is_some() {
+            enhanced_query.max_results *= MMR_CANDIDATE_MULTIPLIER;
+        }
+        // Only vectors of the model the query was embedded with are comparable
+        enhanced_query.filters.embedding_model = self.serving_model();
         
         // Apply content type filters based on intent
         if !processed_query.content_type_hints.is_empty() {
@@ -677,18 +983,18 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
         
         // Perform vector search
         let search_start = std::time::Instant::now();
-        let project_filter = query.filters.project_ids.as_ref()
-            .and_then(|ids| ids.first())
-            .map(|s| s.as_str());
-        
-        let mut vector_results = self.embedding_repository
-            .find_similar_embeddings(&enhanced_query, project_filter)
-            .await?;
+        let vector_results = self.find_candidates(&enhanced_query, project_filter).await?;
+        let mut vector_results = self.aggregate_chunk_results(vector_results);
         
         let search_time = search_start.elapsed().as_millis() as u64;
         
         // Apply enhanced reranking with recency, usage, and quality factors
         vector_results = self.rerank_results_enhanced(vector_results, &processed_query);
+        if let Some(diversity) = diversity {
+            vector_results = self.diversify_results(vector_results, diversity, query.max_results).await?;
+        }
+        vector_results.truncate(query.max_results);
+        self.fill_chunk_snippets(&mut vector_results).await?;
         
         // Convert to enhanced results
         let mut enhanced_results = Vec::new();
@@ -697,6 +1003,9 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
         if !processed_query.content_type_hints.is_empty() {
             filters_applied.push("content_type_intent".to_string());
         }
+        if diversity.is_some() {
+            filters_applied.push("diversity".to_string());
+        }
         
         for vector_result in vector_results {
             let search_metadata = SearchMetadata {
@@ -735,18 +1044,27 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
     async fn find_similar_contexts(&self, context_id: &str, max_results: usize) -> Result<Vec<EnhancedSearchResult>, SemanticSearchError> {
         debug!("Finding similar contexts to: {}", context_id);
         
-        // Get the embedding for the source context
-        let source_embedding = self.embedding_repository
-            .get_embedding_by_context_id(context_id)
-            .await?
-            .ok_or_else(|| SemanticSearchError::QueryProcessingError {
+        // Get the embeddings (one per chunk) for the source context
+        let model_name = self.serving_service().get_model_info().model_name;
+        let mut source_embeddings = self.embedding_repository
+            .get_embeddings_by_context_id(context_id)
+            .await?;
+        if source_embeddings.iter().any(|e| e.embedding_model == model_name) {
+            source_embeddings.retain(|e| e.embedding_model == model_name);
+        }
+        if source_embeddings.is_empty() {
+            return Err(SemanticSearchError::QueryProcessingError {
                 message: format!("No embedding found for context: {}", context_id)
-            })?;
+            });
+        }
+        
+        // A chunked item is represented by the centroid of its chunks
+        let source_vector = centroid(&source_embeddings);
         
         // Create a search query using the source embedding
         let query = VectorSearchQuery {
             query_text: "similar_context_search".to_string(),
-            query_embedding: Some(source_embedding.embedding_vector),
+            query_embedding: Some(source_vector),
             similarity_threshold: 0.5, // Lower threshold for similarity search
             max_results,
             filters: Default::default(),
@@ -814,12 +1132,13 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
             .await?;
         
         let stats = SearchIndexStats {
-            total_indexed_items: embedding_stats.total_embeddings as usize,
+            total_indexed_items: embedding_stats.indexed_contexts as usize,
             items_by_content_type: HashMap::new(), // Would be calculated from embeddings
             items_by_project: HashMap::new(), // Would be calculated from embeddings
             average_embedding_quality: 0.8, // Would be calculated from quality scores
             index_freshness_score: 0.9, // Would be calculated based on update times
             last_updated: embedding_stats.newest_embedding.unwrap_or_else(chrono::Utc::now),
+            vector_index: self.vector_index.as_ref().map(|index| index.stats(DEFAULT_RECALL_SAMPLE_SIZE)),
         };
         
         debug!("Index stats: {} total items", stats.total_indexed_items);
@@ -840,4 +1159,89 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
         info!("Successfully rebuilt index for project: {}", project_id);
         Ok(())
     }
-}
\ No newline at end of file
+    
+    fn serving_model(&self) -> Option<String> {
+        Some(self.serving_service().get_model_info().model_name)
+    }
+    
+    async fn refresh_vocabulary(&self, project_id: &str, force: bool) -> Result<bool, SemanticSearchError> {
+        let services = std::iter::once(self.serving_service()).chain(self.target_service());
+        
+        let mut changed = false;
+        let mut global_changed = false;
+        for service in services {
+            changed |= service.refresh_vocabulary(Some(project_id), force).await?;
+            // Queries spanning projects are weighted with the database-wide statistics
+            global_changed |= service.refresh_vocabulary(None, false).await?;
+        }
+        
+        // Cached query vectors were weighted with the old statistics
+        if changed || global_changed {
+            self.query_cache.lock().await.clear();
+        }
+        Ok(changed)
+    }
+    
+    fn begin_model_migration(
+        &self,
+        serving: Arc<dyn EmbeddingService>,
+        target: Arc<dyn EmbeddingService>,
+    ) -> Result<(), SemanticSearchError> {
+        let serving_model = serving.get_model_info().model_name;
+        let target_model = target.get_model_info().model_name;
+        if serving_model == target_model {
+            return Err(SemanticSearchError::ConfigurationError {
+                message: format!("Cannot migrate embedding model {} to itself", serving_model),
+            });
+        }
+        
+        *self.models.write().unwrap() = EmbeddingModels { serving, target: Some(target) };
+        // Cached query vectors may come from a different model
+        if let Ok(mut cache) = self.query_cache.try_lock() {
+            cache.clear();
+        }
+        
+        info!("Serving {} while re-embedding with {}", serving_model, target_model);
+        Ok(())
+    }
+    
+    async fn index_context_for_target_model(&self, context: &EnhancedContextItem) -> Result<(), SemanticSearchError> {
+        let target = self.target_service().ok_or_else(|| SemanticSearchError::IndexNotReady {
+            message: "No model migration in progress".to_string(),
+        })?;
+        
+        let embeddings = self.generate_context_embeddings(target.as_ref(), context).await?;
+        self.embedding_repository.replace_context_embeddings(&embeddings).await?;
+        Ok(())
+    }
+    
+    async fn complete_model_migration(&self) -> Result<String, SemanticSearchError> {
+        let retired = {
+            let mut models = self.models.write().unwrap();
+            let target = models.target.take().ok_or_else(|| SemanticSearchError::IndexNotReady {
+                message: "No model migration in progress".to_string(),
+            })?;
+            std::mem::replace(&mut models.serving, target)
+        };
+        self.query_cache.lock().await.clear();
+        
+        let retired_model = retired.get_model_info().model_name;
+        info!("Queries now served by {}, retired {}", self.serving_service().get_model_info().model_name, retired_model);
+        Ok(retired_model)
+    }
+}
+/// Normalized mean of the vectors of one item's chunks
+fn centroid(embeddings: &[ContextEmbedding]) -> Vec<f32> {
+    let dimension = embeddings[0].embedding_vector.len();
+    let mut vector = vec![0.0f32; dimension];
+    for embedding in embeddings.iter().filter(|e| e.embedding_vector.len() == dimension) {
+        for (sum, value) in vector.iter_mut().zip(&embedding.embedding_vector) {
+            *sum += value;
+        }
+    }
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+    vector
+}
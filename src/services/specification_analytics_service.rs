@@ -12,9 +12,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
+use schemars::JsonSchema;
 
 /// Progress tracking data for requirements
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RequirementProgress {
     pub requirement_id: String,
     pub title: String,
@@ -32,7 +33,7 @@ pub struct RequirementProgress {
 }
 
 /// Progress tracking data for tasks
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TaskProgress {
     pub task_id: String,
     pub title: String,
@@ -54,7 +55,7 @@ pub struct TaskProgress {
 }
 
 /// Specification completeness analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SpecificationCompleteness {
     pub spec_id: String,
     pub spec_type: SpecType,
@@ -70,7 +71,7 @@ pub struct SpecificationCompleteness {
 }
 
 /// Development velocity metrics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DevelopmentVelocity {
     pub project_id: String,
     pub time_period_days: i64,
@@ -84,7 +85,7 @@ pub struct DevelopmentVelocity {
 }
 
 /// Velocity trend analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum VelocityTrend {
     Increasing,
     Stable,
@@ -93,7 +94,7 @@ pub enum VelocityTrend {
 }
 
 /// Specification health report
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SpecificationHealthReport {
     pub project_id: String,
     pub overall_health_score: f64,
@@ -649,6 +650,7 @@ impl SpecificationAnalyticsService for DefaultSpecificationAnalyticsService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::pagination::{ListQuery, Page};
     use crate::models::specification::{SpecContent, SpecFormat, AcceptanceCriterion, CriterionType, CriterionStatus};
     use crate::repositories::SpecificationRepository;
     use crate::services::analytics_service::{AnalyticsService, UsageStatistics, ProjectInsights};
@@ -724,6 +726,14 @@ mod tests {
                 .collect())
         }
 
+        async fn find_specifications_page(&self, project_id: &str, spec_type: Option<&str>, query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError> {
+            let specifications = self.specifications.iter()
+                .filter(|s| s.project_id == project_id && spec_type.is_none_or(|t| s.spec_type.as_str() == t))
+                .cloned()
+                .collect();
+            Ok(query.window(&["created_at"])?.page(specifications))
+        }
+
         async fn find_specifications_by_type(&self, project_id: &str, spec_type: &str) -> Result<Vec<ProjectSpecification>, McpError> {
             Ok(self.specifications.iter()
                 .filter(|s| s.project_id == project_id && s.spec_type.as_str() == spec_type)
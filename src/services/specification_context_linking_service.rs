@@ -734,6 +734,7 @@ impl SpecificationContextLinkingService for DefaultSpecificationContextLinkingSe
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::pagination::{ListQuery, Page};
     use crate::models::specification::{SpecContent, SpecFormat, SpecType, AcceptanceCriterion, CriterionType};
     use crate::repositories::{SpecificationRepository, EnhancedContextRepository};
     use crate::services::context_query_service::{ContextQueryService, ContextQueryResult};
@@ -762,6 +763,10 @@ mod tests {
             Ok(Vec::new())
         }
         
+        async fn find_specifications_page(&self, _project_id: &str, _spec_type: Option<&str>, _query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError> {
+            Ok(Page { items: Vec::new(), next_cursor: None })
+        }
+
         async fn find_specifications_by_type(&self, _project_id: &str, _spec_type: &str) -> Result<Vec<ProjectSpecification>, McpError> {
             Ok(Vec::new())
         }
@@ -899,6 +904,10 @@ mod tests {
             }
         }
         
+        async fn search_contexts(&self, _project_id: Option<&str>, _query: &str, _limit: usize) -> Result<Vec<crate::models::enhanced_context::KeywordSearchResult>, McpError> {
+            Ok(Vec::new())
+        }
+        
         async fn update_context(&self, context: &EnhancedContextItem) -> Result<EnhancedContextItem, McpError> {
             Ok(context.clone())
         }
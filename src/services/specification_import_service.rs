@@ -16,6 +16,9 @@ pub trait SpecificationImportService: Send + Sync {
     /// Scan and import all specifications from the .kiro/specs directory
     async fn scan_and_import_specifications(&self, base_path: &Path) -> Result<Vec<ProjectSpecification>, McpError>;
     
+    /// Find the specification files under a specs directory without importing them
+    async fn find_specification_files(&self, base_path: &Path) -> Result<Vec<PathBuf>, McpError>;
+    
     /// Import a single specification file
     async fn import_specification_file(&self, file_path: &Path) -> Result<ProjectSpecification, McpError>;
     
@@ -149,8 +152,7 @@ impl SpecificationImportService for DefaultSpecificationImportService {
     async fn scan_and_import_specifications(&self, base_path: &Path) -> Result<Vec<ProjectSpecification>, McpError> {
         info!("Scanning for Kiro specifications in: {}", base_path.display());
 
-        let spec_files = Self::scan_directory(base_path).await
-            .map_err(|e| McpError::internal_error(format!("Failed to scan directory: {}", e), None))?;
+        let spec_files = self.find_specification_files(base_path).await?;
 
         let mut imported_specs = Vec::new();
 
@@ -170,6 +172,11 @@ impl SpecificationImportService for DefaultSpecificationImportService {
         Ok(imported_specs)
     }
 
+    async fn find_specification_files(&self, base_path: &Path) -> Result<Vec<PathBuf>, McpError> {
+        Self::scan_directory(base_path).await
+            .map_err(|e| McpError::internal_error(format!("Failed to scan directory: {}", e), None))
+    }
+
     async fn import_specification_file(&self, file_path: &Path) -> Result<ProjectSpecification, McpError> {
         debug!("Importing specification file: {}", file_path.display());
 
@@ -278,6 +285,7 @@ impl SpecificationImportService for DefaultSpecificationImportService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::pagination::{ListQuery, Page};
     use crate::models::specification::{SpecContent, SpecFormat};
     use crate::repositories::SpecificationRepository;
     use crate::services::SpecificationService;
@@ -320,6 +328,10 @@ mod tests {
             Ok(Vec::new())
         }
 
+        async fn get_specifications_page(&self, _project_id: &str, _spec_type: Option<&str>, _query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError> {
+            Ok(Page { items: Vec::new(), next_cursor: None })
+        }
+
         async fn update_specification(&self, spec: ProjectSpecification) -> Result<ProjectSpecification, McpError> {
             Ok(spec)
         }
@@ -377,6 +389,10 @@ mod tests {
             Ok(Vec::new())
         }
 
+        async fn find_specifications_page(&self, _project_id: &str, _spec_type: Option<&str>, _query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError> {
+            Ok(Page { items: Vec::new(), next_cursor: None })
+        }
+
         async fn find_specifications_by_type(&self, _project_id: &str, _spec_type: &str) -> Result<Vec<ProjectSpecification>, McpError> {
             Ok(Vec::new())
         }
@@ -1,3 +1,4 @@
+use crate::models::pagination::{ListQuery, Page};
 use crate::models::specification::{ProjectSpecification, Requirement, Task, SpecType};
 use crate::repositories::SpecificationRepository;
 use crate::services::SpecificationParser;
@@ -19,6 +20,7 @@ pub trait SpecificationService: Send + Sync {
     async fn get_specification(&self, id: &str) -> Result<Option<ProjectSpecification>, McpError>;
     async fn get_specifications_by_project(&self, project_id: &str) -> Result<Vec<ProjectSpecification>, McpError>;
     async fn get_specifications_by_type(&self, project_id: &str, spec_type: SpecType) -> Result<Vec<ProjectSpecification>, McpError>;
+    async fn get_specifications_page(&self, project_id: &str, spec_type: Option<&str>, query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError>;
     async fn update_specification(&self, spec: ProjectSpecification) -> Result<ProjectSpecification, McpError>;
     async fn delete_specification(&self, id: &str) -> Result<bool, McpError>;
     
@@ -145,6 +147,22 @@ impl SpecificationService for DefaultSpecificationService {
         Ok(specifications)
     }
 
+    async fn get_specifications_page(&self, project_id: &str, spec_type: Option<&str>, query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError> {
+        let mut page = self.repository
+            .find_specifications_page(project_id, spec_type, query)
+            .await?;
+
+        for spec in &mut page.items {
+            let requirements = self.repository.find_requirements_by_spec(&spec.id).await?;
+            spec.requirements = requirements.into_iter().map(|r| r.id).collect();
+
+            let tasks = self.repository.find_tasks_by_spec(&spec.id).await?;
+            spec.tasks = tasks.into_iter().map(|t| t.id).collect();
+        }
+
+        Ok(page)
+    }
+
     async fn update_specification(&self, spec: ProjectSpecification) -> Result<ProjectSpecification, McpError> {
         self.repository.update_specification(&spec).await
     }
@@ -240,6 +258,7 @@ impl SpecificationService for DefaultSpecificationService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::pagination::{ListQuery, Page};
     use crate::models::specification::{SpecContent, SpecFormat, SpecStatus};
     use crate::repositories::SpecificationRepository;
     use async_trait::async_trait;
@@ -262,6 +281,10 @@ mod tests {
             Ok(Vec::new())
         }
 
+        async fn find_specifications_page(&self, _project_id: &str, _spec_type: Option<&str>, _query: &ListQuery) -> Result<Page<ProjectSpecification>, McpError> {
+            Ok(Page { items: Vec::new(), next_cursor: None })
+        }
+
         async fn find_specifications_by_type(&self, _project_id: &str, _spec_type: &str) -> Result<Vec<ProjectSpecification>, McpError> {
             Ok(Vec::new())
         }
@@ -1,3 +1,4 @@
+use crate::db::migrations::run_migrations;
 use crate::models::specification::ProjectSpecification;
 use anyhow::Result;
 use async_trait::async_trait;
@@ -8,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
+use schemars::JsonSchema;
 
 /// Service for managing specification versions and change tracking
 #[async_trait]
@@ -35,7 +37,7 @@ pub trait SpecificationVersioningService: Send + Sync {
 }
 
 /// Represents a version of a specification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SpecificationVersion {
     pub id: String,
     pub spec_id: String,
@@ -52,7 +54,7 @@ pub struct SpecificationVersion {
 }
 
 /// Types of version changes
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum VersionChangeType {
     Created,
     Modified,
@@ -85,7 +87,7 @@ impl VersionChangeType {
 }
 
 /// Comparison between two specification versions
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VersionComparison {
     pub version1: SpecificationVersion,
     pub version2: SpecificationVersion,
@@ -94,7 +96,7 @@ pub struct VersionComparison {
 }
 
 /// Represents a difference between two versions
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VersionDifference {
     pub section: String,
     pub change_type: DifferenceType,
@@ -104,7 +106,7 @@ pub struct VersionDifference {
 }
 
 /// Types of differences between versions
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum DifferenceType {
     Added,
     Removed,
@@ -122,39 +124,11 @@ impl SqliteSpecificationVersioningService {
         Self { db }
     }
 
-    /// Initialize database tables for specification versioning
+    /// Bring the schema, including the specification version tables, up to date
     pub fn initialize_tables(&self) -> Result<(), McpError> {
         let db = self.db.lock().unwrap();
-
-        // Create specification_versions table
-        db.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS specification_versions (
-                id TEXT PRIMARY KEY,
-                spec_id TEXT NOT NULL,
-                version_number INTEGER NOT NULL,
-                content_hash TEXT NOT NULL,
-                raw_content TEXT NOT NULL,
-                parsed_sections TEXT, -- JSON
-                change_description TEXT NOT NULL,
-                change_type TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                created_by TEXT,
-                file_path TEXT,
-                metadata TEXT, -- JSON
-                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE
-            )
-            "#,
-            [],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create specification_versions table: {}", e), None))?;
-
-        // Create indexes for better performance
-        db.execute("CREATE INDEX IF NOT EXISTS idx_spec_versions_spec_id ON specification_versions (spec_id)", [])
-            .map_err(|e| McpError::internal_error(format!("Failed to create index: {}", e), None))?;
-        
-        db.execute("CREATE INDEX IF NOT EXISTS idx_spec_versions_version_number ON specification_versions (spec_id, version_number)", [])
-            .map_err(|e| McpError::internal_error(format!("Failed to create index: {}", e), None))?;
-
+        run_migrations(&db)
+            .map_err(|e| McpError::internal_error(format!("Failed to migrate database: {}", e), None))?;
         Ok(())
     }
 
@@ -0,0 +1,320 @@
+use crate::models::embedding::EmbeddingChunk;
+use serde::{Deserialize, Serialize};
+
+/// How long context items are split before embedding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChunkingConfig {
+    pub enabled: bool,
+    /// Upper bound per chunk; keep below `max_sequence_length * 4` so nothing is truncated
+    pub max_chunk_chars: usize,
+    /// Text repeated between consecutive sliding windows
+    pub overlap_chars: usize,
+    /// Markdown sections shorter than this are merged with their neighbour
+    pub min_chunk_chars: usize,
+}
+
+impl Default for ChunkingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_chunk_chars: 1800,
+            overlap_chars: 200,
+            min_chunk_chars: 200,
+        }
+    }
+}
+
+impl ChunkingConfig {
+    /// Chunk size matching the truncation limit of an embedding model
+    pub fn for_sequence_length(max_sequence_length: usize) -> Self {
+        let max_chunk_chars = (max_sequence_length * 4).saturating_sub(256).max(256);
+        Self {
+            max_chunk_chars,
+            overlap_chars: max_chunk_chars / 9,
+            min_chunk_chars: max_chunk_chars / 9,
+            ..Default::default()
+        }
+    }
+}
+
+/// Splits text into chunks: by heading for Markdown, by overlapping windows
+/// for prose. Text that fits in one chunk is returned as a single chunk.
+pub struct TextChunker {
+    config: ChunkingConfig,
+}
+
+impl TextChunker {
+    pub fn new(config: ChunkingConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &ChunkingConfig {
+        &self.config
+    }
+
+    pub fn chunk(&self, text: &str) -> Vec<EmbeddingChunk> {
+        let max = self.config.max_chunk_chars.max(1);
+        if !self.config.enabled || text.len() <= max {
+            return vec![make_chunk(text, 0, text.len(), None)];
+        }
+
+        let spans = if is_markdown(text) {
+            self.markdown_spans(text)
+        } else {
+            self.window_spans(text, 0, text.len(), None)
+        };
+
+        spans
+            .into_iter()
+            .filter(|(start, end, _)| !text[*start..*end].trim().is_empty())
+            .enumerate()
+            .map(|(index, (start, end, heading))| {
+                let mut chunk = make_chunk(text, start, end, heading);
+                chunk.index = index;
+                chunk
+            })
+            .collect()
+    }
+
+    /// One span per heading section, merging short sections and windowing long ones
+    fn markdown_spans(&self, text: &str) -> Vec<(usize, usize, Option<String>)> {
+        let mut sections: Vec<(usize, usize, Option<String>)> = Vec::new();
+        let mut headings: Vec<(usize, String)> = Vec::new();
+        let mut section_start = 0;
+        let mut section_heading: Option<String> = None;
+
+        let mut offset = 0;
+        for line in text.split_inclusive('\n') {
+            if let Some((level, title)) = parse_heading(line) {
+                if offset > section_start {
+                    sections.push((section_start, offset, section_heading.clone()));
+                }
+                headings.retain(|(l, _)| *l < level);
+                headings.push((level, title));
+                section_heading = Some(
+                    headings
+                        .iter()
+                        .map(|(_, t)| t.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" > "),
+                );
+                section_start = offset;
+            }
+            offset += line.len();
+        }
+        if text.len() > section_start {
+            sections.push((section_start, text.len(), section_heading));
+        }
+
+        let mut merged: Vec<(usize, usize, Option<String>)> = Vec::new();
+        for section in sections {
+            match merged.last_mut() {
+                Some(last)
+                    if last.1 - last.0 < self.config.min_chunk_chars
+                        && section.1 - last.0 <= self.config.max_chunk_chars =>
+                {
+                    last.1 = section.1;
+                    if last.2.is_none() {
+                        last.2 = section.2;
+                    }
+                }
+                _ => merged.push(section),
+            }
+        }
+
+        merged
+            .into_iter()
+            .flat_map(|(start, end, heading)| {
+                if end - start > self.config.max_chunk_chars {
+                    self.window_spans(text, start, end, heading)
+                } else {
+                    vec![(start, end, heading)]
+                }
+            })
+            .collect()
+    }
+
+    /// Overlapping windows over `text[start..end]`, cut at whitespace where possible
+    fn window_spans(
+        &self,
+        text: &str,
+        start: usize,
+        end: usize,
+        heading: Option<String>,
+    ) -> Vec<(usize, usize, Option<String>)> {
+        let max = self.config.max_chunk_chars.max(1);
+        let overlap = self.config.overlap_chars.min(max / 2);
+        let mut spans = Vec::new();
+        let mut window_start = start;
+
+        loop {
+            if end - window_start <= max {
+                spans.push((window_start, end, heading.clone()));
+                break;
+            }
+
+            let limit = floor_char_boundary(text, window_start + max);
+            // Prefer a sentence end, then any whitespace, in the last quarter of the window
+            let search_from = floor_char_boundary(text, window_start + max * 3 / 4);
+            let window = &text[search_from..limit];
+            let cut = window
+                .rfind(". ")
+                .map(|i| search_from + i + 2)
+                .or_else(|| window.rfind(char::is_whitespace).map(|i| search_from + i + 1))
+                .filter(|cut| *cut > window_start)
+                .unwrap_or(limit.max(ceil_char_boundary(text, window_start + 1)));
+
+            spans.push((window_start, cut, heading.clone()));
+
+            let mut next = floor_char_boundary(text, cut.saturating_sub(overlap)).max(window_start + 1);
+            // Start the overlap on a word rather than mid-way through one
+            if let Some(space) = text[next..cut].find(char::is_whitespace) {
+                next += space + 1;
+            }
+            window_start = ceil_char_boundary(text, next.min(cut));
+        }
+
+        spans
+    }
+}
+
+impl Default for TextChunker {
+    fn default() -> Self {
+        Self::new(ChunkingConfig::default())
+    }
+}
+
+/// At most `max_chars` bytes of `text`, cut at a word and marked with an ellipsis
+pub fn snippet(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.len() <= max_chars {
+        return text.to_string();
+    }
+    let limit = floor_char_boundary(text, max_chars);
+    let cut = text[..limit].rfind(char::is_whitespace).filter(|i| *i > limit / 2).unwrap_or(limit);
+    format!("{}…", text[..cut].trim_end())
+}
+
+fn make_chunk(text: &str, start: usize, end: usize, heading: Option<String>) -> EmbeddingChunk {
+    EmbeddingChunk {
+        index: 0,
+        start,
+        end,
+        heading,
+        text: text[start..end].trim().to_string(),
+    }
+}
+
+/// `# Title` style ATX headings; fenced code is not tracked
+fn parse_heading(line: &str) -> Option<(usize, String)> {
+    let trimmed = line.trim_end();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches('#').trim();
+    (!title.is_empty()).then(|| (level, title.to_string()))
+}
+
+fn is_markdown(text: &str) -> bool {
+    text.lines().any(|line| parse_heading(line).is_some())
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+    index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunker(max: usize, overlap: usize) -> TextChunker {
+        TextChunker::new(ChunkingConfig {
+            enabled: true,
+            max_chunk_chars: max,
+            overlap_chars: overlap,
+            min_chunk_chars: 40,
+        })
+    }
+
+    #[test]
+    fn test_short_text_is_single_chunk() {
+        let chunks = chunker(100, 10).chunk("Tokens expire after one day.");
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, "Tokens expire after one day.");
+        assert_eq!(chunks[0].heading, None);
+    }
+
+    #[test]
+    fn test_prose_uses_overlapping_windows() {
+        let text = (0..60).map(|i| format!("Sentence number {} talks about caching.", i)).collect::<Vec<_>>().join(" ");
+        let chunks = chunker(300, 60).chunk(&text);
+
+        assert!(chunks.len() > 5);
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert_eq!(chunk.index, i);
+            assert!(chunk.end - chunk.start <= 300);
+            assert_eq!(chunk.text, text[chunk.start..chunk.end].trim());
+        }
+        // Consecutive windows overlap and together cover the whole text
+        assert!(chunks.windows(2).all(|w| w[1].start < w[0].end));
+        assert_eq!(chunks.first().unwrap().start, 0);
+        assert_eq!(chunks.last().unwrap().end, text.len());
+    }
+
+    #[test]
+    fn test_markdown_splits_on_headings() {
+        let body = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(3);
+        let text = format!(
+            "# Auth\n{body}\n## Tokens\n{body}\n## Sessions\n{body}\n# Billing\n{body}",
+            body = body
+        );
+        let chunks = chunker(250, 20).chunk(&text);
+
+        let headings: Vec<_> = chunks.iter().map(|c| c.heading.clone().unwrap()).collect();
+        assert_eq!(headings, vec!["Auth", "Auth > Tokens", "Auth > Sessions", "Billing"]);
+        assert!(chunks[1].text.starts_with("## Tokens"));
+    }
+
+    #[test]
+    fn test_long_markdown_section_is_windowed_and_keeps_heading() {
+        let text = format!("# Intro\nShort intro paragraph here.\n# Details\n{}", "word ".repeat(200));
+        let chunks = chunker(200, 20).chunk(&text);
+
+        assert!(chunks.len() > 3);
+        assert!(chunks.iter().skip(1).all(|c| c.heading.as_deref() == Some("Details")));
+        assert!(chunks.iter().all(|c| c.end - c.start <= 200));
+    }
+
+    #[test]
+    fn test_snippet_cuts_at_word() {
+        assert_eq!(snippet("short text", 50), "short text");
+        assert_eq!(snippet("rotate access tokens daily", 17), "rotate access…");
+        assert_eq!(snippet("データベース", 7), "デー…");
+    }
+
+    #[test]
+    fn test_multibyte_text_is_cut_on_char_boundaries() {
+        let text = "データベースの移行は元に戻せる必要があります。".repeat(40);
+        let chunks = chunker(120, 30).chunk(&text);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.last().unwrap().end, text.len());
+    }
+}
@@ -0,0 +1,913 @@
+use crate::models::embedding::{
+    ContextEmbedding, EmbeddingConfig, EmbeddingMetadata, ModelInfo, VectorSearchQuery,
+    VectorSearchResult,
+};
+use crate::repositories::embedding_repository::SqliteEmbeddingRepository;
+use crate::services::embedding_service::{rank_by_similarity, EmbeddingError, EmbeddingService};
+use async_trait::async_trait;
+use icu_normalizer::DecomposingNormalizerBorrowed;
+use icu_properties::props::GeneralCategory;
+use icu_properties::CodePointMapData;
+use nalgebra::{DMatrix, RowDVector};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use tracing::{debug, info, warn};
+
+/// Embedding service running a BERT-style sentence-transformer (e.g. all-MiniLM-L6-v2)
+/// fully offline on CPU.
+///
+/// The model directory named by `EmbeddingConfig::model_path` must contain
+/// `model.safetensors`, `config.json` and either `vocab.txt` or `tokenizer.json`;
+/// ONNX exports are not supported. The model is loaded lazily on first use, or eagerly via `initialize`.
+pub struct TransformerEmbeddingService {
+    config: EmbeddingConfig,
+    model: OnceCell<Arc<SentenceTransformer>>,
+}
+
+impl TransformerEmbeddingService {
+    pub fn new(config: EmbeddingConfig) -> Self {
+        Self {
+            config,
+            model: OnceCell::new(),
+        }
+    }
+
+    /// Load the model and tokenizer from disk
+    pub async fn initialize(&self) -> Result<(), EmbeddingError> {
+        self.model().await.map(|_| ())
+    }
+
+    async fn model(&self) -> Result<Arc<SentenceTransformer>, EmbeddingError> {
+        let model = self
+            .model
+            .get_or_try_init(|| async {
+                let model_path = self.config.model_path.clone().ok_or_else(|| {
+                    EmbeddingError::ConfigurationError {
+                        message: "model_path must be set for the transformer embedding service"
+                            .to_string(),
+                    }
+                })?;
+
+                info!("Loading sentence-transformer model from {}", model_path);
+                let model = tokio::task::spawn_blocking(move || {
+                    SentenceTransformer::load(Path::new(&model_path))
+                })
+                .await
+                .map_err(|e| EmbeddingError::ModelLoadError {
+                    message: format!("Model loading task failed: {}", e),
+                })??;
+
+                if model.config.hidden_size != self.config.embedding_dimension {
+                    warn!(
+                        "Configured embedding dimension {} does not match model hidden size {}, using the model's",
+                        self.config.embedding_dimension, model.config.hidden_size
+                    );
+                }
+
+                info!(
+                    "Loaded model {} ({} layers, dimension {})",
+                    self.config.model_name, model.config.num_hidden_layers, model.config.hidden_size
+                );
+                Ok::<_, EmbeddingError>(Arc::new(model))
+            })
+            .await?;
+
+        Ok(model.clone())
+    }
+
+    /// Encode text into a normalized sentence embedding
+    async fn encode(&self, text: &str) -> Result<Vec<f32>, EmbeddingError> {
+        let model = self.model().await?;
+        let text = text.to_string();
+        let max_length = self.config.max_sequence_length;
+
+        tokio::task::spawn_blocking(move || model.encode(&text, max_length))
+            .await
+            .map_err(|e| EmbeddingError::EmbeddingGenerationError {
+                message: format!("Inference task failed: {}", e),
+            })?
+    }
+}
+
+#[async_trait]
+impl EmbeddingService for TransformerEmbeddingService {
+    async fn generate_embedding(&self, text: &str, content_type: &str) -> Result<ContextEmbedding, EmbeddingError> {
+        debug!("Generating transformer embedding for content type: {}", content_type);
+
+        let embedding_vector = self.encode(text).await?;
+        let content_hash = format!("{:x}", md5::compute(text.as_bytes()));
+
+        let mut embedding = ContextEmbedding::new(
+            String::new(), // context_id will be set by caller
+            embedding_vector,
+            self.config.model_name.clone(),
+            "1.0".to_string(),
+            content_hash,
+        );
+
+        embedding.metadata = EmbeddingMetadata {
+            content_type: content_type.to_string(),
+            content_length: text.len(),
+            tokenization_method: self.config.tokenization_method.clone(),
+            preprocessing_steps: vec!["wordpiece_tokenization".to_string(), "mean_pooling".to_string()],
+            quality_score: if text.trim().len() < 10 { 0.5 } else { 1.0 },
+            custom_fields: HashMap::new(),
+        };
+
+        Ok(embedding)
+    }
+
+    async fn generate_embeddings_batch(&self, texts: Vec<(&str, &str, &str)>) -> Result<Vec<ContextEmbedding>, EmbeddingError> {
+        debug!("Generating batch of {} transformer embeddings", texts.len());
+
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for (context_id, text, content_type) in texts {
+            let mut embedding = self.generate_embedding(text, content_type).await?;
+            embedding.context_id = context_id.to_string();
+            embeddings.push(embedding);
+        }
+
+        Ok(embeddings)
+    }
+
+    fn calculate_similarity(&self, embedding1: &ContextEmbedding, embedding2: &ContextEmbedding) -> f32 {
+        embedding1.cosine_similarity(embedding2)
+    }
+
+    async fn find_similar(&self, query: &VectorSearchQuery, embeddings: &[ContextEmbedding]) -> Result<Vec<VectorSearchResult>, EmbeddingError> {
+        let query_embedding = match query.query_embedding {
+            Some(ref embedding) => embedding.clone(),
+            None => self.encode(&query.query_text).await?,
+        };
+
+        Ok(rank_by_similarity(&query_embedding, embeddings, query))
+    }
+
+    fn get_model_info(&self) -> ModelInfo {
+        match self.model.get() {
+            Some(model) => ModelInfo {
+                model_name: self.config.model_name.clone(),
+                model_version: model
+                    .config
+                    .transformers_version
+                    .clone()
+                    .unwrap_or_else(|| "1.0".to_string()),
+                embedding_dimension: model.config.hidden_size,
+                max_sequence_length: self
+                    .config
+                    .max_sequence_length
+                    .min(model.config.max_position_embeddings),
+                model_type: self.config.model_type.clone(),
+            },
+            None => ModelInfo {
+                model_name: self.config.model_name.clone(),
+                model_version: "1.0".to_string(),
+                embedding_dimension: self.config.embedding_dimension,
+                max_sequence_length: self.config.max_sequence_length,
+                model_type: self.config.model_type.clone(),
+            },
+        }
+    }
+
+    async fn update_config(&mut self, config: EmbeddingConfig) -> Result<(), EmbeddingError> {
+        info!("Updating transformer embedding service configuration");
+        let model_changed = config.model_path != self.config.model_path;
+        self.config = config;
+        if model_changed {
+            self.model = OnceCell::new();
+        }
+        self.initialize().await
+    }
+
+    async fn prepare(&self, _repository: &SqliteEmbeddingRepository) -> Result<(), EmbeddingError> {
+        self.initialize().await
+    }
+}
+
+/// Subset of a HuggingFace `config.json` needed to run a BERT encoder
+#[derive(Debug, Clone, Deserialize)]
+pub struct BertConfig {
+    pub hidden_size: usize,
+    pub num_hidden_layers: usize,
+    pub num_attention_heads: usize,
+    pub max_position_embeddings: usize,
+    #[serde(default = "default_layer_norm_eps")]
+    pub layer_norm_eps: f32,
+    #[serde(default)]
+    pub transformers_version: Option<String>,
+}
+
+fn default_layer_norm_eps() -> f32 {
+    1e-12
+}
+
+/// Tokenizer plus BERT encoder with mean pooling and L2 normalization
+struct SentenceTransformer {
+    config: BertConfig,
+    tokenizer: WordPieceTokenizer,
+    encoder: BertEncoder,
+}
+
+impl SentenceTransformer {
+    fn load(model_path: &Path) -> Result<Self, EmbeddingError> {
+        let (config, tokenizer, tensors) = load_model_files(model_path)?;
+        let encoder = BertEncoder::from_tensors(&tensors, &config)?;
+
+        Ok(Self {
+            config,
+            tokenizer,
+            encoder,
+        })
+    }
+
+    fn encode(&self, text: &str, max_length: usize) -> Result<Vec<f32>, EmbeddingError> {
+        let max_length = max_length.min(self.config.max_position_embeddings).max(2);
+        let token_ids = self.tokenizer.encode(text, max_length);
+        let hidden = self.encoder.forward(&token_ids, None)?;
+
+        // Mean pooling over all tokens, then L2 normalization
+        let mut pooled = vec![0.0f32; hidden.ncols()];
+        for row in hidden.row_iter() {
+            for (acc, value) in pooled.iter_mut().zip(row.iter()) {
+                *acc += value;
+            }
+        }
+        let count = hidden.nrows().max(1) as f32;
+        for value in &mut pooled {
+            *value /= count;
+        }
+
+        let norm: f32 = pooled.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for value in &mut pooled {
+                *value /= norm;
+            }
+        }
+
+        Ok(pooled)
+    }
+}
+
+/// BERT cross-encoder (e.g. ms-marco-MiniLM-L-6-v2) scoring a query and a
+/// passage read together, as exported by `BertForSequenceClassification`.
+///
+/// The model directory has the same layout as for `TransformerEmbeddingService`.
+pub struct CrossEncoder {
+    config: BertConfig,
+    tokenizer: WordPieceTokenizer,
+    encoder: BertEncoder,
+    pooler: Option<Linear>,
+    classifier: Linear,
+}
+
+impl CrossEncoder {
+    pub fn load(model_path: &Path) -> Result<Self, EmbeddingError> {
+        let (config, tokenizer, tensors) = load_model_files(model_path)?;
+        let encoder = BertEncoder::from_tensors(&tensors, &config)?;
+
+        Ok(Self {
+            pooler: tensors.linear("pooler.dense").ok(),
+            classifier: tensors.linear("classifier")?,
+            config,
+            tokenizer,
+            encoder,
+        })
+    }
+
+    /// Relevance logit for the pair; higher is more relevant
+    pub fn score(&self, query: &str, passage: &str, max_length: usize) -> Result<f32, EmbeddingError> {
+        let max_length = max_length.min(self.config.max_position_embeddings).max(3);
+        let (token_ids, token_type_ids) = self.tokenizer.encode_pair(query, passage, max_length);
+        let hidden = self.encoder.forward(&token_ids, Some(&token_type_ids))?;
+
+        let mut cls = hidden.rows(0, 1).into_owned();
+        if let Some(pooler) = &self.pooler {
+            cls = pooler.forward(&cls);
+            cls.apply(|x| *x = x.tanh());
+        }
+        let logits = self.classifier.forward(&cls);
+
+        // Single-logit rerankers score directly; two-label models score the "relevant" label
+        Ok(logits[(0, logits.ncols() - 1)])
+    }
+}
+
+/// Read `config.json`, the tokenizer and the weights of a BERT model
+fn load_model_files(model_path: &Path) -> Result<(BertConfig, WordPieceTokenizer, SafeTensors), EmbeddingError> {
+    let (model_dir, weights_path) = resolve_model_files(model_path)?;
+
+    let config_json = std::fs::read_to_string(model_dir.join("config.json"))
+        .map_err(|source| EmbeddingError::IoError { source })?;
+    let config: BertConfig = serde_json::from_str(&config_json).map_err(|e| {
+        EmbeddingError::ModelLoadError {
+            message: format!("Invalid config.json: {}", e),
+        }
+    })?;
+
+    if !config.hidden_size.is_multiple_of(config.num_attention_heads) {
+        return Err(EmbeddingError::ModelLoadError {
+            message: format!(
+                "hidden_size {} is not divisible by num_attention_heads {}",
+                config.hidden_size, config.num_attention_heads
+            ),
+        });
+    }
+
+    let tokenizer = WordPieceTokenizer::from_dir(&model_dir)?;
+    let tensors = SafeTensors::read(&weights_path)?;
+    Ok((config, tokenizer, tensors))
+}
+
+/// Resolve the model directory and weights file from a configured path
+fn resolve_model_files(model_path: &Path) -> Result<(PathBuf, PathBuf), EmbeddingError> {
+    if model_path.is_dir() {
+        let weights = model_path.join("model.safetensors");
+        if !weights.exists() {
+            let onnx_only = model_path.join("model.onnx").exists() || model_path.join("onnx").is_dir();
+            return Err(EmbeddingError::ModelLoadError {
+                message: if onnx_only {
+                    format!(
+                        "No model.safetensors found in {}; ONNX models are not supported, download the safetensors weights instead",
+                        model_path.display()
+                    )
+                } else {
+                    format!("No model.safetensors found in {}", model_path.display())
+                },
+            });
+        }
+        return Ok((model_path.to_path_buf(), weights));
+    }
+
+    match model_path.extension().and_then(|ext| ext.to_str()) {
+        Some("safetensors") => {
+            let dir = model_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from("."));
+            Ok((dir, model_path.to_path_buf()))
+        }
+        Some("onnx") => Err(EmbeddingError::ModelLoadError {
+            message: format!(
+                "ONNX models are not supported; use a directory with model.safetensors instead of {}",
+                model_path.display()
+            ),
+        }),
+        _ => Err(EmbeddingError::ModelLoadError {
+            message: format!(
+                "Expected a model directory or a .safetensors file: {}",
+                model_path.display()
+            ),
+        }),
+    }
+}
+
+/// Minimal reader for the safetensors format
+struct SafeTensors {
+    tensors: HashMap<String, (Vec<usize>, Vec<f32>)>,
+}
+
+#[derive(Deserialize)]
+struct TensorInfo {
+    dtype: String,
+    shape: Vec<usize>,
+    data_offsets: (usize, usize),
+}
+
+impl SafeTensors {
+    fn read(path: &Path) -> Result<Self, EmbeddingError> {
+        let bytes = std::fs::read(path).map_err(|source| EmbeddingError::IoError { source })?;
+        Self::parse(&bytes)
+    }
+
+    fn parse(bytes: &[u8]) -> Result<Self, EmbeddingError> {
+        let invalid = |message: String| EmbeddingError::ModelLoadError { message };
+
+        if bytes.len() < 8 {
+            return Err(invalid("safetensors file is truncated".to_string()));
+        }
+        let header_len = u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize;
+        let data_start = 8 + header_len;
+        if bytes.len() < data_start {
+            return Err(invalid("safetensors header exceeds file size".to_string()));
+        }
+
+        let header: HashMap<String, serde_json::Value> = serde_json::from_slice(&bytes[8..data_start])
+            .map_err(|e| invalid(format!("Invalid safetensors header: {}", e)))?;
+
+        let data = &bytes[data_start..];
+        let mut tensors = HashMap::new();
+        for (name, value) in header {
+            if name == "__metadata__" {
+                continue;
+            }
+            let info: TensorInfo = serde_json::from_value(value)
+                .map_err(|e| invalid(format!("Invalid tensor entry {}: {}", name, e)))?;
+            let (start, end) = info.data_offsets;
+            if end > data.len() || start > end {
+                return Err(invalid(format!("Tensor {} is out of bounds", name)));
+            }
+            let raw = &data[start..end];
+            let values = match info.dtype.as_str() {
+                "F32" => raw
+                    .chunks_exact(4)
+                    .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect(),
+                "F16" => raw
+                    .chunks_exact(2)
+                    .map(|c| f16_to_f32(u16::from_le_bytes([c[0], c[1]])))
+                    .collect(),
+                "BF16" => raw
+                    .chunks_exact(2)
+                    .map(|c| f32::from_bits((u16::from_le_bytes([c[0], c[1]]) as u32) << 16))
+                    .collect(),
+                // Integer buffers such as position_ids are not needed for inference
+                _ => continue,
+            };
+            tensors.insert(name, (info.shape, values));
+        }
+
+        Ok(Self { tensors })
+    }
+
+    /// Look up a tensor, accepting both bare and `bert.`-prefixed names
+    fn get(&self, name: &str) -> Result<&(Vec<usize>, Vec<f32>), EmbeddingError> {
+        self.tensors
+            .get(name)
+            .or_else(|| self.tensors.get(&format!("bert.{}", name)))
+            .ok_or_else(|| EmbeddingError::ModelLoadError {
+                message: format!("Missing tensor: {}", name),
+            })
+    }
+
+    fn matrix(&self, name: &str) -> Result<DMatrix<f32>, EmbeddingError> {
+        let (shape, values) = self.get(name)?;
+        if shape.len() != 2 {
+            return Err(EmbeddingError::ModelLoadError {
+                message: format!("Tensor {} is not a matrix", name),
+            });
+        }
+        Ok(DMatrix::from_row_slice(shape[0], shape[1], values))
+    }
+
+    fn vector(&self, name: &str) -> Result<RowDVector<f32>, EmbeddingError> {
+        let (_, values) = self.get(name)?;
+        Ok(RowDVector::from_row_slice(values))
+    }
+
+    /// Layer norm parameters, accepting both `weight`/`bias` and legacy `gamma`/`beta`
+    fn layer_norm(&self, prefix: &str, eps: f32) -> Result<LayerNorm, EmbeddingError> {
+        let weight = self
+            .vector(&format!("{}.weight", prefix))
+            .or_else(|_| self.vector(&format!("{}.gamma", prefix)))?;
+        let bias = self
+            .vector(&format!("{}.bias", prefix))
+            .or_else(|_| self.vector(&format!("{}.beta", prefix)))?;
+        Ok(LayerNorm { weight, bias, eps })
+    }
+
+    fn linear(&self, prefix: &str) -> Result<Linear, EmbeddingError> {
+        Ok(Linear {
+            weight_t: self.matrix(&format!("{}.weight", prefix))?.transpose(),
+            bias: self.vector(&format!("{}.bias", prefix))?,
+        })
+    }
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) as u32) << 31;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+
+    let magnitude = match exponent {
+        0 if mantissa == 0 => 0,
+        0 => {
+            // Subnormal: renormalize the mantissa
+            let mut e = 127 - 15 + 1;
+            let mut m = mantissa;
+            while m & 0x400 == 0 {
+                m <<= 1;
+                e -= 1;
+            }
+            ((e as u32) << 23) | ((m & 0x3ff) << 13)
+        }
+        0x1f => (0xff << 23) | (mantissa << 13),
+        _ => ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+
+    f32::from_bits(sign | magnitude)
+}
+
+struct Linear {
+    weight_t: DMatrix<f32>,
+    bias: RowDVector<f32>,
+}
+
+impl Linear {
+    fn forward(&self, input: &DMatrix<f32>) -> DMatrix<f32> {
+        let mut output = input * &self.weight_t;
+        for mut row in output.row_iter_mut() {
+            row += &self.bias;
+        }
+        output
+    }
+}
+
+struct LayerNorm {
+    weight: RowDVector<f32>,
+    bias: RowDVector<f32>,
+    eps: f32,
+}
+
+impl LayerNorm {
+    fn forward(&self, input: &mut DMatrix<f32>) {
+        let width = input.ncols() as f32;
+        for mut row in input.row_iter_mut() {
+            let mean = row.sum() / width;
+            let variance = row.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / width;
+            let denom = (variance + self.eps).sqrt();
+            for (i, value) in row.iter_mut().enumerate() {
+                *value = (*value - mean) / denom * self.weight[i] + self.bias[i];
+            }
+        }
+    }
+}
+
+struct EncoderLayer {
+    query: Linear,
+    key: Linear,
+    value: Linear,
+    attention_output: Linear,
+    attention_norm: LayerNorm,
+    intermediate: Linear,
+    output: Linear,
+    output_norm: LayerNorm,
+}
+
+struct BertEncoder {
+    word_embeddings: DMatrix<f32>,
+    position_embeddings: DMatrix<f32>,
+    token_type_embeddings: DMatrix<f32>,
+    embeddings_norm: LayerNorm,
+    layers: Vec<EncoderLayer>,
+    num_heads: usize,
+}
+
+impl BertEncoder {
+    fn from_tensors(tensors: &SafeTensors, config: &BertConfig) -> Result<Self, EmbeddingError> {
+        let eps = config.layer_norm_eps;
+        let mut layers = Vec::with_capacity(config.num_hidden_layers);
+
+        for i in 0..config.num_hidden_layers {
+            let prefix = format!("encoder.layer.{}", i);
+            layers.push(EncoderLayer {
+                query: tensors.linear(&format!("{}.attention.self.query", prefix))?,
+                key: tensors.linear(&format!("{}.attention.self.key", prefix))?,
+                value: tensors.linear(&format!("{}.attention.self.value", prefix))?,
+                attention_output: tensors.linear(&format!("{}.attention.output.dense", prefix))?,
+                attention_norm: tensors.layer_norm(&format!("{}.attention.output.LayerNorm", prefix), eps)?,
+                intermediate: tensors.linear(&format!("{}.intermediate.dense", prefix))?,
+                output: tensors.linear(&format!("{}.output.dense", prefix))?,
+                output_norm: tensors.layer_norm(&format!("{}.output.LayerNorm", prefix), eps)?,
+            });
+        }
+
+        Ok(Self {
+            word_embeddings: tensors.matrix("embeddings.word_embeddings.weight")?,
+            position_embeddings: tensors.matrix("embeddings.position_embeddings.weight")?,
+            token_type_embeddings: tensors.matrix("embeddings.token_type_embeddings.weight")?,
+            embeddings_norm: tensors.layer_norm("embeddings.LayerNorm", eps)?,
+            layers,
+            num_heads: config.num_attention_heads,
+        })
+    }
+
+    /// Run the encoder over a single sequence, returning the last hidden state (tokens x hidden).
+    /// Token types default to 0, the first segment.
+    fn forward(&self, token_ids: &[u32], token_type_ids: Option<&[u32]>) -> Result<DMatrix<f32>, EmbeddingError> {
+        let hidden_size = self.word_embeddings.ncols();
+        let mut hidden = DMatrix::<f32>::zeros(token_ids.len(), hidden_size);
+
+        for (position, &token_id) in token_ids.iter().enumerate() {
+            let token_id = token_id as usize;
+            if token_id >= self.word_embeddings.nrows() || position >= self.position_embeddings.nrows() {
+                return Err(EmbeddingError::EmbeddingGenerationError {
+                    message: format!("Token {} at position {} is outside the model vocabulary", token_id, position),
+                });
+            }
+            let mut row = hidden.row_mut(position);
+            row += self.word_embeddings.row(token_id);
+            row += self.position_embeddings.row(position);
+            let token_type = token_type_ids.and_then(|types| types.get(position)).copied().unwrap_or(0) as usize;
+            row += self.token_type_embeddings.row(token_type.min(self.token_type_embeddings.nrows() - 1));
+        }
+        self.embeddings_norm.forward(&mut hidden);
+
+        for layer in &self.layers {
+            hidden = self.layer_forward(layer, hidden);
+        }
+
+        Ok(hidden)
+    }
+
+    fn layer_forward(&self, layer: &EncoderLayer, input: DMatrix<f32>) -> DMatrix<f32> {
+        let seq_len = input.nrows();
+        let hidden_size = input.ncols();
+        let head_dim = hidden_size / self.num_heads;
+        let scale = 1.0 / (head_dim as f32).sqrt();
+
+        let query = layer.query.forward(&input);
+        let key = layer.key.forward(&input);
+        let value = layer.value.forward(&input);
+
+        let mut context = DMatrix::<f32>::zeros(seq_len, hidden_size);
+        for head in 0..self.num_heads {
+            let offset = head * head_dim;
+            let q = query.columns(offset, head_dim);
+            let k = key.columns(offset, head_dim);
+            let v = value.columns(offset, head_dim);
+
+            let mut scores = (q * k.transpose()) * scale;
+            for mut row in scores.row_iter_mut() {
+                let max = row.max();
+                row.apply(|x| *x = (*x - max).exp());
+                let sum = row.sum();
+                row /= sum;
+            }
+
+            context.columns_mut(offset, head_dim).copy_from(&(scores * v));
+        }
+
+        let mut attention = layer.attention_output.forward(&context) + &input;
+        layer.attention_norm.forward(&mut attention);
+
+        let mut intermediate = layer.intermediate.forward(&attention);
+        intermediate.apply(|x| *x = gelu(*x));
+
+        let mut output = layer.output.forward(&intermediate) + &attention;
+        layer.output_norm.forward(&mut output);
+        output
+    }
+}
+
+/// Exact (erf-based) GELU as used by BERT
+fn gelu(x: f32) -> f32 {
+    0.5 * x * (1.0 + erf(x / std::f32::consts::SQRT_2))
+}
+
+/// Abramowitz & Stegun 7.1.26 approximation of the error function
+fn erf(x: f32) -> f32 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let y = 1.0
+        - (((((1.061_405_4 * t - 1.453_152_1) * t) + 1.421_413_7) * t - 0.284_496_74) * t + 0.254_829_6)
+            * t
+            * (-x * x).exp();
+    sign * y
+}
+
+/// BERT WordPiece tokenizer for uncased models: lower-cases and strips accents
+pub struct WordPieceTokenizer {
+    vocab: HashMap<String, u32>,
+    cls_id: u32,
+    sep_id: u32,
+    unk_id: u32,
+}
+
+impl WordPieceTokenizer {
+    const MAX_CHARS_PER_WORD: usize = 100;
+
+    /// Load the vocabulary from `vocab.txt`, falling back to `tokenizer.json`
+    pub fn from_dir(dir: &Path) -> Result<Self, EmbeddingError> {
+        let vocab_txt = dir.join("vocab.txt");
+        let vocab = if vocab_txt.exists() {
+            let contents = std::fs::read_to_string(&vocab_txt)
+                .map_err(|source| EmbeddingError::IoError { source })?;
+            contents
+                .lines()
+                .enumerate()
+                .map(|(id, token)| (token.to_string(), id as u32))
+                .collect()
+        } else {
+            let contents = std::fs::read_to_string(dir.join("tokenizer.json"))
+                .map_err(|source| EmbeddingError::IoError { source })?;
+            let json: serde_json::Value = serde_json::from_str(&contents).map_err(|e| {
+                EmbeddingError::TokenizationError {
+                    message: format!("Invalid tokenizer.json: {}", e),
+                }
+            })?;
+            let vocab: HashMap<String, u32> = json
+                .pointer("/model/vocab")
+                .cloned()
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| EmbeddingError::TokenizationError {
+                    message: format!("Invalid vocabulary in tokenizer.json: {}", e),
+                })?
+                .unwrap_or_default();
+            vocab
+        };
+
+        Self::from_vocab(vocab)
+    }
+
+    pub fn from_vocab(vocab: HashMap<String, u32>) -> Result<Self, EmbeddingError> {
+        let lookup = |token: &str| {
+            vocab.get(token).copied().ok_or_else(|| EmbeddingError::TokenizationError {
+                message: format!("Vocabulary is missing special token {}", token),
+            })
+        };
+
+        Ok(Self {
+            cls_id: lookup("[CLS]")?,
+            sep_id: lookup("[SEP]")?,
+            unk_id: lookup("[UNK]")?,
+            vocab,
+        })
+    }
+
+    /// Tokenize text into ids wrapped in `[CLS]` / `[SEP]`, truncated to `max_length`
+    pub fn encode(&self, text: &str, max_length: usize) -> Vec<u32> {
+        let mut ids = vec![self.cls_id];
+
+        'words: for word in Self::basic_tokenize(text) {
+            for id in self.word_piece(&word) {
+                if ids.len() + 1 >= max_length {
+                    break 'words;
+                }
+                ids.push(id);
+            }
+        }
+
+        ids.push(self.sep_id);
+        ids
+    }
+
+    /// Tokenize a sentence pair as `[CLS] first [SEP] second [SEP]`, returning ids
+    /// and token type ids. The longer side is truncated first to fit `max_length`.
+    pub fn encode_pair(&self, first: &str, second: &str, max_length: usize) -> (Vec<u32>, Vec<u32>) {
+        let pieces = |text: &str| -> Vec<u32> {
+            Self::basic_tokenize(text).iter().flat_map(|word| self.word_piece(word)).collect()
+        };
+        let mut first = pieces(first);
+        let mut second = pieces(second);
+
+        let budget = max_length.saturating_sub(3);
+        while first.len() + second.len() > budget {
+            if first.len() > second.len() {
+                first.pop();
+            } else {
+                second.pop();
+            }
+        }
+
+        let mut ids = Vec::with_capacity(first.len() + second.len() + 3);
+        ids.push(self.cls_id);
+        ids.extend(first);
+        ids.push(self.sep_id);
+        let second_start = ids.len();
+        ids.extend(second);
+        ids.push(self.sep_id);
+
+        let token_types = (0..ids.len()).map(|i| u32::from(i >= second_start)).collect();
+        (ids, token_types)
+    }
+
+    /// Lower-case and strip accents (NFD, dropping nonspacing marks) as BERT's
+    /// uncased tokenizer does, then split on whitespace and punctuation
+    fn basic_tokenize(text: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut current = String::new();
+
+        let lowered = text.to_lowercase();
+        let decomposed = DecomposingNormalizerBorrowed::new_nfd().normalize(&lowered);
+        let categories = CodePointMapData::<GeneralCategory>::new();
+        for c in decomposed.chars().filter(|&c| categories.get(c) != GeneralCategory::NonspacingMark) {
+            if c.is_whitespace() || c.is_control() {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+            } else if c.is_ascii_punctuation() || (!c.is_alphanumeric() && !c.is_ascii()) {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+                words.push(c.to_string());
+            } else {
+                current.push(c);
+            }
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+
+        words
+    }
+
+    /// Greedy longest-match-first WordPiece segmentation of a single word
+    fn word_piece(&self, word: &str) -> Vec<u32> {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() > Self::MAX_CHARS_PER_WORD {
+            return vec![self.unk_id];
+        }
+
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        while start < chars.len() {
+            let mut end = chars.len();
+            let mut found = None;
+            while start < end {
+                let mut candidate: String = chars[start..end].iter().collect();
+                if start > 0 {
+                    candidate.insert_str(0, "##");
+                }
+                if let Some(&id) = self.vocab.get(&candidate) {
+                    found = Some(id);
+                    break;
+                }
+                end -= 1;
+            }
+
+            match found {
+                Some(id) => {
+                    pieces.push(id);
+                    start = end;
+                }
+                None => return vec![self.unk_id],
+            }
+        }
+
+        pieces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab() -> HashMap<String, u32> {
+        ["[PAD]", "[UNK]", "[CLS]", "[SEP]", "token", "##s", "rotation", "the", ".", "cafe", "resume"]
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.to_string(), i as u32))
+            .collect()
+    }
+
+    #[test]
+    fn test_word_piece_tokenization() {
+        let tokenizer = WordPieceTokenizer::from_vocab(vocab()).unwrap();
+
+        let ids = tokenizer.encode("The tokens rotation.", 32);
+        assert_eq!(ids, vec![2, 7, 4, 5, 6, 8, 3]);
+
+        // Unknown words collapse to [UNK]
+        let ids = tokenizer.encode("xyz", 32);
+        assert_eq!(ids, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn test_accents_are_stripped() {
+        let tokenizer = WordPieceTokenizer::from_vocab(vocab()).unwrap();
+
+        // Precomposed and combining accents both fold to the unaccented word
+        assert_eq!(tokenizer.encode("Café RÉSUMÉ", 32), vec![2, 9, 10, 3]);
+        assert_eq!(tokenizer.encode("Cafe\u{301} re\u{301}sume\u{301}", 32), vec![2, 9, 10, 3]);
+    }
+
+    #[test]
+    fn test_tokenization_truncates_to_max_length() {
+        let tokenizer = WordPieceTokenizer::from_vocab(vocab()).unwrap();
+
+        let ids = tokenizer.encode("the the the the the the", 4);
+        assert_eq!(ids, vec![2, 7, 7, 3]);
+    }
+
+    #[test]
+    fn test_pair_encoding_marks_segments_and_truncates_longer_side() {
+        let tokenizer = WordPieceTokenizer::from_vocab(vocab()).unwrap();
+
+        let (ids, types) = tokenizer.encode_pair("rotation", "the tokens.", 32);
+        assert_eq!(ids, vec![2, 6, 3, 7, 4, 5, 8, 3]);
+        assert_eq!(types, vec![0, 0, 0, 1, 1, 1, 1, 1]);
+
+        let (ids, types) = tokenizer.encode_pair("rotation", "the the the the the", 6);
+        assert_eq!(ids, vec![2, 6, 3, 7, 7, 3]);
+        assert_eq!(types, vec![0, 0, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_f16_conversion() {
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert!((f16_to_f32(0x3555) - 0.333_25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_only_safetensors_models_are_loaded() {
+        let result = resolve_model_files(Path::new("/nonexistent/model.onnx"));
+        assert!(matches!(result, Err(EmbeddingError::ModelLoadError { message }) if message.contains("ONNX models are not supported")));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::db::context_tables::{context_table, project_tables, ContextTable, CONTEXT_TABLES, PROJECT_DEPENDENT_TABLES};
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rmcp::model::ErrorData as McpError;
+use rusqlite::types::Type;
+use rusqlite::{Connection, OptionalExtension, Row, ToSql};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Days a deleted entity stays in the trash before it is purged
+pub const DEFAULT_RETENTION_DAYS: u32 = 30;
+
+/// Soft-deleted context entities: listing, restoring and purging them
+#[async_trait]
+pub trait TrashService: Send + Sync {
+    /// Entities in the trash, most recently deleted first. A project filter
+    /// matches the project itself and the entities that belong to it.
+    async fn list_trash(&self, project_id: Option<&str>) -> Result<Vec<TrashedEntity>, McpError>;
+
+    /// Take an entity out of the trash, returning everything restored with it;
+    /// empty if it is not in the trash. Restoring a project also restores the
+    /// entities that were deleted together with it.
+    async fn restore(&self, entity_type: &str, id: &str) -> Result<Vec<TrashedEntity>, McpError>;
+
+    /// Permanently delete the entities trashed at or before `deleted_before`,
+    /// returning how many were removed. A purged project's enhanced context
+    /// items and specifications are deleted with it.
+    async fn purge(&self, deleted_before: DateTime<Utc>) -> Result<usize, McpError>;
+}
+
+/// An entity in the trash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TrashedEntity {
+    pub entity_type: String,
+    pub id: String,
+    /// Project the entity belongs to; a project's own ID for projects
+    pub project_id: Option<String>,
+    pub name: Option<String>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+pub struct SqliteTrashService {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteTrashService {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    /// Trashed rows of one table matching `condition`
+    fn trashed(
+        conn: &Connection,
+        table: &ContextTable,
+        condition: &str,
+        params: &[&dyn ToSql],
+    ) -> rusqlite::Result<Vec<TrashedEntity>> {
+        let sql = format!(
+            "SELECT id, {}, {}, deleted_at FROM {} WHERE deleted_at IS NOT NULL AND {condition}",
+            table.project_column(),
+            table.name_column,
+            table.table
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params, |row| Self::row_to_trashed(table, row))?;
+        rows.collect()
+    }
+
+    fn row_to_trashed(table: &ContextTable, row: &Row) -> rusqlite::Result<TrashedEntity> {
+        let deleted_at: String = row.get(3)?;
+        Ok(TrashedEntity {
+            entity_type: table.entity_type.to_string(),
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            name: row.get(2)?,
+            deleted_at: DateTime::parse_from_rfc3339(&deleted_at)
+                .map(|at| at.with_timezone(&Utc))
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, Type::Text, Box::new(e)))?,
+        })
+    }
+}
+
+fn db_error(e: rusqlite::Error) -> McpError {
+    McpError::internal_error(format!("Database error: {}", e), None)
+}
+
+#[async_trait]
+impl TrashService for SqliteTrashService {
+    async fn list_trash(&self, project_id: Option<&str>) -> Result<Vec<TrashedEntity>, McpError> {
+        let conn = self.db.lock().unwrap();
+        let mut trash = Vec::new();
+        for table in CONTEXT_TABLES {
+            let entities = match project_id {
+                Some(project_id) => {
+                    let condition = format!("{} = ?1", table.project_column());
+                    Self::trashed(&conn, table, &condition, &[&project_id])
+                }
+                None => Self::trashed(&conn, table, "1 = 1", &[]),
+            };
+            trash.extend(entities.map_err(db_error)?);
+        }
+        trash.sort_by_key(|entity| std::cmp::Reverse(entity.deleted_at));
+        Ok(trash)
+    }
+
+    async fn restore(&self, entity_type: &str, id: &str) -> Result<Vec<TrashedEntity>, McpError> {
+        let table = context_table(entity_type)
+            .ok_or_else(|| McpError::invalid_params(format!("Unknown entity type: {entity_type}"), None))?;
+        let conn = self.db.lock().unwrap();
+        let tx = conn.unchecked_transaction().map_err(db_error)?;
+
+        let Some(entity) = Self::trashed(&tx, table, "id = ?1", &[&id]).map_err(db_error)?.pop() else {
+            return Ok(Vec::new());
+        };
+        if entity_type != "project" {
+            let project_deleted_at: Option<Option<String>> = tx
+                .query_row("SELECT deleted_at FROM projects WHERE id = ?1", [&entity.project_id], |row| row.get(0))
+                .optional()
+                .map_err(db_error)?;
+            if let Some(Some(_)) = project_deleted_at {
+                return Err(McpError::invalid_params(
+                    format!(
+                        "Project {} of {entity_type} {id} is in the trash; restore the project first",
+                        entity.project_id.unwrap_or_default()
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        let mut restored = vec![entity];
+        if entity_type == "project" {
+            // Children deleted along with the project carry its deleted_at
+            let deleted_at = restored[0].deleted_at.to_rfc3339_opts(SecondsFormat::Millis, true);
+            for child in project_tables() {
+                let children = Self::trashed(&tx, child, "project_id = ?1 AND deleted_at = ?2", &[&id, &deleted_at])
+                    .map_err(db_error)?;
+                tx.execute(
+                    &format!("UPDATE {} SET deleted_at = NULL WHERE project_id = ?1 AND deleted_at = ?2", child.table),
+                    [id, deleted_at.as_str()],
+                )
+                .map_err(db_error)?;
+                restored.extend(children);
+            }
+        }
+        tx.execute(&format!("UPDATE {} SET deleted_at = NULL WHERE id = ?1", table.table), [id]).map_err(db_error)?;
+        tx.commit().map_err(db_error)?;
+        Ok(restored)
+    }
+
+    async fn purge(&self, deleted_before: DateTime<Utc>) -> Result<usize, McpError> {
+        let cutoff = deleted_before.to_rfc3339_opts(SecondsFormat::Millis, true);
+        let conn = self.db.lock().unwrap();
+        let tx = conn.unchecked_transaction().map_err(db_error)?;
+        let mut purged = 0;
+        // A purged project takes its enhanced context items and specifications with it
+        let projects: Vec<String> = {
+            let mut stmt = tx
+                .prepare("SELECT id FROM projects WHERE deleted_at IS NOT NULL AND deleted_at <= ?1")
+                .map_err(db_error)?;
+            let ids = stmt.query_map([&cutoff], |row| row.get(0)).map_err(db_error)?;
+            ids.collect::<rusqlite::Result<_>>().map_err(db_error)?
+        };
+        for project_id in &projects {
+            for (table, condition) in PROJECT_DEPENDENT_TABLES.iter().rev() {
+                tx.execute(&format!("DELETE FROM {table} WHERE {condition}"), [project_id]).map_err(db_error)?;
+            }
+        }
+        // Children before projects, so no row is left pointing at a missing project
+        for table in CONTEXT_TABLES.iter().rev() {
+            purged += tx
+                .execute(
+                    &format!("DELETE FROM {} WHERE deleted_at IS NOT NULL AND deleted_at <= ?1", table.table),
+                    [&cutoff],
+                )
+                .map_err(db_error)?;
+        }
+        tx.commit().map_err(db_error)?;
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init::init_db;
+    use chrono::Duration;
+
+    fn service() -> (SqliteTrashService, Arc<Mutex<Connection>>) {
+        let db = Arc::new(Mutex::new(init_db(":memory:").unwrap()));
+        (SqliteTrashService::new(db.clone()), db)
+    }
+
+    #[tokio::test]
+    async fn test_restore_of_a_child_waits_for_its_project() {
+        let (trash, db) = service();
+        {
+            let conn = db.lock().unwrap();
+            conn.execute_batch(
+                "INSERT INTO projects (id, name, deleted_at) VALUES ('p1', 'Shop', '2026-01-01T00:00:00.000Z');
+                 INSERT INTO business_rules (id, project_id, rule_name, deleted_at)
+                     VALUES ('r1', 'p1', 'Refunds', '2026-01-01T00:00:00.000Z');",
+            )
+            .unwrap();
+        }
+
+        let err = trash.restore("business_rule", "r1").await.unwrap_err();
+        assert!(err.message.contains("restore the project first"));
+
+        let restored = trash.restore("project", "p1").await.unwrap();
+        assert_eq!(restored.len(), 2);
+        assert!(trash.list_trash(None).await.unwrap().is_empty());
+        assert!(trash.restore("project", "p1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_purge_only_removes_entries_past_the_cutoff() {
+        let (trash, db) = service();
+        let recent = (Utc::now() - Duration::days(1)).to_rfc3339_opts(SecondsFormat::Millis, true);
+        {
+            let conn = db.lock().unwrap();
+            conn.execute_batch(
+                "INSERT INTO projects (id, name) VALUES ('p1', 'Shop');
+                 INSERT INTO business_rules (id, project_id, rule_name, deleted_at)
+                     VALUES ('old', 'p1', 'Old', '2020-01-01T00:00:00.000Z');",
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO business_rules (id, project_id, rule_name, deleted_at) VALUES ('new', 'p1', 'New', ?1)",
+                [&recent],
+            )
+            .unwrap();
+        }
+
+        let purged = trash.purge(Utc::now() - Duration::days(DEFAULT_RETENTION_DAYS as i64)).await.unwrap();
+        assert_eq!(purged, 1);
+        let remaining = trash.list_trash(Some("p1")).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "new");
+    }
+}
@@ -0,0 +1,1070 @@
+use crate::models::embedding::ContextEmbedding;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+const SIDECAR_MAGIC: &[u8; 8] = b"CSHNSW04";
+
+/// Number of stored vectors replayed as queries when estimating recall
+pub const DEFAULT_RECALL_SAMPLE_SIZE: usize = 20;
+
+/// How long a sampled recall figure is reused before it is measured again
+pub const RECALL_REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Error types for the in-memory vector index
+#[derive(Debug, thiserror::Error)]
+pub enum VectorIndexError {
+    #[error("Dimension mismatch: index has {expected}, vector has {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+
+    #[error("Corrupt index file: {message}")]
+    CorruptIndex { message: String },
+
+    #[error("IO error: {source}")]
+    IoError { source: std::io::Error },
+}
+
+impl From<std::io::Error> for VectorIndexError {
+    fn from(error: std::io::Error) -> Self {
+        VectorIndexError::IoError { source: error }
+    }
+}
+
+/// HNSW construction and query parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HnswParams {
+    /// Maximum neighbours per node on upper layers (twice this on layer 0)
+    pub m: usize,
+    /// Candidate list size while inserting
+    pub ef_construction: usize,
+    /// Candidate list size while searching
+    pub ef_search: usize,
+}
+
+impl Default for HnswParams {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+        }
+    }
+}
+
+/// A search hit returned by the index
+#[derive(Debug, Clone)]
+pub struct VectorHit {
+    pub context_id: String,
+    /// Project the context belongs to, used to scope project searches
+    pub project_id: Option<String>,
+    /// Chunk of the context that matched, `None` for items embedded whole
+    pub chunk_index: Option<usize>,
+    pub similarity: f32,
+    pub content_type: String,
+    pub quality_score: f32,
+}
+
+/// Recall measured by `HnswIndex::sample_recall`, kept until it is refreshed
+#[derive(Debug, Clone, Copy)]
+struct SampledRecall {
+    recall: Option<f32>,
+    sample_size: usize,
+    measured_at: Instant,
+}
+
+/// Size, recall and latency figures for the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorIndexStats {
+    pub embedding_model: String,
+    pub dimension: usize,
+    pub indexed_vectors: usize,
+    pub deleted_vectors: usize,
+    pub memory_usage_bytes: usize,
+    pub total_queries: u64,
+    pub average_query_latency_ms: f64,
+    /// Recall@10 of the graph against exact search over a sample of stored vectors,
+    /// measured after each rebuild and at most every `RECALL_REFRESH_INTERVAL`
+    pub sampled_recall: Option<f32>,
+    pub last_persisted: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    context_id: String,
+    project_id: Option<String>,
+    chunk_index: Option<usize>,
+    vector: Vec<f32>,
+    content_type: String,
+    quality_score: f32,
+    neighbors: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+/// Distance/node pair ordered by distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate(f32, u32);
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+/// Hierarchical navigable small world graph over L2-normalized vectors.
+///
+/// Deletes are tombstones: the node keeps routing searches but is never
+/// returned; `compact` rebuilds the graph from live nodes.
+#[derive(Debug, Clone)]
+pub struct HnswIndex {
+    params: HnswParams,
+    embedding_model: String,
+    dimension: Option<usize>,
+    nodes: Vec<Node>,
+    /// Live nodes of each context, one per chunk
+    ids: HashMap<String, Vec<u32>>,
+    live: usize,
+    entry_point: Option<u32>,
+    max_level: usize,
+    rng_state: u64,
+}
+
+impl HnswIndex {
+    pub fn new(params: HnswParams, embedding_model: &str) -> Self {
+        Self {
+            params,
+            embedding_model: embedding_model.to_string(),
+            dimension: None,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            live: 0,
+            entry_point: None,
+            max_level: 0,
+            rng_state: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    pub fn embedding_model(&self) -> &str {
+        &self.embedding_model
+    }
+
+    pub fn dimension(&self) -> Option<usize> {
+        self.dimension
+    }
+
+    /// Number of live (non-deleted) vectors
+    pub fn len(&self) -> usize {
+        self.live
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live == 0
+    }
+
+    pub fn deleted_count(&self) -> usize {
+        self.nodes.len() - self.live
+    }
+
+    pub fn contains(&self, context_id: &str) -> bool {
+        self.ids.contains_key(context_id)
+    }
+
+    /// Insert or replace the vector for a context (or one of its chunks)
+    pub fn insert(
+        &mut self,
+        context_id: &str,
+        project_id: Option<&str>,
+        chunk_index: Option<usize>,
+        vector: &[f32],
+        content_type: &str,
+        quality_score: f32,
+    ) -> Result<(), VectorIndexError> {
+        match self.dimension {
+            Some(expected) if expected != vector.len() => {
+                return Err(VectorIndexError::DimensionMismatch {
+                    expected,
+                    actual: vector.len(),
+                })
+            }
+            None => self.dimension = Some(vector.len()),
+            _ => {}
+        }
+
+        if let Some(existing) = self.ids.get_mut(context_id) {
+            let nodes = &mut self.nodes;
+            if let Some(position) = existing.iter().position(|&id| nodes[id as usize].chunk_index == chunk_index) {
+                nodes[existing.remove(position) as usize].deleted = true;
+                self.live -= 1;
+            }
+        }
+
+        let vector = normalize(vector);
+        let level = self.random_level();
+        let id = self.nodes.len() as u32;
+        self.nodes.push(Node {
+            context_id: context_id.to_string(),
+            project_id: project_id.map(str::to_string),
+            chunk_index,
+            vector,
+            content_type: content_type.to_string(),
+            quality_score,
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.ids.entry(context_id.to_string()).or_default().push(id);
+        self.live += 1;
+
+        let Some(mut entry) = self.entry_point else {
+            self.entry_point = Some(id);
+            self.max_level = level;
+            return Ok(());
+        };
+
+        let query = self.nodes[id as usize].vector.clone();
+        for layer in (level + 1..=self.max_level).rev() {
+            entry = self.greedy_closest(&query, entry, layer);
+        }
+
+        let mut entry_points = vec![entry];
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let candidates =
+                self.search_layer(&query, &entry_points, self.params.ef_construction, layer);
+            let neighbors: Vec<u32> = candidates
+                .iter()
+                .take(self.params.m)
+                .map(|c| c.1)
+                .collect();
+
+            self.nodes[id as usize].neighbors[layer] = neighbors.clone();
+            for neighbor in neighbors {
+                self.nodes[neighbor as usize].neighbors[layer].push(id);
+                self.prune_neighbors(neighbor, layer);
+            }
+
+            entry_points = candidates.into_iter().map(|c| c.1).collect();
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(id);
+        }
+
+        Ok(())
+    }
+
+    /// Tombstone every vector of a context, returning whether it was present
+    pub fn remove(&mut self, context_id: &str) -> bool {
+        match self.ids.remove(context_id) {
+            Some(ids) => {
+                for id in &ids {
+                    self.nodes[*id as usize].deleted = true;
+                }
+                self.live -= ids.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Approximate top-k search returning hits ordered by similarity
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<VectorHit> {
+        let Some(mut entry) = self.entry_point else {
+            return Vec::new();
+        };
+        if k == 0 || self.dimension != Some(query.len()) {
+            return Vec::new();
+        }
+
+        let query = normalize(query);
+        for layer in (1..=self.max_level).rev() {
+            entry = self.greedy_closest(&query, entry, layer);
+        }
+
+        // Widen the beam so tombstones do not starve the result list
+        let ef = ef.max(k) + self.deleted_count().min(4 * k);
+        self.search_layer(&query, &[entry], ef, 0)
+            .into_iter()
+            .filter(|c| !self.nodes[c.1 as usize].deleted)
+            .take(k)
+            .map(|c| self.hit(c))
+            .collect()
+    }
+
+    /// Exact top-k search by scanning every live vector
+    pub fn exact_search(&self, query: &[f32], k: usize) -> Vec<VectorHit> {
+        if self.dimension != Some(query.len()) {
+            return Vec::new();
+        }
+
+        let query = normalize(query);
+        let mut candidates: Vec<Candidate> = self
+            .ids
+            .values()
+            .flatten()
+            .map(|&id| Candidate(distance(&query, &self.nodes[id as usize].vector), id))
+            .collect();
+        candidates.sort();
+        candidates.into_iter().take(k).map(|c| self.hit(c)).collect()
+    }
+
+    /// Estimate recall@k by comparing graph search with exact search for
+    /// up to `sample_size` stored vectors used as queries
+    pub fn sample_recall(&self, sample_size: usize, k: usize) -> Option<f32> {
+        if self.is_empty() || sample_size == 0 {
+            return None;
+        }
+
+        let mut live: Vec<u32> = self.ids.values().flatten().copied().collect();
+        live.sort_unstable();
+        let step = (live.len() / sample_size).max(1);
+
+        let mut found = 0usize;
+        let mut expected = 0usize;
+        for &id in live.iter().step_by(step).take(sample_size) {
+            let query = &self.nodes[id as usize].vector;
+            let exact: HashSet<(String, Option<usize>)> = self
+                .exact_search(query, k)
+                .into_iter()
+                .map(|hit| (hit.context_id, hit.chunk_index))
+                .collect();
+            let approximate = self.search(query, k, self.params.ef_search);
+
+            expected += exact.len();
+            found += approximate
+                .iter()
+                .filter(|hit| exact.contains(&(hit.context_id.clone(), hit.chunk_index)))
+                .count();
+        }
+
+        if expected == 0 {
+            None
+        } else {
+            Some(found as f32 / expected as f32)
+        }
+    }
+
+    /// Rebuild the graph without tombstoned nodes
+    pub fn compact(&mut self) {
+        let live: Vec<Node> = self
+            .nodes
+            .iter()
+            .filter(|node| !node.deleted)
+            .cloned()
+            .collect();
+
+        let mut rebuilt = HnswIndex::new(self.params.clone(), &self.embedding_model);
+        for node in live {
+            // Dimensions already match, so insertion cannot fail
+            let _ = rebuilt.insert(
+                &node.context_id,
+                node.project_id.as_deref(),
+                node.chunk_index,
+                &node.vector,
+                &node.content_type,
+                node.quality_score,
+            );
+        }
+        *self = rebuilt;
+    }
+
+    /// Rough in-memory footprint of vectors, adjacency lists and ids
+    pub fn memory_usage_bytes(&self) -> usize {
+        self.nodes
+            .iter()
+            .map(|node| {
+                node.vector.len() * std::mem::size_of::<f32>()
+                    + node.neighbors.iter().map(|n| n.len() * 4).sum::<usize>()
+                    + node.context_id.len()
+                    + node.project_id.as_ref().map_or(0, String::len)
+                    + node.content_type.len()
+                    + std::mem::size_of::<Node>()
+            })
+            .sum()
+    }
+
+    fn hit(&self, candidate: Candidate) -> VectorHit {
+        let node = &self.nodes[candidate.1 as usize];
+        VectorHit {
+            context_id: node.context_id.clone(),
+            project_id: node.project_id.clone(),
+            chunk_index: node.chunk_index,
+            similarity: 1.0 - candidate.0,
+            content_type: node.content_type.clone(),
+            quality_score: node.quality_score,
+        }
+    }
+
+    fn max_connections(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.params.m * 2
+        } else {
+            self.params.m
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        // xorshift64* keeps builds reproducible
+        self.rng_state ^= self.rng_state >> 12;
+        self.rng_state ^= self.rng_state << 25;
+        self.rng_state ^= self.rng_state >> 27;
+        let value = self.rng_state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        let uniform = ((value >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+
+        let level_multiplier = 1.0 / (self.params.m.max(2) as f64).ln();
+        ((-uniform.ln() * level_multiplier) as usize).min(16)
+    }
+
+    fn greedy_closest(&self, query: &[f32], mut current: u32, layer: usize) -> u32 {
+        let mut current_distance = distance(query, &self.nodes[current as usize].vector);
+        loop {
+            let mut changed = false;
+            for &neighbor in self.neighbors(current, layer) {
+                let d = distance(query, &self.nodes[neighbor as usize].vector);
+                if d < current_distance {
+                    current_distance = d;
+                    current = neighbor;
+                    changed = true;
+                }
+            }
+            if !changed {
+                return current;
+            }
+        }
+    }
+
+    /// Best-first beam search on one layer, returning candidates closest first
+    fn search_layer(&self, query: &[f32], entry_points: &[u32], ef: usize, layer: usize) -> Vec<Candidate> {
+        let mut visited: HashSet<u32> = entry_points.iter().copied().collect();
+        let mut candidates: BinaryHeap<Reverse<Candidate>> = BinaryHeap::new();
+        let mut results: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for &entry in entry_points {
+            let candidate = Candidate(distance(query, &self.nodes[entry as usize].vector), entry);
+            candidates.push(Reverse(candidate));
+            results.push(candidate);
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(closest)) = candidates.pop() {
+            let furthest = results.peek().map(|c| c.0).unwrap_or(f32::INFINITY);
+            if closest.0 > furthest && results.len() >= ef {
+                break;
+            }
+
+            for &neighbor in self.neighbors(closest.1, layer) {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+
+                let d = distance(query, &self.nodes[neighbor as usize].vector);
+                let furthest = results.peek().map(|c| c.0).unwrap_or(f32::INFINITY);
+                if results.len() < ef || d < furthest {
+                    candidates.push(Reverse(Candidate(d, neighbor)));
+                    results.push(Candidate(d, neighbor));
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    fn neighbors(&self, id: u32, layer: usize) -> &[u32] {
+        self.nodes[id as usize]
+            .neighbors
+            .get(layer)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    fn prune_neighbors(&mut self, id: u32, layer: usize) {
+        let max = self.max_connections(layer);
+        if self.nodes[id as usize].neighbors[layer].len() <= max {
+            return;
+        }
+
+        let base = &self.nodes[id as usize].vector;
+        let mut scored: Vec<Candidate> = self.nodes[id as usize].neighbors[layer]
+            .iter()
+            .map(|&n| Candidate(distance(base, &self.nodes[n as usize].vector), n))
+            .collect();
+        scored.sort();
+        scored.truncate(max);
+        self.nodes[id as usize].neighbors[layer] = scored.into_iter().map(|c| c.1).collect();
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut W, synced_at: DateTime<Utc>) -> Result<(), VectorIndexError> {
+        writer.write_all(SIDECAR_MAGIC)?;
+        writer.write_all(&synced_at.timestamp_millis().to_le_bytes())?;
+        write_str(writer, &self.embedding_model)?;
+        write_u32(writer, self.dimension.unwrap_or(0) as u32)?;
+        write_u32(writer, self.params.m as u32)?;
+        write_u32(writer, self.params.ef_construction as u32)?;
+        write_u32(writer, self.params.ef_search as u32)?;
+        write_u32(writer, self.entry_point.map(|e| e + 1).unwrap_or(0))?;
+        write_u32(writer, self.max_level as u32)?;
+        writer.write_all(&self.rng_state.to_le_bytes())?;
+        write_u32(writer, self.nodes.len() as u32)?;
+
+        for node in &self.nodes {
+            write_str(writer, &node.context_id)?;
+            write_str(writer, node.project_id.as_deref().unwrap_or(""))?;
+            write_u32(writer, node.chunk_index.map(|c| c as u32 + 1).unwrap_or(0))?;
+            write_str(writer, &node.content_type)?;
+            writer.write_all(&node.quality_score.to_le_bytes())?;
+            writer.write_all(&[node.deleted as u8])?;
+            for value in &node.vector {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            write_u32(writer, node.neighbors.len() as u32)?;
+            for layer in &node.neighbors {
+                write_u32(writer, layer.len() as u32)?;
+                for neighbor in layer {
+                    write_u32(writer, *neighbor)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> Result<(Self, DateTime<Utc>), VectorIndexError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != SIDECAR_MAGIC {
+            return Err(VectorIndexError::CorruptIndex {
+                message: "unrecognised file header".to_string(),
+            });
+        }
+        let mut synced_bytes = [0u8; 8];
+        reader.read_exact(&mut synced_bytes)?;
+        let synced_at = DateTime::from_timestamp_millis(i64::from_le_bytes(synced_bytes)).ok_or_else(|| {
+            VectorIndexError::CorruptIndex {
+                message: "sync time out of range".to_string(),
+            }
+        })?;
+
+        let embedding_model = read_str(reader)?;
+        let dimension = read_u32(reader)? as usize;
+        let params = HnswParams {
+            m: read_u32(reader)? as usize,
+            ef_construction: read_u32(reader)? as usize,
+            ef_search: read_u32(reader)? as usize,
+        };
+        let entry_point = read_u32(reader)?.checked_sub(1);
+        let max_level = read_u32(reader)? as usize;
+        let mut rng_bytes = [0u8; 8];
+        reader.read_exact(&mut rng_bytes)?;
+        let node_count = read_u32(reader)? as usize;
+
+        let mut nodes = Vec::with_capacity(node_count);
+        let mut ids: HashMap<String, Vec<u32>> = HashMap::with_capacity(node_count);
+        let mut live = 0;
+        for id in 0..node_count {
+            let context_id = read_str(reader)?;
+            let project_id = Some(read_str(reader)?).filter(|p| !p.is_empty());
+            let chunk_index = read_u32(reader)?.checked_sub(1).map(|c| c as usize);
+            let content_type = read_str(reader)?;
+            let quality_score = read_f32(reader)?;
+            let mut deleted = [0u8; 1];
+            reader.read_exact(&mut deleted)?;
+
+            let mut vector = Vec::with_capacity(dimension);
+            for _ in 0..dimension {
+                vector.push(read_f32(reader)?);
+            }
+
+            let levels = read_u32(reader)? as usize;
+            let mut neighbors = Vec::with_capacity(levels);
+            for _ in 0..levels {
+                let count = read_u32(reader)? as usize;
+                let mut layer = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let neighbor = read_u32(reader)?;
+                    if neighbor as usize >= node_count {
+                        return Err(VectorIndexError::CorruptIndex {
+                            message: format!("neighbour {} out of range", neighbor),
+                        });
+                    }
+                    layer.push(neighbor);
+                }
+                neighbors.push(layer);
+            }
+
+            if deleted[0] == 0 {
+                ids.entry(context_id.clone()).or_default().push(id as u32);
+                live += 1;
+            }
+            nodes.push(Node {
+                context_id,
+                project_id,
+                chunk_index,
+                vector,
+                content_type,
+                quality_score,
+                neighbors,
+                deleted: deleted[0] != 0,
+            });
+        }
+
+        if entry_point.is_some_and(|e| e as usize >= node_count) {
+            return Err(VectorIndexError::CorruptIndex {
+                message: "entry point out of range".to_string(),
+            });
+        }
+
+        let index = Self {
+            params,
+            embedding_model,
+            dimension: if node_count == 0 { None } else { Some(dimension) },
+            nodes,
+            ids,
+            live,
+            entry_point,
+            max_level,
+            rng_state: u64::from_le_bytes(rng_bytes),
+        };
+        Ok((index, synced_at))
+    }
+}
+
+/// Thread-safe HNSW index shared between `SearchIndexManagerImpl`, which keeps
+/// it in sync with `context_embeddings`, and the semantic search service, which
+/// queries it instead of scanning every stored vector.
+pub struct VectorIndex {
+    index: RwLock<HnswIndex>,
+    params: HnswParams,
+    total_queries: AtomicU64,
+    total_query_micros: AtomicU64,
+    /// Time of the snapshot last written to or loaded from the sidecar
+    last_persisted: RwLock<Option<DateTime<Utc>>>,
+    recall: RwLock<Option<SampledRecall>>,
+}
+
+impl VectorIndex {
+    /// Create an empty index accepting vectors of `embedding_model`
+    pub fn new(params: HnswParams, embedding_model: &str) -> Self {
+        Self {
+            index: RwLock::new(HnswIndex::new(params.clone(), embedding_model)),
+            params,
+            total_queries: AtomicU64::new(0),
+            total_query_micros: AtomicU64::new(0),
+            last_persisted: RwLock::new(None),
+            recall: RwLock::new(None),
+        }
+    }
+
+    pub fn embedding_model(&self) -> String {
+        self.index.read().unwrap().embedding_model().to_string()
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.read().unwrap().is_empty()
+    }
+
+    pub fn contains(&self, context_id: &str) -> bool {
+        self.index.read().unwrap().contains(context_id)
+    }
+
+    /// Contexts with at least one live vector
+    pub fn context_ids(&self) -> Vec<String> {
+        self.index.read().unwrap().ids.keys().cloned().collect()
+    }
+
+    /// When the sidecar was last written or loaded; every write to
+    /// `context_embeddings` before this point is reflected in the snapshot
+    pub fn last_persisted(&self) -> Option<DateTime<Utc>> {
+        *self.last_persisted.read().unwrap()
+    }
+
+    /// Replace the whole index with the given embeddings of one model
+    pub fn rebuild(&self, embedding_model: &str, embeddings: &[ContextEmbedding]) -> Result<usize, VectorIndexError> {
+        let mut index = HnswIndex::new(self.params.clone(), embedding_model);
+        for embedding in embeddings.iter().filter(|e| e.embedding_model == embedding_model) {
+            index.insert(
+                &embedding.context_id,
+                embedding.project_id.as_deref(),
+                embedding.chunk.as_ref().map(|chunk| chunk.index),
+                &embedding.embedding_vector,
+                &embedding.metadata.content_type,
+                embedding.metadata.quality_score,
+            )?;
+        }
+
+        let count = index.len();
+        *self.index.write().unwrap() = index;
+        *self.recall.write().unwrap() = None;
+        info!("Built vector index for model {} with {} vectors", embedding_model, count);
+        Ok(count)
+    }
+
+    /// Insert or replace a single embedding
+    pub fn upsert(&self, embedding: &ContextEmbedding) -> Result<(), VectorIndexError> {
+        let mut index = self.index.write().unwrap();
+        if index.embedding_model() != embedding.embedding_model {
+            debug!(
+                "Skipping vector for {} from model {} (index model {})",
+                embedding.context_id,
+                embedding.embedding_model,
+                index.embedding_model()
+            );
+            return Ok(());
+        }
+
+        index.insert(
+            &embedding.context_id,
+            embedding.project_id.as_deref(),
+            embedding.chunk.as_ref().map(|chunk| chunk.index),
+            &embedding.embedding_vector,
+            &embedding.metadata.content_type,
+            embedding.metadata.quality_score,
+        )
+    }
+
+    pub fn remove(&self, context_id: &str) -> bool {
+        self.index.write().unwrap().remove(context_id)
+    }
+
+    /// Swap all vectors of a context for `embeddings` (its chunks), skipping other models
+    pub fn replace_context(&self, context_id: &str, embeddings: &[ContextEmbedding]) -> Result<(), VectorIndexError> {
+        let mut index = self.index.write().unwrap();
+        index.remove(context_id);
+        let model = index.embedding_model().to_string();
+        for embedding in embeddings.iter().filter(|e| e.embedding_model == model) {
+            index.insert(
+                context_id,
+                embedding.project_id.as_deref(),
+                embedding.chunk.as_ref().map(|chunk| chunk.index),
+                &embedding.embedding_vector,
+                &embedding.metadata.content_type,
+                embedding.metadata.quality_score,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Approximate nearest neighbours, recording query latency
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<VectorHit> {
+        let start = Instant::now();
+        let hits = self.index.read().unwrap().search(query, k, self.params.ef_search);
+
+        self.total_queries.fetch_add(1, AtomicOrdering::Relaxed);
+        self.total_query_micros
+            .fetch_add(start.elapsed().as_micros() as u64, AtomicOrdering::Relaxed);
+        hits
+    }
+
+    /// Rebuild the graph when tombstones outnumber live vectors
+    pub fn compact_if_needed(&self) -> bool {
+        let mut index = self.index.write().unwrap();
+        if index.deleted_count() > index.len() {
+            index.compact();
+            *self.recall.write().unwrap() = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Size and latency figures, with recall sampled only when the cached figure
+    /// is missing, stale or was measured with a different sample size
+    pub fn stats(&self, recall_sample_size: usize) -> VectorIndexStats {
+        let sampled_recall = self.sampled_recall(recall_sample_size);
+        let index = self.index.read().unwrap();
+        let total_queries = self.total_queries.load(AtomicOrdering::Relaxed);
+        let total_micros = self.total_query_micros.load(AtomicOrdering::Relaxed);
+
+        VectorIndexStats {
+            embedding_model: index.embedding_model().to_string(),
+            dimension: index.dimension().unwrap_or(0),
+            indexed_vectors: index.len(),
+            deleted_vectors: index.deleted_count(),
+            memory_usage_bytes: index.memory_usage_bytes(),
+            total_queries,
+            average_query_latency_ms: if total_queries == 0 {
+                0.0
+            } else {
+                total_micros as f64 / total_queries as f64 / 1000.0
+            },
+            sampled_recall,
+            last_persisted: *self.last_persisted.read().unwrap(),
+        }
+    }
+
+    fn sampled_recall(&self, sample_size: usize) -> Option<f32> {
+        if let Some(cached) = *self.recall.read().unwrap() {
+            if cached.sample_size == sample_size && cached.measured_at.elapsed() < RECALL_REFRESH_INTERVAL {
+                return cached.recall;
+            }
+        }
+
+        let recall = self.index.read().unwrap().sample_recall(sample_size, 10);
+        *self.recall.write().unwrap() = Some(SampledRecall {
+            recall,
+            sample_size,
+            measured_at: Instant::now(),
+        });
+        recall
+    }
+
+    /// Write the index to a sidecar file, replacing it atomically
+    pub fn save(&self, path: &Path) -> Result<(), VectorIndexError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Taken before the snapshot, so a write racing the save is re-read on load
+        let synced_at = Utc::now();
+        let temp_path = path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(std::fs::File::create(&temp_path)?);
+            self.index.read().unwrap().write_to(&mut writer, synced_at)?;
+            writer.flush()?;
+        }
+        std::fs::rename(&temp_path, path)?;
+
+        *self.last_persisted.write().unwrap() = Some(synced_at);
+        debug!("Persisted vector index to {}", path.display());
+        Ok(())
+    }
+
+    /// Load a sidecar file written by `save`
+    pub fn load(&self, path: &Path) -> Result<usize, VectorIndexError> {
+        let mut reader = BufReader::new(std::fs::File::open(path)?);
+        let (index, synced_at) = HnswIndex::read_from(&mut reader)?;
+
+        let count = index.len();
+        *self.index.write().unwrap() = index;
+        *self.last_persisted.write().unwrap() = Some(synced_at);
+        *self.recall.write().unwrap() = None;
+        info!("Loaded vector index with {} vectors from {}", count, path.display());
+        Ok(count)
+    }
+}
+
+impl Default for VectorIndex {
+    fn default() -> Self {
+        Self::new(HnswParams::default(), "")
+    }
+}
+
+fn normalize(vector: &[f32]) -> Vec<f32> {
+    let norm: f32 = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter().map(|x| x / norm).collect()
+    } else {
+        vector.to_vec()
+    }
+}
+
+/// Cosine distance between normalized vectors
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - a.iter().zip(b.iter()).map(|(x, y)| x * y).sum::<f32>()
+}
+
+fn write_u32<W: Write>(writer: &mut W, value: u32) -> std::io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn write_str<W: Write>(writer: &mut W, value: &str) -> std::io::Result<()> {
+    write_u32(writer, value.len() as u32)?;
+    writer.write_all(value.as_bytes())
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> std::io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_f32<R: Read>(reader: &mut R) -> std::io::Result<f32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(f32::from_le_bytes(bytes))
+}
+
+fn read_str<R: Read>(reader: &mut R) -> Result<String, VectorIndexError> {
+    let len = read_u32(reader)? as usize;
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|e| VectorIndexError::CorruptIndex {
+        message: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random unit vectors
+    fn random_vectors(count: usize, dimension: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                (0..dimension)
+                    .map(|_| {
+                        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                        ((state >> 40) as f32 / (1u64 << 24) as f32) * 2.0 - 1.0
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn build_index(vectors: &[Vec<f32>]) -> HnswIndex {
+        let mut index = HnswIndex::new(HnswParams::default(), "test-model");
+        for (i, vector) in vectors.iter().enumerate() {
+            index.insert(&format!("ctx-{}", i), None, None, vector, "business_rule", 1.0).unwrap();
+        }
+        index
+    }
+
+    #[test]
+    fn test_search_finds_exact_match_first() {
+        let vectors = random_vectors(500, 32, 7);
+        let index = build_index(&vectors);
+
+        let hits = index.search(&vectors[42], 5, 64);
+        assert_eq!(hits[0].context_id, "ctx-42");
+        assert!((hits[0].similarity - 1.0).abs() < 1e-4);
+        assert!(hits.windows(2).all(|w| w[0].similarity >= w[1].similarity));
+    }
+
+    #[test]
+    fn test_recall_against_exact_search() {
+        let vectors = random_vectors(1000, 24, 11);
+        let index = build_index(&vectors);
+
+        let recall = index.sample_recall(50, 10).unwrap();
+        assert!(recall >= 0.9, "recall@10 too low: {}", recall);
+    }
+
+    #[test]
+    fn test_remove_and_replace() {
+        let vectors = random_vectors(100, 16, 3);
+        let mut index = build_index(&vectors);
+
+        assert!(index.remove("ctx-5"));
+        assert!(!index.remove("ctx-5"));
+        assert_eq!(index.len(), 99);
+        assert!(index.search(&vectors[5], 10, 64).iter().all(|h| h.context_id != "ctx-5"));
+
+        // Re-inserting under an existing id replaces the old vector
+        index.insert("ctx-6", None, None, &vectors[5], "business_rule", 1.0).unwrap();
+        assert_eq!(index.search(&vectors[5], 1, 64)[0].context_id, "ctx-6");
+        assert_eq!(index.len(), 99);
+
+        index.compact();
+        assert_eq!(index.deleted_count(), 0);
+        assert_eq!(index.search(&vectors[5], 1, 64)[0].context_id, "ctx-6");
+    }
+
+    #[test]
+    fn test_chunks_share_a_context() {
+        let vectors = random_vectors(4, 16, 11);
+        let mut index = HnswIndex::new(HnswParams::default(), "test-model");
+        for (i, vector) in vectors.iter().enumerate().take(3) {
+            index.insert("doc", None, Some(i), vector, "specification", 1.0).unwrap();
+        }
+        assert_eq!(index.len(), 3);
+
+        // Replacing one chunk leaves the others in place
+        index.insert("doc", None, Some(1), &vectors[3], "specification", 1.0).unwrap();
+        assert_eq!(index.len(), 3);
+        let hit = &index.search(&vectors[3], 1, 64)[0];
+        assert_eq!((hit.context_id.as_str(), hit.chunk_index), ("doc", Some(1)));
+
+        assert!(index.remove("doc"));
+        assert!(index.is_empty());
+        assert_eq!(index.deleted_count(), 4);
+    }
+
+    #[test]
+    fn test_dimension_mismatch_is_rejected() {
+        let mut index = HnswIndex::new(HnswParams::default(), "test-model");
+        index.insert("a", None, None, &[1.0, 0.0, 0.0], "business_rule", 1.0).unwrap();
+        assert!(matches!(
+            index.insert("b", None, None, &[1.0, 0.0], "business_rule", 1.0),
+            Err(VectorIndexError::DimensionMismatch { expected: 3, actual: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_sidecar_round_trip() {
+        let vectors = random_vectors(200, 16, 5);
+        let shared = VectorIndex::default();
+        *shared.index.write().unwrap() = build_index(&vectors);
+        shared.remove("ctx-1");
+        shared.index.write().unwrap().insert("scoped", Some("project-1"), None, &vectors[9], "business_rule", 1.0).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.hnsw");
+        shared.save(&path).unwrap();
+
+        let loaded = VectorIndex::default();
+        assert_eq!(loaded.load(&path).unwrap(), 200);
+        assert_eq!(loaded.embedding_model(), "test-model");
+        assert!(!loaded.contains("ctx-1"));
+        assert_eq!(
+            loaded.last_persisted().map(|t| t.timestamp_millis()),
+            shared.last_persisted().map(|t| t.timestamp_millis())
+        );
+
+        let original: Vec<String> = shared.search(&vectors[9], 5).into_iter().map(|h| h.context_id).collect();
+        let restored: Vec<String> = loaded.search(&vectors[9], 5).into_iter().map(|h| h.context_id).collect();
+        assert_eq!(original, restored);
+        let scoped = loaded.search(&vectors[9], 2).into_iter().find(|h| h.context_id == "scoped").unwrap();
+        assert_eq!(scoped.project_id.as_deref(), Some("project-1"));
+    }
+
+    #[test]
+    fn test_sampled_recall_is_cached_until_rebuild() {
+        let vectors = random_vectors(300, 16, 13);
+        let shared = VectorIndex::default();
+        *shared.index.write().unwrap() = build_index(&vectors);
+
+        let recall = shared.stats(DEFAULT_RECALL_SAMPLE_SIZE).sampled_recall;
+        assert!(recall.is_some());
+        let measured_at = shared.recall.read().unwrap().unwrap().measured_at;
+        shared.stats(DEFAULT_RECALL_SAMPLE_SIZE);
+        assert_eq!(shared.recall.read().unwrap().unwrap().measured_at, measured_at);
+
+        // A different sample size or a rebuild measures again
+        shared.stats(5);
+        assert_eq!(shared.recall.read().unwrap().unwrap().sample_size, 5);
+        shared.rebuild("test-model", &[]).unwrap();
+        assert!(shared.recall.read().unwrap().is_none());
+        assert_eq!(shared.stats(DEFAULT_RECALL_SAMPLE_SIZE).sampled_recall, None);
+    }
+
+    #[test]
+    fn test_corrupt_sidecar_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.hnsw");
+        std::fs::write(&path, b"not an index").unwrap();
+
+        assert!(VectorIndex::default().load(&path).is_err());
+    }
+}
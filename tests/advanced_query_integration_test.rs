@@ -2,7 +2,8 @@ use context_server_rs::services::advanced_query_service::{
     AdvancedQueryService, AdvancedQueryServiceImpl, AdvancedQueryConfig, AdvancedSearchQuery, 
     AdvancedSearchFilters, QualityRange, RankingPreferences, UserContext, UserPreferences, SuggestionType
 };
-use context_server_rs::models::enhanced_context::ContextType;
+use context_server_rs::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem, Priority};
+use context_server_rs::services::SearchQueryParser;
 use tokio;
 
 #[tokio::test]
@@ -275,4 +276,35 @@ async fn test_search_performance_metadata() {
     assert_eq!(stats.final_results, 0, "Simplified implementation has no results");
     
     println!("✓ Search performance metadata working correctly");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_query_language_filters_drop_excluded_and_low_priority_items() {
+    let service = AdvancedQueryServiceImpl::new(AdvancedQueryConfig::default());
+    let item = |title: &str, priority: Priority, tag: &str| {
+        let content = ContextContent {
+            content_type: ContextType::BusinessRule,
+            title: title.to_string(),
+            description: String::new(),
+            data: serde_json::json!({}),
+            source_file: None,
+            source_line: None,
+        };
+        let mut item = EnhancedContextItem::new("project-1".to_string(), content);
+        item.metadata.priority = priority;
+        item.metadata.tags.push(tag.to_string());
+        item
+    };
+    let items = vec![
+        item("Token rotation", Priority::High, "auth"),
+        item("Legacy sessions", Priority::High, "legacy"),
+        item("Button colours", Priority::Low, "ui"),
+    ];
+
+    let parsed = SearchQueryParser::parse("rotation priority:high -tag:legacy").unwrap();
+    let (kept, stats) = service.filter_context_items(items, &parsed.filters).await.unwrap();
+
+    let titles: Vec<&str> = kept.iter().map(|item| item.content.title.as_str()).collect();
+    assert_eq!(titles, vec!["Token rotation"]);
+    assert_eq!((stats.total_candidates, stats.final_results), (3, 1));
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use context_server_rs::db::init::init_db;
+use context_server_rs::infrastructure::SqliteEnhancedContextRepository;
+use context_server_rs::models::embedding::{EmbeddingConfig, ModelType, VectorSearchQuery};
+use context_server_rs::repositories::embedding_repository::SqliteEmbeddingRepository;
+use context_server_rs::services::semantic_search_service::{SemanticSearchConfig, SemanticSearchServiceImpl};
+use context_server_rs::services::{
+    EmbeddingService, EmbeddingServiceFactory, IndexManagerConfig, SearchIndexManager, SearchIndexManagerImpl,
+};
+use tempfile::tempdir;
+
+const ITEMS: &[(&str, &str, &str)] = &[
+    ("rule-1", "Token rotation", "Access tokens must be rotated every 24 hours"),
+    ("rule-2", "Password storage", "Passwords must be hashed with argon2 before storage"),
+    ("rule-3", "Checkout latency", "The checkout page must render in under one second"),
+    ("rule-4", "Audit logging", "Every admin action must be written to the audit log"),
+];
+
+fn seed_context_items(db_path: &str) {
+    insert_context_items(db_path, "project-1", ITEMS);
+}
+
+fn insert_context_items(db_path: &str, project_id: &str, items: &[(&str, &str, &str)]) {
+    let conn = init_db(db_path).unwrap();
+    let conn = Arc::new(std::sync::Mutex::new(conn));
+    SqliteEnhancedContextRepository::new(conn.clone())
+        .initialize_tables()
+        .unwrap();
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let conn = conn.lock().unwrap();
+    for (id, title, description) in items {
+        conn.execute(
+            "INSERT INTO enhanced_context_items (id, project_id, content_type, title, description, data, created_at, updated_at)
+             VALUES (?1, ?2, 'business_rule', ?3, ?4, '{}', ?5, ?5)",
+            rusqlite::params![id, project_id, title, description, now],
+        )
+        .unwrap();
+    }
+}
+
+async fn open_repository(db_path: &str) -> SqliteEmbeddingRepository {
+    let conn = Arc::new(tokio::sync::Mutex::new(rusqlite::Connection::open(db_path).unwrap()));
+    let repository = SqliteEmbeddingRepository::new(conn);
+    repository.initialize().await.unwrap();
+    repository
+}
+
+async fn document_count(repository: &SqliteEmbeddingRepository, project_id: Option<&str>) -> Option<u64> {
+    repository
+        .load_vocabulary("bm25-local", project_id)
+        .await
+        .unwrap()
+        .map(|vocabulary| vocabulary.document_count)
+}
+
+fn bm25_config() -> EmbeddingConfig {
+    EmbeddingConfig {
+        model_name: "bm25-local".to_string(),
+        model_type: ModelType::Custom("bm25".to_string()),
+        embedding_dimension: 256,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_bm25_vocabulary_is_built_and_persisted() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("bm25.db");
+    let db_path = db_path.to_str().unwrap();
+    seed_context_items(db_path);
+
+    let repository = open_repository(db_path).await;
+
+    assert!(repository.load_vocabulary("bm25-local", Some("project-1")).await.unwrap().is_none());
+
+    let service = EmbeddingServiceFactory::create_service_with_repository(bm25_config(), &repository, Some("project-1"))
+        .await
+        .unwrap();
+    assert_eq!(service.get_model_info().model_type, ModelType::Custom("bm25".to_string()));
+
+    let vocabulary = repository
+        .load_vocabulary("bm25-local", Some("project-1"))
+        .await
+        .unwrap()
+        .expect("vocabulary should be persisted");
+    assert_eq!(vocabulary.document_count, ITEMS.len() as u64);
+    assert_eq!(vocabulary.document_frequencies.get("must"), Some(&(ITEMS.len() as u64)));
+    assert_eq!(vocabulary.document_frequencies.get("argon2"), Some(&1));
+
+    let mut embeddings = Vec::new();
+    for (id, title, description) in ITEMS {
+        let mut embedding = service
+            .generate_embedding(&format!("{} {}", title, description), "business_rule")
+            .await
+            .unwrap();
+        embedding.context_id = id.to_string();
+        embeddings.push(embedding);
+    }
+
+    let query = VectorSearchQuery {
+        query_text: "how often are access tokens rotated".to_string(),
+        similarity_threshold: 0.0,
+        ..Default::default()
+    };
+    let results = service.find_similar(&query, &embeddings).await.unwrap();
+    assert_eq!(results[0].context_id, "rule-1");
+    assert!(results[0].similarity_score > 0.3);
+}
+
+#[tokio::test]
+async fn test_bm25_factory_without_repository_is_deterministic() {
+    let service = EmbeddingServiceFactory::create_initialized_service(bm25_config()).await.unwrap();
+
+    let a = service.generate_embedding("audit log for admin actions", "business_rule").await.unwrap();
+    let b = service.generate_embedding("audit log for admin actions", "business_rule").await.unwrap();
+    assert_eq!(a.embedding_vector, b.embedding_vector);
+}
+
+#[tokio::test]
+async fn test_bm25_vocabularies_follow_each_projects_corpus() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("bm25.db");
+    let db_path = db_path.to_str().unwrap();
+    insert_context_items(db_path, "project-1", &[]);
+    let repository = open_repository(db_path).await;
+
+    let service = EmbeddingServiceFactory::create_initialized_service(bm25_config()).await.unwrap();
+    service.prepare(&repository).await.unwrap();
+    assert_eq!(document_count(&repository, None).await, Some(0));
+    assert!(!service.refresh_vocabulary(Some("project-1"), false).await.unwrap());
+
+    // The first items replace the empty statistics of a fresh database
+    insert_context_items(db_path, "project-1", ITEMS);
+    assert!(service.refresh_vocabulary(Some("project-1"), false).await.unwrap());
+    assert!(service.refresh_vocabulary(None, false).await.unwrap());
+    assert_eq!(document_count(&repository, Some("project-1")).await, Some(ITEMS.len() as u64));
+    assert_eq!(document_count(&repository, None).await, Some(ITEMS.len() as u64));
+    assert!(!service.refresh_vocabulary(Some("project-1"), false).await.unwrap());
+
+    // Another project's items leave project-1's statistics alone
+    insert_context_items(
+        db_path,
+        "project-2",
+        &[
+            ("rule-5", "Token rotation", "Refresh tokens expire after one week"),
+            ("rule-6", "Token storage", "Tokens are never written to logs"),
+        ],
+    );
+    assert!(!service.refresh_vocabulary(Some("project-1"), false).await.unwrap());
+    assert!(service.refresh_vocabulary(Some("project-2"), false).await.unwrap());
+    assert_eq!(document_count(&repository, Some("project-1")).await, Some(ITEMS.len() as u64));
+    assert_eq!(document_count(&repository, Some("project-2")).await, Some(2));
+
+    let text = "token rotation for access tokens";
+    let in_project_1 = service.generate_project_embedding(text, "query", Some("project-1")).await.unwrap();
+    let in_project_2 = service.generate_project_embedding(text, "query", Some("project-2")).await.unwrap();
+    assert_ne!(in_project_1.embedding_vector, in_project_2.embedding_vector);
+
+    // A corpus that grew by a fifth is rebuilt
+    insert_context_items(db_path, "project-1", &[("rule-7", "Backups", "Nightly backups are kept for thirty days")]);
+    assert!(service.refresh_vocabulary(Some("project-1"), false).await.unwrap());
+    assert_eq!(document_count(&repository, Some("project-1")).await, Some(ITEMS.len() as u64 + 1));
+}
+
+#[tokio::test]
+async fn test_bm25_prepare_uses_stored_vocabularies() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("bm25.db");
+    let db_path = db_path.to_str().unwrap();
+    seed_context_items(db_path);
+    let repository = open_repository(db_path).await;
+
+    let first = EmbeddingServiceFactory::create_initialized_service(bm25_config()).await.unwrap();
+    first.prepare(&repository).await.unwrap();
+    insert_context_items(db_path, "project-2", &[("rule-5", "Backups", "Nightly backups are kept for thirty days")]);
+
+    // A later start, e.g. serving queries with the previous model during a migration
+    let restarted = EmbeddingServiceFactory::create_initialized_service(bm25_config()).await.unwrap();
+    restarted.prepare(&repository).await.unwrap();
+    let unprepared = EmbeddingServiceFactory::create_initialized_service(bm25_config()).await.unwrap();
+
+    let text = "access tokens must be rotated";
+    let expected = first.generate_embedding(text, "query").await.unwrap().embedding_vector;
+    assert_eq!(restarted.generate_embedding(text, "query").await.unwrap().embedding_vector, expected);
+    assert_ne!(unprepared.generate_embedding(text, "query").await.unwrap().embedding_vector, expected);
+    assert_eq!(document_count(&repository, None).await, Some(ITEMS.len() as u64));
+}
+
+#[tokio::test]
+async fn test_rebuild_project_index_rebuilds_the_vocabulary() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("bm25.db");
+    let db_path = db_path.to_str().unwrap();
+    seed_context_items(db_path);
+    let repository = Arc::new(open_repository(db_path).await);
+
+    let embedding_service: Arc<dyn EmbeddingService> =
+        Arc::from(EmbeddingServiceFactory::create_initialized_service(bm25_config()).await.unwrap());
+    embedding_service.prepare(&repository).await.unwrap();
+    let search = Arc::new(SemanticSearchServiceImpl::new(
+        embedding_service.clone(),
+        repository.clone(),
+        SemanticSearchConfig { embedding_config: bm25_config(), ..Default::default() },
+    ));
+    let manager = SearchIndexManagerImpl::new(search, repository.clone(), embedding_service, IndexManagerConfig::default());
+
+    assert_eq!(document_count(&repository, Some("project-1")).await, None);
+    manager.rebuild_project_index("project-1", &[]).await.unwrap();
+    let built = repository.load_vocabulary("bm25-local", Some("project-1")).await.unwrap().unwrap();
+    assert_eq!(built.document_count, ITEMS.len() as u64);
+
+    // Rebuilding the index always rebuilds the vocabulary, even without drift
+    manager.rebuild_project_index("project-1", &[]).await.unwrap();
+    let rebuilt = repository.load_vocabulary("bm25-local", Some("project-1")).await.unwrap().unwrap();
+    assert!(rebuilt.built_at > built.built_at);
+}
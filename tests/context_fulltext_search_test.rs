@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex};
+
+use context_server_rs::infrastructure::SqliteEnhancedContextRepository;
+use context_server_rs::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem};
+use context_server_rs::repositories::EnhancedContextRepository;
+use rusqlite::Connection;
+
+fn context(project_id: &str, title: &str, description: &str) -> EnhancedContextItem {
+    let content = ContextContent {
+        content_type: ContextType::BusinessRule,
+        title: title.to_string(),
+        description: description.to_string(),
+        data: serde_json::json!({}),
+        source_file: None,
+        source_line: None,
+    };
+    EnhancedContextItem::new(project_id.to_string(), content)
+}
+
+fn repository(conn: Arc<Mutex<Connection>>) -> SqliteEnhancedContextRepository {
+    let repository = SqliteEnhancedContextRepository::new(conn);
+    repository.initialize_tables().unwrap();
+    repository
+}
+
+#[tokio::test]
+async fn test_search_filters_by_project_and_highlights_matches() {
+    let repository = repository(Arc::new(Mutex::new(Connection::open_in_memory().unwrap())));
+    repository
+        .create_context(&context("alpha", "Invoice rounding", "Invoices are rounded half to even at the total"))
+        .await
+        .unwrap();
+    repository
+        .create_context(&context("beta", "Invoice numbering", "Invoice numbers are sequential per year"))
+        .await
+        .unwrap();
+
+    let all = repository.search_contexts(None, "invoice", 10).await.unwrap();
+    assert_eq!(all.len(), 2);
+
+    let alpha = repository.search_contexts(Some("alpha"), "rounded invoices", 10).await.unwrap();
+    assert_eq!(alpha.len(), 1);
+    assert_eq!(alpha[0].context.content.title, "Invoice rounding");
+    assert!(alpha[0].score > 0.0);
+    assert!(alpha[0].snippet.contains("**"));
+
+    assert_eq!(repository.search_contexts(None, "invoice", 1).await.unwrap().len(), 1);
+    assert!(repository.search_contexts(None, "  -- ", 10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_existing_rows_are_indexed_on_first_start() {
+    let conn = Arc::new(Mutex::new(Connection::open_in_memory().unwrap()));
+    {
+        let repository = repository(conn.clone());
+        repository
+            .create_context(&context("alpha", "Refund window", "Refunds are possible within fourteen days"))
+            .await
+            .unwrap();
+    }
+    // Simulate a database created before the full-text index existed
+    conn.lock()
+        .unwrap()
+        .execute_batch(
+            "DROP TRIGGER enhanced_context_fts_insert;
+             DROP TRIGGER enhanced_context_fts_delete;
+             DROP TRIGGER enhanced_context_fts_update;
+             DROP TABLE enhanced_context_fts;
+             DROP TABLE schema_migrations;",
+        )
+        .unwrap();
+
+    let repository = repository(conn);
+    let results = repository.search_contexts(Some("alpha"), "refund", 10).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].context.content.title, "Refund window");
+}
@@ -0,0 +1,122 @@
+use std::sync::{Arc, Mutex};
+
+use context_server_rs::api::ContextPrompts;
+use context_server_rs::container::AppContainer;
+use context_server_rs::db::init::init_db;
+use context_server_rs::infrastructure::SqliteSpecificationRepository;
+use context_server_rs::models::specification::{
+    AcceptanceCriterion, CriterionType, ProjectSpecification, Requirement, SpecContent, SpecFormat, SpecType,
+};
+use context_server_rs::repositories::SpecificationRepository;
+use rmcp::model::{ErrorCode, JsonObject, PromptMessageContent};
+use rusqlite::Connection;
+use serde_json::json;
+use tempfile::tempdir;
+
+fn arguments(value: serde_json::Value) -> Option<JsonObject> {
+    value.as_object().cloned()
+}
+
+#[tokio::test]
+async fn test_prompts_assemble_context_for_the_feature_area() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("prompts.db").to_str().unwrap().to_string();
+    init_db(&db_path).unwrap();
+    let container = Arc::new(AppContainer::new(&db_path).unwrap());
+
+    let project = container.project_service.create_project("Shop", Some("Online store"), None).await.unwrap();
+    let crud = &container.context_crud_service;
+    let lockout = crud
+        .create_business_rule(&project.id, "Account lockout", Some("Lock after five failed attempts"), Some("authentication"))
+        .await
+        .unwrap();
+    crud.create_business_rule(&project.id, "Refund window", Some("Refunds within 30 days"), Some("billing"))
+        .await
+        .unwrap();
+    crud.create_architectural_decision(&project.id, "Use JWT sessions", Some("Stateless API"), Some("Short-lived JWTs"))
+        .await
+        .unwrap();
+    let extended = &container.extended_context_crud_service;
+    extended
+        .create_project_convention(&project.id, Some("naming"), Some("Use snake_case for handlers"))
+        .await
+        .unwrap();
+    extended
+        .create_security_policy(&project.id, "Password hashing", Some("authentication"))
+        .await
+        .unwrap();
+    extended
+        .create_security_policy(&project.id, "PCI scope", Some("billing"))
+        .await
+        .unwrap();
+
+    // Requirements are stored through the specification repository directly
+    let specs = SqliteSpecificationRepository::new(Arc::new(Mutex::new(Connection::open(&db_path).unwrap())));
+    let spec = ProjectSpecification::new(
+        project.id.clone(),
+        SpecType::Feature,
+        "Login".to_string(),
+        SpecContent::new(SpecFormat::Markdown, "# Login".to_string()),
+    );
+    specs.create_specification(&spec).await.unwrap();
+    let mut mentioned = Requirement::new(spec.id.clone(), "Authentication audit log".to_string(), "Record sign-ins".to_string());
+    mentioned
+        .acceptance_criteria
+        .push(AcceptanceCriterion::new("Failed sign-ins are logged".to_string(), CriterionType::Functional));
+    specs.create_requirement(&mentioned).await.unwrap();
+    let linked = Requirement::new(spec.id.clone(), "Session timeout".to_string(), "Expire idle sessions".to_string());
+    specs.create_requirement(&linked).await.unwrap();
+    specs.link_requirement_to_context(&linked.id, &lockout.id).await.unwrap();
+    let unrelated = Requirement::new(spec.id.clone(), "Invoice export".to_string(), "CSV download".to_string());
+    specs.create_requirement(&unrelated).await.unwrap();
+
+    let prompts = ContextPrompts::new(container.clone());
+    let result = prompts
+        .get_prompt(
+            "implement_feature",
+            arguments(json!({"project_id": project.id, "feature_area": "authentication", "components": "login, signup"})),
+        )
+        .await
+        .unwrap();
+    assert!(result.description.unwrap().contains("Shop"));
+    let text = match &result.messages[0].content {
+        PromptMessageContent::Text { text } => text.clone(),
+        other => panic!("expected text content, got {other:?}"),
+    };
+
+    assert!(text.contains("authentication"));
+    assert!(text.contains("**Account lockout**: Lock after five failed attempts"));
+    assert!(!text.contains("Refund window"));
+    assert!(text.contains("**Use JWT sessions**"));
+    assert!(text.contains("**naming**: Use snake_case for handlers"));
+    assert!(text.contains("**Password hashing**"));
+    assert!(!text.contains("PCI scope"));
+    assert!(text.contains("**Authentication audit log**"));
+    assert!(text.contains("  - [ ] Failed sign-ins are logged"));
+    assert!(text.contains("**Session timeout**"));
+    assert!(!text.contains("Invoice export"));
+
+    // Without a feature area every rule is included
+    let result = prompts
+        .get_prompt("review_change", arguments(json!({"project_id": project.id})))
+        .await
+        .unwrap();
+    let PromptMessageContent::Text { text } = &result.messages[0].content else {
+        panic!("expected text content");
+    };
+    assert!(text.contains("Refund window"));
+    assert!(text.contains("Invoice export"));
+
+    let names: Vec<String> = ContextPrompts::get_prompts().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, ["implement_feature", "review_change", "write_tests"]);
+
+    let error = prompts.get_prompt("deploy", arguments(json!({"project_id": project.id}))).await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    let error = prompts.get_prompt("write_tests", None).await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    let error = prompts
+        .get_prompt("write_tests", arguments(json!({"project_id": "missing"})))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+}
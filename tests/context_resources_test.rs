@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use context_server_rs::api::ContextResources;
+use context_server_rs::container::AppContainer;
+use context_server_rs::db::init::init_db;
+use context_server_rs::EnhancedContextMcpServer;
+use rmcp::model::{ErrorCode, ResourceContents};
+use rmcp::ServiceExt;
+use serde_json::{json, Value};
+use tempfile::{tempdir, TempDir};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+fn database() -> (TempDir, String) {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("resources.db").to_str().unwrap().to_string();
+    init_db(&db_path).unwrap();
+    (dir, db_path)
+}
+
+fn text(contents: &ResourceContents) -> &str {
+    match contents {
+        ResourceContents::TextResourceContents { text, .. } => text,
+        ResourceContents::BlobResourceContents { .. } => panic!("expected text contents"),
+    }
+}
+
+#[tokio::test]
+async fn test_context_is_browsable_as_markdown_resources() {
+    let (_dir, db_path) = database();
+    let container = Arc::new(AppContainer::new(&db_path).unwrap());
+    let project = container.project_service.create_project("Shop", None, None).await.unwrap();
+    let rule = container
+        .context_crud_service
+        .create_business_rule(&project.id, "Account lockout", Some("Lock after five failed attempts"), Some("authentication"))
+        .await
+        .unwrap();
+    let decision = container
+        .context_crud_service
+        .create_architectural_decision(&project.id, "Use SQLite", Some("Single node"), Some("Embed SQLite"))
+        .await
+        .unwrap();
+
+    let resources = ContextResources::new(container.clone());
+    let listed: Vec<String> = resources.list_resources().await.unwrap().into_iter().map(|r| r.raw.uri).collect();
+    let rule_uri = format!("context://{}/business_rules/{}", project.id, rule.id);
+    let collection_uri = format!("context://{}/business_rules", project.id);
+    assert!(listed.contains(&rule_uri));
+    assert!(listed.contains(&collection_uri));
+    assert!(listed.contains(&format!("context://{}/architectural_decisions/{}", project.id, decision.id)));
+
+    let item = resources.read_resource(&rule_uri).await.unwrap();
+    assert!(matches!(
+        &item.contents[0],
+        ResourceContents::TextResourceContents { mime_type: Some(mime), .. } if mime == "text/markdown"
+    ));
+    let markdown = text(&item.contents[0]);
+    assert!(markdown.starts_with("# Account lockout\n"));
+    assert!(markdown.contains("- **Domain area:** authentication"));
+
+    let collection = resources.read_resource(&collection_uri).await.unwrap();
+    assert!(text(&collection.contents[0]).contains(&format!("## [Account lockout]({rule_uri})")));
+
+    let decision_uri = format!("context://{}/architectural_decisions/{}", project.id, decision.id);
+    let decision_markdown = resources.read_resource(&decision_uri).await.unwrap();
+    assert!(text(&decision_markdown.contents[0]).contains("## Decision\n\nEmbed SQLite\n"));
+
+    let templates = ContextResources::resource_templates();
+    assert!(templates.iter().any(|t| t.raw.uri_template == "spec://{spec_id}/requirements"));
+
+    // An item is only served under its own project
+    let error = resources
+        .read_resource(&format!("context://other/business_rules/{}", rule.id))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::RESOURCE_NOT_FOUND);
+    let error = resources.read_resource("spec://missing/requirements").await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::RESOURCE_NOT_FOUND);
+    let error = resources.read_resource("file:///etc/passwd").await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+}
+
+struct Client {
+    reader: BufReader<tokio::io::ReadHalf<tokio::io::DuplexStream>>,
+    writer: tokio::io::WriteHalf<tokio::io::DuplexStream>,
+}
+
+impl Client {
+    async fn send(&mut self, message: Value) {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await.unwrap();
+    }
+
+    async fn receive(&mut self) -> Value {
+        let mut line = String::new();
+        tokio::time::timeout(Duration::from_secs(5), self.reader.read_line(&mut line))
+            .await
+            .expect("timed out waiting for the server")
+            .unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    /// Send a request and collect notifications until its response arrives
+    async fn request(&mut self, id: u64, method: &str, params: Value) -> (Value, Vec<Value>) {
+        self.send(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})).await;
+        let mut notifications = Vec::new();
+        loop {
+            let message = self.receive().await;
+            if message["id"] == id {
+                return (message, notifications);
+            }
+            notifications.push(message);
+        }
+    }
+}
+
+fn create_entity(entity_type: &str, data: Value) -> Value {
+    json!({"name": "create_entity", "arguments": {"entity_type": entity_type, "data": data}})
+}
+
+#[tokio::test]
+async fn test_subscribers_are_notified_when_rows_change() {
+    let (_dir, db_path) = database();
+    let server = EnhancedContextMcpServer::new(&db_path).unwrap();
+    let (client_stream, server_stream) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        let service = server.serve(tokio::io::split(server_stream)).await.unwrap();
+        let _ = service.waiting().await;
+    });
+    let (reader, writer) = tokio::io::split(client_stream);
+    let mut client = Client { reader: BufReader::new(reader), writer };
+
+    let (init, _) = client
+        .request(
+            1,
+            "initialize",
+            json!({"protocolVersion": "2024-11-05", "capabilities": {}, "clientInfo": {"name": "test", "version": "1"}}),
+        )
+        .await;
+    assert_eq!(init["result"]["capabilities"]["resources"]["subscribe"], true);
+    client.send(json!({"jsonrpc": "2.0", "method": "notifications/initialized"})).await;
+
+    let (response, _) = client
+        .request(2, "tools/call", create_entity("project", json!({"name": "Shop"})))
+        .await;
+    let project = &response["result"]["structuredContent"];
+    let project_id = project["id"].as_str().unwrap();
+
+    let collection_uri = format!("context://{project_id}/business_rules");
+    let (response, _) = client.request(3, "resources/subscribe", json!({"uri": collection_uri})).await;
+    assert!(response.get("error").is_none(), "{response}");
+
+    let rule = create_entity("business_rule", json!({"project_id": project_id, "rule_name": "Refund window"}));
+    let (_, mut notifications) = client.request(4, "tools/call", rule).await;
+    while notifications.is_empty() {
+        notifications.push(client.receive().await);
+    }
+    assert_eq!(notifications[0]["method"], "notifications/resources/updated");
+    assert_eq!(notifications[0]["params"]["uri"], collection_uri.as_str());
+
+    let (response, _) = client.request(5, "resources/read", json!({"uri": collection_uri})).await;
+    assert!(response["result"]["contents"][0]["text"].as_str().unwrap().contains("Refund window"));
+
+    // No notifications once unsubscribed
+    client.request(6, "resources/unsubscribe", json!({"uri": collection_uri})).await;
+    let rule = create_entity("business_rule", json!({"project_id": project_id, "rule_name": "Invoice rounding"}));
+    let (_, notifications) = client.request(7, "tools/call", rule).await;
+    let (_, later) = client.request(8, "resources/templates/list", json!({})).await;
+    assert!(notifications.iter().chain(later.iter()).all(|n| n["method"] != "notifications/resources/updated"));
+}
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use context_server_rs::db::init::init_db;
+use context_server_rs::models::embedding::{EmbeddingConfig, ModelType, VectorSearchQuery};
+use context_server_rs::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem};
+use context_server_rs::repositories::embedding_repository::{EmbeddingRepository, SqliteEmbeddingRepository};
+use context_server_rs::services::semantic_search_service::{
+    SemanticSearchConfig, SemanticSearchService, SemanticSearchServiceImpl,
+};
+use context_server_rs::services::{ChunkingConfig, EmbeddingService, EmbeddingServiceFactory};
+use rusqlite::Connection;
+use tempfile::{tempdir, TempDir};
+
+const SPEC: &str = "\
+# Authentication
+Users sign in with an email address and a password. Accounts are locked for fifteen minutes after five failed attempts in a row, and the lockout is recorded in the security log.
+
+## Tokens
+Access tokens expire after one hour and refresh tokens must be rotated on every use. A refresh token that is presented twice revokes the whole token family for that device.
+
+## Passwords
+Passwords are hashed with argon2 using the library defaults. Password reset links are single use and expire after thirty minutes; requesting a new link invalidates older ones.
+
+# Billing
+Invoices are issued on the first day of every month in the customer currency. Amounts are rounded half to even at the invoice total, never per line item, to avoid drift.
+
+## Refunds
+Refunds are only possible within fourteen days of payment and go back to the original payment method. Partial refunds require a note explaining the adjustment.
+";
+
+fn context(id: &str, title: &str, description: &str) -> EnhancedContextItem {
+    let content = ContextContent {
+        content_type: ContextType::FeatureContext,
+        title: title.to_string(),
+        description: description.to_string(),
+        data: serde_json::json!({}),
+        source_file: None,
+        source_line: None,
+    };
+    let mut context = EnhancedContextItem::new("project-1".to_string(), content);
+    context.id = id.to_string();
+    context
+}
+
+struct Harness {
+    _dir: TempDir,
+    repository: Arc<SqliteEmbeddingRepository>,
+    search: SemanticSearchServiceImpl,
+}
+
+async fn harness(dir: TempDir) -> Harness {
+    let db_path = dir.path().join("chunks.db");
+    let conn = Arc::new(tokio::sync::Mutex::new(init_db(db_path.to_str().unwrap()).unwrap()));
+    let repository = Arc::new(SqliteEmbeddingRepository::new(conn));
+    repository.initialize().await.unwrap();
+
+    let embedding_config = EmbeddingConfig {
+        model_name: "bm25-test".to_string(),
+        model_type: ModelType::Custom("bm25".to_string()),
+        embedding_dimension: 256,
+        ..Default::default()
+    };
+    let embedding_service: Arc<dyn EmbeddingService> =
+        Arc::from(EmbeddingServiceFactory::create_initialized_service(embedding_config.clone()).await.unwrap());
+
+    let search = SemanticSearchServiceImpl::new(
+        embedding_service,
+        repository.clone(),
+        SemanticSearchConfig {
+            embedding_config,
+            enable_intent_detection: false,
+            chunking: ChunkingConfig {
+                enabled: true,
+                max_chunk_chars: 300,
+                overlap_chars: 30,
+                min_chunk_chars: 100,
+            },
+            ..Default::default()
+        },
+    );
+
+    Harness { _dir: dir, repository, search }
+}
+
+#[tokio::test]
+async fn test_long_spec_is_chunked_and_aggregated() {
+    let h = harness(tempdir().unwrap()).await;
+    h.search.index_context(&context("spec-1", "Accounts and billing", SPEC)).await.unwrap();
+    h.search
+        .index_context(&context("rule-1", "Deploy window", "Deployments happen on weekdays only"))
+        .await
+        .unwrap();
+
+    let chunks = h.repository.get_embeddings_by_context_id("spec-1").await.unwrap();
+    assert!(chunks.len() >= 4);
+    assert!(chunks.iter().all(|e| e.context_id == "spec-1"));
+    assert!(chunks.iter().enumerate().all(|(i, e)| e.chunk_index() == i));
+    let headings: Vec<_> = chunks.iter().filter_map(|e| e.chunk.as_ref()?.heading.clone()).collect();
+    assert!(headings.contains(&"Billing > Refunds".to_string()));
+
+    let short = h.repository.get_embeddings_by_context_id("rule-1").await.unwrap();
+    assert_eq!(short.len(), 1);
+    assert!(short[0].chunk.is_none());
+
+    let query = VectorSearchQuery {
+        query_text: "refund original payment method fourteen days".to_string(),
+        similarity_threshold: 0.0,
+        max_results: 5,
+        ..Default::default()
+    };
+    let results = h.search.search(&query).await.unwrap();
+
+    // One result per item, whatever the number of matching chunks
+    let spec_results: Vec<_> = results.iter().filter(|r| r.vector_result.context_id == "spec-1").collect();
+    assert_eq!(spec_results.len(), 1);
+    assert_eq!(results[0].vector_result.context_id, "spec-1");
+
+    let metadata = &results[0].vector_result.metadata;
+    let chunk = metadata.chunk.as_ref().expect("chunk match metadata");
+    assert_eq!(chunk.heading.as_deref(), Some("Billing > Refunds"));
+    assert!(chunk.matched_chunks >= 1);
+    assert!(metadata.content_preview.contains("Refunds are only possible"));
+    assert!(metadata.content_preview.chars().count() <= 301);
+
+    let stats = h.search.get_index_stats(None).await.unwrap();
+    assert_eq!(stats.total_indexed_items, 2);
+}
+
+#[tokio::test]
+async fn test_reindexing_shorter_content_drops_stale_chunks() {
+    let h = harness(tempdir().unwrap()).await;
+    h.search.index_context(&context("spec-1", "Accounts and billing", SPEC)).await.unwrap();
+    assert!(h.repository.get_embeddings_by_context_id("spec-1").await.unwrap().len() > 1);
+
+    h.search
+        .index_context(&context("spec-1", "Accounts and billing", "Billing moved to a separate spec"))
+        .await
+        .unwrap();
+    let remaining = h.repository.get_embeddings_by_context_id("spec-1").await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert!(remaining[0].chunk.is_none());
+
+    let stats = h.repository.get_embedding_stats(None).await.unwrap();
+    assert_eq!(stats.total_embeddings, 1);
+    assert_eq!(stats.indexed_contexts, 1);
+}
+
+#[tokio::test]
+async fn test_table_without_chunk_columns_is_migrated() {
+    let dir = tempdir().unwrap();
+    {
+        // Binary vectors, but written before chunking existed
+        let conn = Connection::open(dir.path().join("chunks.db")).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE context_embeddings (
+                id TEXT PRIMARY KEY,
+                context_id TEXT NOT NULL,
+                project_id TEXT,
+                embedding_vector BLOB NOT NULL,
+                vector_dimension INTEGER,
+                quantization TEXT,
+                quantized_vector BLOB,
+                quantization_scale REAL,
+                embedding_model TEXT NOT NULL,
+                embedding_version TEXT NOT NULL,
+                content_hash TEXT NOT NULL,
+                content_type TEXT,
+                content_length INTEGER,
+                tokenization_method TEXT,
+                preprocessing_steps TEXT,
+                quality_score REAL,
+                custom_metadata TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT DEFAULT (datetime('now')),
+                UNIQUE(context_id, embedding_model, embedding_version)
+            );
+            "#,
+        )
+        .unwrap();
+        let vector: Vec<u8> = [0.6f32, 0.8].iter().flat_map(|v| v.to_le_bytes()).collect();
+        conn.execute(
+            "INSERT INTO context_embeddings VALUES
+             ('emb-1', 'ctx-1', NULL, ?1, 2, NULL, NULL, NULL, 'test-model', '1.0', 'hash', 'business_rule', 10, 'standard', '[]', 0.9, '{}', ?2, NULL)",
+            rusqlite::params![vector, chrono::Utc::now().to_rfc3339()],
+        )
+        .unwrap();
+    }
+
+    let h = harness(dir).await;
+    let stored = h.repository.get_embeddings_by_context_id("ctx-1").await.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].embedding_vector, vec![0.6, 0.8]);
+    assert_eq!(stored[0].chunk_index(), 0);
+    assert!(stored[0].chunk.is_none());
+
+    // The widened unique key now admits several chunks per item
+    h.search.index_context(&context("spec-1", "Accounts and billing", SPEC)).await.unwrap();
+    assert!(h.repository.get_embeddings_by_context_id("spec-1").await.unwrap().len() > 1);
+}
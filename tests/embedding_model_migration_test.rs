@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use context_server_rs::db::init::init_db;
+use context_server_rs::models::embedding::{EmbeddingConfig, ModelType, VectorSearchQuery};
+use context_server_rs::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem};
+use context_server_rs::repositories::embedding_repository::{EmbeddingRepository, SqliteEmbeddingRepository};
+use context_server_rs::services::semantic_search_service::{
+    SemanticSearchConfig, SemanticSearchService, SemanticSearchServiceImpl,
+};
+use context_server_rs::services::{
+    EmbeddingService, EmbeddingServiceFactory, IndexManagerConfig, ModelMigrationStatus, SearchIndexManager,
+    SearchIndexManagerImpl, VectorIndex,
+};
+use tempfile::tempdir;
+
+const ITEMS: &[(&str, &str, &str)] = &[
+    ("rule-1", "Token rotation", "Access tokens must be rotated every 24 hours"),
+    ("rule-2", "Password storage", "Passwords must be hashed with argon2 before storage"),
+    ("rule-3", "Checkout latency", "The checkout page must render in under one second"),
+    ("rule-4", "Audit logging", "Every admin action must be written to the audit log"),
+    ("rule-5", "Backups", "Nightly database backups are kept for thirty days"),
+];
+
+fn context(id: &str, title: &str, description: &str) -> EnhancedContextItem {
+    let content = ContextContent {
+        content_type: ContextType::BusinessRule,
+        title: title.to_string(),
+        description: description.to_string(),
+        data: serde_json::json!({}),
+        source_file: None,
+        source_line: None,
+    };
+    let mut context = EnhancedContextItem::new("project-1".to_string(), content);
+    context.id = id.to_string();
+    context.quality_score = 0.9;
+    context
+}
+
+fn model_config(model_name: &str, dimension: usize) -> EmbeddingConfig {
+    EmbeddingConfig {
+        model_name: model_name.to_string(),
+        model_type: ModelType::Custom("bm25".to_string()),
+        embedding_dimension: dimension,
+        ..Default::default()
+    }
+}
+
+async fn service(config: &EmbeddingConfig) -> Arc<dyn EmbeddingService> {
+    Arc::from(EmbeddingServiceFactory::create_initialized_service(config.clone()).await.unwrap())
+}
+
+struct Harness {
+    repository: Arc<SqliteEmbeddingRepository>,
+    search: Arc<SemanticSearchServiceImpl>,
+    manager: Arc<SearchIndexManagerImpl>,
+    index: Arc<VectorIndex>,
+}
+
+/// Services for one server start configured with `config`
+async fn harness(db_path: &str, config: &EmbeddingConfig) -> Harness {
+    let conn = Arc::new(tokio::sync::Mutex::new(init_db(db_path).unwrap()));
+    let repository = Arc::new(SqliteEmbeddingRepository::new(conn));
+    repository.initialize().await.unwrap();
+
+    let embedding_service = service(config).await;
+    let manager_config = IndexManagerConfig { batch_size: 2, ..Default::default() };
+    let index = Arc::new(VectorIndex::new(manager_config.hnsw.clone(), &config.model_name));
+    let search = Arc::new(
+        SemanticSearchServiceImpl::new(
+            embedding_service.clone(),
+            repository.clone(),
+            SemanticSearchConfig {
+                embedding_config: config.clone(),
+                enable_intent_detection: false,
+                ..Default::default()
+            },
+        )
+        .with_vector_index(index.clone()),
+    );
+    let manager = Arc::new(
+        SearchIndexManagerImpl::new(search.clone(), repository.clone(), embedding_service, manager_config)
+            .with_vector_index(index.clone()),
+    );
+
+    Harness { repository, search, manager, index }
+}
+
+async fn top_result(search: &SemanticSearchServiceImpl, text: &str) -> String {
+    let query = VectorSearchQuery {
+        query_text: text.to_string(),
+        similarity_threshold: 0.0,
+        max_results: 3,
+        ..Default::default()
+    };
+    search.search(&query).await.unwrap()[0].vector_result.context_id.clone()
+}
+
+#[tokio::test]
+async fn test_model_change_re_embeds_then_flips() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("migration.db");
+    let db_path = db_path.to_str().unwrap();
+    let (v1, v2) = (model_config("bm25-v1", 128), model_config("bm25-v2", 96));
+    let mut contexts: Vec<_> = ITEMS.iter().map(|(id, t, d)| context(id, t, d)).collect();
+
+    let h = harness(db_path, &v1).await;
+    assert!(h.manager.register_embedding_model(&v1).await.unwrap().is_none());
+    for context in &contexts {
+        h.manager.auto_index_context(context).await.unwrap();
+    }
+
+    // Restart configured with a new model
+    let h = harness(db_path, &v2).await;
+    let previous = h.manager.register_embedding_model(&v2).await.unwrap().expect("model change detected");
+    assert_eq!(previous.model_name, "bm25-v1");
+
+    let progress = h.manager.start_model_migration(service(&previous).await, v2.clone()).await.unwrap();
+    assert_eq!(progress.status, ModelMigrationStatus::Running);
+    assert_eq!((progress.total_contexts, progress.migrated_contexts), (ITEMS.len(), 0));
+    assert_eq!(h.manager.initialize_vector_index().await.unwrap(), ITEMS.len());
+    assert_eq!(h.index.embedding_model(), "bm25-v1");
+
+    // Writes during the migration land under both models
+    let extra = context("rule-6", "Feature flags", "Feature flags are removed within two releases");
+    h.manager.auto_index_context(&extra).await.unwrap();
+    contexts.push(extra);
+    h.search.index_context_for_target_model(&contexts[0]).await.unwrap();
+
+    let report = h.manager.get_health_report(None).await.unwrap();
+    let migration = report.model_migration.expect("migration progress reported");
+    assert_eq!((migration.total_contexts, migration.migrated_contexts), (ITEMS.len() + 1, 2));
+    assert!((migration.coverage - 2.0 / 6.0).abs() < 1e-6);
+    assert_eq!(report.outdated_embeddings, 4);
+
+    // Partial coverage: queries are still embedded and matched with the old model
+    assert_eq!(h.search.serving_model().as_deref(), Some("bm25-v1"));
+    assert_eq!(top_result(&h.search, "rotate access tokens").await, "rule-1");
+
+    let progress = h.manager.spawn_model_migration(contexts.clone()).await.unwrap().unwrap();
+    assert_eq!(progress.status, ModelMigrationStatus::Completed);
+    assert_eq!(progress.coverage, 1.0);
+
+    assert_eq!(h.search.serving_model().as_deref(), Some("bm25-v2"));
+    assert_eq!(h.index.embedding_model(), "bm25-v2");
+    assert_eq!(h.index.len(), contexts.len());
+    assert_eq!(top_result(&h.search, "rotate access tokens").await, "rule-1");
+
+    let stats = h.repository.get_embedding_stats(None).await.unwrap();
+    assert_eq!(stats.embeddings_by_model.get("bm25-v1"), None);
+    assert_eq!(stats.embeddings_by_model.get("bm25-v2"), Some(&(contexts.len() as u64)));
+    assert_eq!(h.repository.get_active_embedding_config().await.unwrap().unwrap().model_name, "bm25-v2");
+
+    // The next start sees no model change
+    let h = harness(db_path, &v2).await;
+    assert!(h.manager.register_embedding_model(&v2).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_missing_contexts_are_dropped_before_the_flip() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("migration.db");
+    let db_path = db_path.to_str().unwrap();
+    let (v1, v2) = (model_config("bm25-v1", 128), model_config("bm25-v2", 96));
+    let contexts: Vec<_> = ITEMS.iter().map(|(id, t, d)| context(id, t, d)).collect();
+
+    let h = harness(db_path, &v1).await;
+    h.manager.register_embedding_model(&v1).await.unwrap();
+    h.search.index_contexts_batch(&contexts).await.unwrap();
+
+    let h = harness(db_path, &v2).await;
+    assert!(h.manager.run_model_migration(&contexts).await.is_err());
+    let previous = h.manager.register_embedding_model(&v2).await.unwrap().unwrap();
+    h.manager.start_model_migration(service(&previous).await, v2.clone()).await.unwrap();
+
+    // rule-5 was deleted while the server was down; its old vectors go away
+    let progress = h.manager.run_model_migration(&contexts[..4]).await.unwrap();
+    assert_eq!(progress.status, ModelMigrationStatus::Completed);
+    assert_eq!(progress.total_contexts, 4);
+    assert!(!h.repository.embedding_exists("rule-5").await.unwrap());
+    assert_eq!(h.repository.get_embedding_stats(None).await.unwrap().total_embeddings, 4);
+}
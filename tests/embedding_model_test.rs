@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use context_server_rs::api::SearchTools;
+use context_server_rs::container::AppContainer;
+use context_server_rs::models::embedding::{EmbeddingConfig, ModelType};
+use context_server_rs::services::hybrid_search_service::{RankedSearchResult, StageScores};
+use context_server_rs::services::transformer_embedding_service::CrossEncoder;
+use context_server_rs::services::{CrossEncoderReranker, EmbeddingServiceFactory, Reranker, SearchIndexManager};
+use tempfile::tempdir;
+
+const VOCAB: &[&str] = &[
+    "[PAD]", "[UNK]", "[CLS]", "[SEP]", "access", "tokens", "must", "be", "rotated", "every",
+    "day", "rotate", "daily", "the", "button", "is", "rendered", "in", "blue", "on", "checkout",
+    "page", ".",
+];
+
+const HIDDEN: usize = 32;
+const INTERMEDIATE: usize = 64;
+
+/// Deterministic pseudo-random weights so the fixture model is reproducible
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, scale: f32) -> f32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0) * scale
+    }
+
+    fn tensor(&mut self, len: usize, scale: f32) -> Vec<f32> {
+        (0..len).map(|_| self.next(scale)).collect()
+    }
+}
+
+/// Write a tiny single-layer BERT in HuggingFace layout (config.json, vocab.txt, model.safetensors),
+/// returning its weights by tensor name
+fn write_fixture_model(dir: &Path) -> HashMap<String, Vec<f32>> {
+    write_model(dir, false)
+}
+
+/// The fixture BERT plus a pooler and a single-logit classifier head, as in a cross-encoder
+fn write_cross_encoder_fixture(dir: &Path) {
+    write_model(dir, true);
+}
+
+fn write_model(dir: &Path, classifier: bool) -> HashMap<String, Vec<f32>> {
+    std::fs::write(
+        dir.join("config.json"),
+        serde_json::json!({
+            "hidden_size": HIDDEN,
+            "num_hidden_layers": 1,
+            "num_attention_heads": 4,
+            "intermediate_size": INTERMEDIATE,
+            "max_position_embeddings": 64,
+            "layer_norm_eps": 1e-12
+        })
+        .to_string(),
+    )
+    .unwrap();
+    std::fs::write(dir.join("vocab.txt"), VOCAB.join("\n")).unwrap();
+
+    let mut rng = Lcg(42);
+    let mut tensors: Vec<(String, Vec<usize>, Vec<f32>)> = vec![
+        ("embeddings.word_embeddings.weight".into(), vec![VOCAB.len(), HIDDEN], rng.tensor(VOCAB.len() * HIDDEN, 1.0)),
+        ("embeddings.position_embeddings.weight".into(), vec![64, HIDDEN], rng.tensor(64 * HIDDEN, 0.05)),
+        ("embeddings.token_type_embeddings.weight".into(), vec![2, HIDDEN], rng.tensor(2 * HIDDEN, 0.05)),
+        ("embeddings.LayerNorm.weight".into(), vec![HIDDEN], vec![1.0; HIDDEN]),
+        ("embeddings.LayerNorm.bias".into(), vec![HIDDEN], vec![0.0; HIDDEN]),
+    ];
+
+    let prefix = "encoder.layer.0";
+    let linears = [
+        ("attention.self.query", HIDDEN, HIDDEN),
+        ("attention.self.key", HIDDEN, HIDDEN),
+        ("attention.self.value", HIDDEN, HIDDEN),
+        ("attention.output.dense", HIDDEN, HIDDEN),
+        ("intermediate.dense", INTERMEDIATE, HIDDEN),
+        ("output.dense", HIDDEN, INTERMEDIATE),
+    ];
+    for (name, out_dim, in_dim) in linears {
+        tensors.push((format!("{prefix}.{name}.weight"), vec![out_dim, in_dim], rng.tensor(out_dim * in_dim, 0.1)));
+        tensors.push((format!("{prefix}.{name}.bias"), vec![out_dim], vec![0.0; out_dim]));
+    }
+    for name in ["attention.output.LayerNorm", "output.LayerNorm"] {
+        tensors.push((format!("{prefix}.{name}.weight"), vec![HIDDEN], vec![1.0; HIDDEN]));
+        tensors.push((format!("{prefix}.{name}.bias"), vec![HIDDEN], vec![0.0; HIDDEN]));
+    }
+    if classifier {
+        tensors.push(("bert.pooler.dense.weight".into(), vec![HIDDEN, HIDDEN], rng.tensor(HIDDEN * HIDDEN, 0.1)));
+        tensors.push(("bert.pooler.dense.bias".into(), vec![HIDDEN], vec![0.0; HIDDEN]));
+        tensors.push(("classifier.weight".into(), vec![1, HIDDEN], rng.tensor(HIDDEN, 0.5)));
+        tensors.push(("classifier.bias".into(), vec![1], vec![0.0]));
+    }
+
+    let mut header = HashMap::new();
+    let mut weights = HashMap::new();
+    let mut data = Vec::new();
+    for (name, shape, values) in tensors {
+        let start = data.len();
+        for value in &values {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        weights.insert(name.clone(), values);
+        header.insert(
+            name,
+            serde_json::json!({"dtype": "F32", "shape": shape, "data_offsets": [start, data.len()]}),
+        );
+    }
+    let header = serde_json::to_vec(&header).unwrap();
+
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend_from_slice(&header);
+    bytes.extend_from_slice(&data);
+    std::fs::write(dir.join("model.safetensors"), bytes).unwrap();
+    weights
+}
+
+/// The fixture's sentence embedding computed with plain loops in f64, independently
+/// of the service's matrix code: BERT embeddings, one encoder layer, mean pooling
+/// and L2 normalization
+fn reference_embedding(weights: &HashMap<String, Vec<f32>>, token_ids: &[usize]) -> Vec<f32> {
+    type Rows = Vec<Vec<f64>>;
+    let tensor = |name: &str| -> Vec<f64> { weights[name].iter().map(|&x| x as f64).collect() };
+    // PyTorch linear layers store their weight as out x in
+    let linear = |input: &Rows, name: &str, out_dim: usize| -> Rows {
+        let weight = tensor(&format!("{name}.weight"));
+        let bias = tensor(&format!("{name}.bias"));
+        let in_dim = weight.len() / out_dim;
+        input
+            .iter()
+            .map(|row| (0..out_dim).map(|o| bias[o] + (0..in_dim).map(|i| weight[o * in_dim + i] * row[i]).sum::<f64>()).collect())
+            .collect()
+    };
+    let layer_norm = |input: Rows, name: &str| -> Rows {
+        let weight = tensor(&format!("{name}.weight"));
+        let bias = tensor(&format!("{name}.bias"));
+        input
+            .into_iter()
+            .map(|row| {
+                let mean = row.iter().sum::<f64>() / row.len() as f64;
+                let variance = row.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / row.len() as f64;
+                row.iter().enumerate().map(|(i, x)| (x - mean) / (variance + 1e-12).sqrt() * weight[i] + bias[i]).collect()
+            })
+            .collect()
+    };
+    let add = |a: Rows, b: &Rows| -> Rows {
+        a.into_iter().zip(b).map(|(a, b)| a.into_iter().zip(b).map(|(a, b)| a + b).collect()).collect()
+    };
+    // Maclaurin series; the fixture's activations stay well inside its range
+    let erf = |x: f64| -> f64 {
+        let (mut term, mut sum) = (x, x);
+        for n in 1..80 {
+            term *= -x * x / n as f64;
+            sum += term / (2 * n + 1) as f64;
+        }
+        sum * 2.0 / std::f64::consts::PI.sqrt()
+    };
+
+    let (word, position, token_type) = (
+        tensor("embeddings.word_embeddings.weight"),
+        tensor("embeddings.position_embeddings.weight"),
+        tensor("embeddings.token_type_embeddings.weight"),
+    );
+    let embedded: Rows = token_ids
+        .iter()
+        .enumerate()
+        .map(|(p, &t)| (0..HIDDEN).map(|h| word[t * HIDDEN + h] + position[p * HIDDEN + h] + token_type[h]).collect())
+        .collect();
+    let hidden = layer_norm(embedded, "embeddings.LayerNorm");
+
+    let layer = |name: &str| format!("encoder.layer.0.{name}");
+    let query = linear(&hidden, &layer("attention.self.query"), HIDDEN);
+    let key = linear(&hidden, &layer("attention.self.key"), HIDDEN);
+    let value = linear(&hidden, &layer("attention.self.value"), HIDDEN);
+    let (heads, n) = (4, token_ids.len());
+    let head_dim = HIDDEN / heads;
+    let mut context = vec![vec![0.0; HIDDEN]; n];
+    for head in 0..heads {
+        let dims = head * head_dim..(head + 1) * head_dim;
+        for i in 0..n {
+            let scores: Vec<f64> = (0..n)
+                .map(|j| dims.clone().map(|d| query[i][d] * key[j][d]).sum::<f64>() / (head_dim as f64).sqrt())
+                .collect();
+            let total: f64 = scores.iter().map(|s| s.exp()).sum();
+            for d in dims.clone() {
+                context[i][d] = (0..n).map(|j| scores[j].exp() / total * value[j][d]).sum();
+            }
+        }
+    }
+    let attention = add(linear(&context, &layer("attention.output.dense"), HIDDEN), &hidden);
+    let attention = layer_norm(attention, &layer("attention.output.LayerNorm"));
+    let intermediate: Rows = linear(&attention, &layer("intermediate.dense"), INTERMEDIATE)
+        .into_iter()
+        .map(|row| row.into_iter().map(|x| 0.5 * x * (1.0 + erf(x / std::f64::consts::SQRT_2))).collect())
+        .collect();
+    let output = add(linear(&intermediate, &layer("output.dense"), HIDDEN), &attention);
+    let output = layer_norm(output, &layer("output.LayerNorm"));
+
+    let pooled: Vec<f64> = (0..HIDDEN).map(|h| output.iter().map(|row| row[h]).sum::<f64>() / n as f64).collect();
+    let norm = pooled.iter().map(|x| x * x).sum::<f64>().sqrt();
+    pooled.iter().map(|x| (x / norm) as f32).collect()
+}
+
+async fn similarity_ordering(config: EmbeddingConfig, related: [&str; 2], unrelated: &str) -> (f32, f32) {
+    let service = EmbeddingServiceFactory::create_initialized_service(config)
+        .await
+        .expect("model should load");
+
+    let a = service.generate_embedding(related[0], "business_rule").await.unwrap();
+    let b = service.generate_embedding(related[1], "business_rule").await.unwrap();
+    let c = service.generate_embedding(unrelated, "business_rule").await.unwrap();
+
+    (
+        service.calculate_similarity(&a, &b),
+        service.calculate_similarity(&a, &c),
+    )
+}
+
+#[tokio::test]
+async fn test_local_model_ranks_similar_texts_higher() {
+    let dir = tempdir().unwrap();
+    write_fixture_model(dir.path());
+
+    let config = EmbeddingConfig {
+        model_name: "fixture-bert".to_string(),
+        model_path: Some(dir.path().to_string_lossy().to_string()),
+        model_type: ModelType::SentenceTransformer,
+        embedding_dimension: HIDDEN,
+        ..Default::default()
+    };
+
+    let service = EmbeddingServiceFactory::create_initialized_service(config.clone())
+        .await
+        .unwrap();
+    let info = service.get_model_info();
+    assert_eq!(info.embedding_dimension, HIDDEN);
+    assert_eq!(info.model_type, ModelType::SentenceTransformer);
+
+    let embedding = service.generate_embedding("access tokens must be rotated", "business_rule").await.unwrap();
+    assert_eq!(embedding.embedding_vector.len(), HIDDEN);
+    let norm: f32 = embedding.embedding_vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    assert!((norm - 1.0).abs() < 1e-4, "embeddings should be L2 normalized");
+
+    let (related, unrelated) = similarity_ordering(
+        config,
+        ["access tokens must be rotated every day.", "rotate access tokens daily."],
+        "the button is rendered in blue on the checkout page.",
+    )
+    .await;
+    assert!(
+        related > unrelated,
+        "paraphrases should be closer ({related}) than unrelated text ({unrelated})"
+    );
+}
+
+#[tokio::test]
+async fn test_local_model_matches_a_reference_forward_pass() {
+    let dir = tempdir().unwrap();
+    let weights = write_fixture_model(dir.path());
+    let config = EmbeddingConfig {
+        model_name: "fixture-bert".to_string(),
+        model_path: Some(dir.path().to_string_lossy().to_string()),
+        model_type: ModelType::SentenceTransformer,
+        embedding_dimension: HIDDEN,
+        ..Default::default()
+    };
+    let service = EmbeddingServiceFactory::create_initialized_service(config).await.unwrap();
+
+    let text = "Access tokens must be rotated every day.";
+    let token_ids: Vec<usize> = ["[CLS]", "access", "tokens", "must", "be", "rotated", "every", "day", ".", "[SEP]"]
+        .iter()
+        .map(|token| VOCAB.iter().position(|t| t == token).unwrap())
+        .collect();
+    let expected = reference_embedding(&weights, &token_ids);
+    let actual = service.generate_embedding(text, "business_rule").await.unwrap().embedding_vector;
+
+    for (i, (actual, expected)) in actual.iter().zip(&expected).enumerate() {
+        assert!((actual - expected).abs() < 1e-4, "dimension {i}: {actual} != {expected}");
+    }
+}
+
+#[tokio::test]
+async fn test_missing_model_reports_load_error() {
+    let dir = tempdir().unwrap();
+    let config = EmbeddingConfig {
+        model_path: Some(dir.path().to_string_lossy().to_string()),
+        ..Default::default()
+    };
+
+    let result = EmbeddingServiceFactory::create_initialized_service(config).await;
+    assert!(result.is_err(), "an empty model directory should fail to load");
+}
+
+#[tokio::test]
+async fn test_cross_encoder_scores_pairs() {
+    let dir = tempdir().unwrap();
+    write_cross_encoder_fixture(dir.path());
+
+    let model = CrossEncoder::load(dir.path()).unwrap();
+    let query = "rotate access tokens";
+    let related = model.score(query, "access tokens must be rotated every day.", 64).unwrap();
+    let unrelated = model.score(query, "the button is rendered in blue on the checkout page.", 64).unwrap();
+    assert!(related.is_finite() && unrelated.is_finite());
+    assert_ne!(related, unrelated, "the passage should change the score");
+    assert_eq!(related, model.score(query, "access tokens must be rotated every day.", 64).unwrap());
+
+    let candidates: Vec<_> = ["access tokens must be rotated every day.", "the button is blue."]
+        .iter()
+        .map(|preview| RankedSearchResult {
+            context_id: preview.to_string(),
+            context: None,
+            preview: preview.to_string(),
+            scores: StageScores::default(),
+        })
+        .collect();
+    let reranker = CrossEncoderReranker::new(dir.path());
+    let scores = reranker.rerank(query, &candidates).await.unwrap();
+    assert_eq!(scores.len(), 2);
+    assert!(scores.iter().all(|s| *s > 0.0 && *s < 1.0));
+    assert!((scores[0] - 1.0 / (1.0 + (-related as f64).exp())).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_cross_encoder_requires_classifier_head() {
+    let dir = tempdir().unwrap();
+    write_fixture_model(dir.path());
+    assert!(CrossEncoder::load(dir.path()).is_err());
+}
+
+#[tokio::test]
+async fn test_container_embeds_with_the_configured_model() {
+    let dir = tempdir().unwrap();
+    let model_dir = dir.path().join("model");
+    std::fs::create_dir(&model_dir).unwrap();
+    write_fixture_model(&model_dir);
+    let db_path = dir.path().join("context.db");
+
+    let config = EmbeddingConfig {
+        model_name: "fixture-bert".to_string(),
+        model_path: Some(model_dir.to_string_lossy().to_string()),
+        model_type: ModelType::SentenceTransformer,
+        embedding_dimension: HIDDEN,
+        ..Default::default()
+    };
+    let container = AppContainer::with_embedding_config(db_path.to_str().unwrap(), config).unwrap();
+    container.prepare_search().await.unwrap();
+
+    let project = container.project_service.create_project("Shop", None, None).await.unwrap();
+    let rule = container
+        .context_crud_service
+        .create_business_rule(&project.id, "Token rotation", Some("access tokens must be rotated every day."), None)
+        .await
+        .unwrap();
+    let item = SearchTools::context_item_for_entity("business_rule", &serde_json::to_value(&rule).unwrap()).unwrap();
+    container.search_index_manager.auto_index_context(&item).await.unwrap();
+
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    let (model, dimension): (String, usize) = conn
+        .query_row("SELECT embedding_model, vector_dimension FROM context_embeddings", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!((model.as_str(), dimension), ("fixture-bert", HIDDEN));
+
+    let empty_dir = dir.path().join("empty");
+    std::fs::create_dir(&empty_dir).unwrap();
+    let config = EmbeddingConfig {
+        model_path: Some(empty_dir.to_string_lossy().to_string()),
+        model_type: ModelType::SentenceTransformer,
+        ..Default::default()
+    };
+    let container = AppContainer::with_embedding_config(dir.path().join("other.db").to_str().unwrap(), config).unwrap();
+    assert!(container.prepare_search().await.is_err(), "a missing model should not fall back silently");
+}
+
+/// Needs a real all-MiniLM-L6-v2 checkout: `CONTEXT_SERVER_TEST_MODEL=<dir> cargo test -- --ignored`
+#[tokio::test]
+#[ignore = "needs CONTEXT_SERVER_TEST_MODEL"]
+async fn test_minilm_paraphrase_similarity() {
+    let model_path = std::env::var("CONTEXT_SERVER_TEST_MODEL").expect("CONTEXT_SERVER_TEST_MODEL names the model directory");
+
+    let config = EmbeddingConfig {
+        model_path: Some(model_path),
+        ..Default::default()
+    };
+
+    let (related, unrelated) = similarity_ordering(
+        config,
+        [
+            "Users must re-authenticate after thirty minutes of inactivity",
+            "Sessions expire when a user has been idle for half an hour",
+        ],
+        "Invoices are exported as PDF at the end of each month",
+    )
+    .await;
+    assert!(related > unrelated + 0.2, "related={related} unrelated={unrelated}");
+}
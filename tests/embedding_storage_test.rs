@@ -0,0 +1,204 @@
+use std::sync::Arc;
+
+use context_server_rs::db::init::init_db;
+use context_server_rs::models::embedding::{
+    ContextEmbedding, QuantizedVector, VectorQuantization, VectorSearchQuery,
+};
+use context_server_rs::repositories::embedding_repository::{EmbeddingRepository, SqliteEmbeddingRepository};
+use rusqlite::Connection;
+use tempfile::{tempdir, TempDir};
+use tokio::sync::Mutex;
+
+const DIMENSION: usize = 64;
+
+/// Deterministic, roughly unit length vector that differs per seed
+fn vector(seed: usize) -> Vec<f32> {
+    (0..DIMENSION)
+        .map(|i| (((i * 31 + seed * 17) % 97) as f32 / 48.5 - 1.0) / (DIMENSION as f32).sqrt())
+        .collect()
+}
+
+fn embedding(context_id: &str, seed: usize) -> ContextEmbedding {
+    ContextEmbedding::new(
+        context_id.to_string(),
+        vector(seed),
+        "test-model".to_string(),
+        "1.0".to_string(),
+        format!("hash-{}", seed),
+    )
+}
+
+struct Harness {
+    _dir: TempDir,
+    conn: Arc<Mutex<Connection>>,
+    repository: SqliteEmbeddingRepository,
+}
+
+async fn open(dir: TempDir, quantization: VectorQuantization) -> Harness {
+    let db_path = dir.path().join("embeddings.db");
+    let conn = Arc::new(Mutex::new(init_db(db_path.to_str().unwrap()).unwrap()));
+    let repository = SqliteEmbeddingRepository::new(conn.clone()).with_quantization(quantization);
+    repository.initialize().await.unwrap();
+    Harness { _dir: dir, conn, repository }
+}
+
+async fn repository(quantization: VectorQuantization) -> Harness {
+    open(tempdir().unwrap(), quantization).await
+}
+
+#[tokio::test]
+async fn test_f32_blob_round_trip() {
+    let h = repository(VectorQuantization::None).await;
+    let (conn, repository) = (&h.conn, &h.repository);
+    let original = embedding("ctx-1", 1);
+    repository.store_embedding(&original).await.unwrap();
+
+    let stored = repository.get_embedding_by_context_id("ctx-1").await.unwrap().unwrap();
+    assert_eq!(stored.embedding_vector, original.embedding_vector);
+    assert!(stored.quantized_vector.is_none());
+
+    let (storage_class, bytes, dimension): (String, i64, i64) = conn
+        .lock()
+        .await
+        .query_row(
+            "SELECT typeof(embedding_vector), length(embedding_vector), vector_dimension FROM context_embeddings",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(storage_class, "blob");
+    assert_eq!(bytes as usize, DIMENSION * 4);
+    assert_eq!(dimension as usize, DIMENSION);
+
+    let stats = repository.get_embedding_stats(None).await.unwrap();
+    assert_eq!(stats.total_embeddings, 1);
+    assert_eq!(stats.vector_storage_bytes as usize, DIMENSION * 4);
+    assert_eq!(stats.quantized_storage_bytes, 0);
+    assert!(stats.quantized_embeddings.is_empty());
+    assert_eq!(stats.average_vector_dimension, DIMENSION as f64);
+}
+
+#[tokio::test]
+async fn test_quantized_round_trip() {
+    for (method, expected_bytes) in [(VectorQuantization::Int8, DIMENSION), (VectorQuantization::Binary, DIMENSION / 8)] {
+        let h = repository(method).await;
+        let repository = &h.repository;
+        let original = embedding("ctx-1", 3);
+        repository.store_embedding(&original).await.unwrap();
+
+        let stored = repository.get_embedding_by_context_id("ctx-1").await.unwrap().unwrap();
+        assert_eq!(stored.embedding_vector, original.embedding_vector);
+
+        let quantized = stored.quantized_vector.expect("quantized copy should be stored");
+        assert_eq!(quantized.method, method);
+        assert_eq!(quantized.data.len(), expected_bytes);
+        assert_eq!(Some(quantized.clone()), QuantizedVector::quantize(&original.embedding_vector, method));
+        assert!(quantized.approximate_similarity(&quantized) > 0.99);
+
+        if method == VectorQuantization::Int8 {
+            for (restored, exact) in quantized.dequantize().iter().zip(&original.embedding_vector) {
+                assert!((restored - exact).abs() <= quantized.scale);
+            }
+        }
+
+        let stats = repository.get_embedding_stats(None).await.unwrap();
+        assert_eq!(stats.quantized_embeddings.get(method.as_str()), Some(&1));
+        assert_eq!(stats.quantized_storage_bytes as usize, expected_bytes);
+    }
+}
+
+#[tokio::test]
+async fn test_json_vectors_are_migrated_once() {
+    let dir = tempdir().unwrap();
+    {
+        // Database written before binary storage existed
+        let conn = Connection::open(dir.path().join("embeddings.db")).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE context_embeddings (
+                id TEXT PRIMARY KEY,
+                context_id TEXT NOT NULL,
+                project_id TEXT,
+                embedding_vector TEXT NOT NULL,
+                embedding_model TEXT NOT NULL,
+                embedding_version TEXT NOT NULL,
+                content_hash TEXT NOT NULL,
+                content_type TEXT,
+                content_length INTEGER,
+                tokenization_method TEXT,
+                preprocessing_steps TEXT,
+                quality_score REAL,
+                custom_metadata TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT DEFAULT (datetime('now')),
+                UNIQUE(context_id, embedding_model, embedding_version)
+            );
+            CREATE INDEX idx_embeddings_context_id ON context_embeddings(context_id);
+            "#,
+        )
+        .unwrap();
+        for seed in 0..3 {
+            conn.execute(
+                "INSERT INTO context_embeddings VALUES (?1, ?2, NULL, ?3, 'test-model', '1.0', 'hash', 'business_rule', 10, 'standard', '[]', 0.9, '{}', ?4, NULL)",
+                rusqlite::params![
+                    format!("emb-{}", seed),
+                    format!("ctx-{}", seed),
+                    serde_json::to_string(&vector(seed)).unwrap(),
+                    chrono::Utc::now().to_rfc3339(),
+                ],
+            )
+            .unwrap();
+        }
+    }
+
+    let h = open(dir, VectorQuantization::Int8).await;
+    let (conn, repository) = (&h.conn, &h.repository);
+
+    let stats = repository.get_embedding_stats(None).await.unwrap();
+    assert_eq!(stats.total_embeddings, 3);
+    assert_eq!(stats.legacy_json_embeddings, 0);
+    assert_eq!(stats.quantized_embeddings.get("int8"), Some(&3));
+
+    for seed in 0..3 {
+        let stored = repository
+            .get_embedding_by_context_id(&format!("ctx-{}", seed))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.embedding_vector, vector(seed));
+        assert!(stored.quantized_vector.is_some());
+    }
+
+    let declared_type: String = conn
+        .lock()
+        .await
+        .query_row(
+            "SELECT type FROM pragma_table_info('context_embeddings') WHERE name = 'embedding_vector'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(declared_type, "BLOB");
+    assert_eq!(repository.backfill_quantized_vectors().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_quantized_prefilter_keeps_exact_ranking() {
+    let h = repository(VectorQuantization::Binary).await;
+    let repository = &h.repository;
+    let embeddings: Vec<_> = (0..120).map(|seed| embedding(&format!("ctx-{}", seed), seed)).collect();
+    repository.store_embeddings_batch(&embeddings).await.unwrap();
+
+    let query = VectorSearchQuery {
+        query_embedding: Some(vector(42)),
+        similarity_threshold: 0.0,
+        max_results: 5,
+        ..Default::default()
+    };
+    let results = repository.find_similar_embeddings(&query, None).await.unwrap();
+
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[0].context_id, "ctx-42");
+    assert!((results[0].similarity_score - 1.0).abs() < 1e-5);
+    assert!(results.windows(2).all(|w| w[0].similarity_score >= w[1].similarity_score));
+}
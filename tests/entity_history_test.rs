@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use context_server_rs::api::{ToolContext, ToolRegistry};
+use context_server_rs::container::AppContainer;
+use rmcp::model::{ErrorCode, JsonObject};
+use serde_json::{json, Value};
+use tempfile::{tempdir, TempDir};
+
+fn setup() -> (TempDir, ToolContext) {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("history.db");
+    let container = Arc::new(AppContainer::new(db_path.to_str().unwrap()).unwrap());
+    (dir, ToolContext::new(container, Arc::new(ToolRegistry::new())))
+}
+
+fn arguments(value: Value) -> Option<JsonObject> {
+    value.as_object().cloned()
+}
+
+async fn call(tools: &ToolContext, name: &str, args: Value) -> Value {
+    let result = tools.call_tool(name, arguments(args)).await.unwrap();
+    result.structured_content.expect("tools return structured content")
+}
+
+#[tokio::test]
+async fn test_entity_writes_are_recorded_diffed_and_restorable() {
+    let (_dir, tools) = setup();
+    let project = call(&tools, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+    let project_id = project["id"].as_str().unwrap();
+
+    let rule = call(
+        &tools,
+        "create_entity",
+        json!({
+            "entity_type": "business_rule",
+            "data": {"project_id": project_id, "rule_name": "Refunds", "domain_area": "billing"},
+            "author": "alice"
+        }),
+    )
+    .await;
+    let rule_id = rule["id"].as_str().unwrap();
+    call(
+        &tools,
+        "update_entity",
+        json!({"entity_type": "business_rule", "id": rule_id, "data": {"rule_name": "Refund window"}, "author": "bob"}),
+    )
+    .await;
+
+    let revisions = call(&tools, "list_revisions", json!({"entity_type": "business_rule", "id": rule_id})).await;
+    let revisions = revisions["result"].as_array().unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0]["operation"], "create");
+    assert_eq!(revisions[0]["author"], "alice");
+    assert_eq!(revisions[1]["author"], "bob");
+    assert_eq!(
+        revisions[1]["diff"],
+        json!([{"field": "rule_name", "before": "Refunds", "after": "Refund window"}])
+    );
+
+    let diff = call(&tools, "diff_revisions", json!({"entity_type": "business_rule", "id": rule_id, "from_revision": 1})).await;
+    assert_eq!(diff["to_revision"], 2);
+    assert_eq!(diff["changes"][0]["field"], "rule_name");
+
+    let restored = call(&tools, "restore_revision", json!({"entity_type": "business_rule", "id": rule_id, "revision": 1})).await;
+    assert_eq!(restored["rule_name"], "Refunds");
+    let revisions = call(&tools, "list_revisions", json!({"entity_type": "business_rule", "id": rule_id})).await;
+    assert_eq!(revisions["result"][2]["operation"], "restore");
+
+    let unknown = tools
+        .call_tool(
+            "diff_revisions",
+            arguments(json!({"entity_type": "business_rule", "id": rule_id, "from_revision": 9})),
+        )
+        .await;
+    assert_eq!(unknown.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+
+    call(&tools, "delete_entity", json!({"entity_type": "business_rule", "id": rule_id})).await;
+    let gone = tools
+        .call_tool(
+            "restore_revision",
+            arguments(json!({"entity_type": "business_rule", "id": rule_id, "revision": 1})),
+        )
+        .await;
+    assert_eq!(gone.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_project_can_be_read_as_of_an_earlier_time() {
+    let (_dir, tools) = setup();
+    let project = call(&tools, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+    let project_id = project["id"].as_str().unwrap();
+    let convention = call(
+        &tools,
+        "create_entity",
+        json!({
+            "entity_type": "project_convention",
+            "data": {"project_id": project_id, "convention_type": "naming", "convention_rule": "snake_case modules"}
+        }),
+    )
+    .await;
+    let convention_id = convention["id"].as_str().unwrap();
+
+    let revisions = call(&tools, "list_revisions", json!({"entity_type": "project_convention", "id": convention_id})).await;
+    let created_at = revisions["result"][0]["created_at"].clone();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+    call(
+        &tools,
+        "update_entity",
+        json!({"entity_type": "project_convention", "id": convention_id, "data": {"convention_rule": "kebab-case crates"}}),
+    )
+    .await;
+    call(
+        &tools,
+        "create_entity",
+        json!({"entity_type": "business_rule", "data": {"project_id": project_id, "rule_name": "Refunds"}}),
+    )
+    .await;
+
+    let then = call(&tools, "query_project_as_of", json!({"project_id": project_id, "as_of": created_at})).await;
+    assert_eq!(then["total"], 2);
+    assert_eq!(then["entities"]["project"][0]["name"], "Shop");
+    assert_eq!(then["entities"]["project_convention"][0]["convention_rule"], "snake_case modules");
+    assert!(then["entities"].get("business_rule").is_none());
+
+    let now = call(
+        &tools,
+        "query_project_as_of",
+        json!({"project_id": project_id, "as_of": chrono::Utc::now(), "entity_type": "project_convention"}),
+    )
+    .await;
+    assert_eq!(now["total"], 1);
+    assert_eq!(now["entities"]["project_convention"][0]["convention_rule"], "kebab-case crates");
+
+    let listed = call(&tools, "list_entities", json!({"entity_type": "project_convention", "project_id": project_id})).await;
+    assert_eq!(listed["items"].as_array().unwrap().len(), 1);
+}
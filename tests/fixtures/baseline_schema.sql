@@ -0,0 +1,460 @@
+-- Schema and sample rows of a database created before versioned migrations
+-- (CREATE TABLE IF NOT EXISTS in init_db and the repositories), used to test upgrading it
+
+CREATE TABLE projects (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT,
+            repository_url TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now'))
+        );
+
+CREATE TABLE business_rules (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            rule_name TEXT NOT NULL,
+            description TEXT,
+            domain_area TEXT,
+            implementation_pattern TEXT,
+            constraints TEXT,
+            examples TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE architectural_decisions (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            decision_title TEXT NOT NULL,
+            context TEXT,
+            decision TEXT,
+            consequences TEXT,
+            alternatives_considered TEXT,
+            status TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE performance_requirements (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            component_area TEXT,
+            requirement_type TEXT,
+            target_value TEXT,
+            optimization_patterns TEXT,
+            avoid_patterns TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE security_policies (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            policy_name TEXT NOT NULL,
+            policy_area TEXT,
+            requirements TEXT,
+            implementation_pattern TEXT,
+            forbidden_patterns TEXT,
+            compliance_notes TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE project_conventions (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            convention_type TEXT,
+            convention_rule TEXT,
+            good_examples TEXT,
+            bad_examples TEXT,
+            rationale TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE feature_context (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            feature_name TEXT NOT NULL,
+            business_purpose TEXT,
+            user_personas TEXT,
+            key_workflows TEXT,
+            integration_points TEXT,
+            edge_cases TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE framework_components (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            component_name TEXT NOT NULL,
+            component_type TEXT NOT NULL, -- 'widget', 'provider', 'service', 'repository', 'model', 'utility'
+            architecture_layer TEXT NOT NULL, -- 'presentation', 'domain', 'data', 'core'
+            file_path TEXT,
+            dependencies TEXT, -- JSON array of dependencies
+            metadata TEXT, -- JSON metadata for framework-specific properties
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE flutter_components (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            component_name TEXT NOT NULL,
+            component_type TEXT NOT NULL, -- 'widget', 'provider', 'service', 'repository'
+            architecture_layer TEXT NOT NULL, -- 'presentation', 'domain', 'data', 'core'
+            file_path TEXT,
+            dependencies TEXT, -- JSON array of dependencies
+            riverpod_scope TEXT, -- 'global', 'scoped', 'local'
+            widget_type TEXT, -- 'StatelessWidget', 'StatefulWidget', 'ConsumerWidget'
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE development_phases (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            phase_name TEXT NOT NULL, -- 'Setup', 'Chat UI', 'Model Management', 'Polish'
+            phase_order INTEGER NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending', -- 'pending', 'in_progress', 'completed', 'blocked'
+            description TEXT,
+            completion_criteria TEXT, -- JSON array of criteria
+            dependencies TEXT, -- JSON array of phase dependencies
+            started_at TEXT,
+            completed_at TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE privacy_rules (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            rule_name TEXT NOT NULL,
+            rule_type TEXT NOT NULL, -- 'forbidden_import', 'required_local_storage', 'data_flow'
+            pattern TEXT NOT NULL, -- import pattern, storage key, etc.
+            description TEXT,
+            severity TEXT DEFAULT 'error', -- 'error', 'warning', 'info'
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE privacy_violations (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            rule_id TEXT NOT NULL,
+            file_path TEXT NOT NULL,
+            line_number INTEGER,
+            violation_text TEXT,
+            status TEXT DEFAULT 'open', -- 'open', 'resolved', 'suppressed'
+            detected_at TEXT DEFAULT (datetime('now')),
+            resolved_at TEXT,
+            FOREIGN KEY (project_id) REFERENCES projects(id),
+            FOREIGN KEY (rule_id) REFERENCES privacy_rules(id)
+        );
+
+CREATE TABLE architecture_layers (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            layer_name TEXT NOT NULL, -- 'presentation', 'domain', 'data', 'core'
+            allowed_dependencies TEXT, -- JSON array of allowed layer dependencies
+            forbidden_imports TEXT, -- JSON array of forbidden import patterns
+            description TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE model_context (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            model_name TEXT NOT NULL,
+            model_path TEXT,
+            model_type TEXT, -- 'GGUF', 'ONNX', etc.
+            model_size TEXT,
+            performance_metrics TEXT, -- JSON with inference times, memory usage
+            configuration TEXT, -- JSON with model settings
+            is_active BOOLEAN DEFAULT 0,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE code_templates (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            template_name TEXT NOT NULL,
+            template_type TEXT NOT NULL, -- 'widget', 'provider', 'repository', 'test'
+            template_content TEXT NOT NULL,
+            variables TEXT, -- JSON array of template variables
+            description TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+CREATE TABLE context_embeddings (
+            id TEXT PRIMARY KEY,
+            context_id TEXT NOT NULL,
+            project_id TEXT,
+            embedding_vector BLOB NOT NULL, -- little-endian f32
+            vector_dimension INTEGER,
+            quantization TEXT, -- 'int8' or 'binary', NULL when not quantized
+            quantized_vector BLOB,
+            quantization_scale REAL,
+            chunk_index INTEGER NOT NULL DEFAULT 0,
+            chunk_start INTEGER,
+            chunk_end INTEGER,
+            chunk_heading TEXT,
+            chunk_text TEXT, -- NULL for items embedded whole
+            embedding_model TEXT NOT NULL,
+            embedding_version TEXT NOT NULL,
+            content_hash TEXT NOT NULL,
+            content_type TEXT,
+            content_length INTEGER,
+            tokenization_method TEXT,
+            preprocessing_steps TEXT, -- JSON array
+            quality_score REAL,
+            custom_metadata TEXT, -- JSON object
+            created_at TEXT NOT NULL,
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id),
+            UNIQUE(context_id, embedding_model, embedding_version, chunk_index)
+        );
+
+CREATE INDEX idx_embeddings_context_id ON context_embeddings(context_id);
+
+CREATE INDEX idx_embeddings_project_id ON context_embeddings(project_id);
+
+CREATE INDEX idx_embeddings_model ON context_embeddings(embedding_model);
+
+CREATE INDEX idx_embeddings_content_hash ON context_embeddings(content_hash);
+
+CREATE INDEX idx_embeddings_created_at ON context_embeddings(created_at);
+
+CREATE TABLE embedding_vocabularies (
+            embedding_model TEXT NOT NULL,
+            project_id TEXT NOT NULL DEFAULT '',
+            document_count INTEGER NOT NULL,
+            average_document_length REAL NOT NULL,
+            built_at TEXT NOT NULL,
+            PRIMARY KEY (embedding_model, project_id)
+        );
+
+CREATE TABLE embedding_vocabulary_terms (
+            embedding_model TEXT NOT NULL,
+            project_id TEXT NOT NULL DEFAULT '',
+            term TEXT NOT NULL,
+            document_frequency INTEGER NOT NULL,
+            PRIMARY KEY (embedding_model, project_id, term)
+        );
+
+CREATE TABLE embedding_model_state (
+            role TEXT PRIMARY KEY,
+            embedding_model TEXT NOT NULL,
+            config TEXT NOT NULL, -- JSON EmbeddingConfig
+            updated_at TEXT NOT NULL
+        );
+
+CREATE TABLE analytics_events (
+            id TEXT PRIMARY KEY,
+            event_type TEXT NOT NULL,
+            project_id TEXT,
+            entity_type TEXT,
+            entity_id TEXT,
+            user_agent TEXT,
+            metadata TEXT,
+            timestamp TEXT NOT NULL,
+            duration_ms INTEGER,
+            success BOOLEAN NOT NULL,
+            error_message TEXT
+        );
+
+CREATE INDEX idx_analytics_events_project_id ON analytics_events(project_id);
+
+CREATE INDEX idx_analytics_events_entity ON analytics_events(entity_type, entity_id);
+
+CREATE INDEX idx_analytics_events_timestamp ON analytics_events(timestamp);
+
+CREATE TABLE specifications (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                spec_type TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT,
+                content_format TEXT NOT NULL,
+                raw_content TEXT NOT NULL,
+                parsed_sections TEXT, -- JSON
+                content_metadata TEXT, -- JSON
+                status TEXT NOT NULL,
+                version INTEGER NOT NULL DEFAULT 1,
+                file_path TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                metadata TEXT -- JSON
+            );
+
+CREATE TABLE requirements (
+                id TEXT PRIMARY KEY,
+                spec_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT NOT NULL,
+                user_story TEXT,
+                priority TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                metadata TEXT, -- JSON
+                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE
+            );
+
+CREATE TABLE acceptance_criteria (
+                id TEXT PRIMARY KEY,
+                requirement_id TEXT NOT NULL,
+                description TEXT NOT NULL,
+                criterion_type TEXT NOT NULL,
+                status TEXT NOT NULL,
+                test_cases TEXT, -- JSON array
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
+            );
+
+CREATE TABLE tasks (
+                id TEXT PRIMARY KEY,
+                spec_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT NOT NULL,
+                status TEXT NOT NULL,
+                task_type TEXT NOT NULL,
+                parent_task TEXT,
+                estimated_effort TEXT,
+                actual_effort TEXT,
+                assigned_to TEXT,
+                progress REAL NOT NULL DEFAULT 0.0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                started_at TEXT,
+                completed_at TEXT,
+                metadata TEXT, -- JSON
+                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE,
+                FOREIGN KEY (parent_task) REFERENCES tasks (id) ON DELETE SET NULL
+            );
+
+CREATE TABLE task_dependencies (
+                task_id TEXT NOT NULL,
+                depends_on_task_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (task_id, depends_on_task_id),
+                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+                FOREIGN KEY (depends_on_task_id) REFERENCES tasks (id) ON DELETE CASCADE
+            );
+
+CREATE TABLE requirement_context_links (
+                requirement_id TEXT NOT NULL,
+                context_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (requirement_id, context_id),
+                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
+            );
+
+CREATE TABLE task_context_links (
+                task_id TEXT NOT NULL,
+                context_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (task_id, context_id),
+                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+            );
+
+CREATE TABLE task_requirement_links (
+                task_id TEXT NOT NULL,
+                requirement_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (task_id, requirement_id),
+                FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+                FOREIGN KEY (requirement_id) REFERENCES requirements (id) ON DELETE CASCADE
+            );
+
+CREATE INDEX idx_specifications_project_id ON specifications (project_id);
+
+CREATE INDEX idx_requirements_spec_id ON requirements (spec_id);
+
+CREATE INDEX idx_tasks_spec_id ON tasks (spec_id);
+
+CREATE TABLE specification_versions (
+                id TEXT PRIMARY KEY,
+                spec_id TEXT NOT NULL,
+                version_number INTEGER NOT NULL,
+                content_hash TEXT NOT NULL,
+                raw_content TEXT NOT NULL,
+                parsed_sections TEXT, -- JSON
+                change_description TEXT NOT NULL,
+                change_type TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                created_by TEXT,
+                file_path TEXT,
+                metadata TEXT, -- JSON
+                FOREIGN KEY (spec_id) REFERENCES specifications (id) ON DELETE CASCADE
+            );
+
+CREATE INDEX idx_spec_versions_spec_id ON specification_versions (spec_id);
+
+CREATE INDEX idx_spec_versions_version_number ON specification_versions (spec_id, version_number);
+
+CREATE TABLE enhanced_context_items (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                content_type TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT NOT NULL,
+                data TEXT NOT NULL, -- JSON data
+                source_file TEXT,
+                source_line INTEGER,
+                quality_score REAL NOT NULL DEFAULT 0.0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                version INTEGER NOT NULL DEFAULT 1,
+                -- Metadata fields
+                tags TEXT, -- JSON array
+                priority TEXT NOT NULL DEFAULT 'medium',
+                confidence REAL NOT NULL DEFAULT 1.0,
+                source TEXT NOT NULL DEFAULT 'manual',
+                validation_status TEXT NOT NULL DEFAULT 'pending',
+                last_accessed TEXT,
+                access_count INTEGER NOT NULL DEFAULT 0,
+                custom_fields TEXT -- JSON object
+            );
+
+CREATE INDEX idx_enhanced_context_project ON enhanced_context_items (project_id);
+
+CREATE INDEX idx_enhanced_context_type ON enhanced_context_items (content_type);
+
+CREATE VIRTUAL TABLE enhanced_context_fts USING fts5(
+                title, description, data, tags,
+                content = 'enhanced_context_items',
+                content_rowid = 'rowid',
+                tokenize = 'porter unicode61'
+            );
+
+CREATE TRIGGER enhanced_context_fts_insert AFTER INSERT ON enhanced_context_items BEGIN
+                INSERT INTO enhanced_context_fts (rowid, title, description, data, tags)
+                VALUES (new.rowid, new.title, new.description, new.data, new.tags);
+            END;
+
+CREATE TRIGGER enhanced_context_fts_delete AFTER DELETE ON enhanced_context_items BEGIN
+                INSERT INTO enhanced_context_fts (enhanced_context_fts, rowid, title, description, data, tags)
+                VALUES ('delete', old.rowid, old.title, old.description, old.data, old.tags);
+            END;
+
+CREATE TRIGGER enhanced_context_fts_update
+            AFTER UPDATE OF title, description, data, tags ON enhanced_context_items BEGIN
+                INSERT INTO enhanced_context_fts (enhanced_context_fts, rowid, title, description, data, tags)
+                VALUES ('delete', old.rowid, old.title, old.description, old.data, old.tags);
+                INSERT INTO enhanced_context_fts (rowid, title, description, data, tags)
+                VALUES (new.rowid, new.title, new.description, new.data, new.tags);
+            END;
+
+INSERT INTO projects (id, name, description) VALUES ('shop', 'Shop', 'Online store');
+INSERT INTO business_rules (id, project_id, rule_name, description, domain_area) VALUES ('rule-refunds', 'shop', 'Refund window', 'Refunds are accepted within 30 days', 'payments');
+INSERT INTO architectural_decisions (id, project_id, decision_title, decision, status) VALUES ('adr-sqlite', 'shop', 'Use SQLite', 'Store context in SQLite', 'accepted');
+INSERT INTO specifications (id, project_id, spec_type, title, content_format, raw_content, status, created_at, updated_at) VALUES ('spec-checkout', 'shop', 'Requirements', 'Checkout', 'Markdown', '# Checkout', 'Draft', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');
+INSERT INTO enhanced_context_items (id, project_id, content_type, title, description, data, created_at, updated_at, tags) VALUES ('rule-refunds', 'shop', 'business_rule', 'Refund window', 'Refunds are accepted within 30 days', '{"domain_area":"payments"}', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z', '["business_rule"]');
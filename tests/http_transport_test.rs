@@ -0,0 +1,153 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use context_server_rs::container::AppContainer;
+use context_server_rs::db::init::init_db;
+use context_server_rs::http_transport::{serve_http, MCP_ENDPOINT};
+use futures_util::StreamExt;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use tempfile::tempdir;
+use tokio::net::TcpListener;
+
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// JSON-RPC messages carried in the complete `data:` lines of an SSE body
+fn sse_messages(body: &str) -> Vec<Value> {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str(data.trim()).ok())
+        .collect()
+}
+
+struct Session {
+    client: reqwest::Client,
+    url: String,
+    id: String,
+}
+
+impl Session {
+    async fn initialize(url: &str) -> Self {
+        let client = reqwest::Client::new();
+        let response = client
+            .post(url)
+            .header("accept", "application/json, text/event-stream")
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {"protocolVersion": "2025-03-26", "capabilities": {}, "clientInfo": {"name": "test", "version": "1"}}
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let id = response.headers()[SESSION_HEADER].to_str().unwrap().to_string();
+        let messages = sse_messages(&response.text().await.unwrap());
+        assert_eq!(messages[0]["result"]["serverInfo"]["name"], "enhanced-context-server-rs");
+
+        let session = Self { client, url: url.to_string(), id };
+        let response = session.post(json!({"jsonrpc": "2.0", "method": "notifications/initialized"})).await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        session
+    }
+
+    async fn post(&self, message: Value) -> reqwest::Response {
+        self.client
+            .post(&self.url)
+            .header("accept", "application/json, text/event-stream")
+            .header(SESSION_HEADER, &self.id)
+            .json(&message)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    async fn request(&self, id: u64, method: &str, params: Value) -> Value {
+        let response = self.post(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        sse_messages(&response.text().await.unwrap())
+            .into_iter()
+            .find(|message| message["id"] == id)
+            .expect("no response to the request")
+    }
+
+    async fn create_entity(&self, id: u64, entity_type: &str, data: Value) -> Value {
+        let params = json!({"name": "create_entity", "arguments": {"entity_type": entity_type, "data": data}});
+        let response = self.request(id, "tools/call", params).await;
+        response["result"]["structuredContent"].clone()
+    }
+}
+
+#[tokio::test]
+async fn test_sessions_share_one_database_over_http() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("http.db").to_str().unwrap().to_string();
+    init_db(&db_path).unwrap();
+    let container = Arc::new(AppContainer::new(&db_path).unwrap());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}{}", listener.local_addr().unwrap(), MCP_ENDPOINT);
+    tokio::spawn(serve_http(container, listener));
+
+    let first = Session::initialize(&url).await;
+    let second = Session::initialize(&url).await;
+    assert_ne!(first.id, second.id);
+
+    // The second agent writes into a project created by the first
+    let project = first.create_entity(2, "project", json!({"name": "Shop"})).await;
+    let project_id = project["id"].as_str().unwrap();
+    let collection_uri = format!("context://{project_id}/business_rules");
+    let response = first.request(3, "resources/subscribe", json!({"uri": collection_uri})).await;
+    assert!(response.get("error").is_none(), "{response}");
+
+    // Server-initiated notifications arrive on the session's GET stream
+    let events = first
+        .client
+        .get(&url)
+        .header("accept", "text/event-stream")
+        .header(SESSION_HEADER, &first.id)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(events.status(), StatusCode::OK);
+    let mut events = events.bytes_stream();
+
+    second
+        .create_entity(2, "business_rule", json!({"project_id": project_id, "rule_name": "Refund window"}))
+        .await;
+
+    let notification = tokio::time::timeout(Duration::from_secs(5), async {
+        let mut body = String::new();
+        loop {
+            let chunk = events.next().await.expect("event stream closed").unwrap();
+            body.push_str(&String::from_utf8_lossy(&chunk));
+            if let Some(message) = sse_messages(&body)
+                .into_iter()
+                .find(|m| m["method"] == "notifications/resources/updated")
+            {
+                return message;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for the notification");
+    assert_eq!(notification["params"]["uri"], collection_uri.as_str());
+
+    let response = first.request(4, "resources/read", json!({"uri": collection_uri})).await;
+    assert!(response["result"]["contents"][0]["text"].as_str().unwrap().contains("Refund window"));
+
+    // Requests without a known session are rejected
+    let response = reqwest::Client::new()
+        .post(&url)
+        .header("accept", "application/json, text/event-stream")
+        .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_client_error());
+
+    let response = second.client.delete(&url).header(SESSION_HEADER, &second.id).send().await.unwrap();
+    assert!(response.status().is_success());
+    let response = second.post(json!({"jsonrpc": "2.0", "id": 3, "method": "tools/list"})).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
@@ -109,3 +109,56 @@ async fn test_framework_component_operations() {
     assert!(components.is_ok(), "Component listing should succeed");
     assert_eq!(components.unwrap().len(), 1, "Should have one component");
 }
+
+#[tokio::test]
+async fn test_query_context_uses_task_type_and_components() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let db_path_str = db_path.to_str().unwrap();
+    let container = AppContainer::new(db_path_str).unwrap();
+    let project = container.project_service.create_project("Shop", None, None).await.unwrap();
+    let project_id = project.id.as_str();
+
+    // checkout_form depends on payment_client, which has its own rules
+    let mut form = container
+        .framework_service
+        .create_component(project_id, "checkout_form", "widget", "presentation", None, None)
+        .await
+        .unwrap();
+    form.dependencies = vec!["payment_client".to_string()];
+    container.framework_service.update_component(&form).await.unwrap();
+
+    let crud = &container.context_crud_service;
+    crud.create_business_rule(project_id, "Checkout needs an account", None, Some("checkout")).await.unwrap();
+    crud.create_business_rule(project_id, "Retry declined cards once", None, Some("payment_client")).await.unwrap();
+    crud.create_business_rule(project_id, "Reports run nightly", None, Some("reporting")).await.unwrap();
+
+    let extended = &container.extended_context_crud_service;
+    extended.create_security_policy(project_id, "Never log card numbers", Some("payment_client")).await.unwrap();
+    extended.create_security_policy(project_id, "Rate limit public endpoints", Some("api_gateway")).await.unwrap();
+    extended.create_project_convention(project_id, Some("naming"), Some("snake_case files")).await.unwrap();
+    extended.create_project_convention(project_id, Some("testing"), Some("One test file per module")).await.unwrap();
+
+    let components = vec!["checkout_form".to_string()];
+    let result = container
+        .context_query_service
+        .query_context(project_id, "checkout", "implement", &components)
+        .await
+        .unwrap();
+    let mut rules: Vec<&str> = result.business_rules.iter().map(|r| r.rule_name.as_str()).collect();
+    rules.sort();
+    assert_eq!(rules, ["Checkout needs an account", "Retry declined cards once"]);
+    let policies: Vec<&str> = result.security_policies.iter().map(|p| p.policy_name.as_str()).collect();
+    assert_eq!(policies, ["Never log card numbers"]);
+    assert!(result.project_conventions.is_empty());
+
+    let api = container.context_query_service.query_context(project_id, "checkout", "api", &[]).await.unwrap();
+    assert_eq!(api.security_policies.len(), 2);
+    assert!(api.project_conventions.is_empty());
+
+    let testing = container.context_query_service.query_context(project_id, "checkout", "testing", &[]).await.unwrap();
+    assert_eq!(testing.project_conventions.len(), 2);
+    assert!(testing.security_policies.is_empty());
+    let rules: Vec<&str> = testing.business_rules.iter().map(|r| r.rule_name.as_str()).collect();
+    assert_eq!(rules, ["Checkout needs an account"]);
+}
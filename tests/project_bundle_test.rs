@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use context_server_rs::api::{ToolContext, ToolRegistry};
+use context_server_rs::container::AppContainer;
+use context_server_rs::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem};
+use rmcp::model::JsonObject;
+use rusqlite::Connection;
+use serde_json::{json, Value};
+use tempfile::{tempdir, TempDir};
+
+fn setup() -> (TempDir, ToolContext) {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("bundle.db");
+    let container = Arc::new(AppContainer::new(db_path.to_str().unwrap()).unwrap());
+    (dir, ToolContext::new(container, Arc::new(ToolRegistry::new())))
+}
+
+fn arguments(value: Value) -> Option<JsonObject> {
+    value.as_object().cloned()
+}
+
+async fn call(tools: &ToolContext, name: &str, args: Value) -> Value {
+    let result = tools.call_tool(name, arguments(args)).await.unwrap();
+    result.structured_content.expect("tools return structured content")
+}
+
+async fn create(tools: &ToolContext, entity_type: &str, data: Value) -> String {
+    let entity = call(tools, "create_entity", json!({"entity_type": entity_type, "data": data})).await;
+    entity["id"].as_str().unwrap().to_string()
+}
+
+async fn count(tools: &ToolContext, entity_type: &str, project_id: Option<&str>) -> usize {
+    let listed = call(tools, "list_entities", json!({"entity_type": entity_type, "project_id": project_id})).await;
+    listed["items"].as_array().unwrap().len()
+}
+
+#[tokio::test]
+async fn test_exported_project_imports_as_a_copy() {
+    let (_dir, tools) = setup();
+    let project_id = create(&tools, "project", json!({"name": "Shop"})).await;
+    create(&tools, "business_rule", json!({"project_id": project_id, "rule_name": "Refunds"})).await;
+    create(&tools, "security_policy", json!({"project_id": project_id, "policy_name": "Secrets"})).await;
+    let deleted = create(&tools, "business_rule", json!({"project_id": project_id, "rule_name": "Old"})).await;
+    call(&tools, "delete_entity", json!({"entity_type": "business_rule", "id": deleted})).await;
+
+    let export = call(&tools, "export_project_context", json!({"project_id": project_id, "format": "yaml"})).await;
+    assert_eq!(export["rows"]["business_rules"], 1);
+    assert_eq!(export["rows"]["security_policies"], 1);
+    let bundle = export["bundle"].as_str().unwrap().to_string();
+    assert!(bundle.starts_with("format_version: 1"));
+
+    let preview = call(&tools, "import_project_context", json!({"bundle": bundle, "remap_ids": true, "dry_run": true})).await;
+    assert_eq!(preview["tables"]["business_rules"]["created"], 1);
+    assert_eq!(count(&tools, "project", None).await, 1);
+
+    let report = call(&tools, "import_project_context", json!({"bundle": bundle, "remap_ids": true})).await;
+    let copy_id = report["project_id"].as_str().unwrap();
+    assert_ne!(copy_id, project_id);
+    assert_eq!(count(&tools, "project", None).await, 2);
+    assert_eq!(count(&tools, "business_rule", Some(copy_id)).await, 1);
+    assert_eq!(count(&tools, "security_policy", Some(copy_id)).await, 1);
+
+    // Importing over the original changes nothing by default
+    let again = call(&tools, "import_project_context", json!({"bundle": bundle})).await;
+    assert_eq!(again["tables"]["business_rules"]["skipped"], 1);
+    assert_eq!(again["tables"]["business_rules"]["created"], 0);
+}
+
+#[tokio::test]
+async fn test_overwritten_context_items_stay_searchable() {
+    let (dir, tools) = setup();
+    let project_id = create(&tools, "project", json!({"name": "Shop"})).await;
+    let repository = tools.container().enhanced_context_repository.clone();
+    let content = ContextContent {
+        content_type: ContextType::BusinessRule,
+        title: "Refund window".to_string(),
+        description: "Refunds are accepted within fourteen days".to_string(),
+        data: json!({}),
+        source_file: None,
+        source_line: None,
+    };
+    let mut item = repository.create_context(&EnhancedContextItem::new(project_id.clone(), content)).await.unwrap();
+
+    let export = call(&tools, "export_project_context", json!({"project_id": project_id})).await;
+    let bundle = export["bundle"].as_str().unwrap().to_string();
+
+    item.content.description = "Refunds are accepted within thirty days".to_string();
+    repository.update_context(&item).await.unwrap();
+
+    let report = call(&tools, "import_project_context", json!({"bundle": bundle, "conflict_policy": "overwrite"})).await;
+    assert_eq!(report["tables"]["enhanced_context_items"]["overwritten"], 1);
+
+    let found = repository.search_contexts(Some(&project_id), "fourteen", 10).await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert!(repository.search_contexts(Some(&project_id), "thirty", 10).await.unwrap().is_empty());
+
+    // The replaced text must be gone from the index itself, not just unreachable
+    let conn = Connection::open(dir.path().join("bundle.db")).unwrap();
+    let stale: i64 = conn
+        .query_row("SELECT COUNT(*) FROM enhanced_context_fts WHERE enhanced_context_fts MATCH 'thirty'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(stale, 0);
+}
@@ -0,0 +1,85 @@
+use context_server_rs::container::AppContainer;
+use context_server_rs::db::migrations::{applied_migrations, latest_version, run_migrations, schema_status, MIGRATIONS};
+use rusqlite::Connection;
+use tempfile::tempdir;
+
+const BASELINE_SCHEMA: &str = include_str!("fixtures/baseline_schema.sql");
+
+/// Columns and indexes of every table, ignoring the SQL text that created them
+fn schema_shape(conn: &Connection) -> Vec<String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT m.type || ' ' || m.name || ' ' || COALESCE(
+                 (SELECT group_concat(c.name || ':' || c.type || ':' || c.\"notnull\" || ':' || c.pk, ',')
+                  FROM pragma_table_info(m.name) c), '')
+             FROM sqlite_master m
+             WHERE m.name NOT LIKE 'sqlite_%' AND m.name != 'schema_migrations'
+             ORDER BY m.type, m.name",
+        )
+        .unwrap();
+    let shape = stmt.query_map([], |row| row.get(0)).unwrap().collect::<Result<Vec<String>, _>>().unwrap();
+    shape
+}
+
+fn baseline_database() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(BASELINE_SCHEMA).unwrap();
+    conn
+}
+
+#[test]
+fn test_baseline_database_is_upgraded_in_place() {
+    let conn = baseline_database();
+    assert_eq!(schema_status(&conn).unwrap().current_version, 0);
+
+    let applied = run_migrations(&conn).unwrap();
+    assert_eq!(applied.len(), MIGRATIONS.len());
+    let recorded: Vec<u32> = applied_migrations(&conn).unwrap().iter().map(|m| m.version).collect();
+    assert_eq!(recorded, (1..=latest_version()).collect::<Vec<_>>());
+    let user_version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+    assert_eq!(user_version, latest_version());
+
+    let rule: String = conn
+        .query_row("SELECT rule_name FROM business_rules WHERE id = 'rule-refunds'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(rule, "Refund window");
+    let specs: i64 = conn.query_row("SELECT COUNT(*) FROM specifications", [], |row| row.get(0)).unwrap();
+    assert_eq!(specs, 1);
+    let matched: String = conn
+        .query_row(
+            "SELECT i.id FROM enhanced_context_fts f JOIN enhanced_context_items i ON i.rowid = f.rowid
+             WHERE enhanced_context_fts MATCH 'refund'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(matched, "rule-refunds");
+
+    assert!(run_migrations(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn test_upgraded_and_fresh_databases_have_the_same_schema() {
+    let upgraded = baseline_database();
+    run_migrations(&upgraded).unwrap();
+    let fresh = Connection::open_in_memory().unwrap();
+    run_migrations(&fresh).unwrap();
+
+    assert_eq!(schema_shape(&upgraded), schema_shape(&fresh));
+}
+
+#[tokio::test]
+async fn test_container_opens_an_upgraded_database() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("context.db");
+    let path = path.to_str().unwrap();
+    Connection::open(path).unwrap().execute_batch(BASELINE_SCHEMA).unwrap();
+
+    let container = AppContainer::new(path).unwrap();
+    container.prepare_search().await.unwrap();
+
+    let rules = container.context_crud_service.list_business_rules("shop").await.unwrap();
+    assert_eq!(rules.len(), 1);
+    let status = schema_status(&Connection::open(path).unwrap()).unwrap();
+    assert!(status.pending.is_empty());
+}
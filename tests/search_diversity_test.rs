@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use context_server_rs::db::init::init_db;
+use context_server_rs::models::embedding::{EmbeddingConfig, ModelType, RankingMethod, VectorSearchQuery};
+use context_server_rs::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem};
+use context_server_rs::repositories::embedding_repository::SqliteEmbeddingRepository;
+use context_server_rs::services::semantic_search_service::{
+    SemanticSearchConfig, SemanticSearchService, SemanticSearchServiceImpl,
+};
+use context_server_rs::services::{EmbeddingService, EmbeddingServiceFactory};
+use tempfile::{tempdir, TempDir};
+
+fn context(id: &str, content_type: ContextType, title: &str, description: &str) -> EnhancedContextItem {
+    let content = ContextContent {
+        content_type,
+        title: title.to_string(),
+        description: description.to_string(),
+        data: serde_json::json!({}),
+        source_file: None,
+        source_line: None,
+    };
+    let mut context = EnhancedContextItem::new("project-1".to_string(), content);
+    context.id = id.to_string();
+    context
+}
+
+async fn search_service(dir: &TempDir) -> SemanticSearchServiceImpl {
+    let db_path = dir.path().join("diversity.db");
+    let conn = Arc::new(tokio::sync::Mutex::new(init_db(db_path.to_str().unwrap()).unwrap()));
+    let repository = Arc::new(SqliteEmbeddingRepository::new(conn));
+    repository.initialize().await.unwrap();
+
+    let embedding_config = EmbeddingConfig {
+        model_name: "bm25-test".to_string(),
+        model_type: ModelType::Custom("bm25".to_string()),
+        embedding_dimension: 256,
+        ..Default::default()
+    };
+    let embedding_service: Arc<dyn EmbeddingService> =
+        Arc::from(EmbeddingServiceFactory::create_initialized_service(embedding_config.clone()).await.unwrap());
+
+    SemanticSearchServiceImpl::new(
+        embedding_service,
+        repository,
+        SemanticSearchConfig {
+            embedding_config,
+            enable_intent_detection: false,
+            ..Default::default()
+        },
+    )
+}
+
+#[tokio::test]
+async fn test_mmr_surfaces_distinct_results() {
+    let dir = tempdir().unwrap();
+    let search = search_service(&dir).await;
+
+    for i in 0..5 {
+        search
+            .index_context(&context(
+                &format!("rule-{}", i),
+                ContextType::BusinessRule,
+                "Authentication required",
+                &format!("Users must complete authentication before checkout, variant {}", i),
+            ))
+            .await
+            .unwrap();
+    }
+    search
+        .index_context(&context(
+            "policy-1",
+            ContextType::SecurityPolicy,
+            "Authentication tokens",
+            "Authentication tokens are signed and rotated by the identity service",
+        ))
+        .await
+        .unwrap();
+    search
+        .index_context(&context(
+            "adr-1",
+            ContextType::ArchitecturalDecision,
+            "Use OpenID Connect",
+            "Authentication is delegated to an OpenID Connect provider",
+        ))
+        .await
+        .unwrap();
+
+    let query = VectorSearchQuery {
+        query_text: "users authentication before checkout".to_string(),
+        similarity_threshold: 0.0,
+        max_results: 3,
+        ..Default::default()
+    };
+
+    let relevance_only = search.search(&query).await.unwrap();
+    let ids: Vec<_> = relevance_only.iter().map(|r| r.vector_result.context_id.as_str()).collect();
+    assert!(ids.iter().all(|id| id.starts_with("rule-")), "{:?}", ids);
+
+    let diverse = search.search(&query.clone().with_diversity(0.7)).await.unwrap();
+    let ids: Vec<_> = diverse.iter().map(|r| r.vector_result.context_id.as_str()).collect();
+    assert_eq!(ids.len(), 3);
+    // The most relevant result is still first, near-duplicates make way
+    assert!(ids[0].starts_with("rule-"));
+    assert_eq!(ids.iter().filter(|id| id.starts_with("rule-")).count(), 1, "{:?}", ids);
+    assert!(diverse.iter().enumerate().all(|(i, r)| r.vector_result.rank == i + 1));
+    assert!(diverse[0].search_metadata.ranking_method_used.starts_with("mmr"));
+
+    // Diversity 0 is plain relevance ranking
+    assert_eq!(query.clone().with_diversity(0.0).ranking_method, RankingMethod::CosineSimilarity);
+    let same = search
+        .search(&VectorSearchQuery {
+            ranking_method: RankingMethod::MaximalMarginalRelevance { diversity: 0.0 },
+            ..query.clone()
+        })
+        .await
+        .unwrap();
+    let same_ids: Vec<_> = same.iter().map(|r| r.vector_result.context_id.clone()).collect();
+    let relevance_ids: Vec<_> = relevance_only.iter().map(|r| r.vector_result.context_id.clone()).collect();
+    assert_eq!(same_ids, relevance_ids);
+}
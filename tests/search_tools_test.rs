@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use context_server_rs::api::{SearchTools, ToolContext, ToolRegistry};
+use context_server_rs::container::AppContainer;
+use context_server_rs::db::init::init_db;
+use rmcp::model::{CallToolResult, ErrorCode};
+use serde_json::{json, Value};
+use tempfile::{tempdir, TempDir};
+
+async fn setup() -> (TempDir, AppContainer, SearchTools) {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("search.db");
+    let db_path_str = db_path.to_str().unwrap();
+    init_db(db_path_str).unwrap();
+
+    let container = AppContainer::new(db_path_str).unwrap();
+    container.prepare_search().await.unwrap();
+    let tools = SearchTools::new(
+        container.semantic_search_service.clone(),
+        container.hybrid_search_service.clone(),
+        container.search_index_manager.clone(),
+        container.enhanced_context_repository.clone(),
+    );
+    (dir, container, tools)
+}
+
+async fn create_rule(
+    container: &AppContainer,
+    tools: &SearchTools,
+    project_id: &str,
+    name: &str,
+    description: &str,
+    domain_area: &str,
+) -> String {
+    let rule = container
+        .context_crud_service
+        .create_business_rule(project_id, name, Some(description), Some(domain_area))
+        .await
+        .unwrap();
+    let entity = serde_json::to_value(&rule).unwrap();
+    let item = SearchTools::context_item_for_entity("business_rule", &entity).unwrap();
+    tools.index_context_item(item).await.unwrap();
+    rule.id
+}
+
+/// Structured content of a result; list results are wrapped in `result`
+fn json_content(result: &CallToolResult) -> Value {
+    let content = result.structured_content.clone().unwrap();
+    content.get("result").cloned().unwrap_or(content)
+}
+
+#[tokio::test]
+async fn test_search_tools_find_indexed_business_rules() {
+    let (_dir, container, tools) = setup().await;
+    let project = container.project_service.create_project("Shop", None, None).await.unwrap();
+
+    let rotation = create_rule(
+        &container,
+        &tools,
+        &project.id,
+        "Refresh token rotation",
+        "Refresh tokens are rotated on every use and reuse revokes the token family",
+        "authentication",
+    )
+    .await;
+    let lockout = create_rule(
+        &container,
+        &tools,
+        &project.id,
+        "Account lockout",
+        "Accounts lock for fifteen minutes after five failed password attempts",
+        "authentication",
+    )
+    .await;
+    create_rule(
+        &container,
+        &tools,
+        &project.id,
+        "Invoice rounding",
+        "Invoice totals are rounded half to even in the customer currency",
+        "billing",
+    )
+    .await;
+
+    let semantic = tools
+        .handle_tool_call("semantic_search", json!({"query": "refresh token rotation", "project_id": project.id}))
+        .await
+        .unwrap();
+    let results = json_content(&semantic);
+    assert_eq!(results[0]["vector_result"]["context_id"], rotation.as_str());
+    assert_eq!(results[0]["context_item"]["content"]["title"], "Refresh token rotation");
+
+    let hybrid = tools
+        .handle_tool_call("hybrid_search", json!({"project_id": project.id, "query": "token rotation"}))
+        .await
+        .unwrap();
+    let hybrid = json_content(&hybrid);
+    assert_eq!(hybrid["ranked_results"][0]["context_id"], rotation.as_str());
+    assert_eq!(hybrid["total_results"], hybrid["ranked_results"].as_array().unwrap().len());
+
+    // Query filters narrow the results
+    let filtered = tools
+        .handle_tool_call(
+            "hybrid_search",
+            json!({"project_id": project.id, "query": "\"failed password\" type:business_rule -tag:legacy"}),
+        )
+        .await
+        .unwrap();
+    let filtered = json_content(&filtered);
+    let ids: Vec<&str> = filtered["ranked_results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["context_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec![lockout.as_str()]);
+
+    let similar = tools
+        .handle_tool_call("find_similar_contexts", json!({"context_id": rotation, "max_results": 5}))
+        .await
+        .unwrap();
+    let similar = json_content(&similar);
+    assert!(similar.as_array().unwrap().iter().all(|r| r["vector_result"]["context_id"] != rotation.as_str()));
+
+    let health = tools.handle_tool_call("index_health", json!({"project_id": project.id})).await.unwrap();
+    let health = json_content(&health);
+    assert_eq!(health["indexed_contexts"], 3);
+
+    let suggestions = tools.handle_tool_call("suggest_queries", json!({"partial_query": "token"})).await.unwrap();
+    assert!(!json_content(&suggestions).as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_removed_context_items_leave_the_index() {
+    let (_dir, container, tools) = setup().await;
+    let project = container.project_service.create_project("Shop", None, None).await.unwrap();
+    let rule = create_rule(
+        &container,
+        &tools,
+        &project.id,
+        "Refund window",
+        "Refunds are possible within fourteen days of payment",
+        "billing",
+    )
+    .await;
+
+    container.context_crud_service.delete_business_rule(&rule).await.unwrap();
+    tools.remove_context_item(&rule).await.unwrap();
+
+    let result = tools
+        .handle_tool_call("semantic_search", json!({"query": "refund payment", "project_id": project.id}))
+        .await
+        .unwrap();
+    assert!(json_content(&result).as_array().unwrap().is_empty());
+    assert!(container.enhanced_context_repository.find_context_by_id(&rule).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_invalid_search_query_is_rejected() {
+    let (_dir, _container, tools) = setup().await;
+
+    let error = tools
+        .handle_tool_call("semantic_search", json!({"query": "tokens priority:urgent"}))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    assert!(error.data.unwrap()["column"].is_number());
+
+    let error = tools
+        .handle_tool_call("hybrid_search", json!({"project_id": "p", "query": "type:business_rule"}))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_every_context_entity_type_is_mirrored_into_search() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("search.db");
+    let container = Arc::new(AppContainer::new(db_path.to_str().unwrap()).unwrap());
+    let tools = ToolContext::new(container, Arc::new(ToolRegistry::new()));
+    let call = |name: &'static str, args: Value| {
+        let tools = &tools;
+        async move { tools.call_tool(name, args.as_object().cloned()).await.unwrap().structured_content.unwrap() }
+    };
+
+    let project = call("create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+    let top_result = |query: &'static str| {
+        let search = call("semantic_search", json!({"query": query, "project_id": project["id"]}));
+        async move { search.await["result"][0]["vector_result"]["context_id"].as_str().map(str::to_string) }
+    };
+
+    let mut ids = Vec::new();
+    for (entity_type, data, query) in [
+        ("security_policy", json!({"policy_name": "Secret rotation", "requirements": "Rotate vault secrets quarterly"}), "secret rotation"),
+        ("project_convention", json!({"convention_type": "naming", "convention_rule": "Repositories end in Repository"}), "repository naming"),
+        ("feature_context", json!({"feature_name": "Invoice export", "business_purpose": "Export invoices as PDF"}), "invoice export"),
+    ] {
+        let mut data = data;
+        data["project_id"] = project["id"].clone();
+        let entity = call("create_entity", json!({"entity_type": entity_type, "data": data})).await;
+        let id = entity["id"].as_str().unwrap().to_string();
+        assert_eq!(top_result(query).await.as_deref(), Some(id.as_str()), "{entity_type} is searchable");
+        ids.push(id);
+    }
+
+    call("delete_entity", json!({"entity_type": "security_policy", "id": ids[0]})).await;
+    assert_ne!(top_result("secret rotation").await.as_deref(), Some(ids[0].as_str()));
+}
@@ -0,0 +1,35 @@
+use std::sync::{Arc, Mutex};
+
+use context_server_rs::infrastructure::SqliteSpecificationRepository;
+use context_server_rs::models::specification::{ProjectSpecification, SpecContent, SpecFormat, SpecStatus, SpecType};
+use context_server_rs::repositories::SpecificationRepository;
+use rusqlite::Connection;
+
+#[tokio::test]
+async fn test_specification_columns_round_trip() {
+    let repository = SqliteSpecificationRepository::new(Arc::new(Mutex::new(Connection::open_in_memory().unwrap())));
+    repository.initialize_tables().unwrap();
+
+    let mut content = SpecContent::new(SpecFormat::Markdown, "# Checkout".to_string());
+    content.parsed_sections.insert("overview".to_string(), "Customers pay by card".to_string());
+    content.metadata.insert("source".to_string(), serde_json::json!("import"));
+    let mut spec = ProjectSpecification::new("shop".to_string(), SpecType::Feature, "Checkout".to_string(), content);
+    spec.status = SpecStatus::Approved;
+    spec.version = 3;
+    spec.file_path = Some("specs/checkout.md".to_string());
+    spec.metadata.tags = vec!["payments".to_string()];
+    repository.create_specification(&spec).await.unwrap();
+
+    let stored = repository.find_specification_by_id(&spec.id).await.unwrap().unwrap();
+    assert_eq!(stored.content.parsed_sections.get("overview").map(String::as_str), Some("Customers pay by card"));
+    assert_eq!(stored.content.metadata.get("source"), Some(&serde_json::json!("import")));
+    assert!(matches!(stored.status, SpecStatus::Approved));
+    assert_eq!(stored.version, 3);
+    assert_eq!(stored.file_path.as_deref(), Some("specs/checkout.md"));
+    assert_eq!(stored.created_at.timestamp(), spec.created_at.timestamp());
+    assert_eq!(stored.updated_at.timestamp(), spec.updated_at.timestamp());
+    assert_eq!(stored.metadata.tags, vec!["payments".to_string()]);
+
+    let listed = repository.find_specifications_by_project("shop").await.unwrap();
+    assert_eq!(listed.len(), 1);
+}
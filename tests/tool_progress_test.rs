@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use context_server_rs::api::tool_registry::REQUEST_CANCELLED;
+use context_server_rs::api::{ToolCall, ToolContext, ToolRegistry};
+use context_server_rs::container::AppContainer;
+use context_server_rs::db::init::init_db;
+use context_server_rs::EnhancedContextMcpServer;
+use rmcp::ServiceExt;
+use serde_json::{json, Value};
+use tempfile::{tempdir, TempDir};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream, Lines, ReadHalf, WriteHalf};
+use tokio_util::sync::CancellationToken;
+
+fn container() -> (TempDir, Arc<AppContainer>) {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("progress.db");
+    let db_path_str = db_path.to_str().unwrap();
+    init_db(db_path_str).unwrap();
+    (dir, Arc::new(AppContainer::new(db_path_str).unwrap()))
+}
+
+/// Raw JSON-RPC client talking to the server over an in-memory stream
+struct Client {
+    lines: Lines<BufReader<ReadHalf<DuplexStream>>>,
+    writer: WriteHalf<DuplexStream>,
+}
+
+impl Client {
+    async fn connect(container: Arc<AppContainer>) -> Self {
+        let (client_stream, server_stream) = tokio::io::duplex(1 << 20);
+        tokio::spawn(async move {
+            let service = EnhancedContextMcpServer::with_container(container).serve(server_stream).await.unwrap();
+            let _ = service.waiting().await;
+        });
+        let (reader, writer) = tokio::io::split(client_stream);
+        let mut client = Self { lines: BufReader::new(reader).lines(), writer };
+
+        client
+            .send(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {"protocolVersion": "2025-03-26", "capabilities": {}, "clientInfo": {"name": "test", "version": "1"}}
+            }))
+            .await;
+        client.receive_until(1).await;
+        client.send(json!({"jsonrpc": "2.0", "method": "notifications/initialized"})).await;
+        client
+    }
+
+    async fn send(&mut self, message: Value) {
+        let mut line = serde_json::to_vec(&message).unwrap();
+        line.push(b'\n');
+        self.writer.write_all(&line).await.unwrap();
+    }
+
+    /// Messages up to and including the response to request `id`
+    async fn receive_until(&mut self, id: u64) -> Vec<Value> {
+        let mut messages = Vec::new();
+        while let Some(line) = self.lines.next_line().await.unwrap() {
+            let message: Value = serde_json::from_str(&line).unwrap();
+            let done = message["id"] == id;
+            messages.push(message);
+            if done {
+                return messages;
+            }
+        }
+        panic!("connection closed before the response to request {id}");
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_operations_report_progress_per_entity() {
+    let (_dir, container) = container();
+    let project = container.project_service.create_project("Shop", None, None).await.unwrap();
+    let mut client = Client::connect(container).await;
+
+    let rules: Vec<Value> = ["Refunds", "Discounts", "Shipping"]
+        .iter()
+        .map(|name| json!({"project_id": project.id, "rule_name": name}))
+        .collect();
+    client
+        .send(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "_meta": {"progressToken": "bulk-1"},
+                "name": "bulk_operations",
+                "arguments": {"operation": "create", "entity_type": "business_rule", "data": rules}
+            }
+        }))
+        .await;
+    let messages = client.receive_until(2).await;
+
+    let progress: Vec<(f64, f64)> = messages
+        .iter()
+        .filter(|m| m["method"] == "notifications/progress")
+        .map(|m| {
+            assert_eq!(m["params"]["progressToken"], "bulk-1");
+            (m["params"]["progress"].as_f64().unwrap(), m["params"]["total"].as_f64().unwrap())
+        })
+        .collect();
+    assert_eq!(progress, [(1.0, 3.0), (2.0, 3.0), (3.0, 3.0)]);
+
+    let response = messages.last().unwrap();
+    let created = &response["result"]["structuredContent"]["result"];
+    assert_eq!(created.as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_cancelled_tools_stop_before_writing() {
+    let (_dir, container) = container();
+    let project = container.project_service.create_project("Shop", None, None).await.unwrap();
+    let tools = ToolContext::new(container.clone(), Arc::new(ToolRegistry::new()));
+    tools
+        .call_tool(
+            "create_entity",
+            json!({"entity_type": "business_rule", "data": {"project_id": project.id, "rule_name": "Refunds"}})
+                .as_object()
+                .cloned(),
+        )
+        .await
+        .unwrap();
+
+    let cancellation = CancellationToken::new();
+    cancellation.cancel();
+    let cancelled = tools.for_call(ToolCall::new(cancellation));
+
+    let bulk = cancelled
+        .call_tool(
+            "bulk_operations",
+            json!({
+                "operation": "create",
+                "entity_type": "business_rule",
+                "data": [{"project_id": project.id, "rule_name": "Discounts"}]
+            })
+            .as_object()
+            .cloned(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(bulk.code, REQUEST_CANCELLED);
+    assert_eq!(bulk.data, Some(json!({"processed": 0, "total": 1, "results": []})));
+    let rules = container.context_crud_service.list_business_rules(&project.id).await.unwrap();
+    assert_eq!(rules.len(), 1, "nothing is written after cancellation");
+
+    let rebuild = cancelled
+        .call_tool("rebuild_project_index", json!({"project_id": project.id}).as_object().cloned())
+        .await
+        .unwrap_err();
+    assert_eq!(rebuild.code, REQUEST_CANCELLED);
+    assert_eq!(rebuild.data.unwrap()["reindexed"], 0);
+
+    let rebuilt = tools
+        .call_tool("rebuild_project_index", json!({"project_id": project.id}).as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(rebuilt.structured_content.unwrap()["reindexed"], 1);
+}
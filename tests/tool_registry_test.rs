@@ -0,0 +1,288 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use context_server_rs::api::{ToolContext, ToolRegistry};
+use context_server_rs::container::AppContainer;
+use context_server_rs::db::init::init_db;
+use rmcp::model::{CallToolResult, ErrorCode, JsonObject};
+use serde_json::{json, Value};
+use tempfile::{tempdir, TempDir};
+
+fn setup() -> (TempDir, ToolContext) {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("tools.db");
+    let db_path_str = db_path.to_str().unwrap();
+    init_db(db_path_str).unwrap();
+
+    let container = Arc::new(AppContainer::new(db_path_str).unwrap());
+    (dir, ToolContext::new(container, Arc::new(ToolRegistry::new())))
+}
+
+fn arguments(value: Value) -> Option<JsonObject> {
+    value.as_object().cloned()
+}
+
+fn structured_content(result: &CallToolResult) -> Value {
+    result.structured_content.clone().expect("tools return structured content")
+}
+
+async fn call(tools: &ToolContext, name: &str, args: Value) -> Value {
+    structured_content(&tools.call_tool(name, arguments(args)).await.unwrap())
+}
+
+#[test]
+fn test_every_tool_has_a_generated_object_schema() {
+    let registry = ToolRegistry::new();
+    let tools = registry.tools();
+
+    let names: HashSet<&str> = tools.iter().map(|t| t.name.as_ref()).collect();
+    assert_eq!(names.len(), tools.len(), "tool names are unique");
+    for name in ["query_context", "get_entity", "list_entities", "semantic_search", "track_tasks_progress"] {
+        assert!(names.contains(name), "{name} is listed");
+    }
+    for tool in &tools {
+        assert_eq!(tool.input_schema.get("type"), Some(&json!("object")), "{}", tool.name);
+        let output_schema = tool.output_schema.as_ref().expect("every tool declares its output");
+        assert_eq!(output_schema.get("type"), Some(&json!("object")), "{}", tool.name);
+    }
+
+    // Report types keep their fields; lists are wrapped in `result`
+    let output = |name: &str| tools.iter().find(|t| t.name == name).unwrap().output_schema.clone().unwrap();
+    assert!(output("query_context")["properties"].get("security_policies").is_some());
+    assert!(output("generate_specification_health_report")["properties"].get("executive_summary").is_some());
+    assert_eq!(output("suggest_queries")["required"], json!(["result"]));
+
+    // get_entity and list_entities share one entity type enum
+    let entity_types = |name: &str| {
+        let tool = tools.iter().find(|t| t.name == name).unwrap();
+        serde_json::to_string(&tool.input_schema["definitions"]["EntityType"]).unwrap()
+    };
+    assert_eq!(entity_types("get_entity"), entity_types("list_entities"));
+    assert!(entity_types("get_entity").contains("security_policy"));
+}
+
+#[tokio::test]
+async fn test_server_capabilities_list_every_registered_tool() {
+    let (_dir, tools) = setup();
+
+    let capabilities = call(&tools, "get_server_capabilities", json!({})).await;
+    let listed: HashSet<String> = capabilities["mcp_tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap().to_string())
+        .collect();
+    let registered: HashSet<String> = tools.registry().tools().iter().map(|t| t.name.to_string()).collect();
+    assert_eq!(listed, registered);
+
+    let get_entity = capabilities["mcp_tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "get_entity")
+        .unwrap();
+    assert_eq!(get_entity["required_params"], json!(["entity_type", "id"]));
+}
+
+#[tokio::test]
+async fn test_invalid_arguments_are_rejected_before_the_handler_runs() {
+    let (_dir, tools) = setup();
+
+    let missing = tools.call_tool("get_entity", arguments(json!({"entity_type": "project"}))).await;
+    assert_eq!(missing.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+
+    let unknown_type = tools.call_tool("get_entity", arguments(json!({"entity_type": "widget", "id": "1"}))).await;
+    assert_eq!(unknown_type.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+
+    let unknown_tool = tools.call_tool("no_such_tool", None).await;
+    assert_eq!(unknown_tool.unwrap_err().code, ErrorCode::METHOD_NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_entity_tools_support_every_entity_type() {
+    let (_dir, tools) = setup();
+    let project = call(&tools, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+    let project_id = project["id"].as_str().unwrap();
+
+    let policy = call(
+        &tools,
+        "create_entity",
+        json!({
+            "entity_type": "security_policy",
+            "data": {"project_id": project_id, "policy_name": "Rotate secrets", "policy_area": "secrets"}
+        }),
+    )
+    .await;
+    let policy_id = policy["id"].as_str().unwrap();
+
+    let fetched = call(&tools, "get_entity", json!({"entity_type": "security_policy", "id": policy_id})).await;
+    assert_eq!(fetched["result"]["policy_name"], "Rotate secrets");
+
+    let updated = call(
+        &tools,
+        "update_entity",
+        json!({"entity_type": "security_policy", "id": policy_id, "data": {"policy_area": "credentials"}}),
+    )
+    .await;
+    assert_eq!(updated["policy_name"], "Rotate secrets");
+    assert_eq!(updated["policy_area"], "credentials");
+
+    call(
+        &tools,
+        "create_entity",
+        json!({
+            "entity_type": "feature_context",
+            "data": {"project_id": project_id, "feature_name": "Checkout"}
+        }),
+    )
+    .await;
+    let features = call(&tools, "list_entities", json!({"entity_type": "feature_context", "project_id": project_id})).await;
+    assert_eq!(features["items"].as_array().unwrap().len(), 1);
+
+    let deleted = call(&tools, "delete_entity", json!({"entity_type": "security_policy", "id": policy_id})).await;
+    assert_eq!(deleted, json!({"deleted": true, "policy_id": policy_id}));
+    let missing = call(&tools, "get_entity", json!({"entity_type": "security_policy", "id": policy_id})).await;
+    assert!(missing["result"].is_null());
+
+    let invalid = tools
+        .call_tool("create_entity", arguments(json!({"entity_type": "business_rule", "data": {"project_id": project_id}})))
+        .await;
+    assert_eq!(invalid.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_results_render_as_markdown_text() {
+    let (_dir, tools) = setup();
+    let project = call(&tools, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+
+    let result = tools
+        .call_tool(
+            "create_entity",
+            arguments(json!({
+                "entity_type": "business_rule",
+                "data": {"project_id": project["id"], "rule_name": "Refunds"}
+            })),
+        )
+        .await
+        .unwrap();
+    let rule = structured_content(&result);
+    let content = result.content.as_ref().unwrap();
+    let text = &content[0].as_text().unwrap().text;
+    assert!(text.contains("- **rule_name**: Refunds\n"), "{text}");
+    assert!(text.contains(&format!("- **id**: {}\n", rule["id"].as_str().unwrap())), "{text}");
+    assert!(!text.contains("null"), "null fields are left out: {text}");
+}
+
+#[tokio::test]
+async fn test_list_tools_page_sort_and_project() {
+    let (_dir, tools) = setup();
+    let project = call(&tools, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+    let project_id = project["id"].as_str().unwrap();
+    for rule_name in ["Refunds", "Discounts", "Shipping", "Taxes", "Invoices"] {
+        call(
+            &tools,
+            "create_entity",
+            json!({
+                "entity_type": "business_rule",
+                "data": {"project_id": project_id, "rule_name": rule_name, "description": "details"}
+            }),
+        )
+        .await;
+    }
+
+    let list = json!({
+        "entity_type": "business_rule",
+        "project_id": project_id,
+        "limit": 2,
+        "sort_by": "rule_name",
+        "sort_order": "desc",
+        "fields": ["rule_name"]
+    });
+    let mut names = Vec::new();
+    let mut cursor = Value::Null;
+    loop {
+        let mut args = list.clone();
+        args["cursor"] = cursor;
+        let page = call(&tools, "list_entities", args).await;
+        for item in page["items"].as_array().unwrap() {
+            let keys: HashSet<&str> = item.as_object().unwrap().keys().map(String::as_str).collect();
+            assert_eq!(keys, HashSet::from(["id", "rule_name"]));
+            names.push(item["rule_name"].as_str().unwrap().to_string());
+        }
+        cursor = page["next_cursor"].clone();
+        if cursor.is_null() {
+            break;
+        }
+    }
+    assert_eq!(names, ["Taxes", "Shipping", "Refunds", "Invoices", "Discounts"]);
+
+    let projects = call(&tools, "list_projects", json!({"fields": ["name"]})).await;
+    assert_eq!(projects["items"], json!([{"id": project_id, "name": "Shop"}]));
+    assert!(projects["next_cursor"].is_null());
+
+    let specs = call(&tools, "list_specifications", json!({"project_id": project_id, "spec_type": "tasks"})).await;
+    assert_eq!(specs, json!({"items": [], "next_cursor": null}));
+
+    let unsortable = tools
+        .call_tool("list_entities", arguments(json!({"entity_type": "project", "sort_by": "description"})))
+        .await;
+    assert_eq!(unsortable.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+    let bad_cursor = tools.call_tool("list_projects", arguments(json!({"cursor": "bogus"}))).await;
+    assert_eq!(bad_cursor.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_build_context_pack_fits_the_token_budget() {
+    let (_dir, tools) = setup();
+    let project = call(&tools, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+    let project_id = project["id"].as_str().unwrap();
+    for (rule_name, description) in [
+        ("Refund window", "Refunds are accepted within 30 days of delivery"),
+        ("Gift wrapping", "Gift wrap is offered at checkout"),
+    ] {
+        call(
+            &tools,
+            "create_entity",
+            json!({
+                "entity_type": "business_rule",
+                "data": {"project_id": project_id, "rule_name": rule_name, "description": description}
+            }),
+        )
+        .await;
+    }
+    call(
+        &tools,
+        "create_entity",
+        json!({
+            "entity_type": "performance_requirement",
+            "data": {"project_id": project_id, "component_area": "refunds", "requirement_type": "latency", "target_value": "200ms"}
+        }),
+    )
+    .await;
+
+    let pack_args = |token_budget: usize| {
+        json!({
+            "project_id": project_id,
+            "task": "Handle refund requests",
+            "token_budget": token_budget,
+            "components": ["refunds"]
+        })
+    };
+    let pack = call(&tools, "build_context_pack", pack_args(1000)).await;
+    let titles: Vec<&str> = pack["items"].as_array().unwrap().iter().map(|i| i["title"].as_str().unwrap()).collect();
+    assert!(titles.contains(&"Refund window"), "{pack}");
+    assert!(titles.contains(&"Performance: refunds"), "performance requirements are candidates: {pack}");
+    let accounted = pack["items"].as_array().unwrap().len() + pack["dropped"].as_array().unwrap().len();
+    assert_eq!(accounted, 3);
+    assert!(pack["tokens_used"].as_u64().unwrap() <= 1000);
+
+    let tight = call(&tools, "build_context_pack", pack_args(15)).await;
+    assert!(tight["tokens_used"].as_u64().unwrap() <= 15);
+    assert!(
+        tight["dropped"].as_array().unwrap().iter().any(|d| d["reason"] == "over_budget"),
+        "{tight}"
+    );
+
+    let empty = tools.call_tool("build_context_pack", arguments(pack_args(0))).await;
+    assert_eq!(empty.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+}
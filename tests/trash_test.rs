@@ -0,0 +1,150 @@
+use std::sync::{Arc, Mutex};
+
+use context_server_rs::api::{ToolContext, ToolRegistry};
+use context_server_rs::container::AppContainer;
+use context_server_rs::infrastructure::SqliteSpecificationRepository;
+use context_server_rs::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem};
+use context_server_rs::models::specification::{
+    AcceptanceCriterion, CriterionType, ProjectSpecification, Requirement, SpecContent, SpecFormat, SpecType,
+};
+use context_server_rs::repositories::SpecificationRepository;
+use rmcp::model::{ErrorCode, JsonObject};
+use rusqlite::Connection;
+use serde_json::{json, Value};
+use tempfile::{tempdir, TempDir};
+
+fn setup() -> (TempDir, ToolContext) {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("trash.db");
+    let container = Arc::new(AppContainer::new(db_path.to_str().unwrap()).unwrap());
+    (dir, ToolContext::new(container, Arc::new(ToolRegistry::new())))
+}
+
+fn arguments(value: Value) -> Option<JsonObject> {
+    value.as_object().cloned()
+}
+
+async fn call(tools: &ToolContext, name: &str, args: Value) -> Value {
+    let result = tools.call_tool(name, arguments(args)).await.unwrap();
+    result.structured_content.expect("tools return structured content")
+}
+
+async fn create(tools: &ToolContext, entity_type: &str, data: Value) -> String {
+    let entity = call(tools, "create_entity", json!({"entity_type": entity_type, "data": data})).await;
+    entity["id"].as_str().unwrap().to_string()
+}
+
+/// Give a project an enhanced context item and a specification with a requirement
+async fn add_specification(dir: &TempDir, tools: &ToolContext, project_id: &str) {
+    let content = ContextContent {
+        content_type: ContextType::BusinessRule,
+        title: "Moderation".to_string(),
+        description: "Comments are reviewed before publishing".to_string(),
+        data: json!({}),
+        source_file: None,
+        source_line: None,
+    };
+    let item = EnhancedContextItem::new(project_id.to_string(), content);
+    let item = tools.container().enhanced_context_repository.create_context(&item).await.unwrap();
+
+    let specs = SqliteSpecificationRepository::new(Arc::new(Mutex::new(db(dir))));
+    let spec = ProjectSpecification::new(
+        project_id.to_string(),
+        SpecType::Feature,
+        "Comments".to_string(),
+        SpecContent::new(SpecFormat::Markdown, "# Comments".to_string()),
+    );
+    specs.create_specification(&spec).await.unwrap();
+    let mut requirement = Requirement::new(spec.id.clone(), "Review queue".to_string(), "Hold new comments".to_string());
+    requirement
+        .acceptance_criteria
+        .push(AcceptanceCriterion::new("New comments are hidden".to_string(), CriterionType::Functional));
+    specs.create_requirement(&requirement).await.unwrap();
+    specs.link_requirement_to_context(&requirement.id, &item.id).await.unwrap();
+}
+
+fn db(dir: &TempDir) -> Connection {
+    Connection::open(dir.path().join("trash.db")).unwrap()
+}
+
+fn rows(conn: &Connection, table: &str) -> i64 {
+    conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0)).unwrap()
+}
+
+#[tokio::test]
+async fn test_deleted_entities_can_be_listed_and_restored() {
+    let (_dir, tools) = setup();
+    let project_id = create(&tools, "project", json!({"name": "Shop"})).await;
+    let rule_id = create(&tools, "business_rule", json!({"project_id": project_id, "rule_name": "Refunds"})).await;
+
+    call(&tools, "delete_entity", json!({"entity_type": "business_rule", "id": rule_id})).await;
+    let listed = call(&tools, "list_entities", json!({"entity_type": "business_rule", "project_id": project_id})).await;
+    assert!(listed["items"].as_array().unwrap().is_empty());
+
+    let trash = call(&tools, "list_trash", json!({})).await;
+    let trash = trash["result"].as_array().unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0]["entity_type"], "business_rule");
+    assert_eq!(trash[0]["name"], "Refunds");
+
+    let restored = call(&tools, "restore_from_trash", json!({"entity_type": "business_rule", "id": rule_id})).await;
+    assert_eq!(restored["restored"][0]["id"], rule_id.as_str());
+    let listed = call(&tools, "list_entities", json!({"entity_type": "business_rule", "project_id": project_id})).await;
+    assert_eq!(listed["items"].as_array().unwrap().len(), 1);
+    let revisions = call(&tools, "list_revisions", json!({"entity_type": "business_rule", "id": rule_id})).await;
+    assert_eq!(revisions["result"][2]["operation"], "restore");
+
+    let again = tools
+        .call_tool("restore_from_trash", arguments(json!({"entity_type": "business_rule", "id": rule_id})))
+        .await;
+    assert_eq!(again.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_deleting_a_project_trashes_its_entities() {
+    let (dir, tools) = setup();
+    let project_id = create(&tools, "project", json!({"name": "Shop"})).await;
+    let other_id = create(&tools, "project", json!({"name": "Blog"})).await;
+    create(&tools, "business_rule", json!({"project_id": project_id, "rule_name": "Refunds"})).await;
+    create(&tools, "feature_context", json!({"project_id": project_id, "feature_name": "Checkout"})).await;
+    create(&tools, "business_rule", json!({"project_id": other_id, "rule_name": "Comments"})).await;
+
+    call(&tools, "manage_project", json!({"action": "delete", "id": project_id})).await;
+    let projects = call(&tools, "list_entities", json!({"entity_type": "project"})).await;
+    assert_eq!(projects["items"].as_array().unwrap().len(), 1);
+    let rules = call(&tools, "list_entities", json!({"entity_type": "business_rule", "project_id": project_id})).await;
+    assert!(rules["items"].as_array().unwrap().is_empty());
+
+    let trash = call(&tools, "list_trash", json!({"project_id": project_id})).await;
+    assert_eq!(trash["result"].as_array().unwrap().len(), 3);
+
+    let restored = call(&tools, "restore_from_trash", json!({"entity_type": "project", "id": project_id})).await;
+    assert_eq!(restored["restored"].as_array().unwrap().len(), 3);
+    let rules = call(&tools, "list_entities", json!({"entity_type": "business_rule", "project_id": project_id})).await;
+    assert_eq!(rules["items"].as_array().unwrap().len(), 1);
+
+    add_specification(&dir, &tools, &project_id).await;
+    add_specification(&dir, &tools, &other_id).await;
+    call(&tools, "delete_entity", json!({"entity_type": "project", "id": other_id})).await;
+    let kept = call(&tools, "purge_trash", json!({})).await;
+    assert_eq!(kept["purged"], 0);
+    let conn = db(&dir);
+    assert_eq!(rows(&conn, "specifications"), 2);
+    let purged = call(&tools, "purge_trash", json!({"older_than_days": 0})).await;
+    assert_eq!(purged["purged"], 2);
+    let trash = call(&tools, "list_trash", json!({})).await;
+    assert!(trash["result"].as_array().unwrap().is_empty());
+
+    // The purged project's specifications and enhanced context items go with it
+    for table in ["enhanced_context_items", "specifications"] {
+        let count = |project_id: &str| -> i64 {
+            let sql = format!("SELECT COUNT(*) FROM {table} WHERE project_id = ?1");
+            conn.query_row(&sql, [project_id], |row| row.get(0)).unwrap()
+        };
+        assert_eq!(count(&other_id), 0, "{table}");
+        assert!(count(&project_id) > 0, "{table}");
+    }
+    for table in ["requirements", "acceptance_criteria", "requirement_context_links"] {
+        assert_eq!(rows(&conn, table), 1, "{table}");
+    }
+}
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use context_server_rs::db::init::init_db;
+use context_server_rs::models::embedding::{EmbeddingConfig, ModelType, SearchFilters, VectorSearchQuery};
+use context_server_rs::models::enhanced_context::{ContextContent, ContextType, EnhancedContextItem};
+use context_server_rs::repositories::embedding_repository::{EmbeddingRepository, SqliteEmbeddingRepository};
+use context_server_rs::services::semantic_search_service::{
+    SemanticSearchConfig, SemanticSearchService, SemanticSearchServiceImpl,
+};
+use context_server_rs::services::{
+    EmbeddingService, EmbeddingServiceFactory, IndexManagerConfig, SearchIndexManager,
+    SearchIndexManagerImpl, VectorIndex,
+};
+use tempfile::tempdir;
+
+const ITEMS: &[(&str, &str, &str)] = &[
+    ("rule-1", "Token rotation", "Access tokens must be rotated every 24 hours"),
+    ("rule-2", "Password storage", "Passwords must be hashed with argon2 before storage"),
+    ("rule-3", "Checkout latency", "The checkout page must render in under one second"),
+    ("rule-4", "Audit logging", "Every admin action must be written to the audit log"),
+    ("rule-5", "Backups", "Nightly database backups are kept for thirty days"),
+];
+
+fn context(id: &str, title: &str, description: &str) -> EnhancedContextItem {
+    let content = ContextContent {
+        content_type: ContextType::BusinessRule,
+        title: title.to_string(),
+        description: description.to_string(),
+        data: serde_json::json!({}),
+        source_file: None,
+        source_line: None,
+    };
+    let mut context = EnhancedContextItem::new("project-1".to_string(), content);
+    context.id = id.to_string();
+    context.quality_score = 0.9;
+    context
+}
+
+struct Harness {
+    search: Arc<SemanticSearchServiceImpl>,
+    manager: SearchIndexManagerImpl,
+    index: Arc<VectorIndex>,
+}
+
+async fn harness(db_path: &str, sidecar: &str) -> Harness {
+    let conn = Arc::new(tokio::sync::Mutex::new(init_db(db_path).unwrap()));
+    let repository = Arc::new(SqliteEmbeddingRepository::new(conn));
+    repository.initialize().await.unwrap();
+
+    let embedding_config = EmbeddingConfig {
+        model_name: "bm25-test".to_string(),
+        model_type: ModelType::Custom("bm25".to_string()),
+        embedding_dimension: 128,
+        ..Default::default()
+    };
+    let embedding_service: Arc<dyn EmbeddingService> =
+        Arc::from(EmbeddingServiceFactory::create_initialized_service(embedding_config.clone()).await.unwrap());
+
+    let config = IndexManagerConfig {
+        vector_index_path: Some(sidecar.to_string()),
+        ..Default::default()
+    };
+    let index = Arc::new(VectorIndex::new(config.hnsw.clone(), "bm25-test"));
+
+    let search = Arc::new(
+        SemanticSearchServiceImpl::new(
+            embedding_service.clone(),
+            repository.clone(),
+            SemanticSearchConfig {
+                embedding_config,
+                enable_intent_detection: false,
+                ..Default::default()
+            },
+        )
+        .with_vector_index(index.clone()),
+    );
+    let manager = SearchIndexManagerImpl::new(search.clone(), repository, embedding_service, config)
+        .with_vector_index(index.clone());
+
+    Harness { search, manager, index }
+}
+
+#[tokio::test]
+async fn test_vector_index_tracks_indexing_and_persists() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("ann.db");
+    let sidecar = dir.path().join("ann.hnsw");
+    let (db_path, sidecar) = (db_path.to_str().unwrap(), sidecar.to_str().unwrap());
+
+    let h = harness(db_path, sidecar).await;
+    assert_eq!(h.manager.initialize_vector_index().await.unwrap(), 0);
+
+    for (id, title, description) in ITEMS {
+        h.manager.auto_index_context(&context(id, title, description)).await.unwrap();
+    }
+    assert_eq!(h.index.len(), ITEMS.len());
+
+    let query = VectorSearchQuery {
+        query_text: "rotate access tokens".to_string(),
+        similarity_threshold: 0.0,
+        max_results: 3,
+        ..Default::default()
+    };
+    let results = h.search.search(&query).await.unwrap();
+    assert_eq!(results[0].vector_result.context_id, "rule-1");
+
+    h.manager.remove_from_index("rule-1").await.unwrap();
+    assert!(!h.index.contains("rule-1"));
+    let results = h.search.search(&query).await.unwrap();
+    assert!(results.iter().all(|r| r.vector_result.context_id != "rule-1"));
+
+    let report = h.manager.get_health_report(None).await.unwrap();
+    assert_eq!(report.performance_metrics.vector_index_size, ITEMS.len() - 1);
+    assert_eq!(report.performance_metrics.vector_index_queries, 2);
+    assert!(report.performance_metrics.vector_index_recall.is_some());
+
+    let stats = h.search.get_index_stats(None).await.unwrap();
+    assert_eq!(stats.vector_index.unwrap().indexed_vectors, ITEMS.len() - 1);
+
+    h.manager.optimize_index(None).await.unwrap();
+    assert!(std::path::Path::new(sidecar).exists());
+
+    // A restart loads the sidecar instead of rebuilding
+    let restarted = harness(db_path, sidecar).await;
+    assert_eq!(restarted.manager.initialize_vector_index().await.unwrap(), ITEMS.len() - 1);
+    assert!(restarted.index.contains("rule-2"));
+    assert!(!restarted.index.contains("rule-1"));
+}
+
+#[tokio::test]
+async fn test_stale_sidecar_is_rebuilt_from_sqlite() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("ann.db");
+    let sidecar = dir.path().join("ann.hnsw");
+    let (db_path, sidecar) = (db_path.to_str().unwrap(), sidecar.to_str().unwrap());
+
+    let h = harness(db_path, sidecar).await;
+    for (id, title, description) in &ITEMS[..2] {
+        h.manager.auto_index_context(&context(id, title, description)).await.unwrap();
+    }
+    h.manager.persist_vector_index().await.unwrap();
+
+    // Rows written without the manager leave the sidecar behind SQLite
+    let extra: Vec<_> = ITEMS[2..].iter().map(|(id, t, d)| context(id, t, d)).collect();
+    h.search.index_contexts_batch(&extra).await.unwrap();
+
+    let restarted = harness(db_path, sidecar).await;
+    assert_eq!(restarted.manager.initialize_vector_index().await.unwrap(), ITEMS.len());
+    assert!(restarted.index.contains("rule-5"));
+}
+
+#[tokio::test]
+async fn test_sidecar_load_reads_only_rows_written_since_the_save() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("ann.db");
+    let sidecar = dir.path().join("ann.hnsw");
+    let (db_path, sidecar) = (db_path.to_str().unwrap(), sidecar.to_str().unwrap());
+
+    let h = harness(db_path, sidecar).await;
+    for (id, title, description) in ITEMS {
+        h.manager.auto_index_context(&context(id, title, description)).await.unwrap();
+    }
+    h.manager.persist_vector_index().await.unwrap();
+
+    // Rows untouched since the save keep the sidecar's copy, so tampering with one
+    // behind the index's back goes unnoticed; changed and deleted rows are applied
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    conn.execute("UPDATE context_embeddings SET updated_at = '2000-01-01 00:00:00'", []).unwrap();
+    conn.execute(
+        "UPDATE context_embeddings SET content_type = 'tampered' WHERE context_id = 'rule-5'",
+        [],
+    )
+    .unwrap();
+    conn.execute("DELETE FROM context_embeddings WHERE context_id = 'rule-3'", []).unwrap();
+    let rewritten = context("rule-2", "Invoice export", "Invoices are exported to the ledger as CSV");
+    h.search.index_contexts_batch(&[rewritten]).await.unwrap();
+
+    let restarted = harness(db_path, sidecar).await;
+    assert_eq!(restarted.manager.initialize_vector_index().await.unwrap(), ITEMS.len() - 1);
+    assert!(!restarted.index.contains("rule-3"));
+
+    let query = VectorSearchQuery {
+        query_text: "export invoices to the ledger".to_string(),
+        similarity_threshold: 0.0,
+        max_results: 1,
+        ..Default::default()
+    };
+    assert_eq!(restarted.search.search(&query).await.unwrap()[0].vector_result.context_id, "rule-2");
+
+    let stored = SqliteEmbeddingRepository::new(Arc::new(tokio::sync::Mutex::new(conn)));
+    let rule_5 = stored.get_embeddings_by_context_id("rule-5").await.unwrap();
+    let hit = &restarted.index.search(&rule_5[0].embedding_vector, 1)[0];
+    assert_eq!((hit.context_id.as_str(), hit.content_type.as_str()), ("rule-5", "business_rule"));
+}
+
+#[tokio::test]
+async fn test_project_search_filters_ann_hits_and_falls_back_to_a_scan() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("ann.db");
+    let sidecar = dir.path().join("ann.hnsw");
+    let (db_path, sidecar) = (db_path.to_str().unwrap(), sidecar.to_str().unwrap());
+
+    let h = harness(db_path, sidecar).await;
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    for project in ["project-1", "project-2"] {
+        conn.execute("INSERT INTO projects (id, name) VALUES (?1, ?1)", [project]).unwrap();
+    }
+    for (id, title, description) in ITEMS {
+        h.manager.auto_index_context(&context(id, title, description)).await.unwrap();
+    }
+    // Enough project-1 neighbours to outnumber what a one-result search fetches
+    for n in 0..12 {
+        let item = context(&format!("token-{n}"), &format!("Access tokens {n}"), "Access tokens are checked on every request");
+        h.manager.auto_index_context(&item).await.unwrap();
+    }
+    let others = [
+        ("other-1", "Key rotation", "Signing keys and service tokens are rotated every 90 days"),
+        ("other-2", "Office plants", "The plants on the third floor are watered on Fridays"),
+    ];
+    for (id, title, description) in others {
+        let mut item = context(id, title, description);
+        item.project_id = "project-2".to_string();
+        h.manager.auto_index_context(&item).await.unwrap();
+    }
+
+    let search = |query_text: &str, project_id: &str| VectorSearchQuery {
+        query_text: query_text.to_string(),
+        similarity_threshold: 0.1,
+        max_results: 1,
+        filters: SearchFilters {
+            project_ids: Some(vec![project_id.to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Project-1 vectors fill the neighbourhood, but the project-2 match is among them
+    let results = h.search.search(&search("rotate signing keys", "project-2")).await.unwrap();
+    assert_eq!(results[0].vector_result.context_id, "other-1");
+    assert!(h.index.stats(1).total_queries > 0);
+
+    // Every neighbour fetched for this query is project-1's, so project-2 is scanned
+    let results = h.search.search(&search("access tokens", "project-2")).await.unwrap();
+    assert_eq!(results[0].vector_result.context_id, "other-1");
+
+    let results = h.search.search(&search("nightly database backups", "project-1")).await.unwrap();
+    assert_eq!(results[0].vector_result.context_id, "rule-5");
+}